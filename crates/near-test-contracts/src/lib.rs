@@ -83,3 +83,42 @@ pub fn many_functions_contract(function_count: u32) -> Vec<u8> {
     );
     wat::parse_str(code).unwrap()
 }
+
+/// Contract whose `main` runs `body` `iterations` times in a loop, used to
+/// measure the cost of wasm instructions and host functions.
+///
+/// `imports` is spliced into the module, so `body` can call host functions.
+/// It can also hold data segments that fill the memory before the call. The
+/// body can use the `$x` (i64) local, the `$id` function and the first page
+/// of memory.
+pub fn loop_contract(imports: &str, body: &str, iterations: u32) -> Vec<u8> {
+    let code = format!(
+        r#"(module
+            {imports}
+            (memory 1)
+            (func $id (param i64) (result i64) (local.get 0))
+            (func (export "main")
+                (local $i i32) (local $x i64)
+                (block $done
+                    (loop $loop
+                        (br_if $done (i32.ge_u (local.get $i) (i32.const {iterations})))
+                        {body}
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br $loop)))))"#,
+        imports = imports,
+        body = body,
+        iterations = iterations,
+    );
+    wat::parse_str(code).unwrap()
+}
+
+#[test]
+fn loop_contract_smoke_test() {
+    assert!(!loop_contract("", "", 10).is_empty());
+    let with_host_call = loop_contract(
+        r#"(import "env" "block_number" (func $block_number (result i64)))"#,
+        "(drop (call $block_number))",
+        10,
+    );
+    assert!(!with_host_call.is_empty());
+}
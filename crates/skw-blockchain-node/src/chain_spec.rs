@@ -1,5 +1,6 @@
 use skyekiwi_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
+	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, ParentchainConfig,
+	RegistryConfig, SAccountConfig, SContractConfig, SecretsConfig, Signature, SudoConfig,
 	SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
@@ -37,6 +38,30 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
+/// Generate a secret keeper account and its identity public key from seed.
+pub fn secret_keeper_from_seed(s: &str) -> (AccountId, Vec<u8>) {
+	(get_account_id_from_seed::<sr25519::Public>(s), get_from_seed::<sr25519::Public>(s).0.to_vec())
+}
+
+/// Shard 0 started at genesis, operated by the first secret keeper and run by all of them.
+pub struct ShardGenesis {
+	pub secret_keepers: Vec<(AccountId, Vec<u8>)>,
+	/// number of matching outcomes needed to confirm a block
+	pub threshold: u64,
+	/// public key calls to the shard are encrypted to
+	pub public_key: [u8; 32],
+}
+
+impl ShardGenesis {
+	pub fn from_seeds(secret_keepers: &[&str], threshold: u64) -> Self {
+		ShardGenesis {
+			secret_keepers: secret_keepers.iter().map(|s| secret_keeper_from_seed(s)).collect(),
+			threshold,
+			public_key: get_from_seed::<sr25519::Public>("//Shard0").0,
+		}
+	}
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
@@ -60,6 +85,52 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("//Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("//Bob//stash"),
 				],
+				// Shard 0 run by Alice alone
+				ShardGenesis::from_seeds(&["//Alice"], 1),
+				true,
+			)
+		},
+		// Bootnodes
+		vec![],
+		// Telemetry
+		None,
+		// Protocol ID
+		None,
+		// Properties
+		None,
+		// Extensions
+		None,
+		None,
+	))
+}
+
+pub fn local_testnet_config() -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		"Local Testnet",
+		// ID
+		"local_testnet",
+		ChainType::Local,
+		move || {
+			testnet_genesis(
+				wasm_binary,
+				// Initial PoA authorities
+				vec![authority_keys_from_seed("//Alice"), authority_keys_from_seed("//Bob")],
+				// Sudo account
+				get_account_id_from_seed::<sr25519::Public>("//Alice"),
+				// Pre-funded accounts
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("//Alice"),
+					get_account_id_from_seed::<sr25519::Public>("//Bob"),
+					get_account_id_from_seed::<sr25519::Public>("//Charlie"),
+					get_account_id_from_seed::<sr25519::Public>("//Dave"),
+					get_account_id_from_seed::<sr25519::Public>("//Alice//stash"),
+					get_account_id_from_seed::<sr25519::Public>("//Bob//stash"),
+				],
+				// Shard 0 run by Alice, Bob and Charlie, confirmed by 2 of them
+				ShardGenesis::from_seeds(&["//Alice", "//Bob", "//Charlie"], 2),
 				true,
 			)
 		},
@@ -128,8 +199,12 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	shard: ShardGenesis,
 	_enable_println: bool,
 ) -> GenesisConfig {
+	let operator = shard.secret_keepers.first().map(|(k, _)| k.clone())
+		.expect("shard 0 needs at least one secret keeper");
+
 	GenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
@@ -149,5 +224,21 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: Some(root_key),
 		},
+		secrets: SecretsConfig { secrets: vec![] },
+		s_contract: SContractConfig {
+			shard_operators: vec![],
+			shards: vec![(0, operator, vec![], shard.public_key)],
+		},
+		registry: RegistryConfig {
+			running_shards: shard.secret_keepers.iter().map(|(k, _)| (k.clone(), 0)).collect(),
+			secret_keepers: shard.secret_keepers,
+		},
+		parentchain: ParentchainConfig {
+			shard_confirmation_thresholds: vec![(0, shard.threshold)],
+		},
+		s_account: SAccountConfig {
+			// every pre-funded account gets an enclave account on shard 0
+			enclave_accounts: endowed_accounts.iter().cloned().map(|k| (k, 0)).collect(),
+		},
 	}
 }
@@ -0,0 +1,69 @@
+//! Benchmarking setup for pallet-naming
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
+use sp_runtime::traits::Bounded;
+use sp_std::vec;
+#[allow(unused)]
+use crate::Pallet as Naming;
+
+fn funded_account<T: Config>(who: T::AccountId) -> T::AccountId {
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+fn registered_name<T: Config>(owner: &T::AccountId) -> Result<T::Hash, &'static str> {
+	let name = T::Hashing::hash(b"benchmark");
+	Naming::<T>::set_or_renew_name(RawOrigin::Signed(owner.clone()).into(), name, T::MaxPeriod::get())?;
+	Ok(name)
+}
+
+benchmarks! {
+	set_or_renew_name {
+		let caller = funded_account::<T>(whitelisted_caller());
+		let name = registered_name::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), name, T::MaxPeriod::get())
+	verify { }
+
+	set_name_target {
+		let caller = funded_account::<T>(whitelisted_caller());
+		let name = registered_name::<T>(&caller)?;
+		let contract_name = vec![0u8; T::MaxContractNameLength::get() as usize];
+	}: _(RawOrigin::Signed(caller), name, Some(NameTarget::Contract(0, contract_name)))
+	verify {
+		assert!(Naming::<T>::resolve(&name).is_some());
+	}
+
+	transfer_name {
+		let caller = funded_account::<T>(whitelisted_caller());
+		let to = funded_account::<T>(account("to", 0, 0));
+		let name = registered_name::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), name, to.clone())
+	verify {
+		assert_eq!(Naming::<T>::name_of(name).unwrap().owner, to);
+	}
+
+	clear_name {
+		let caller = funded_account::<T>(whitelisted_caller());
+		let name = registered_name::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), name)
+	verify {
+		assert!(Naming::<T>::name_of(name).is_none());
+	}
+
+	force_clear_name {
+		let caller = funded_account::<T>(whitelisted_caller());
+		let name = registered_name::<T>(&caller)?;
+	}: _(RawOrigin::Root, name)
+	verify {
+		assert!(Naming::<T>::name_of(name).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	Naming,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
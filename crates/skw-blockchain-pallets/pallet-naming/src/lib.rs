@@ -0,0 +1,341 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+use frame_support::{
+	Blake2_128Concat, dispatch::DispatchResult, pallet_prelude::*,
+	traits::{
+		Currency, ReservableCurrency, EnsureOrigin
+	}
+};
+use sp_runtime::ArithmeticError;
+use sp_runtime::traits::{CheckedAdd, CheckedMul, Hash};
+use sp_std::prelude::*;
+use skw_blockchain_primitives::{
+	types::{ShardId, PublicKey},
+	traits::ContractNaming,
+};
+pub use pallet::*;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+/// what a name resolves to
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum NameTarget<ContractName> {
+	/// a secret contract deployed on a shard
+	Contract(ShardId, ContractName),
+	/// an offchain account, by its public key
+	OffchainAccount(PublicKey),
+}
+
+/// a registered name - the owner keeps `deposit` reserved until the name is cleared or taken over
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct NameRecord<AccountId, BlockNumber, Balance, ContractName> {
+	pub owner: AccountId,
+	pub target: Option<NameTarget<ContractName>>,
+	pub expiration: BlockNumber,
+	pub deposit: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type ContractNameOf<T> = BoundedVec<u8, <T as Config>::MaxContractNameLength>;
+	pub type NameRecordOf<T> = NameRecord<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+		ContractNameOf<T>,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		type WeightInfo: WeightInfo;
+
+		/// deposit reserved for each period a name is registered for
+		#[pallet::constant]
+		type ReservationFee: Get<BalanceOf <Self>>;
+
+		#[pallet::constant]
+		type BlockPerPeriod: Get<<Self as frame_system::Config>::BlockNumber>;
+
+		/// maximum number of periods a name can be registered or renewed for at once
+		#[pallet::constant]
+		type MaxPeriod: Get<u32>;
+
+		/// maximum length of the contract name a name can resolve to
+		#[pallet::constant]
+		type MaxContractNameLength: Get<u32>;
+
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// hash of a name -> owner, target, expiration, reserved deposit
+	#[pallet::storage]
+	#[pallet::getter(fn name_of)]
+	pub(super) type Naming<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, NameRecordOf<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		NameRegistered(T::Hash, T::AccountId, T::BlockNumber),
+		NameRenewed(T::Hash, T::AccountId, T::BlockNumber),
+		NameTargetSet(T::Hash),
+		NameTransferred(T::Hash, T::AccountId, T::AccountId),
+		NameCleared(T::Hash),
+		NameForceCleared(T::Hash),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		Unnamed,
+		NameTaken,
+		NameExpired,
+		PeriodTooLong,
+		InvalidPeriod,
+		Unauthorized,
+		InvalidContractName,
+	}
+
+	#[pallet::call]
+	impl<T:Config> Pallet<T> {
+		/// send in hash of the name and register it to the origin, or renew it when the origin owns it
+		#[pallet::weight(<T as Config>::WeightInfo::set_or_renew_name())]
+		pub fn set_or_renew_name(
+			origin: OriginFor<T>,
+			name: T::Hash,
+			period: u32 // in days
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_or_renew_name(who, name, period)?;
+			Ok(())
+		}
+
+		/// (NAME OWNER ONLY) set what the name resolves to
+		#[pallet::weight(<T as Config>::WeightInfo::set_name_target())]
+		pub fn set_name_target(
+			origin: OriginFor<T>,
+			name: T::Hash,
+			target: Option<NameTarget<Vec<u8>>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let target = match target {
+				Some(t) => Some(Self::bound_target(t)?),
+				None => None,
+			};
+
+			Self::do_set_name_target(&who, name, target)
+		}
+
+		/// (NAME OWNER ONLY) hand a name over to another account, the deposit is reserved from the new owner
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_name())]
+		pub fn transfer_name(
+			origin: OriginFor<T>,
+			name: T::Hash,
+			to: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut record = Self::name_of(&name).ok_or(Error::<T>::Unnamed)?;
+			ensure!(record.owner == who, Error::<T>::Unauthorized);
+			ensure!(!Self::is_expired(&record), Error::<T>::NameExpired);
+
+			T::Currency::reserve(&to, record.deposit)?;
+			T::Currency::unreserve(&who, record.deposit);
+
+			record.owner = to.clone();
+			<Naming<T>>::insert(&name, record);
+
+			Self::deposit_event(Event::<T>::NameTransferred(name, who, to));
+			Ok(())
+		}
+
+		/// clear a name and release the deposit - anyone can clear a name once it has expired
+		#[pallet::weight(<T as Config>::WeightInfo::clear_name())]
+		pub fn clear_name(
+			origin: OriginFor<T>,
+			name: T::Hash
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let record = Self::name_of(&name).ok_or(Error::<T>::Unnamed)?;
+			ensure!(record.owner == who || Self::is_expired(&record), Error::<T>::Unauthorized);
+
+			<Naming<T>>::remove(&name);
+			T::Currency::unreserve(&record.owner, record.deposit);
+			Self::deposit_event(Event::<T>::NameCleared(name));
+			Ok(())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::force_clear_name())]
+		pub fn force_clear_name(
+			origin: OriginFor<T>,
+			name: T::Hash
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			if let Some(record) = <Naming<T>>::take(&name) {
+				T::Currency::unreserve(&record.owner, record.deposit);
+				Self::deposit_event(Event::<T>::NameForceCleared(name));
+			} else {
+				Err(Error::<T>::Unnamed)?
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn is_expired(record: &NameRecordOf<T>) -> bool {
+			record.expiration < frame_system::Pallet::<T>::block_number()
+		}
+
+		/// what a name resolves to, expired names do not resolve
+		pub fn resolve(name: &T::Hash) -> Option<NameTarget<ContractNameOf<T>>> {
+			Self::name_of(name)
+				.filter(|record| !Self::is_expired(record))
+				.and_then(|record| record.target)
+		}
+
+		/// resolve a plain name, hashed the same way clients do before registering it
+		pub fn resolve_name(name: &[u8]) -> Option<NameTarget<ContractNameOf<T>>> {
+			Self::resolve(&T::Hashing::hash(name))
+		}
+
+		pub fn bound_target(target: NameTarget<Vec<u8>>) -> Result<NameTarget<ContractNameOf<T>>, Error<T>> {
+			Ok(match target {
+				NameTarget::Contract(shard_id, contract_name) => NameTarget::Contract(
+					shard_id,
+					ContractNameOf::<T>::try_from(contract_name).map_err(|_| Error::<T>::InvalidContractName)?,
+				),
+				NameTarget::OffchainAccount(public_key) => NameTarget::OffchainAccount(public_key),
+			})
+		}
+
+		/// register a name to `who` or renew it, returns the new expiration
+		pub fn do_set_or_renew_name(
+			who: T::AccountId,
+			name: T::Hash,
+			period: u32,
+		) -> Result<T::BlockNumber, DispatchError> {
+			ensure!(period > 0, Error::<T>::InvalidPeriod);
+			ensure!(period <= T::MaxPeriod::get(), Error::<T>::PeriodTooLong);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let deposit = <BalanceOf<T>>::from(period)
+				.checked_mul(&T::ReservationFee::get())
+				.ok_or(ArithmeticError::Overflow)?;
+
+			let duration = T::BlockPerPeriod::get()
+				.checked_mul(&T::BlockNumber::from(period))
+				.ok_or(ArithmeticError::Overflow)?;
+
+			if let Some(old) = <Naming<T>>::get(&name) {
+				// EITHER the previous naming expired *OR* a renewal operation
+				ensure!(old.expiration < now || old.owner == who, Error::<T>::NameTaken);
+
+				// now the user is authorized to take or renew the name
+				let (record, renew) = if old.expiration < now {
+					// register to new user, what the name resolved to is dropped with the old owner
+					(
+						NameRecord {
+							owner: who.clone(),
+							target: None,
+							expiration: now.checked_add(&duration).ok_or(ArithmeticError::Overflow)?,
+							deposit,
+						},
+						false,
+					)
+				} else {
+					// renewal
+					(
+						NameRecord {
+							expiration: old.expiration.checked_add(&duration).ok_or(ArithmeticError::Overflow)?,
+							deposit: old.deposit.checked_add(&deposit).ok_or(ArithmeticError::Overflow)?,
+							..old.clone()
+						},
+						true,
+					)
+				};
+
+				T::Currency::reserve(&who, deposit)?;
+				if !renew {
+					// release the old reserve
+					// it should be the deposit from the record instead of "deposit"
+					T::Currency::unreserve(&old.owner, old.deposit);
+				}
+
+				let expiration = record.expiration;
+				<Naming<T>>::insert(&name, record);
+
+				if renew {
+					Self::deposit_event(Event::<T>::NameRenewed(name, who, expiration));
+				} else {
+					Self::deposit_event(Event::<T>::NameRegistered(name, who, expiration));
+				}
+				Ok(expiration)
+			} else {
+				// empty name
+				T::Currency::reserve(&who, deposit)?;
+				let expiration = now.checked_add(&duration).ok_or(ArithmeticError::Overflow)?;
+				<Naming<T>>::insert(&name, NameRecord {
+					owner: who.clone(),
+					target: None,
+					expiration,
+					deposit,
+				});
+				Self::deposit_event(Event::<T>::NameRegistered(name, who, expiration));
+				Ok(expiration)
+			}
+		}
+
+		pub fn do_set_name_target(
+			who: &T::AccountId,
+			name: T::Hash,
+			target: Option<NameTarget<ContractNameOf<T>>>,
+		) -> DispatchResult {
+			<Naming<T>>::try_mutate(&name, |record| -> DispatchResult {
+				let record = record.as_mut().ok_or(Error::<T>::Unnamed)?;
+				ensure!(&record.owner == who, Error::<T>::Unauthorized);
+				ensure!(!Self::is_expired(record), Error::<T>::NameExpired);
+
+				record.target = target;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::NameTargetSet(name));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> ContractNaming<T::AccountId> for Pallet<T> {
+		fn claim_contract_name(
+			who: &T::AccountId,
+			name: &[u8],
+			period: u32,
+			shard_id: ShardId,
+			contract_name: &[u8],
+		) -> DispatchResult {
+			let contract_name = ContractNameOf::<T>::try_from(contract_name.to_vec())
+				.map_err(|_| Error::<T>::InvalidContractName)?;
+
+			let name = T::Hashing::hash(name);
+			Self::do_set_or_renew_name(who.clone(), name, period)?;
+			Self::do_set_name_target(who, name, Some(NameTarget::Contract(shard_id, contract_name)))
+		}
+	}
+}
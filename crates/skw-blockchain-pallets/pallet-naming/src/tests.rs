@@ -0,0 +1,137 @@
+use super::*;
+use frame_support::{assert_ok, assert_noop};
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT};
+use crate::mock::*;
+
+const ALICE: u64 = 1;
+const BOB: u64 = 2;
+const CHARLIE: u64 = 3;
+
+#[test]
+fn it_sets_and_renews_names() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let name = BlakeTwo256::hash(b"test_name");
+
+		// register the name for 10 * 5 = 50 blocks
+		assert_ok!( Naming::set_or_renew_name(Origin::signed(ALICE), name, 10) );
+		assert_eq!( Balances::free_balance(ALICE), 80 );
+		assert_eq!( Balances::reserved_balance(ALICE), 20 );
+
+		let record = Naming::name_of(name).unwrap();
+		assert_eq!( (record.owner, record.expiration, record.deposit), (ALICE, 51, 20) );
+
+		assert_noop!(
+			Naming::set_or_renew_name(Origin::signed(BOB), name, 10),
+			Error::<Test>::NameTaken
+		);
+		assert_noop!(
+			Naming::set_or_renew_name(Origin::signed(ALICE), name, 21),
+			Error::<Test>::PeriodTooLong
+		);
+		assert_noop!(
+			Naming::set_or_renew_name(Origin::signed(ALICE), name, 0),
+			Error::<Test>::InvalidPeriod
+		);
+
+		// renewal extends the expiration and adds to the deposit
+		assert_ok!( Naming::set_or_renew_name(Origin::signed(ALICE), name, 2) );
+		let record = Naming::name_of(name).unwrap();
+		assert_eq!( (record.expiration, record.deposit), (61, 24) );
+		assert_eq!( Balances::reserved_balance(ALICE), 24 );
+
+		// once expired, the name can be taken over and the old deposit is released
+		System::set_block_number(62);
+		assert_ok!( Naming::set_or_renew_name(Origin::signed(BOB), name, 1) );
+		assert_eq!( Balances::reserved_balance(ALICE), 0 );
+		assert_eq!( Balances::reserved_balance(BOB), 2 );
+		assert_eq!( Naming::name_of(name).unwrap().owner, BOB );
+	});
+}
+
+#[test]
+fn it_resolves_names() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let name = BlakeTwo256::hash(b"contract");
+
+		assert_ok!( Naming::set_or_renew_name(Origin::signed(ALICE), name, 1) );
+		assert_eq!( Naming::resolve(&name), None );
+
+		assert_noop!(
+			Naming::set_name_target(Origin::signed(BOB), name, Some(NameTarget::OffchainAccount([1u8; 32]))),
+			Error::<Test>::Unauthorized
+		);
+		assert_noop!(
+			Naming::set_name_target(Origin::signed(ALICE), name, Some(NameTarget::Contract(0, vec![0u8; 33]))),
+			Error::<Test>::InvalidContractName
+		);
+
+		assert_ok!( Naming::set_name_target(Origin::signed(ALICE), name, Some(NameTarget::Contract(0, b"hello".to_vec()))) );
+		assert_eq!(
+			Naming::resolve_name(b"contract"),
+			Some(NameTarget::Contract(0, b"hello".to_vec().try_into().unwrap()))
+		);
+
+		assert_ok!( Naming::set_name_target(Origin::signed(ALICE), name, Some(NameTarget::OffchainAccount([1u8; 32]))) );
+		assert_eq!( Naming::resolve(&name), Some(NameTarget::OffchainAccount([1u8; 32])) );
+
+		// expired names do not resolve
+		System::set_block_number(7);
+		assert_eq!( Naming::resolve(&name), None );
+	});
+}
+
+#[test]
+fn it_transfers_and_clears_names() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let name = BlakeTwo256::hash(b"test_name");
+
+		assert_ok!( Naming::set_or_renew_name(Origin::signed(ALICE), name, 10) );
+
+		assert_noop!( Naming::transfer_name(Origin::signed(BOB), name, CHARLIE), Error::<Test>::Unauthorized );
+		// CHARLIE cannot afford the deposit
+		assert!( Naming::transfer_name(Origin::signed(ALICE), name, CHARLIE).is_err() );
+
+		assert_ok!( Naming::transfer_name(Origin::signed(ALICE), name, BOB) );
+		assert_eq!( Balances::reserved_balance(ALICE), 0 );
+		assert_eq!( Balances::reserved_balance(BOB), 20 );
+		assert_eq!( Naming::name_of(name).unwrap().owner, BOB );
+
+		// only the owner can clear a name before it expires
+		assert_noop!( Naming::clear_name(Origin::signed(ALICE), name), Error::<Test>::Unauthorized );
+
+		System::set_block_number(52);
+		assert_ok!( Naming::clear_name(Origin::signed(ALICE), name) );
+		assert_eq!( Balances::reserved_balance(BOB), 0 );
+		assert_eq!( Naming::name_of(name), None );
+
+		assert_noop!( Naming::force_clear_name(Origin::root(), name), Error::<Test>::Unnamed );
+	});
+}
+
+#[test]
+fn it_claims_contract_names() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( <Naming as ContractNaming<u64>>::claim_contract_name(&ALICE, b"name", 1, 0, b"contract") );
+		assert_eq!(
+			Naming::resolve_name(b"name"),
+			Some(NameTarget::Contract(0, b"contract".to_vec().try_into().unwrap()))
+		);
+
+		// taken by ALICE
+		assert_noop!(
+			<Naming as ContractNaming<u64>>::claim_contract_name(&BOB, b"name", 1, 0, b"contract"),
+			Error::<Test>::NameTaken
+		);
+
+		// a name cannot be claimed for zero periods
+		assert_noop!(
+			<Naming as ContractNaming<u64>>::claim_contract_name(&BOB, b"other", 0, 0, b"contract"),
+			Error::<Test>::InvalidPeriod
+		);
+	});
+}
@@ -0,0 +1,126 @@
+// This file is part of SkyeKiwi Network.
+
+// Copyright (C) 2021 - 2022 SkyeKiwi.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Autogenerated weights for pallet_naming
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-07-25, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: None, DB CACHE: 1024
+
+// Executed Command:
+// /Users/songzhou/Desktop/skyekiwi-network/target/release/skyekiwi-node
+// benchmark
+// pallet
+// --steps
+// 50
+// --repeat
+// 20
+// --pallet
+// pallet_naming
+// --extrinsic
+// *
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --heap-pages
+// 4096
+// --output
+// /Users/songzhou/Desktop/skyekiwi-network/crates/skw-blockchain-pallets/pallet-naming/src/weights.rs
+// --template
+// /Users/songzhou/Desktop/skyekiwi-network/misc/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_naming.
+pub trait WeightInfo {
+	fn set_or_renew_name() -> Weight;
+	fn set_name_target() -> Weight;
+	fn transfer_name() -> Weight;
+	fn clear_name() -> Weight;
+	fn force_clear_name() -> Weight;
+}
+
+/// Weights for pallet_naming using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn set_or_renew_name() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Naming Naming (r:1 w:1)
+	fn set_name_target() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn transfer_name() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_name() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn force_clear_name() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn set_or_renew_name() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Naming Naming (r:1 w:1)
+	fn set_name_target() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn transfer_name() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_name() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn force_clear_name() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
@@ -1,13 +1,14 @@
 use super::*;
 
 use frame_system::RawOrigin;
+use frame_support::traits::EnsureOrigin;
 use frame_benchmarking::{benchmarks, whitelisted_caller, impl_benchmark_test_suite};
 
 #[allow(unused)]
 use crate::Pallet as Parentchain;
 use pallet_registry::Pallet as Registry;
 use sp_std::vec::Vec;
-use skw_blockchain_primitives::types::CallIndex;
+use skw_blockchain_primitives::{types::{CallIndex, ShardId}, traits::CallHistoryProvider};
 
 const PUBLIC_KEY: [u8; 32] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
 
@@ -15,7 +16,8 @@ benchmarks! {
 	set_shard_confirmation_threshold {
 		let s = 0u32;
 		let caller: T::AccountId = whitelisted_caller();
-	}: set_shard_confirmation_threshold(RawOrigin::Root, s, 2)
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, s, 2)
 	verify {
 		assert_eq!(ShardConfirmationThreshold::<T>::get(s), Some(2u64));
 	}
@@ -39,11 +41,42 @@ benchmarks! {
 		
 		Registry::<T>::register_secret_keeper( RawOrigin::Signed(caller.clone()).into(), PUBLIC_KEY.to_vec(), Vec::new() )?;
 		Registry::<T>::register_running_shard( RawOrigin::Signed(caller.clone()).into(), 0 )?;
-		Parentchain::<T>::set_shard_confirmation_threshold( RawOrigin::Root.into(), shard_id, 1 )?;
+		Parentchain::<T>::set_shard_confirmation_threshold( T::AdminOrigin::successful_origin(), shard_id, 1 )?;
 	}: submit_outcome(RawOrigin::Signed(caller), now, shard_id, state_root, outcome_call_index, outcome)
 	verify {
 		assert_eq!(Confirmation::<T>::get(shard_id, now), Some(1u64));
 	}
+
+	submit_outcome_with_receipts {
+		let s in 1 .. 19;
+		let r in 1 .. 19;
+
+		let shard_id = 0u32;
+		let caller: T::AccountId = whitelisted_caller();
+		let now = frame_system::Pallet::<T>::block_number();
+
+		let state_root = [0u8; 32];
+
+		let mut outcome_call_index: Vec<CallIndex> = Vec::new();
+		let mut outcome: Vec<Vec<u8>> = Vec::new();
+		let mut receipts: Vec<(CallIndex, ShardId, Vec<u8>)> = Vec::new();
+
+		for i in 0 .. s {
+			outcome_call_index.push(i as CallIndex);
+			outcome.push([i as u8; 1_000].to_vec());
+		}
+		for i in 0 .. r {
+			receipts.push((0, 1, [i as u8; 1_000].to_vec()));
+		}
+		
+		Registry::<T>::register_secret_keeper( RawOrigin::Signed(caller.clone()).into(), PUBLIC_KEY.to_vec(), Vec::new() )?;
+		Registry::<T>::register_running_shard( RawOrigin::Signed(caller.clone()).into(), 0 )?;
+		Parentchain::<T>::set_shard_confirmation_threshold( T::AdminOrigin::successful_origin(), shard_id, 1 )?;
+		T::Calls::set_calls_of(shard_id, now, outcome_call_index.clone());
+	}: submit_outcome_with_receipts(RawOrigin::Signed(caller), now, shard_id, state_root, outcome_call_index, outcome, receipts)
+	verify {
+		assert_eq!(PendingReceipts::<T>::get(shard_id, now), None);
+	}
 }
 
 impl_benchmark_test_suite!(
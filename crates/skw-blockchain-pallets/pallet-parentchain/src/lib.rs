@@ -4,6 +4,8 @@ pub use pallet::*;
 pub mod weights;
 pub use weights::WeightInfo;
 
+pub mod offchain;
+
 #[cfg(test)]
 mod tests;
 
@@ -16,13 +18,17 @@ mod benchmarking;
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
-	use skw_blockchain_primitives::types::{ShardId, CallIndex};
+	use frame_system::{pallet_prelude::*, offchain::{AppCrypto, CreateSignedTransaction}};
+	use skw_blockchain_primitives::{
+		types::{ShardId, CallIndex},
+		traits::{ShardStatusProvider, ShardAdmins, OnBlockConfirmed, CallHistoryProvider, ReceiptRouter},
+	};
 	use sp_std::vec::Vec;	
+	use sp_runtime::traits::Hash;
 	use super::WeightInfo;
 	
 	#[pallet::config]
-	pub trait Config: frame_system::Config + pallet_registry::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config + pallet_registry::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		type WeightInfo: WeightInfo;
@@ -38,6 +44,27 @@ pub mod pallet {
 		/// Maximum length of sizze for each outcome submitted
 		#[pallet::constant]
 		type MaxSizePerOutcome: Get<u32>;
+
+		/// Lifecycle status and admins of shards - outcomes are only accepted for shards that are still running
+		type Shards: ShardStatusProvider + ShardAdmins<Self::AccountId>;
+
+		/// Called once a block of a shard reaches its confirmation threshold
+		type OnBlockConfirmed: OnBlockConfirmed<Self::AccountId, Self::BlockNumber>;
+
+		/// Calls scheduled for shards - read by the offchain worker of secret keepers
+		type Calls: CallHistoryProvider<Self::BlockNumber>;
+
+		/// Pushes the calls receipts of confirmed blocks ask for on other shards
+		type Receipts: ReceiptRouter<Self::BlockNumber>;
+
+		/// Key the offchain worker signs outcome submissions with
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// origin allowed to configure every shard
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// origin of shard admins, who can only configure the shards they are appointed to
+		type ShardAdminOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
 	}
 
 	#[pallet::pallet]
@@ -50,6 +77,12 @@ pub mod pallet {
 	pub(super) type ShardConfirmationThreshold<T: Config> = StorageMap<_, Twox64Concat, 
 		ShardId, u64>;
 
+	/// Threshold a block has to reach, fixed when the first outcome for it is submitted
+	#[pallet::storage]
+	#[pallet::getter(fn block_confirmation_threshold_of)]
+	pub(super) type BlockConfirmationThreshold<T: Config> = StorageDoubleMap<_, Twox64Concat, ShardId,
+		Twox64Concat, T::BlockNumber, u64>;
+
 	/// state_root of offchain runtime
 	#[pallet::storage]
 	#[pallet::getter(fn state_root_at)]
@@ -62,17 +95,66 @@ pub mod pallet {
 	pub(super) type Confirmation<T: Config> = StorageDoubleMap<_, Twox64Concat, ShardId,
 	Twox64Concat, T::BlockNumber, u64>;
 
+	/// secret keepers that submitted a matching state root for offchain runtime blocks
+	#[pallet::storage]
+	#[pallet::getter(fn confirmers_of)]
+	pub(super) type Confirmers<T: Config> = StorageDoubleMap<_, Twox64Concat, ShardId,
+		Twox64Concat, T::BlockNumber, BoundedVec<T::AccountId, <T as pallet_registry::Config>::MaxSecretKeepers>>;
+
 	/// outcome received each call 
 	#[pallet::storage]
 	#[pallet::getter(fn outcome_of)]
 	pub(super) type Outcome<T: Config> = StorageMap<_, Twox64Concat, 
 		CallIndex, BoundedVec<u8, T::MaxSizePerOutcome>>;
 
+	/// the offchain runtime block an outcome was submitted for CallIndex -> (ShardId, BlockNumber)
+	#[pallet::storage]
+	#[pallet::getter(fn outcome_block_of)]
+	pub(super) type OutcomeBlock<T: Config> = StorageMap<_, Twox64Concat, 
+		CallIndex, (ShardId, T::BlockNumber)>;
+
+	/// receipts of a block waiting for its confirmation: (source call index, target shard, encoded calls)
+	#[pallet::storage]
+	#[pallet::getter(fn pending_receipts_of)]
+	pub(super) type PendingReceipts<T: Config> = StorageDoubleMap<_, Twox64Concat, ShardId,
+		Twox64Concat, T::BlockNumber,
+		BoundedVec<(CallIndex, ShardId, BoundedVec<u8, T::MaxSizePerOutcome>), T::MaxOutcomePerSubmission>>;
+
+	/// hash of the receipts of a block, which every confirmation has to submit as well
+	#[pallet::storage]
+	#[pallet::getter(fn receipts_root_at)]
+	pub(super) type ReceiptsRoot<T: Config> = StorageDoubleMap<_, Twox64Concat, ShardId,
+		Twox64Concat, T::BlockNumber, T::Hash>;
+
+	/// the highest confirmed block of each shard
+	#[pallet::storage]
+	#[pallet::getter(fn latest_confirmed_block_of)]
+	pub(super) type LatestConfirmedBlock<T: Config> = StorageMap<_, Twox64Concat, 
+		ShardId, T::BlockNumber>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {
+		/// (shard, confirmation threshold) set at genesis
+		pub shard_confirmation_thresholds: Vec<(ShardId, u64)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			for (shard_id, threshold) in self.shard_confirmation_thresholds.iter() {
+				<ShardConfirmationThreshold<T>>::insert(shard_id, threshold);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		BlockSynced(T::BlockNumber),
 		BlockConfirmed(T::BlockNumber),
+		ReceiptRouted(ShardId, CallIndex, ShardId),
+		ReceiptDropped(ShardId, CallIndex, ShardId),
 	}
 
 	#[pallet::error]
@@ -81,22 +163,36 @@ pub mod pallet {
 		NotInBeaconTurn,
 		OutcomeSubmissionTooLate,
 		InvalidShardId,
+		ShardNotActive,
 		InvalidOutcome,
 		InconsistentState,
+		DuplicateSubmission,
 		Unexpected,
+		InvalidReceipt,
+		InconsistentReceipts,
+		NotShardMember,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn offchain_worker(now: T::BlockNumber) {
+			if let Err(e) = Self::run_offchain_worker(now) {
+				log::debug!(target: "runtime::parentchain", "offchain worker did not run at {:?}: {:?}", now, e);
+			}
+		}
 	}
 
 	#[pallet::call]
 	impl<T:Config> Pallet<T> {
 
-		/// (ROOT ONLY) set the confirmation threshold for a shard
+		/// (SHARD ADMIN ONLY) set the confirmation threshold for a shard
 		#[pallet::weight(<T as Config>::WeightInfo::set_shard_confirmation_threshold())]
 		pub fn set_shard_confirmation_threshold(
 			origin: OriginFor<T>,
 			shard_id: ShardId,
 			threshold: u64,
 		) -> DispatchResult {
-			ensure_root(origin)?;
+			Self::ensure_shard_admin(origin, shard_id)?;
 			<ShardConfirmationThreshold<T>>::mutate(&shard_id, |t| {
 				* t = Some(threshold)
 			});
@@ -115,15 +211,87 @@ pub mod pallet {
 			outcome: Vec<Vec<u8>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::do_submit_outcome(who, block_number, shard_id, state_root, outcome_call_index, outcome, Vec::new())
+		}
+
+		/// submit a batch of outcomes for a block, with the calls some of them push to other shards.
+		/// Receipts are (source call index, target shard, encoded calls) and are routed once the block is confirmed
+		#[pallet::weight(<T as Config>::WeightInfo::submit_outcome_with_receipts(
+			outcome_call_index.len() as u32,
+			receipts.len() as u32,
+		))]
+		pub fn submit_outcome_with_receipts(
+			origin: OriginFor<T>,
+			block_number: T::BlockNumber, shard_id: ShardId,
+
+			state_root: [u8; 32],
+
+			outcome_call_index: Vec<CallIndex>,
+			outcome: Vec<Vec<u8>>,
+			receipts: Vec<(CallIndex, ShardId, Vec<u8>)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_submit_outcome(who, block_number, shard_id, state_root, outcome_call_index, outcome, receipts)
+		}
 		
+		/// (ROOT ONLY/TEST ONLY) WILL BE REMOVED force remove all call_records
+		#[pallet::weight(0)]
+		pub fn reset_outcomes_record(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			<StateRoot::<T>>::remove_prefix(0, None);
+			<Confirmation::<T>>::remove_prefix(0, None);
+			<Confirmers::<T>>::remove_prefix(0, None);
+			<BlockConfirmationThreshold::<T>>::remove_prefix(0, None);
+			<ReceiptsRoot::<T>>::remove_prefix(0, None);
+			<PendingReceipts::<T>>::remove_prefix(0, None);
+			<LatestConfirmedBlock::<T>>::remove(0);
+
+			let mut cur_call_index: CallIndex = 0;
+			while Self::outcome_of(cur_call_index).is_some() {
+				<Outcome::<T>>::remove(cur_call_index);
+				<OutcomeBlock::<T>>::remove(cur_call_index);
+				cur_call_index += 1;
+			}
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// the admin origin, or the shard admin origin of the account appointed to the shard
+		pub fn ensure_shard_admin(origin: OriginFor<T>, shard_id: ShardId) -> DispatchResult {
+			let origin = match T::AdminOrigin::try_origin(origin) {
+				Ok(_) => return Ok(()),
+				Err(origin) => origin,
+			};
+
+			let who = T::ShardAdminOrigin::ensure_origin(origin)?;
+			ensure!(T::Shards::is_shard_admin(shard_id, &who), Error::<T>::Unauthorized);
+			Ok(())
+		}
+
+		/// record the outcomes of a keeper for a block, the first submission of a state root
+		/// stores the outcomes and receipts that later matching submissions confirm.
+		/// A confirmation attests the state root and the receipts of the block
+		pub fn do_submit_outcome(
+			who: T::AccountId,
+			block_number: T::BlockNumber, shard_id: ShardId,
+			state_root: [u8; 32],
+			outcome_call_index: Vec<CallIndex>,
+			outcome: Vec<Vec<u8>>,
+			receipts: Vec<(CallIndex, ShardId, Vec<u8>)>,
+		) -> DispatchResult {
 			// TODO: validate outcome
 			ensure!(pallet_registry::Pallet::<T>::is_valid_shard_id(shard_id), Error::<T>::InvalidShardId);
+			ensure!(T::Shards::is_accepting_outcomes(shard_id), Error::<T>::ShardNotActive);
 			ensure!(pallet_registry::Pallet::<T>::is_valid_secret_keeper(&who), Error::<T>::NotInBeaconTurn);
+			ensure!(pallet_registry::Pallet::<T>::is_shard_member(shard_id, &who), Error::<T>::NotShardMember);
 			let now = frame_system::Pallet::<T>::block_number();
 			ensure!(now <= block_number + T::DelayThreshold::get(), Error::<T>::OutcomeSubmissionTooLate);
 
-			// by default - confirmation at 1
-			let threshold = Self::shard_confirmation_threshold(shard_id).unwrap_or(1);
+			// later changes of the shard threshold do not apply to blocks already submitted
+			let threshold = Self::confirmation_threshold_at(shard_id, block_number);
 
 			// TODO: maybe we should allow any submission and let clients handle the rest
 			// ensure!(pallet_registry::Pallet::<T>::is_beacon_turn(block_number, &who, shard_id, threshold), Error::<T>::Unauthorized);
@@ -135,6 +303,22 @@ pub mod pallet {
 				ensure!(Self::validate_outcome(&o), Error::<T>::InvalidOutcome);
 			}
 
+			// receipts are pushed on behalf of a call of this submission that is scheduled for the block
+			let mut bounded_receipts = BoundedVec::<_, T::MaxOutcomePerSubmission>::default();
+			for (call_index, target_shard_id, calls) in receipts.into_iter() {
+				ensure!(outcome_call_index.contains(&call_index), Error::<T>::InvalidReceipt);
+				ensure!(T::Calls::is_call_of(shard_id, block_number, call_index), Error::<T>::InvalidReceipt);
+				let calls = BoundedVec::<u8, T::MaxSizePerOutcome>::try_from(calls)
+					.map_err(|_| Error::<T>::InvalidReceipt)?;
+				bounded_receipts.try_push((call_index, target_shard_id, calls))
+					.map_err(|_| Error::<T>::InvalidReceipt)?;
+			}
+			let receipts_root = T::Hashing::hash_of(&bounded_receipts);
+
+			let mut confirmers = Self::confirmers_of(shard_id, block_number).unwrap_or_default();
+			ensure!(!confirmers.contains(&who), Error::<T>::DuplicateSubmission);
+			confirmers.try_push(who.clone()).map_err(|_| Error::<T>::Unexpected)?;
+
 			let old_state_root = Self::state_root_at(shard_id, block_number);
 			// 1. existing state_root doesnt match the current state_root 
 			if old_state_root != Some(state_root) {
@@ -147,17 +331,23 @@ pub mod pallet {
 				// 3. The record has never been written before
 				// We can write it - exit the if statement
 			} else {
-				// 4. old state root matches the current state_root
+				// 4. old state root matches the current state_root - so must the receipts
+				ensure!(
+					Self::receipts_root_at(shard_id, block_number) == Some(receipts_root),
+					Error::<T>::InconsistentReceipts
+				);
+
 				// 5. update the confirmation count
 				<Confirmation<T>>::mutate(&shard_id, &block_number, |confirmation| {
 					*confirmation = Some(confirmation.unwrap_or(0) + 1);
 				});
+				<Confirmers<T>>::insert(&shard_id, &block_number, &confirmers);
 				let confirms = Self::confirmation_of(shard_id, block_number).unwrap_or(0);
 				// ensure!(confirms <= threshold, Error::<T>::Unauthorized);
 
 				// 6. threshold has been met. The block is confirmed!
 				if confirms == threshold {
-					Self::deposit_event(Event::<T>::BlockConfirmed(block_number));
+					Self::confirm_block(shard_id, block_number, &confirmers[..]);
 				}
 
 				return Ok(());
@@ -178,9 +368,17 @@ pub mod pallet {
 					.map_err(|_| Error::<T>::InvalidOutcome)?;
 
 				<Outcome<T>>::insert(&call_index, &bounded_outcome);
+				<OutcomeBlock<T>>::insert(&call_index, (shard_id, block_number));
+			}
+
+			if !bounded_receipts.is_empty() {
+				<PendingReceipts<T>>::insert(&shard_id, &block_number, bounded_receipts);
 			}
 
 			<StateRoot<T>>::insert(&shard_id, &block_number, &state_root);
+			<ReceiptsRoot<T>>::insert(&shard_id, &block_number, receipts_root);
+			<Confirmers<T>>::insert(&shard_id, &block_number, &confirmers);
+			<BlockConfirmationThreshold<T>>::insert(&shard_id, &block_number, threshold);
 
 			// emit BlockSynced only at the first time!
 			Self::deposit_event(Event::<T>::BlockSynced(block_number));
@@ -188,32 +386,68 @@ pub mod pallet {
 			if threshold == 1 {
 				// if this is the first time the state is syced & threshold == 1
 				// the block is confirmed!
-				Self::deposit_event(Event::<T>::BlockConfirmed(block_number));
+				Self::confirm_block(shard_id, block_number, &confirmers[..]);
 			}
 			Ok(())
 		}
-		
-		/// (ROOT ONLY/TEST ONLY) WILL BE REMOVED force remove all call_records
-		#[pallet::weight(0)]
-		pub fn reset_outcomes_record(origin: OriginFor<T>) -> DispatchResult {
-			ensure_root(origin)?;
 
-			<StateRoot::<T>>::remove_prefix(0, None);
-			<Confirmation::<T>>::remove_prefix(0, None);
+		pub fn validate_outcome(outcome: &Vec<u8>) -> bool {
+			outcome.len() < T::MaxSizePerOutcome::get() as usize
+		}
 
-			let mut cur_call_index: CallIndex = 0;
-			while Self::outcome_of(cur_call_index).is_some() {
-				<Outcome::<T>>::remove(cur_call_index);
-				cur_call_index += 1;
+		pub fn confirm_block(
+			shard_id: ShardId,
+			block_number: T::BlockNumber,
+			confirmers: &[T::AccountId],
+		) {
+			<LatestConfirmedBlock<T>>::mutate(&shard_id, |latest| {
+				if latest.map_or(true, |l| l < block_number) {
+					*latest = Some(block_number);
+				}
+			});
+
+			T::OnBlockConfirmed::on_block_confirmed(shard_id, block_number, confirmers);
+			Self::deposit_event(Event::<T>::BlockConfirmed(block_number));
+
+			for (call_index, target_shard_id, calls) in <PendingReceipts<T>>::take(&shard_id, &block_number).unwrap_or_default() {
+				if T::Receipts::route_receipt(shard_id, block_number, call_index, target_shard_id, calls.into_inner()) {
+					Self::deposit_event(Event::<T>::ReceiptRouted(shard_id, call_index, target_shard_id));
+				} else {
+					Self::deposit_event(Event::<T>::ReceiptDropped(shard_id, call_index, target_shard_id));
+				}
 			}
+		}
 
-			Ok(())
+		/// the threshold of a block once an outcome has been submitted for it, the current threshold
+		/// of the shard otherwise - confirmation at 1 by default
+		pub fn confirmation_threshold_at(shard_id: ShardId, block_number: T::BlockNumber) -> u64 {
+			Self::block_confirmation_threshold_of(shard_id, block_number)
+				.or_else(|| Self::shard_confirmation_threshold(shard_id))
+				.unwrap_or(1)
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		pub fn validate_outcome(outcome: &Vec<u8>) -> bool {
-			outcome.len() < T::MaxSizePerOutcome::get() as usize
+		/// blocks nothing has been submitted for are not confirmed
+		pub fn is_block_confirmed(shard_id: ShardId, block_number: T::BlockNumber) -> bool {
+			match Self::confirmation_of(shard_id, block_number) {
+				Some(confirms) => confirms >= Self::confirmation_threshold_at(shard_id, block_number),
+				None => false,
+			}
+		}
+
+		/// the outcome of a call, only once the block it was submitted for is confirmed
+		pub fn confirmed_outcome_of(call_index: CallIndex) -> Option<Vec<u8>> {
+			let (shard_id, block_number) = Self::outcome_block_of(call_index)?;
+			if !Self::is_block_confirmed(shard_id, block_number) {
+				return None;
+			}
+
+			Self::outcome_of(call_index).map(|o| o.into_inner())
+		}
+
+		/// the latest confirmed block of a shard and its state root
+		pub fn latest_confirmed_state_root_of(shard_id: ShardId) -> Option<(T::BlockNumber, [u8; 32])> {
+			let block_number = Self::latest_confirmed_block_of(shard_id)?;
+			Self::state_root_at(shard_id, block_number).map(|root| (block_number, root))
 		}
 	}
 }
@@ -4,9 +4,14 @@ use pallet_registry;
 use frame_support::traits::{ConstU16, ConstU32, ConstU64};
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
 };
+use skw_blockchain_primitives::{
+	types::{CallIndex, ShardId},
+	traits::{CallHistoryProvider, ReceiptRouter},
+};
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -57,6 +62,7 @@ impl pallet_registry::Config for Test {
 	type RegistrationDuration = ConstU64<100_000>;
 	type MaxActiveShards = ConstU32<1_000>;
 	type MaxSecretKeepers = ConstU32<5_000>;
+	type MaxExpirationChecksPerBlock = ConstU32<3>;
 }
 
 impl pallet_parentchain::Config for Test {
@@ -65,6 +71,99 @@ impl pallet_parentchain::Config for Test {
 	type DelayThreshold = ConstU64<5_000>;
 	type MaxOutcomePerSubmission = ConstU32<20>;
 	type MaxSizePerOutcome = ConstU32<1024>;
+	type Shards = ();
+	type OnBlockConfirmed = ();
+	type Calls = MockCalls;
+	type Receipts = MockReceipts;
+	type AuthorityId = TestAuthId;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type ShardAdminOrigin = frame_system::EnsureSigned<u64>;
+}
+
+pub type Extrinsic = TestXt<Call, ()>;
+
+thread_local! {
+	pub static SCHEDULED_CALLS: RefCell<Vec<(ShardId, u64, CallIndex)>> = RefCell::new(Vec::new());
+}
+
+/// a single call at block 1 of shard 0, plus the calls benchmarks schedule
+pub struct MockCalls;
+impl CallHistoryProvider<u64> for MockCalls {
+	fn calls_of(shard_id: ShardId, block_number: u64) -> Vec<(CallIndex, Vec<u8>)> {
+		let mut calls = match (shard_id, block_number) {
+			(0, 1) => vec![(0, vec![1, 2, 3])],
+			_ => Vec::new(),
+		};
+		SCHEDULED_CALLS.with(|c| calls.extend(
+			c.borrow().iter()
+				.filter(|(s, b, _)| *s == shard_id && *b == block_number)
+				.map(|(_, _, call_index)| (*call_index, Vec::new()))
+		));
+		calls
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_calls_of(shard_id: ShardId, block_number: u64, call_indexes: Vec<CallIndex>) {
+		SCHEDULED_CALLS.with(|c| c.borrow_mut().extend(
+			call_indexes.into_iter().map(|call_index| (shard_id, block_number, call_index))
+		));
+	}
+}
+
+thread_local! {
+	pub static ROUTED_RECEIPTS: RefCell<Vec<(ShardId, CallIndex, ShardId, Vec<u8>)>> = RefCell::new(Vec::new());
+}
+
+/// records the routed receipts, shard 2 does not accept any
+pub struct MockReceipts;
+impl ReceiptRouter<u64> for MockReceipts {
+	fn route_receipt(
+		source_shard_id: ShardId,
+		_source_block_number: u64,
+		source_call_index: CallIndex,
+		target_shard_id: ShardId,
+		calls: Vec<u8>,
+	) -> bool {
+		if target_shard_id == 2 {
+			return false;
+		}
+		ROUTED_RECEIPTS.with(|r| r.borrow_mut().push((source_shard_id, source_call_index, target_shard_id, calls)));
+		true
+	}
+}
+
+pub struct TestAuthId;
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
 }
 
 // Build genesis storage according to the mock runtime.
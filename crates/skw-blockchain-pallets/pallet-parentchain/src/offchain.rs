@@ -0,0 +1,286 @@
+//! Offchain worker for secret keeper nodes.
+//!
+//! For every shard the keeper is running (see `pallet_registry::register_running_shard`),
+//! calls scheduled in new blocks are handed to a local runner over HTTP and the outcomes
+//! returned by the runner are submitted through a signed `submit_outcome`, or
+//! `submit_outcome_with_receipts` when some of them push calls to other shards.
+//!
+//! The worker is disabled unless both are configured on the keeper node:
+//! - a key of type `skw!` in the keystore, belonging to a registered secret keeper
+//! - the runner endpoint in the persistent offchain storage under `RUNNER_ENDPOINT_KEY`,
+//!   e.g. through the `offchain_localStorageSet` RPC
+//!
+//! The runner receives a SCALE encoded `RunnerRequest` as the body of a POST request
+//! and is expected to reply with a SCALE encoded `RunnerResponse`.
+//!
+//! A block is only marked as processed once the submission of the keeper is included or
+//! outcomes can no longer be submitted for it. Submissions that are not included within
+//! `RESUBMIT_AFTER_BLOCKS` blocks are sent again, and a failing shard does not hold back
+//! the other shards of the keeper.
+
+use crate::pallet::{Call, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use frame_system::offchain::{Account, SendSignedTransaction, Signer};
+use skw_blockchain_primitives::{
+	types::{CallIndex, ShardId},
+	traits::{CallHistoryProvider, ShardStatusProvider},
+};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+	offchain::{
+		http,
+		storage::StorageValueRef,
+		StorageKind,
+		storage_lock::{StorageLock, Time},
+		Duration,
+	},
+	traits::{One, Saturating},
+	RuntimeDebug,
+};
+use sp_std::{vec, vec::Vec};
+
+/// key type of the accounts secret keepers submit outcomes with
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"skw!");
+
+/// persistent offchain storage key of the runner endpoint, e.g. `http://localhost:8845`
+pub const RUNNER_ENDPOINT_KEY: &[u8] = b"skw-parentchain::runner-endpoint";
+
+const LAST_PROCESSED_KEY: &[u8] = b"skw-parentchain::last-processed::";
+const SUBMITTED_KEY: &[u8] = b"skw-parentchain::submitted::";
+const LOCK_KEY: &[u8] = b"skw-parentchain::lock";
+
+/// maximum number of blocks of a shard handed to the runner in one run
+const MAX_BLOCKS_PER_RUN: u32 = 10;
+/// blocks to wait for a submission to be included before sending it again
+const RESUBMIT_AFTER_BLOCKS: u32 = 5;
+const HTTP_TIMEOUT_MS: u64 = 10_000;
+const LOCK_TIMEOUT_MS: u64 = 60_000;
+
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct OutcomeAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OutcomeAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for OutcomeAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// calls of a shard scheduled in a block, sent to the runner
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RunnerRequest<BlockNumber> {
+	pub shard_id: ShardId,
+	pub block_number: BlockNumber,
+	pub calls: Vec<(CallIndex, Vec<u8>)>,
+}
+
+/// result of executing a block of calls, returned by the runner
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RunnerResponse {
+	pub state_root: [u8; 32],
+	pub outcome_call_index: Vec<CallIndex>,
+	pub outcome: Vec<Vec<u8>>,
+	/// (source call index, target shard, encoded calls) pushed to other shards
+	pub receipts: Vec<(CallIndex, ShardId, Vec<u8>)>,
+}
+
+#[derive(PartialEq, Eq, RuntimeDebug)]
+pub enum OffchainError {
+	/// no runner endpoint configured or it is not valid utf8
+	NoRunner,
+	/// no key of a registered secret keeper in the keystore
+	NoSigner,
+	/// another run of the worker holds the lock
+	Locked,
+	Http(http::Error),
+	InvalidResponse,
+	SubmissionFailed,
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn run_offchain_worker(now: T::BlockNumber) -> Result<(), OffchainError> {
+		// stored as raw bytes, so that it can be set with `offchain_localStorageSet`
+		let endpoint = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, RUNNER_ENDPOINT_KEY)
+			.ok_or(OffchainError::NoRunner)?;
+		let endpoint = sp_std::str::from_utf8(&endpoint).map_err(|_| OffchainError::NoRunner)?;
+
+		let mut lock = StorageLock::<Time>::with_deadline(LOCK_KEY, Duration::from_millis(LOCK_TIMEOUT_MS));
+		let _guard = lock.try_lock().map_err(|_| OffchainError::Locked)?;
+
+		let signer = Signer::<T, T::AuthorityId>::all_accounts();
+		let account = signer
+			.accounts_from_keys()
+			.find(|account| pallet_registry::Pallet::<T>::is_valid_secret_keeper(&account.id))
+			.ok_or(OffchainError::NoSigner)?;
+
+		let shards = pallet_registry::Pallet::<T>::running_shards_of(&account.id).unwrap_or_default();
+		for shard_id in shards.into_iter() {
+			if !T::Shards::is_accepting_outcomes(shard_id) {
+				continue;
+			}
+
+			if let Err(e) = Self::process_shard(endpoint, &account, shard_id, now) {
+				log::debug!(target: "runtime::parentchain", "offchain worker stopped on shard {} at {:?}: {:?}", shard_id, now, e);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// process the blocks of a shard following the last processed one. The last processed
+	/// block only moves forward over blocks that are done, so a pending submission is checked
+	/// again in the next run.
+	fn process_shard(
+		endpoint: &str,
+		account: &Account<T>,
+		shard_id: ShardId,
+		now: T::BlockNumber,
+	) -> Result<(), OffchainError> {
+		let last_processed_key = Self::last_processed_key(shard_id);
+		let last_processed = StorageValueRef::persistent(&last_processed_key);
+
+		// start from the current block the first time a shard is seen
+		let mut block_number = match last_processed.get::<T::BlockNumber>().ok().flatten() {
+			Some(b) => b.saturating_add(One::one()),
+			None => {
+				last_processed.set(&now.saturating_sub(One::one()));
+				now
+			},
+		};
+		let stop = block_number.saturating_add(MAX_BLOCKS_PER_RUN.into()).min(now.saturating_add(One::one()));
+
+		let mut all_done = true;
+		while block_number < stop {
+			let done = Self::process_block(endpoint, account, shard_id, block_number, now)?;
+			all_done = all_done && done;
+			if all_done {
+				last_processed.set(&block_number);
+			}
+			block_number += One::one();
+		}
+
+		Ok(())
+	}
+
+	/// run and submit the calls of a block, unless it is already submitted. Returns whether
+	/// nothing is left to do for the block: it has no calls, the submission of `account` is
+	/// included or outcomes can no longer be submitted.
+	fn process_block(
+		endpoint: &str,
+		account: &Account<T>,
+		shard_id: ShardId,
+		block_number: T::BlockNumber,
+		now: T::BlockNumber,
+	) -> Result<bool, OffchainError> {
+		let submitted_key = Self::submitted_key(shard_id, block_number);
+		let mut submitted = StorageValueRef::persistent(&submitted_key);
+
+		let included = Self::confirmers_of(shard_id, block_number)
+			.map_or(false, |confirmers| confirmers.contains(&account.id));
+		if included || now > block_number + T::DelayThreshold::get() {
+			submitted.clear();
+			return Ok(true);
+		}
+
+		let calls = T::Calls::calls_of(shard_id, block_number);
+		if calls.is_empty() {
+			return Ok(true);
+		}
+
+		// give a pending submission time to be included
+		if let Some(submitted_at) = submitted.get::<T::BlockNumber>().ok().flatten() {
+			if now < submitted_at.saturating_add(RESUBMIT_AFTER_BLOCKS.into()) {
+				return Ok(false);
+			}
+		}
+
+		let response = Self::run_calls(endpoint, RunnerRequest { shard_id, block_number, calls })?;
+		let results = Signer::<T, T::AuthorityId>::all_accounts()
+			.with_filter(vec![account.public.clone()])
+			.send_signed_transaction(|_| if response.receipts.is_empty() {
+				Call::submit_outcome {
+					block_number,
+					shard_id,
+					state_root: response.state_root,
+					outcome_call_index: response.outcome_call_index.clone(),
+					outcome: response.outcome.clone(),
+				}
+			} else {
+				Call::submit_outcome_with_receipts {
+					block_number,
+					shard_id,
+					state_root: response.state_root,
+					outcome_call_index: response.outcome_call_index.clone(),
+					outcome: response.outcome.clone(),
+					receipts: response.receipts.clone(),
+				}
+			});
+
+		if results.is_empty() || results.iter().any(|(_, result)| result.is_err()) {
+			return Err(OffchainError::SubmissionFailed);
+		}
+
+		submitted.set(&now);
+		Ok(false)
+	}
+
+	/// hand a block of calls to the runner and wait for the outcomes
+	pub fn run_calls(
+		endpoint: &str,
+		request: RunnerRequest<T::BlockNumber>,
+	) -> Result<RunnerResponse, OffchainError> {
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(HTTP_TIMEOUT_MS));
+		let pending = http::Request::post(endpoint, vec![request.encode()])
+			.add_header("Content-Type", "application/octet-stream")
+			.deadline(deadline)
+			.send()
+			.map_err(|_| OffchainError::Http(http::Error::IoError))?;
+
+		let response = pending
+			.try_wait(deadline)
+			.map_err(|_| OffchainError::Http(http::Error::DeadlineReached))?
+			.map_err(OffchainError::Http)?;
+		if response.code != 200 {
+			return Err(OffchainError::Http(http::Error::Unknown));
+		}
+
+		let body = response.body().collect::<Vec<u8>>();
+		let response = RunnerResponse::decode(&mut &body[..]).map_err(|_| OffchainError::InvalidResponse)?;
+		if response.outcome_call_index.len() != response.outcome.len() || response.outcome.iter().any(|o| !Self::validate_outcome(o)) {
+			return Err(OffchainError::InvalidResponse);
+		}
+
+		Ok(response)
+	}
+
+	fn last_processed_key(shard_id: ShardId) -> Vec<u8> {
+		let mut key = LAST_PROCESSED_KEY.to_vec();
+		key.extend(shard_id.encode());
+		key
+	}
+
+	fn submitted_key(shard_id: ShardId, block_number: T::BlockNumber) -> Vec<u8> {
+		let mut key = SUBMITTED_KEY.to_vec();
+		key.extend((shard_id, block_number).encode());
+		key
+	}
+}
+
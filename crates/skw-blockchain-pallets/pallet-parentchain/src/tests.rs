@@ -11,7 +11,7 @@ const PUBLIC_KEY: [u8; 32] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
 type AccountId = u64;
 const ALICE: AccountId = 1;
 const BOB: AccountId = 2;
-// const CHARLIE: AccountId = 3;
+const CHARLIE: AccountId = 3;
 // const DAVE: AccountId = 4;
 // const FRED: AccountId = 5;
 
@@ -295,3 +295,349 @@ fn it_validate_state_root_n_file_hash() {
 		);
 	});
 }
+
+#[test]
+fn it_records_confirmers_and_rejects_duplicates() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let public_key = PUBLIC_KEY[..].to_vec();
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(ALICE),  public_key.clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 0 ) );
+
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(BOB),  public_key.clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(BOB), 0 ) );
+
+		assert_ok!(
+			Parentchain::set_shard_confirmation_threshold( 
+				Origin::root(), 0,  2 //two confirmation
+			)
+		);
+
+		assert_ok!( Parentchain::submit_outcome( Origin::signed(ALICE), 1, 0, [0u8; 32], vec![], vec![] ) );
+		assert_noop!(
+			Parentchain::submit_outcome( Origin::signed(ALICE), 1, 0, [0u8; 32], vec![], vec![] ),
+			ParentchainError::<Test>::DuplicateSubmission
+		);
+
+		assert_ok!( Parentchain::submit_outcome( Origin::signed(BOB), 1, 0, [0u8; 32], vec![], vec![] ) );
+		assert_eq! (Parentchain::confirmers_of(0, 1).unwrap().into_inner(), vec![ALICE, BOB]);
+		assert_eq! (Parentchain::confirmation_of(0, 1), Some(2));
+		assert! (System::events().iter().any(|evt| {
+			evt.event == Event::Parentchain(ParentchainEvent::BlockConfirmed(1))
+		}));
+	});
+}
+
+#[test]
+fn it_exposes_outcomes_only_once_confirmed() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let public_key = PUBLIC_KEY[..].to_vec();
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(ALICE),  public_key.clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 0 ) );
+
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(BOB),  public_key.clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(BOB), 0 ) );
+
+		assert_ok!(
+			Parentchain::set_shard_confirmation_threshold( 
+				Origin::root(), 0,  2 //two confirmation
+			)
+		);
+
+		assert_ok!( Parentchain::submit_outcome( Origin::signed(ALICE), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]] ) );
+		assert_eq! (Parentchain::outcome_block_of(0), Some((0, 1)));
+		assert_eq! (Parentchain::confirmed_outcome_of(0), None);
+		assert_eq! (Parentchain::latest_confirmed_state_root_of(0), None);
+
+		assert_ok!( Parentchain::submit_outcome( Origin::signed(BOB), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]] ) );
+		assert_eq! (Parentchain::confirmed_outcome_of(0), Some(vec![7u8]));
+		assert_eq! (Parentchain::latest_confirmed_state_root_of(0), Some((1, [1u8; 32])));
+	});
+}
+
+#[test]
+fn it_keeps_the_threshold_of_submitted_blocks() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let public_key = PUBLIC_KEY[..].to_vec();
+		for keeper in [ALICE, BOB] {
+			assert_ok!( Registry::register_secret_keeper( Origin::signed(keeper),  public_key.clone(), Vec::new() ) );
+			assert_ok!( Registry::register_running_shard( Origin::signed(keeper), 0 ) );
+		}
+
+		// unknown block
+		assert! (!Parentchain::is_block_confirmed(0, 1));
+		assert_eq! (Parentchain::confirmed_outcome_of(0), None);
+
+		// block 1 is confirmed at the default threshold of 1
+		assert_ok!( Parentchain::submit_outcome( Origin::signed(ALICE), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]] ) );
+		assert! (Parentchain::is_block_confirmed(0, 1));
+		assert_eq! (Parentchain::block_confirmation_threshold_of(0, 1), Some(1));
+
+		// block 2 is submitted once before the threshold is raised, and stays unconfirmed
+		assert_ok!( Parentchain::set_shard_confirmation_threshold( Origin::root(), 0, 3 ) );
+		assert_ok!( Parentchain::submit_outcome( Origin::signed(ALICE), 2, 0, [2u8; 32], vec![1], vec![vec![8u8]] ) );
+		assert! (!Parentchain::is_block_confirmed(0, 2));
+		assert_eq! (Parentchain::confirmed_outcome_of(1), None);
+
+		// raising the threshold does not unconfirm block 1
+		assert! (Parentchain::is_block_confirmed(0, 1));
+		assert_eq! (Parentchain::confirmed_outcome_of(0), Some(vec![7u8]));
+		assert_eq! (Parentchain::confirmation_threshold_at(0, 1), 1);
+
+		// lowering it does not confirm block 2 behind the back of its confirmers
+		assert_ok!( Parentchain::set_shard_confirmation_threshold( Origin::root(), 0, 1 ) );
+		assert! (!Parentchain::is_block_confirmed(0, 2));
+		assert_eq! (Parentchain::confirmation_threshold_at(0, 2), 3);
+
+		// blocks nothing has been submitted for follow the current threshold
+		assert_eq! (Parentchain::confirmation_threshold_at(0, 3), 1);
+		assert! (!Parentchain::is_block_confirmed(0, 3));
+	});
+}
+
+#[test]
+fn it_routes_receipts_once_confirmed() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let public_key = PUBLIC_KEY[..].to_vec();
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(ALICE),  public_key.clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 0 ) );
+
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(BOB),  public_key.clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(BOB), 0 ) );
+
+		assert_ok!(
+			Parentchain::set_shard_confirmation_threshold( 
+				Origin::root(), 0,  2 //two confirmation
+			)
+		);
+
+		// receipts have to come from a call of the submission
+		assert_noop!(
+			Parentchain::submit_outcome_with_receipts(
+				Origin::signed(ALICE), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]], vec![(1, 1, vec![1u8])]
+			),
+			ParentchainError::<Test>::InvalidReceipt
+		);
+
+		// .. that is scheduled for the block on the shard
+		assert_noop!(
+			Parentchain::submit_outcome_with_receipts(
+				Origin::signed(ALICE), 1, 0, [1u8; 32], vec![0, 5], vec![vec![7u8], vec![8u8]], vec![(5, 1, vec![1u8])]
+			),
+			ParentchainError::<Test>::InvalidReceipt
+		);
+
+		// only keepers running the shard submit its outcomes
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(CHARLIE),  public_key.clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(CHARLIE), 1 ) );
+		assert_noop!(
+			Parentchain::submit_outcome_with_receipts(
+				Origin::signed(CHARLIE), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]], vec![(0, 1, vec![1u8])]
+			),
+			ParentchainError::<Test>::NotShardMember
+		);
+
+		assert_ok!(
+			Parentchain::submit_outcome_with_receipts(
+				Origin::signed(ALICE), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]], vec![(0, 1, vec![1u8]), (0, 2, vec![2u8])]
+			)
+		);
+		assert_eq! (Parentchain::pending_receipts_of(0, 1).unwrap().len(), 2);
+		ROUTED_RECEIPTS.with(|r| assert! (r.borrow().is_empty()));
+
+		// confirmations attest the receipts along with the state root
+		assert_noop!(
+			Parentchain::submit_outcome( Origin::signed(BOB), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]] ),
+			ParentchainError::<Test>::InconsistentReceipts
+		);
+		assert_noop!(
+			Parentchain::submit_outcome_with_receipts(
+				Origin::signed(BOB), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]], vec![(0, 1, vec![3u8]), (0, 2, vec![2u8])]
+			),
+			ParentchainError::<Test>::InconsistentReceipts
+		);
+
+		assert_ok!(
+			Parentchain::submit_outcome_with_receipts(
+				Origin::signed(BOB), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]], vec![(0, 1, vec![1u8]), (0, 2, vec![2u8])]
+			)
+		);
+		assert_eq! (Parentchain::pending_receipts_of(0, 1), None);
+		ROUTED_RECEIPTS.with(|r| assert_eq! (r.borrow().clone(), vec![(0, 0, 1, vec![1u8])]));
+
+		let events = System::events();
+		assert! (events.iter().any(|evt| evt.event == Event::Parentchain(ParentchainEvent::ReceiptRouted(0, 0, 1))));
+		assert! (events.iter().any(|evt| evt.event == Event::Parentchain(ParentchainEvent::ReceiptDropped(0, 0, 2))));
+	});
+}
+
+#[test]
+fn it_runs_calls_and_submits_outcomes_offchain() {
+	use codec::{Decode, Encode};
+	use frame_support::traits::Hooks;
+	use sp_core::offchain::{
+		testing::{self, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt,
+	};
+	use sp_runtime::testing::UintAuthorityId;
+	use crate::offchain::{RunnerRequest, RunnerResponse, RUNNER_ENDPOINT_KEY};
+
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+
+	let mut t = new_test_ext();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+
+	let response = RunnerResponse {
+		state_root: [1u8; 32],
+		outcome_call_index: vec![0],
+		outcome: vec![vec![7u8]],
+		receipts: vec![],
+	};
+	offchain_state.write().expect_request(testing::PendingRequest {
+		method: "POST".into(),
+		uri: "http://localhost:8845".into(),
+		headers: vec![("Content-Type".into(), "application/octet-stream".into())],
+		body: RunnerRequest { shard_id: 0, block_number: 1u64, calls: vec![(0, vec![1, 2, 3])] }.encode(),
+		response: Some(response.encode()),
+		sent: true,
+		..Default::default()
+	});
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+		UintAuthorityId::set_all_keys(vec![ALICE]);
+
+		let public_key = PUBLIC_KEY[..].to_vec();
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(ALICE),  public_key.clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 0 ) );
+
+		// no runner configured - nothing happens
+		Parentchain::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, RUNNER_ENDPOINT_KEY, b"http://localhost:8845");
+		Parentchain::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature.unwrap().0, 0);
+		assert_eq!(tx.call, Call::Parentchain(crate::Call::submit_outcome {
+			block_number: 1,
+			shard_id: 0,
+			state_root: [1u8; 32],
+			outcome_call_index: vec![0],
+			outcome: vec![vec![7u8]],
+		}));
+
+		// block 1 is not handed to the runner twice while the submission is pending
+		Parentchain::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// a submission that is not included is sent again
+		offchain_state.write().expect_request(testing::PendingRequest {
+			method: "POST".into(),
+			uri: "http://localhost:8845".into(),
+			headers: vec![("Content-Type".into(), "application/octet-stream".into())],
+			body: RunnerRequest { shard_id: 0, block_number: 1u64, calls: vec![(0, vec![1, 2, 3])] }.encode(),
+			response: Some(response.encode()),
+			sent: true,
+			..Default::default()
+		});
+		Parentchain::offchain_worker(6);
+		assert_eq!(pool_state.write().transactions.len(), 1);
+		pool_state.write().transactions.clear();
+
+		// once included, block 1 is done
+		assert_ok!( Parentchain::submit_outcome(Origin::signed(ALICE), 1, 0, [1u8; 32], vec![0], vec![vec![7u8]]) );
+		Parentchain::offchain_worker(12);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn it_keeps_processing_shards_after_a_failing_shard() {
+	use codec::{Decode, Encode};
+	use frame_support::traits::Hooks;
+	use sp_core::offchain::{
+		testing::{self, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt,
+	};
+	use sp_runtime::testing::UintAuthorityId;
+	use crate::offchain::{RunnerRequest, RunnerResponse, RUNNER_ENDPOINT_KEY};
+
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+
+	let mut t = new_test_ext();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+
+	let request = |shard_id, calls, response: Vec<u8>| testing::PendingRequest {
+		method: "POST".into(),
+		uri: "http://localhost:8845".into(),
+		headers: vec![("Content-Type".into(), "application/octet-stream".into())],
+		body: RunnerRequest { shard_id, block_number: 1u64, calls }.encode(),
+		response: Some(response),
+		sent: true,
+		..Default::default()
+	};
+	let response = |call_index| RunnerResponse {
+		state_root: [1u8; 32],
+		outcome_call_index: vec![call_index],
+		outcome: vec![vec![7u8]],
+		receipts: vec![],
+	}.encode();
+
+	// the runner fails on shard 0 but shard 1 is still processed
+	offchain_state.write().expect_request(request(0, vec![(0, vec![1, 2, 3])], vec![0u8]));
+	offchain_state.write().expect_request(request(1, vec![(5, Vec::new())], response(5)));
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+		UintAuthorityId::set_all_keys(vec![ALICE]);
+		SCHEDULED_CALLS.with(|c| c.borrow_mut().push((1, 1, 5)));
+
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(ALICE),  PUBLIC_KEY[..].to_vec(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 0 ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 1 ) );
+
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, RUNNER_ENDPOINT_KEY, b"http://localhost:8845");
+		Parentchain::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.call, Call::Parentchain(crate::Call::submit_outcome {
+			block_number: 1,
+			shard_id: 1,
+			state_root: [1u8; 32],
+			outcome_call_index: vec![5],
+			outcome: vec![vec![7u8]],
+		}));
+
+		// the failed block of shard 0 is retried in the next run
+		offchain_state.write().expect_request(request(0, vec![(0, vec![1, 2, 3])], response(0)));
+		Parentchain::offchain_worker(2);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert!(matches!(tx.call, Call::Parentchain(crate::Call::submit_outcome { shard_id: 0, .. })));
+	});
+}
@@ -43,53 +43,142 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn set_shard_confirmation_threshold() -> Weight;
 	fn submit_outcome(s: u32, ) -> Weight;
+	fn submit_outcome_with_receipts(s: u32, r: u32, ) -> Weight;
 }
 
 /// Weights for pallet_parentchain using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: SContract ShardAdmin (r:1 w:0)
 	// Storage: Parentchain ShardConfirmationThreshold (r:1 w:1)
 	fn set_shard_confirmation_threshold() -> Weight {
-		(2_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+		(3_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: Registry Expiration (r:1 w:0)
 	// Storage: Registry PublicKey (r:1 w:0)
+	// Storage: Registry BeaconIndex (r:1 w:0)
+	// Storage: Parentchain BlockConfirmationThreshold (r:1 w:1)
 	// Storage: Parentchain ShardConfirmationThreshold (r:1 w:0)
 	// Storage: Parentchain StateRoot (r:1 w:1)
+	// Storage: Parentchain ReceiptsRoot (r:1 w:1)
 	// Storage: Parentchain Confirmation (r:1 w:1)
+	// Storage: Parentchain Confirmers (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract PruneQueueRange (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SContract PruneQueue (r:0 w:1)
+	// Storage: Parentchain LatestConfirmedBlock (r:1 w:1)
 	// Storage: Parentchain Outcome (r:0 w:1)
+	// Storage: Parentchain OutcomeBlock (r:0 w:1)
 	fn submit_outcome(s: u32, ) -> Weight {
-		(0 as Weight)
+		(18_000_000 as Weight)
 			// Standard Error: 91_000
 			.saturating_add((26_741_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+	}
+	// Storage: Registry Expiration (r:1 w:0)
+	// Storage: Registry PublicKey (r:1 w:0)
+	// Storage: Registry BeaconIndex (r:1 w:0)
+	// Storage: Parentchain BlockConfirmationThreshold (r:1 w:1)
+	// Storage: Parentchain ShardConfirmationThreshold (r:1 w:0)
+	// Storage: Parentchain StateRoot (r:1 w:1)
+	// Storage: Parentchain ReceiptsRoot (r:1 w:1)
+	// Storage: Parentchain Confirmation (r:1 w:1)
+	// Storage: Parentchain Confirmers (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract PruneQueueRange (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SContract PruneQueue (r:0 w:1)
+	// Storage: Parentchain LatestConfirmedBlock (r:1 w:1)
+	// Storage: Parentchain PendingReceipts (r:1 w:2)
+	// Storage: Parentchain Outcome (r:0 w:1)
+	// Storage: Parentchain OutcomeBlock (r:0 w:1)
+	// Storage: SContract CallHistory (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallQueue (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
+	// Storage: SContract ReceiptSource (r:0 w:1)
+	fn submit_outcome_with_receipts(s: u32, r: u32, ) -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add((26_741_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((22_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(r as Weight)))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// Storage: SContract ShardAdmin (r:1 w:0)
 	// Storage: Parentchain ShardConfirmationThreshold (r:1 w:1)
 	fn set_shard_confirmation_threshold() -> Weight {
-		(2_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+		(3_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	// Storage: Registry Expiration (r:1 w:0)
 	// Storage: Registry PublicKey (r:1 w:0)
+	// Storage: Registry BeaconIndex (r:1 w:0)
+	// Storage: Parentchain BlockConfirmationThreshold (r:1 w:1)
 	// Storage: Parentchain ShardConfirmationThreshold (r:1 w:0)
 	// Storage: Parentchain StateRoot (r:1 w:1)
+	// Storage: Parentchain ReceiptsRoot (r:1 w:1)
 	// Storage: Parentchain Confirmation (r:1 w:1)
+	// Storage: Parentchain Confirmers (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract PruneQueueRange (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SContract PruneQueue (r:0 w:1)
+	// Storage: Parentchain LatestConfirmedBlock (r:1 w:1)
 	// Storage: Parentchain Outcome (r:0 w:1)
+	// Storage: Parentchain OutcomeBlock (r:0 w:1)
 	fn submit_outcome(s: u32, ) -> Weight {
-		(0 as Weight)
+		(18_000_000 as Weight)
 			// Standard Error: 91_000
 			.saturating_add((26_741_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+	}
+	// Storage: Registry Expiration (r:1 w:0)
+	// Storage: Registry PublicKey (r:1 w:0)
+	// Storage: Registry BeaconIndex (r:1 w:0)
+	// Storage: Parentchain BlockConfirmationThreshold (r:1 w:1)
+	// Storage: Parentchain ShardConfirmationThreshold (r:1 w:0)
+	// Storage: Parentchain StateRoot (r:1 w:1)
+	// Storage: Parentchain ReceiptsRoot (r:1 w:1)
+	// Storage: Parentchain Confirmation (r:1 w:1)
+	// Storage: Parentchain Confirmers (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract PruneQueueRange (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SContract PruneQueue (r:0 w:1)
+	// Storage: Parentchain LatestConfirmedBlock (r:1 w:1)
+	// Storage: Parentchain PendingReceipts (r:1 w:2)
+	// Storage: Parentchain Outcome (r:0 w:1)
+	// Storage: Parentchain OutcomeBlock (r:0 w:1)
+	// Storage: SContract CallHistory (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallQueue (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
+	// Storage: SContract ReceiptSource (r:0 w:1)
+	fn submit_outcome_with_receipts(s: u32, r: u32, ) -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add((26_741_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((22_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(r as Weight)))
 	}
 }
@@ -1,14 +1,27 @@
 use super::*;
 
 use frame_system::RawOrigin;
-use frame_benchmarking::{benchmarks, whitelisted_caller, impl_benchmark_test_suite};
+use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
+use frame_support::{traits::Hooks, BoundedVec};
 
 #[allow(unused)]
 use crate::Pallet as Registry;
-use sp_std::vec;
+use sp_std::{vec, vec::Vec};
+use skw_blockchain_primitives::types::ShardId;
 
 const PUBLIC_KEY: [u8; 32] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
 
+/// `m` members holding the first beacon indexes of a shard
+fn fill_shard<T: Config>(shard: ShardId, m: u32) {
+	let members: Vec<T::AccountId> = (0..m).map(|i| account("filler", i, 0)).collect();
+	for (index, who) in members.iter().enumerate() {
+		<BeaconIndex<T>>::insert(shard, who, (index + 1) as u64);
+	}
+	<BeaconCount<T>>::insert(shard, m as u64);
+	<ShardMembers<T>>::insert(shard, BoundedVec::<T::AccountId, T::MaxSecretKeepers>::try_from(members)
+		.expect("benchmarks stay within MaxSecretKeepers"));
+}
+
 benchmarks! {
 	register_secret_keeper {
 		let caller: T::AccountId = whitelisted_caller();
@@ -32,15 +45,33 @@ benchmarks! {
 	}
 
 	remove_registration {
+		let s in 1 .. T::MaxActiveShards::get();
+		// members of the first shard ahead of the caller
+		let m in 0 .. T::MaxSecretKeepers::get() - 2;
+
+		fill_shard::<T>(0, m);
 		let caller: T::AccountId = whitelisted_caller();
+		let member: T::AccountId = account("member", 0, 0);
 		Registry::<T>::register_secret_keeper(RawOrigin::Signed(caller.clone()).into(), PUBLIC_KEY.to_vec(), vec![0, 0, 0, 0, 0, 0])?;
+		Registry::<T>::register_secret_keeper(RawOrigin::Signed(member.clone()).into(), PUBLIC_KEY.to_vec(), vec![0, 0, 0, 0, 0, 0])?;
+
+		// the member registered last takes over the beacon index of the caller in every shard
+		for shard in 0 .. s {
+			Registry::<T>::register_running_shard(RawOrigin::Signed(caller.clone()).into(), shard)?;
+			Registry::<T>::register_running_shard(RawOrigin::Signed(member.clone()).into(), shard)?;
+		}
 	}: remove_registration(RawOrigin::Signed(caller.clone()))
 	verify {
 		let all_secret_keepers = Registry::<T>::secret_keepers().unwrap();
-		assert_eq! (all_secret_keepers.len(), 0);
+		assert_eq! (all_secret_keepers.len(), 1);
+		assert_eq! (Registry::<T>::beacon_index_of(0, &member), Some(m as u64 + 1));
 	}
 
 	register_running_shard {
+		// members of the shard before the caller
+		let m in 0 .. T::MaxSecretKeepers::get() - 1;
+
+		fill_shard::<T>(0, m);
 		let caller: T::AccountId = whitelisted_caller();
 		Registry::<T>::register_secret_keeper(RawOrigin::Signed(caller.clone()).into(), PUBLIC_KEY.to_vec(), vec![0, 0, 0, 0, 0, 0])?;
 	}: register_running_shard(RawOrigin::Signed(caller.clone()), 0)
@@ -50,6 +81,36 @@ benchmarks! {
 		let caller: T::AccountId = whitelisted_caller();
 	}: register_user_public_key(RawOrigin::Signed(caller.clone()), PUBLIC_KEY.to_vec())
 	verify { }
+
+	on_initialize {
+		let c in 1 .. T::MaxExpirationChecksPerBlock::get();
+		// number of shards each expired keeper runs
+		let s in 1 .. T::MaxActiveShards::get();
+		// members of the first shard ahead of the keepers
+		let m in 0 .. T::MaxSecretKeepers::get() - T::MaxExpirationChecksPerBlock::get() - 1;
+
+		fill_shard::<T>(0, m);
+		for i in 0 .. c {
+			let keeper: T::AccountId = account("keeper", i, 0);
+			Registry::<T>::register_secret_keeper(RawOrigin::Signed(keeper.clone()).into(), PUBLIC_KEY.to_vec(), vec![0, 0, 0, 0, 0, 0])?;
+			for shard in 0 .. s {
+				Registry::<T>::register_running_shard(RawOrigin::Signed(keeper.clone()).into(), shard)?;
+			}
+		}
+
+		// a member outliving the keepers takes over a beacon index in every shard
+		let now = frame_system::Pallet::<T>::block_number() + T::RegistrationDuration::get() + 1u32.into();
+		frame_system::Pallet::<T>::set_block_number(now);
+		let member: T::AccountId = account("member", 0, 0);
+		Registry::<T>::register_secret_keeper(RawOrigin::Signed(member.clone()).into(), PUBLIC_KEY.to_vec(), vec![0, 0, 0, 0, 0, 0])?;
+		for shard in 0 .. s {
+			Registry::<T>::register_running_shard(RawOrigin::Signed(member.clone()).into(), shard)?;
+		}
+	}: { Registry::<T>::on_initialize(now); }
+	verify {
+		assert! (Registry::<T>::is_valid_secret_keeper(&member));
+		assert! (Registry::<T>::expiration_of(&account::<T::AccountId>("keeper", 0, 0)).is_none());
+	}
 }
 
 impl_benchmark_test_suite!(
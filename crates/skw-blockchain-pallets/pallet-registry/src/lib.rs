@@ -20,10 +20,13 @@ pub mod pallet {
 	use super::WeightInfo;
 	use skw_blockchain_primitives::{
 		types::{ShardId, PublicKey as PublicKeyType}, 
+		traits::SecretKeeperSet,
 	};
 	use frame_support::sp_runtime::SaturatedConversion;
 	use sp_std::vec::Vec;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -41,12 +44,15 @@ pub mod pallet {
 		/// maximum number of shards allowed
 		#[pallet::constant]
 		type MaxSecretKeepers: Get<u32>;
-		
-		// type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// maximum number of secret keepers checked for expiration in each block
+		#[pallet::constant]
+		type MaxExpirationChecksPerBlock: Get<u32>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// a list of all active secret keepers
@@ -85,13 +91,62 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn beacon_count_of)]
 	pub(super) type BeaconCount<T: Config> = StorageMap<_, Twox64Concat, ShardId, u64>;
-	
+
+	/// shards each secret keeper is running - reverse index of ShardMembers
+	#[pallet::storage]
+	#[pallet::getter(fn running_shards_of)]
+	pub(super) type RunningShards<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<ShardId, T::MaxActiveShards>>;
+
+	/// position in SecretKeepers where the next expiration sweep starts
+	#[pallet::storage]
+	#[pallet::getter(fn expiration_cursor)]
+	pub(super) type ExpirationCursor<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// (secret keeper, identity public key) registered at genesis
+		pub secret_keepers: Vec<(T::AccountId, Vec<u8>)>,
+		/// (secret keeper, shard) running from genesis, beacon indexes are assigned in order
+		pub running_shards: Vec<(T::AccountId, ShardId)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				secret_keepers: Vec::new(),
+				running_shards: Vec::new(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (secret_keeper, public_key) in self.secret_keepers.iter() {
+				Pallet::<T>::register_secret_keeper(
+					frame_system::RawOrigin::Signed(secret_keeper.clone()).into(),
+					public_key.clone(),
+					Vec::new(),
+				).expect("genesis secret keepers should be valid");
+			}
+
+			for (secret_keeper, shard) in self.running_shards.iter() {
+				Pallet::<T>::register_running_shard(
+					frame_system::RawOrigin::Signed(secret_keeper.clone()).into(),
+					*shard,
+				).expect("genesis running shards should be registered secret keepers");
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		SecretKeeperRegistered(T::AccountId),
 		SecretKeeperRenewed(T::AccountId),
 		SecretKeeperRemoved(T::AccountId),
+		SecretKeeperExpired(T::AccountId),
 		NewMemberForShard(ShardId),
 	}
 
@@ -104,6 +159,48 @@ pub mod pallet {
 		InvalidShardId,
 		InvalidPublicKey,
 		SecretKeeperAtFullCapacity,
+		ShardAlreadyRegistered,
+		TooManyShards,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// evict a bounded number of expired secret keepers each block, within the block
+		/// weight left after the base block weight
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let remaining_weight = T::BlockWeights::get().max_block
+				.saturating_sub(frame_system::Pallet::<T>::block_weight().total());
+			let (checked, shards, members) = Self::sweep_expired_secret_keepers(
+				now, T::MaxExpirationChecksPerBlock::get(), remaining_weight
+			);
+			<T as Config>::WeightInfo::on_initialize(checked, shards, members)
+		}
+
+		/// secret keepers registered before RunningShards existed get it rebuilt from ShardMembers
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return 0;
+			}
+
+			let mut reads: Weight = 1;
+			let mut writes: Weight = 1;
+			for (shard, shard_members) in <ShardMembers<T>>::iter() {
+				reads += 1;
+				for member in shard_members.iter() {
+					reads += 1;
+					<RunningShards<T>>::mutate(member, |running_shards| {
+						let running_shards = running_shards.get_or_insert_with(Default::default);
+						// a keeper in more shards than allowed keeps the first ones it was found in
+						if !running_shards.contains(&shard) && running_shards.try_push(shard).is_ok() {
+							writes += 1;
+						}
+					});
+				}
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
 	}
 
 	#[pallet::call]
@@ -163,33 +260,41 @@ pub mod pallet {
 		}
 
 		/// remove ones own registration record
-		#[pallet::weight(<T as Config>::WeightInfo::remove_registration())]
+		#[pallet::weight(<T as Config>::WeightInfo::remove_registration(
+			T::MaxActiveShards::get(),
+			T::MaxActiveShards::get().saturating_mul(T::MaxSecretKeepers::get()),
+		))]
 		pub fn remove_registration(
 			origin: OriginFor<T>,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			ensure!(Self::is_valid_secret_keeper(&who), Error::<T>::InvalidSecretKeeper);
 
 			match Self::try_remove_registration(who.clone()) {
-				true => {
+				Some((shards, members)) => {
 					Self::deposit_event(Event::<T>::SecretKeeperRemoved(who));
-					Ok(())
+					Ok(Some(<T as Config>::WeightInfo::remove_registration(shards, members)).into())
 				},
-				false => Err(Error::<T>::RegistrationNotFound.into())
+				None => Err(Error::<T>::RegistrationNotFound.into())
 			}
 		}
 
 		/// register all active shards one is running
-		#[pallet::weight(<T as Config>::WeightInfo::register_running_shard())]
+		#[pallet::weight(<T as Config>::WeightInfo::register_running_shard(T::MaxSecretKeepers::get()))]
 		pub fn register_running_shard(
 			origin: OriginFor<T>,
 			shard: ShardId,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			
 			ensure!(Self::is_valid_shard_id(shard), Error::<T>::InvalidShardId);
 			ensure!(Self::is_valid_secret_keeper(&who), Error::<T>::InvalidSecretKeeper);
+			ensure!(Self::beacon_index_of(shard, &who).is_none(), Error::<T>::ShardAlreadyRegistered);
+
+			let mut running_shards = Self::running_shards_of(&who).unwrap_or_default();
+			running_shards.try_push(shard).map_err(|_| Error::<T>::TooManyShards)?;
+
 			let shard_members = Self::shard_members_of(shard);
 			let shard_members = match shard_members {
 				None => {
@@ -209,8 +314,11 @@ pub mod pallet {
 				|count| *count = Some(shard_members.len() as u64));
 			<ShardMembers<T>>::mutate(&shard, 
 				|members| *members = Some(shard_members));
+			<RunningShards<T>>::insert(&who, running_shards);
 
-			Ok(())
+			// the members decoded, before this one was pushed
+			let members = shard_members.len().saturating_sub(1) as u32;
+			Ok(Some(<T as Config>::WeightInfo::register_running_shard(members)).into())
 		}
 
 		/// register a user's public key
@@ -255,20 +363,136 @@ pub mod pallet {
 			}
 		}
 
+		/// remove a secret keeper from all registry records. Returns the number of shards
+		/// it was removed from and the shard members decoded, or None when it is not registered.
 		pub fn try_remove_registration(
 			account_id: T::AccountId,
-		) -> bool {
-			let mut secret_keepers = Self::secret_keepers().unwrap();
+		) -> Option<(u32, u32)> {
+			let mut secret_keepers = Self::secret_keepers().unwrap_or_default();
 			match secret_keepers.iter().position(|id| *id == account_id) {
 				Some(index) => {
 					secret_keepers.swap_remove(index);
 					<SecretKeepers<T>>::set( Some(secret_keepers) );
 					<Expiration<T>>::remove(&account_id);
 					<PublicKey<T>>::remove(&account_id);
-					true
+					Some(Self::remove_from_all_shards(&account_id))
 				},
-				None => false
+				None => None
+			}
+		}
+
+		/// remove a secret keeper from every shard it runs. The last member of each shard takes
+		/// over the beacon index of the removed one, so only one beacon index is rewritten per
+		/// shard. Returns the number of shards touched, at most MaxActiveShards, and the total
+		/// number of shard members decoded on the way.
+		pub fn remove_from_all_shards(
+			account_id: &T::AccountId,
+		) -> (u32, u32) {
+			let running_shards = <RunningShards<T>>::take(account_id).unwrap_or_default();
+			let mut members_decoded = 0u32;
+
+			for shard in running_shards.iter() {
+				<BeaconIndex<T>>::remove(shard, account_id);
+
+				let mut shard_members = match Self::shard_members_of(shard) {
+					Some(m) => m,
+					None => continue,
+				};
+				members_decoded = members_decoded.saturating_add(shard_members.len() as u32);
+
+				if let Some(pos) = shard_members.iter().position(|id| id == account_id) {
+					shard_members.swap_remove(pos);
+
+					// beacon index is 1-based and follows the order of ShardMembers
+					if let Some(moved) = shard_members.get(pos) {
+						<BeaconIndex<T>>::insert(shard, moved, (pos + 1) as u64);
+					}
+				}
+
+				if shard_members.is_empty() {
+					<ShardMembers<T>>::remove(shard);
+					<BeaconCount<T>>::remove(shard);
+				} else {
+					<BeaconCount<T>>::insert(shard, shard_members.len() as u64);
+					<ShardMembers<T>>::insert(shard, shard_members);
+				}
+			}
+
+			(running_shards.len() as u32, members_decoded)
+		}
+
+		/// check at most `max_checks` secret keepers for expiration, starting from the
+		/// ExpirationCursor, and evict the expired ones from all registry records. The sweep
+		/// stops before a check or an eviction would take the sweep over `max_weight`.
+		/// Returns (keepers checked, shards cleaned up, shard members decoded).
+		pub fn sweep_expired_secret_keepers(
+			now: T::BlockNumber,
+			max_checks: u32,
+			max_weight: Weight,
+		) -> (u32, u32, u32) {
+			let mut secret_keepers = match Self::secret_keepers() {
+				Some(sk) if !sk.is_empty() => sk,
+				_ => return (0, 0, 0),
+			};
+
+			let max_checks = max_checks.min(secret_keepers.len() as u32);
+			let mut cursor = Self::expiration_cursor() as usize;
+			let mut checked = 0u32;
+			let mut shards_cleaned = 0u32;
+			let mut members_decoded = 0u32;
+			let mut evicted = false;
+
+			while checked < max_checks && !secret_keepers.is_empty() {
+				if cursor >= secret_keepers.len() {
+					cursor = 0;
+				}
+
+				let weight = <T as Config>::WeightInfo::on_initialize(checked + 1, shards_cleaned, members_decoded);
+				if weight > max_weight {
+					break;
+				}
+
+				checked += 1;
+				let account_id = secret_keepers[cursor].clone();
+				let expired = match Self::expiration_of(&account_id) {
+					Some(expiration) => expiration < now,
+					None => true,
+				};
+
+				if expired {
+					// a keeper running more shards than fit in this block is evicted in a later
+					// block, the cursor stays on it
+					let shards = Self::running_shards_of(&account_id).map(|s| s.len() as u32).unwrap_or(0);
+					let eviction_weight = <T as Config>::WeightInfo::on_initialize(
+						checked,
+						shards_cleaned.saturating_add(shards),
+						members_decoded.saturating_add(shards.saturating_mul(T::MaxSecretKeepers::get())),
+					);
+					if eviction_weight > max_weight {
+						break;
+					}
+
+					// the last keeper is moved into `cursor` - check it next without advancing
+					secret_keepers.swap_remove(cursor);
+					<Expiration<T>>::remove(&account_id);
+					<PublicKey<T>>::remove(&account_id);
+					let (shards, members) = Self::remove_from_all_shards(&account_id);
+					shards_cleaned = shards_cleaned.saturating_add(shards);
+					members_decoded = members_decoded.saturating_add(members);
+					evicted = true;
+
+					Self::deposit_event(Event::<T>::SecretKeeperExpired(account_id));
+				} else {
+					cursor += 1;
+				}
 			}
+
+			if evicted {
+				<SecretKeepers<T>>::put(secret_keepers);
+			}
+			<ExpirationCursor<T>>::put(cursor as u32);
+
+			(checked, shards_cleaned, members_decoded)
 		}
 
 		pub fn is_valid_secret_keeper(who: &T::AccountId) -> bool {
@@ -285,8 +509,26 @@ pub mod pallet {
 			}
 		}
 
+		/// whether `who` registered as running `shard`
+		pub fn is_shard_member(shard: ShardId, who: &T::AccountId) -> bool {
+			<BeaconIndex<T>>::contains_key(shard, who)
+		}
+
+		/// shard ids range from 0 to MaxActiveShards - 1
 		pub fn is_valid_shard_id(shard: ShardId) -> bool {
-			shard <= T::MaxActiveShards::get().into()
+			shard < T::MaxActiveShards::get().into()
+		}
+
+		/// beacon count of a shard and the beacon index of each of its members
+		pub fn beacon_schedule_of(shard: ShardId) -> (u64, Vec<(T::AccountId, u64)>) {
+			let beacon_count = Self::beacon_count_of(shard).unwrap_or(0);
+			let schedule = Self::shard_members_of(shard)
+				.unwrap_or_default()
+				.into_iter()
+				.filter_map(|who| Self::beacon_index_of(shard, &who).map(|index| (who, index)))
+				.collect();
+
+			(beacon_count, schedule)
 		}
 
 		pub fn is_beacon_turn(
@@ -344,5 +586,18 @@ pub mod pallet {
 			)
 		}
 	}
+
+	impl<T: Config> SecretKeeperSet<T::AccountId> for Pallet<T> {
+		fn secret_keepers_of(shard_id: ShardId) -> Vec<T::AccountId> {
+			Self::shard_members_of(shard_id).map(|members| members.into_inner()).unwrap_or_default()
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn set_secret_keepers_of(shard_id: ShardId, keepers: Vec<T::AccountId>) {
+			let keepers = BoundedVec::<T::AccountId, T::MaxSecretKeepers>::try_from(keepers)
+				.expect("benchmarks stay within MaxSecretKeepers");
+			<ShardMembers<T>>::insert(shard_id, keepers);
+		}
+	}
 }
 
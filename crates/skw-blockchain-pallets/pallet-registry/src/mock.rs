@@ -55,6 +55,7 @@ impl pallet_registry::Config for Test {
 	type RegistrationDuration = ConstU64<100_000>;
 	type MaxActiveShards = ConstU32<1_000>;
 	type MaxSecretKeepers = ConstU32<5_000>;
+	type MaxExpirationChecksPerBlock = ConstU32<3>;
 }
 
 // Build genesis storage according to the mock runtime.
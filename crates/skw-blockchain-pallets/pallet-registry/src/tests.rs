@@ -1,6 +1,8 @@
 use super::Event as RegistryEvent;
 
-use frame_support::{assert_ok};
+use frame_support::{assert_ok, assert_noop, traits::{GenesisBuild, Hooks, StorageVersion}};
+use super::Error as RegistryError;
+use crate::WeightInfo;
 use crate::mock::{Event, *};
 
 const PUBLIC_KEY: [u8; 32] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
@@ -177,3 +179,211 @@ fn insert_pk_for_user() {
 		assert!( Registry::user_public_key_of(&ALICE).unwrap().to_vec() == PUBLIC_KEY.to_vec().clone());
 	});
 }
+
+#[test]
+fn it_evicts_expired_secret_keepers() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(ALICE),  PUBLIC_KEY.to_vec().clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 0 ) );
+
+		// BOB registers later and outlives ALICE
+		System::set_block_number(10);
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(BOB),  PUBLIC_KEY.to_vec().clone(), Vec::new() ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(BOB), 0 ) );
+
+		// ALICE is still valid on the last block of her registration
+		Registry::on_initialize(100_001);
+		assert_eq! (Registry::secret_keepers().unwrap().len(), 2);
+
+		Registry::on_initialize(100_002);
+		assert! (System::events().iter().any(|evt| {
+				evt.event == Event::Registry(RegistryEvent::SecretKeeperExpired(ALICE))
+			})
+		);
+
+		let all_secret_keepers = Registry::secret_keepers().unwrap();
+		assert_eq! (all_secret_keepers.len(), 1);
+		assert_eq! (all_secret_keepers[0], BOB);
+
+		assert_eq! (Registry::expiration_of(&ALICE), None);
+		assert_eq! (Registry::public_key_of(&ALICE), None);
+		assert_eq! (Registry::running_shards_of(&ALICE), None);
+		assert_eq! (Registry::beacon_index_of(0, &ALICE), None);
+
+		// BOB takes over the first beacon slot
+		assert_eq! (Registry::shard_members_of(0).unwrap().into_inner(), vec![BOB]);
+		assert_eq! (Registry::beacon_index_of(0, &BOB), Some(1));
+		assert_eq! (Registry::beacon_count_of(0), Some(1));
+		assert!( Registry::is_beacon_turn(100_002, &BOB, 0, 1) == true);
+	});
+}
+
+#[test]
+fn it_bounds_expiration_checks_per_block() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for keeper in [ALICE, BOB, CHARLIE, DAVE, FRED] {
+			assert_ok!( Registry::register_secret_keeper( Origin::signed(keeper),  PUBLIC_KEY.to_vec().clone(), Vec::new() ) );
+			assert_ok!( Registry::register_running_shard( Origin::signed(keeper), 0 ) );
+		}
+
+		// MaxExpirationChecksPerBlock = 3 in the mock runtime
+		Registry::on_initialize(200_000);
+		assert_eq! (Registry::secret_keepers().unwrap().len(), 2);
+		assert_eq! (Registry::beacon_count_of(0), Some(2));
+
+		Registry::on_initialize(200_001);
+		assert_eq! (Registry::secret_keepers().unwrap().len(), 0);
+		assert_eq! (Registry::shard_members_of(0), None);
+		assert_eq! (Registry::beacon_count_of(0), None);
+	});
+}
+
+#[test]
+fn it_bounds_expiration_sweep_by_weight() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for keeper in [ALICE, BOB] {
+			assert_ok!( Registry::register_secret_keeper( Origin::signed(keeper),  PUBLIC_KEY.to_vec().clone(), Vec::new() ) );
+			assert_ok!( Registry::register_running_shard( Origin::signed(keeper), 0 ) );
+		}
+
+		// the budget covers one check but not the eviction of a keeper running a shard
+		let budget = <() as WeightInfo>::on_initialize(1, 0, 0);
+		assert_eq! (Registry::sweep_expired_secret_keepers(200_000, 3, budget), (1, 0, 0));
+		assert_eq! (Registry::secret_keepers().unwrap().len(), 2);
+		assert_eq! (Registry::beacon_count_of(0), Some(2));
+
+		// the sweep resumes on the keeper it stopped at
+		let budget = <() as WeightInfo>::on_initialize(1, 1, 5_000);
+		assert_eq! (Registry::sweep_expired_secret_keepers(200_000, 3, budget), (1, 1, 2));
+		assert_eq! (Registry::secret_keepers().unwrap().into_inner(), vec![BOB]);
+		assert_eq! (Registry::beacon_count_of(0), Some(1));
+	});
+}
+
+#[test]
+fn it_rejects_shard_ids_out_of_range() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( Registry::register_secret_keeper( Origin::signed(ALICE),  PUBLIC_KEY.to_vec().clone(), Vec::new() ) );
+
+		// MaxActiveShards = 1_000 in the mock runtime
+		assert_noop!(
+			Registry::register_running_shard( Origin::signed(ALICE), 1_000 ),
+			RegistryError::<Test>::InvalidShardId
+		);
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 999 ) );
+	});
+}
+
+#[test]
+fn it_compacts_beacon_index_on_removal() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for keeper in [ALICE, BOB, CHARLIE] {
+			assert_ok!( Registry::register_secret_keeper( Origin::signed(keeper),  PUBLIC_KEY.to_vec().clone(), Vec::new() ) );
+			assert_ok!( Registry::register_running_shard( Origin::signed(keeper), 0 ) );
+		}
+
+		assert_noop!(
+			Registry::register_running_shard( Origin::signed(ALICE), 0 ),
+			RegistryError::<Test>::ShardAlreadyRegistered
+		);
+
+		assert_ok!( Registry::remove_registration( Origin::signed(BOB) ) );
+
+		assert_eq! (Registry::shard_members_of(0).unwrap().into_inner(), vec![ALICE, CHARLIE]);
+		assert_eq! (Registry::beacon_index_of(0, &ALICE), Some(1));
+		assert_eq! (Registry::beacon_index_of(0, &BOB), None);
+		assert_eq! (Registry::beacon_index_of(0, &CHARLIE), Some(2));
+		assert_eq! (Registry::beacon_count_of(0), Some(2));
+	});
+}
+
+#[test]
+fn it_registers_secret_keepers_at_genesis() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		secret_keepers: vec![(ALICE, PUBLIC_KEY.to_vec()), (BOB, PUBLIC_KEY.to_vec())],
+		running_shards: vec![(ALICE, 0), (BOB, 0)],
+	}.assimilate_storage(&mut t).unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert! (Registry::is_valid_secret_keeper(&ALICE));
+		assert_eq! (Registry::public_key_of(&BOB), Some(PUBLIC_KEY));
+		assert_eq! (Registry::shard_members_of(0).unwrap().into_inner(), vec![ALICE, BOB]);
+		assert_eq! (Registry::beacon_index_of(0, &BOB), Some(2));
+		assert_eq! (Registry::beacon_count_of(0), Some(2));
+	});
+}
+
+#[test]
+fn it_moves_last_member_into_removed_beacon_slot() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for keeper in [ALICE, BOB, CHARLIE, DAVE] {
+			assert_ok!( Registry::register_secret_keeper( Origin::signed(keeper),  PUBLIC_KEY.to_vec().clone(), Vec::new() ) );
+			assert_ok!( Registry::register_running_shard( Origin::signed(keeper), 0 ) );
+		}
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 1 ) );
+		assert_ok!( Registry::register_running_shard( Origin::signed(BOB), 1 ) );
+
+		assert_ok!( Registry::remove_registration( Origin::signed(ALICE) ) );
+
+		assert_eq! (Registry::shard_members_of(0).unwrap().into_inner(), vec![DAVE, BOB, CHARLIE]);
+		assert_eq! (Registry::beacon_index_of(0, &DAVE), Some(1));
+		assert_eq! (Registry::beacon_index_of(0, &BOB), Some(2));
+		assert_eq! (Registry::beacon_index_of(0, &CHARLIE), Some(3));
+		assert_eq! (Registry::beacon_count_of(0), Some(3));
+
+		assert_eq! (Registry::shard_members_of(1).unwrap().into_inner(), vec![BOB]);
+		assert_eq! (Registry::beacon_index_of(1, &BOB), Some(1));
+		assert_eq! (Registry::beacon_count_of(1), Some(1));
+	});
+}
+
+#[test]
+fn it_migrates_running_shards() {
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for keeper in [ALICE, BOB] {
+			assert_ok!( Registry::register_secret_keeper( Origin::signed(keeper),  PUBLIC_KEY.to_vec().clone(), Vec::new() ) );
+			assert_ok!( Registry::register_running_shard( Origin::signed(keeper), 0 ) );
+		}
+		assert_ok!( Registry::register_running_shard( Origin::signed(ALICE), 1 ) );
+
+		// keepers registered before RunningShards existed
+		crate::RunningShards::<Test>::remove(&ALICE);
+		crate::RunningShards::<Test>::remove(&BOB);
+		StorageVersion::new(0).put::<Registry>();
+
+		Registry::on_runtime_upgrade();
+
+		assert_eq! (Registry::running_shards_of(&ALICE).unwrap().into_inner().len(), 2);
+		assert! (Registry::running_shards_of(&ALICE).unwrap().contains(&0));
+		assert! (Registry::running_shards_of(&ALICE).unwrap().contains(&1));
+		assert_eq! (Registry::running_shards_of(&BOB).unwrap().into_inner(), vec![0]);
+		assert_eq! (StorageVersion::get::<Registry>(), 1);
+
+		// removal now cleans up the shards of migrated keepers
+		assert_ok!( Registry::remove_registration( Origin::signed(ALICE) ) );
+		assert_eq! (Registry::shard_members_of(1), None);
+		assert_eq! (Registry::shard_members_of(0).unwrap().into_inner(), vec![BOB]);
+	});
+}
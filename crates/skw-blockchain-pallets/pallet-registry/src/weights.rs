@@ -3,34 +3,13 @@
 // Copyright (C) 2021 - 2022 SkyeKiwi.
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-//! Autogenerated weights for pallet_registry
+//! Weights for pallet_registry
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2022-07-25, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: None, DB CACHE: 1024
-
-// Executed Command:
-// /Users/songzhou/Desktop/skyekiwi-network/target/release/skyekiwi-node
-// benchmark
-// pallet
-// --steps
-// 50
-// --repeat
-// 20
-// --pallet
-// pallet_registry
-// --extrinsic
-// *
-// --execution
-// wasm
-// --wasm-execution
-// compiled
-// --heap-pages
-// 4096
-// --output
-// /Users/songzhou/Desktop/skyekiwi-network/crates/skw-blockchain-pallets/pallet-registry/src/weights.rs
-// --template
-// /Users/songzhou/Desktop/skyekiwi-network/misc/frame-weight-template.hbs
+//! These weights are estimated by hand from the storage accesses of each call, on top of the
+//! last benchmark run (2022-07-25). The `m` components weigh the decoding and encoding of
+//! ShardMembers, which holds up to MaxSecretKeepers accounts. Rerun the benchmarks with
+//! `skyekiwi-node benchmark pallet --pallet pallet_registry --extrinsic *` before relying on
+//! them.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -43,9 +22,10 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn register_secret_keeper() -> Weight;
 	fn renew_registration() -> Weight;
-	fn remove_registration() -> Weight;
-	fn register_running_shard() -> Weight;
+	fn remove_registration(s: u32, m: u32, ) -> Weight;
+	fn register_running_shard(m: u32, ) -> Weight;
 	fn register_user_public_key() -> Weight;
+	fn on_initialize(c: u32, s: u32, m: u32, ) -> Weight;
 }
 
 /// Weights for pallet_registry using the Substrate node and recommended hardware.
@@ -69,26 +49,56 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Registry Expiration (r:1 w:1)
 	// Storage: Registry PublicKey (r:1 w:1)
 	// Storage: Registry SecretKeepers (r:1 w:1)
-	fn remove_registration() -> Weight {
-		(17_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	// Storage: Registry RunningShards (r:1 w:1)
+	// Storage: Registry ShardMembers (r:1 w:1)
+	// Storage: Registry BeaconIndex (r:0 w:2)
+	// Storage: Registry BeaconCount (r:0 w:1)
+	fn remove_registration(s: u32, m: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((40_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(s as Weight)))
 	}
 	// Storage: Registry Expiration (r:1 w:0)
 	// Storage: Registry PublicKey (r:1 w:0)
+	// Storage: Registry BeaconIndex (r:1 w:1)
+	// Storage: Registry RunningShards (r:1 w:1)
 	// Storage: Registry ShardMembers (r:1 w:1)
 	// Storage: Registry BeaconCount (r:1 w:1)
-	// Storage: Registry BeaconIndex (r:0 w:1)
-	fn register_running_shard() -> Weight {
-		(13_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	fn register_running_shard(m: u32, ) -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add((40_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	// Storage: Registry UserPublicKey (r:0 w:1)
 	fn register_user_public_key() -> Weight {
 		(1_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Registry SecretKeepers (r:1 w:1)
+	// Storage: Registry ExpirationCursor (r:1 w:1)
+	// Storage: Registry Expiration (r:1 w:1)
+	// Storage: Registry PublicKey (r:0 w:1)
+	// Storage: Registry RunningShards (r:1 w:1)
+	// Storage: Registry ShardMembers (r:1 w:1)
+	// Storage: Registry BeaconIndex (r:0 w:2)
+	// Storage: Registry BeaconCount (r:0 w:1)
+	fn on_initialize(c: u32, s: u32, m: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((5_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((40_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(s as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -111,24 +121,54 @@ impl WeightInfo for () {
 	// Storage: Registry Expiration (r:1 w:1)
 	// Storage: Registry PublicKey (r:1 w:1)
 	// Storage: Registry SecretKeepers (r:1 w:1)
-	fn remove_registration() -> Weight {
-		(17_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	// Storage: Registry RunningShards (r:1 w:1)
+	// Storage: Registry ShardMembers (r:1 w:1)
+	// Storage: Registry BeaconIndex (r:0 w:2)
+	// Storage: Registry BeaconCount (r:0 w:1)
+	fn remove_registration(s: u32, m: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((40_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(s as Weight)))
 	}
 	// Storage: Registry Expiration (r:1 w:0)
 	// Storage: Registry PublicKey (r:1 w:0)
+	// Storage: Registry BeaconIndex (r:1 w:1)
+	// Storage: Registry RunningShards (r:1 w:1)
 	// Storage: Registry ShardMembers (r:1 w:1)
 	// Storage: Registry BeaconCount (r:1 w:1)
-	// Storage: Registry BeaconIndex (r:0 w:1)
-	fn register_running_shard() -> Weight {
-		(13_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	fn register_running_shard(m: u32, ) -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add((40_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	// Storage: Registry UserPublicKey (r:0 w:1)
 	fn register_user_public_key() -> Weight {
 		(1_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Registry SecretKeepers (r:1 w:1)
+	// Storage: Registry ExpirationCursor (r:1 w:1)
+	// Storage: Registry Expiration (r:1 w:1)
+	// Storage: Registry PublicKey (r:0 w:1)
+	// Storage: Registry RunningShards (r:1 w:1)
+	// Storage: Registry ShardMembers (r:1 w:1)
+	// Storage: Registry BeaconIndex (r:0 w:2)
+	// Storage: Registry BeaconCount (r:0 w:1)
+	fn on_initialize(c: u32, s: u32, m: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((5_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((40_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(s as Weight)))
+	}
 }
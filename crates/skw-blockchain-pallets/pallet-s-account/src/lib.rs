@@ -54,6 +54,31 @@ pub mod pallet {
 	#[pallet::getter(fn reserved_amount_of)]
 	pub(super) type ReservedAmount <T: Config> = StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, ShardId, Balance>;
 
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// (account, shard) of enclave accounts created at genesis, the shards must be active from genesis
+		pub enclave_accounts: Vec<(T::AccountId, ShardId)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { enclave_accounts: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (account, shard_id) in self.enclave_accounts.iter() {
+				Pallet::<T>::do_create_enclave_account(
+					*shard_id,
+					account.clone(),
+				).expect("genesis enclave accounts should be created on active shards");
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -72,22 +97,15 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T:Config> Pallet<T> {
 
-		/// (ROOT ONLY) force create an account inside the enclave
+		/// (SHARD ADMIN ONLY) force create an account inside the enclave
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::force_create_enclave_account())]
 		pub fn force_create_enclave_account(
 			origin: OriginFor<T>,
 			shard_id: ShardId,
 			account: T::AccountId,
 		) -> DispatchResult {
-			ensure_root(origin.clone())?;
-			
-			let encoded_call = Self::build_account_creation_call(&account);
-
-			let system_origin: T::AccountId = T::SContractRoot::get().into_account();
-			pallet_s_contract::Pallet::<T>::push_call(RawOrigin::Signed(system_origin).into(), shard_id, encoded_call)?;
-
-			Self::deposit_event(Event::<T>::EnclaveAccountCreated(account, shard_id));
-			Ok(())
+			pallet_s_contract::Pallet::<T>::ensure_shard_admin(origin, shard_id)?;
+			Self::do_create_enclave_account(shard_id, account)
 		}
 
 		/// reserve some token and create an account in the enclave 
@@ -100,7 +118,7 @@ pub mod pallet {
 			ensure!(Self::reserved_amount_of(who.clone(), shard_id).is_none(), Error::<T>::AlreadyCreated);
 
 			let treasury = T::PalletId::get().into_account();
-			T::Currency::transfer(&who, &treasury, T::ReservationRequirement::get(), KeepAlive)?;
+			<T as pallet_treasury::Config>::Currency::transfer(&who, &treasury, T::ReservationRequirement::get(), KeepAlive)?;
 			
 			// token transfered to treasury is a flat fee paid to the system - so reserved_amount is 0
 			<ReservedAmount<T>>::insert(&who, &shard_id, 0);
@@ -121,6 +139,20 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// push the account creation call for `account` to the shard, without any fee
+		pub fn do_create_enclave_account(
+			shard_id: ShardId,
+			account: T::AccountId,
+		) -> DispatchResult {
+			let encoded_call = Self::build_account_creation_call(&account);
+
+			let system_origin: T::AccountId = T::SContractRoot::get().into_account();
+			pallet_s_contract::Pallet::<T>::push_call(RawOrigin::Signed(system_origin).into(), shard_id, encoded_call)?;
+
+			Self::deposit_event(Event::<T>::EnclaveAccountCreated(account, shard_id));
+			Ok(())
+		}
+
 		pub fn build_account_creation_call(
 			account: &T::AccountId,
 		) -> Vec<u8> {
@@ -8,7 +8,7 @@ use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup}, Permill,
 };
-use frame_system::{EnsureRoot};
+use frame_system::{EnsureRoot, EnsureSigned};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -108,6 +108,9 @@ impl pallet_secrets::Config for Test {
 	type WeightInfo = ();
 	type Event = Event;
 	type Preimage = Preimage;
+	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type SecretKeepers = ();
+	type MaxAuditLogLength = frame_support::traits::ConstU32<3>;
 }
 
 impl pallet_s_contract::Config for Test {
@@ -117,7 +120,17 @@ impl pallet_s_contract::Config for Test {
 	type MinContractNameLength = ConstU32<1>;
 	type MaxContractNameLength = ConstU32<32>;
 	type MaxCallPerBlock = ConstU32<1_000>;
+	type MaxQueuedCallsPerShard = ConstU32<5_000>;
+	type Currency = Balances;
+	type TipRefundDelay = ConstU64<10>;
 	type SContractRoot = SContractPalletId;
+	type CallRecordPruneDepth = ConstU64<10>;
+	type MaxPrunedCallsPerBlock = ConstU32<1_000>;
+	type MaxContractVersions = ConstU32<3>;
+	type MaxPreviousShardKeys = ConstU32<3>;
+	type Naming = ();
+	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type ShardAdminOrigin = EnsureSigned<Self::AccountId>;
 }
 impl pallet_s_account::Config for Test {
 	type WeightInfo = ();
@@ -19,6 +19,7 @@ fn it_create_account() {
 			)
 		);
 
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
 		assert_ok!(
 			SContract::initialize_shard(
 				Origin::signed(account1.clone()), 0,
@@ -45,6 +46,7 @@ fn it_force_create_account() {
 			)
 		);
 
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
 		assert_ok!(
 			SContract::initialize_shard(
 				Origin::signed(account1.clone()), 0,
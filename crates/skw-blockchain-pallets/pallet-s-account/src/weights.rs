@@ -48,12 +48,13 @@ pub trait WeightInfo {
 /// Weights for pallet_s_account using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: SContract ShardAdmin (r:1 w:0)
 	// Storage: SContract CurrentCallIndex (r:1 w:1)
 	// Storage: SContract CallHistory (r:1 w:1)
 	// Storage: SContract CallRecord (r:0 w:1)
 	fn force_create_enclave_account() -> Weight {
-		(20_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	// Storage: SAccount ReservedAmount (r:1 w:1)
@@ -70,12 +71,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// Storage: SContract ShardAdmin (r:1 w:0)
 	// Storage: SContract CurrentCallIndex (r:1 w:1)
 	// Storage: SContract CallHistory (r:1 w:1)
 	// Storage: SContract CallRecord (r:0 w:1)
 	fn force_create_enclave_account() -> Weight {
-		(20_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	// Storage: SAccount ReservedAmount (r:1 w:1)
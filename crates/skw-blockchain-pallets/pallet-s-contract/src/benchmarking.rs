@@ -1,24 +1,63 @@
 use super::*;
 use sp_std::vec::Vec;
 use frame_system::RawOrigin;
-use frame_benchmarking::{benchmarks, whitelisted_caller, impl_benchmark_test_suite};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, impl_benchmark_test_suite};
+use skw_blockchain_primitives::traits::{OnBlockConfirmed, SecretKeeperSet};
+use frame_support::traits::{Currency, EnsureOrigin};
+use frame_support::sp_runtime::traits::Bounded;
 #[allow(unused)]
 use crate::Pallet as SContract;
 use skw_blockchain_primitives::types::PublicKey;
 
 const IPFS_CID_1: &str = "QmaibP61e3a4r6Bp895FQFB6ohqt5gMK4yeNy6yXxBmi8N";
+const IPFS_CID_2: &str = "QmRZdc3mAMXpv6Akz9Ekp1y4vDSjazTx2dCQRkxVy1yUj6";
 const PUBLIC_KEY: PublicKey = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+const NEW_PUBLIC_KEY: PublicKey = [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+
+/// shares sealed to two secret keepers, which become the keepers of shard 0
+fn sealed_shares<T: Config>() -> Vec<u8> {
+	<T as pallet_secrets::Config>::SecretKeepers::set_secret_keepers_of(
+		0,
+		(0..2).map(|i| account("keeper", i, 0)).collect()
+	);
+	(0..2u8).flat_map(|i| [&64u32.to_be_bytes()[..], &[i; 64][..]].concat()).collect()
+}
 
 benchmarks! {
 	
 	add_authorized_shard_operator {
 		let caller: T::AccountId = whitelisted_caller();
-	}: add_authorized_shard_operator(RawOrigin::Root, 0, caller)
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 0, caller)
 	verify { }
 
+	remove_authorized_shard_operator {
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0,
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 0, caller.clone())
+	verify {
+		assert_eq! (SContract::<T>::shard_operator(0, &caller), None);
+	}
+
+	set_shard_admin {
+		let caller: T::AccountId = whitelisted_caller();
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 0, Some(caller.clone()))
+	verify {
+		assert_eq! (SContract::<T>::shard_admin_of(0), Some(caller));
+	}
+
 	initialize_shard {
 		let caller: T::AccountId = whitelisted_caller();
-		SContract::<T>::add_authorized_shard_operator(RawOrigin::Root.into(), 0, caller.clone())?;
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
 	}: initialize_shard(RawOrigin::Signed(caller), 0,
 		IPFS_CID_1.as_bytes().to_vec(),
 		PUBLIC_KEY
@@ -26,7 +65,7 @@ benchmarks! {
 
 	register_contract {
 		let caller: T::AccountId = whitelisted_caller();
-		SContract::<T>::add_authorized_shard_operator(RawOrigin::Root.into(), 0, caller.clone())?;
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
 		
 		let calls = skw_blockchain_primitives::types::Calls {
 			ops: Vec::new(),
@@ -34,6 +73,7 @@ benchmarks! {
 			shard_id: 0
 		};
 		let encoded_calls = skw_blockchain_primitives::BorshSerialize::try_to_vec(&calls).unwrap();
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
 		SContract::<T>::initialize_shard(
 			RawOrigin::Signed(caller.clone()).into(), 0,
 			IPFS_CID_1.as_bytes().to_vec(),
@@ -47,9 +87,109 @@ benchmarks! {
 		0
 	) verify { }
 
+	register_contract_with_name {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0,
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+	}: register_contract_with_name (
+		RawOrigin::Signed(caller),
+		"contract_name".as_bytes().to_vec(),
+		IPFS_CID_1.as_bytes().to_vec(),
+		SContract::<T>::empty_calls(),
+		0,
+		"name".as_bytes().to_vec(),
+		1
+	) verify { }
+
+	register_contract_with_code_hash {
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0,
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		SContract::<T>::register_contract(
+			RawOrigin::Signed(caller.clone()).into(),
+			"contract_name".as_bytes().to_vec(),
+			IPFS_CID_1.as_bytes().to_vec(),
+			SContract::<T>::empty_calls(),
+			0
+		)?;
+		let code_hash = SContract::<T>::wasm_blob_of(0, "contract_name".as_bytes().to_vec()).unwrap();
+	}: register_contract_with_code_hash (
+		RawOrigin::Signed(caller),
+		"another_contract".as_bytes().to_vec(),
+		code_hash,
+		SContract::<T>::empty_calls(),
+		0
+	) verify { }
+
+	upgrade_contract {
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0,
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		SContract::<T>::register_contract(
+			RawOrigin::Signed(caller.clone()).into(),
+			"contract_name".as_bytes().to_vec(),
+			IPFS_CID_1.as_bytes().to_vec(),
+			SContract::<T>::empty_calls(),
+			0
+		)?;
+	}: upgrade_contract (
+		RawOrigin::Signed(caller),
+		"contract_name".as_bytes().to_vec(),
+		IPFS_CID_2.as_bytes().to_vec(),
+		0
+	) verify { }
+
+	upgrade_contract_with_code_hash {
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0,
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		SContract::<T>::register_contract(
+			RawOrigin::Signed(caller.clone()).into(),
+			"contract_name".as_bytes().to_vec(),
+			IPFS_CID_1.as_bytes().to_vec(),
+			SContract::<T>::empty_calls(),
+			0
+		)?;
+		SContract::<T>::register_contract(
+			RawOrigin::Signed(caller.clone()).into(),
+			"another_contract".as_bytes().to_vec(),
+			IPFS_CID_2.as_bytes().to_vec(),
+			SContract::<T>::empty_calls(),
+			0
+		)?;
+		let code_hash = SContract::<T>::wasm_blob_of(0, "another_contract".as_bytes().to_vec()).unwrap();
+	}: upgrade_contract_with_code_hash (
+		RawOrigin::Signed(caller),
+		"contract_name".as_bytes().to_vec(),
+		code_hash,
+		0
+	) verify { }
+
 	push_call {
 		let caller: T::AccountId = whitelisted_caller();
-		SContract::<T>::add_authorized_shard_operator(RawOrigin::Root.into(), 0, caller.clone())?;
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
 		SContract::<T>::initialize_shard(
 			RawOrigin::Signed(caller.clone()).into(), 0, 
 			IPFS_CID_1.as_bytes().to_vec(),
@@ -63,15 +203,282 @@ benchmarks! {
 		let encoded_calls = skw_blockchain_primitives::BorshSerialize::try_to_vec(&calls).unwrap();
 	}: push_call( RawOrigin::Signed(caller), 0, encoded_calls.clone() ) verify { }
 
+	push_call_with_tip {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0, 
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		let tip: BalanceOf<T> = 1_000u32.into();
+	}: push_call_with_tip( RawOrigin::Signed(caller), 0, SContract::<T>::empty_calls(), tip, Some(PUBLIC_KEY) ) verify { }
+
+	push_call_with_key {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0, 
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, operator.clone())?;
+
+		// the call is encrypted to the previous key of a rotated shard
+		let secret_id = SContract::<T>::shard_secret_id(0).unwrap();
+		pallet_secrets::Pallet::<T>::update_shares(
+			RawOrigin::Signed(caller.clone()).into(), secret_id, 2, 2, sealed_shares::<T>()
+		)?;
+		SContract::<T>::propose_key_rotation(
+			RawOrigin::Signed(caller.clone()).into(), 0,
+			NEW_PUBLIC_KEY, 10, IPFS_CID_2.as_bytes().to_vec()
+		)?;
+		SContract::<T>::approve_key_rotation(RawOrigin::Signed(operator).into(), 0, NEW_PUBLIC_KEY)?;
+	}: push_call_with_key( RawOrigin::Signed(caller), 0, SContract::<T>::empty_calls(), PUBLIC_KEY ) verify { }
+
+	push_calls {
+		let c in 1 .. T::MaxCallPerBlock::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let mut calls = Vec::new();
+		for shard_id in 0 .. c {
+			SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), shard_id, caller.clone())?;
+			SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), shard_id)?;
+			SContract::<T>::initialize_shard(
+				RawOrigin::Signed(caller.clone()).into(), shard_id,
+				IPFS_CID_1.as_bytes().to_vec(),
+				PUBLIC_KEY
+			)?;
+			calls.push((shard_id, SContract::<T>::empty_calls()));
+		}
+	}: push_calls( RawOrigin::Signed(caller), calls )
+	verify {
+		assert_eq! (SContract::<T>::current_call_index_of(), c);
+	}
+
+	// worst case - every queued call is for a different shard
+	schedule_calls {
+		let c in 1 .. 100;
+
+		let caller: T::AccountId = whitelisted_caller();
+		for shard_id in 0 .. c {
+			SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), shard_id, caller.clone())?;
+			SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), shard_id)?;
+			SContract::<T>::initialize_shard(
+				RawOrigin::Signed(caller.clone()).into(), shard_id,
+				IPFS_CID_1.as_bytes().to_vec(),
+				PUBLIC_KEY
+			)?;
+			SContract::<T>::push_call(RawOrigin::Signed(caller.clone()).into(), shard_id, SContract::<T>::empty_calls())?;
+		}
+		let now = frame_system::Pallet::<T>::block_number();
+	}: { SContract::<T>::schedule_queued_calls(now); }
+	verify {
+		assert_eq! (SContract::<T>::call_history_of(c - 1, now).map(|h| h.len()), Some(1));
+	}
+
+	refund_tips {
+		let c in 1 .. T::MaxCallPerBlock::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0,
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		for i in 0 .. c {
+			let pusher: T::AccountId = account("pusher", i, 0);
+			T::Currency::make_free_balance_be(&pusher, BalanceOf::<T>::max_value() / 2u32.into());
+			SContract::<T>::push_call_with_tip(RawOrigin::Signed(pusher).into(), 0, SContract::<T>::empty_calls(), 1_000u32.into(), None)?;
+		}
+		let now = frame_system::Pallet::<T>::block_number();
+		SContract::<T>::schedule_queued_calls(now);
+		frame_system::Pallet::<T>::set_block_number(now + T::TipRefundDelay::get() + 1u32.into());
+	}: _( RawOrigin::Signed(caller), 0, now )
+	verify {
+		assert! (SContract::<T>::block_tips_of(0, now).is_none());
+	}
+
 	shard_rollup {
 		let caller: T::AccountId = whitelisted_caller();
-		SContract::<T>::add_authorized_shard_operator(RawOrigin::Root.into(), 0, caller.clone())?;
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
 		SContract::<T>::initialize_shard(
 			RawOrigin::Signed(caller.clone()).into(), 0, 
 			IPFS_CID_1.as_bytes().to_vec(),
 			PUBLIC_KEY
 		)?;
 	}: shard_rollup ( RawOrigin::Signed(caller), 0, IPFS_CID_1.as_bytes().to_vec(), 10_000 ) verify { }
+
+	propose_key_rotation {
+		let s in 0 .. 4194304;
+		let metadata = sp_std::vec![1u8; s as usize];
+
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0, 
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		let secret_id = SContract::<T>::shard_secret_id(0).unwrap();
+		pallet_secrets::Pallet::<T>::update_shares(
+			RawOrigin::Signed(caller.clone()).into(), secret_id, 2, 2, sealed_shares::<T>()
+		)?;
+	}: propose_key_rotation ( RawOrigin::Signed(caller), 0, NEW_PUBLIC_KEY, 10, metadata )
+	verify {
+		assert! (SContract::<T>::pending_key_rotation_of(0).is_some());
+	}
+
+	approve_key_rotation {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0, 
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, operator.clone())?;
+
+		// the second approval rotates the key
+		let secret_id = SContract::<T>::shard_secret_id(0).unwrap();
+		pallet_secrets::Pallet::<T>::update_shares(
+			RawOrigin::Signed(caller.clone()).into(), secret_id, 2, 2, sealed_shares::<T>()
+		)?;
+		SContract::<T>::propose_key_rotation(
+			RawOrigin::Signed(caller).into(), 0,
+			NEW_PUBLIC_KEY, 10, IPFS_CID_2.as_bytes().to_vec()
+		)?;
+	}: approve_key_rotation ( RawOrigin::Signed(operator), 0, NEW_PUBLIC_KEY )
+	verify {
+		assert_eq! (SContract::<T>::shard_public_key(0), Some(NEW_PUBLIC_KEY));
+	}
+
+	cancel_key_rotation {
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0, 
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		let secret_id = SContract::<T>::shard_secret_id(0).unwrap();
+		pallet_secrets::Pallet::<T>::update_shares(
+			RawOrigin::Signed(caller.clone()).into(), secret_id, 2, 2, sealed_shares::<T>()
+		)?;
+		SContract::<T>::propose_key_rotation(
+			RawOrigin::Signed(caller).into(), 0,
+			NEW_PUBLIC_KEY, 10, IPFS_CID_2.as_bytes().to_vec()
+		)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin> ( origin, 0 )
+	verify {
+		assert_eq! (SContract::<T>::pending_key_rotation_of(0), None);
+	}
+
+	propose_shard {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin> ( origin, 0 )
+	verify {
+		assert_eq! (SContract::<T>::shard_status(0), Some(ShardState::Proposed));
+	}
+
+	set_shard_status {
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0, 
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin> ( origin, 0, ShardState::Paused )
+	verify {
+		assert_eq! (SContract::<T>::shard_status(0), Some(ShardState::Paused));
+	}
+
+	migrate_shard {
+		let o in 1 .. 100;
+		let c in 0 .. 50;
+
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0, 
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		for i in 1 .. o {
+			let operator: T::AccountId = account("operator", i, 0);
+			SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, operator)?;
+		}
+		for i in 0 .. c {
+			SContract::<T>::register_contract(
+				RawOrigin::Signed(caller.clone()).into(),
+				[&b"contract_"[..], &i.to_be_bytes()[..]].concat(),
+				IPFS_CID_1.as_bytes().to_vec(),
+				SContract::<T>::empty_calls(),
+				0
+			)?;
+		}
+		SContract::<T>::set_shard_status(T::AdminOrigin::successful_origin(), 0, ShardState::Retiring)?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 1)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin> ( origin, 0, 1, o, c )
+	verify {
+		assert_eq! (SContract::<T>::shard_status(1), Some(ShardState::Active));
+		if c > 0 {
+			assert! (SContract::<T>::wasm_blob_of(1, [&b"contract_"[..], &0u32.to_be_bytes()[..]].concat()).is_some());
+		}
+	}
+
+	on_initialize {
+		let b in 1 .. 10;
+		let c in 1 .. 20;
+
+		let caller: T::AccountId = whitelisted_caller();
+		SContract::<T>::add_authorized_shard_operator(T::AdminOrigin::successful_origin(), 0, caller.clone())?;
+		SContract::<T>::propose_shard(T::AdminOrigin::successful_origin(), 0)?;
+		SContract::<T>::initialize_shard(
+			RawOrigin::Signed(caller.clone()).into(), 0, 
+			IPFS_CID_1.as_bytes().to_vec(),
+			PUBLIC_KEY
+		)?;
+		let calls = skw_blockchain_primitives::types::Calls {
+			ops: Vec::new(),
+			block_number: Some(1),
+			shard_id: 0
+		};
+		let encoded_calls = skw_blockchain_primitives::BorshSerialize::try_to_vec(&calls).unwrap();
+
+		for i in 0 .. b {
+			let block_number: T::BlockNumber = (i + 1).into();
+			frame_system::Pallet::<T>::set_block_number(block_number);
+			for _ in 0 .. c {
+				SContract::<T>::push_call(RawOrigin::Signed(caller.clone()).into(), 0, encoded_calls.clone())?;
+			}
+			SContract::<T>::schedule_queued_calls(block_number);
+			<SContract<T> as OnBlockConfirmed<T::AccountId, T::BlockNumber>>::on_block_confirmed(0, block_number, &[]);
+		}
+
+		let now = frame_system::Pallet::<T>::block_number() + T::CallRecordPruneDepth::get();
+	}: { SContract::<T>::prune_call_records(now, b * c); }
+	verify {
+		assert_eq! (SContract::<T>::call_history_of(0, now - T::CallRecordPruneDepth::get()), None);
+	}
 }
 
 impl_benchmark_test_suite!(
@@ -14,17 +14,42 @@ mod benchmarking;
 pub mod weights;
 pub use weights::WeightInfo;
 
+pub mod merkle;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
-		pallet_prelude::*, ensure, PalletId,
-		sp_runtime::traits::AccountIdConversion, StorageHasher, dispatch::DispatchResult
+		pallet_prelude::*, ensure, PalletId, transactional,
+		sp_runtime::traits::{AccountIdConversion, Hash as HashT, Zero, One, Saturating}, StorageHasher, dispatch::DispatchResult,
+		traits::{Currency, EnsureOrigin, ExistenceRequirement::{KeepAlive, AllowDeath}},
 	};
 	use frame_system::pallet_prelude::*;
 	use super::WeightInfo;
-	use skw_blockchain_primitives::types::{CallIndex, EncodedCall, ShardId, PublicKey, SecretId, Bytes};
+	use skw_blockchain_primitives::{
+		types::{CallIndex, EncodedCall, ShardId, PublicKey, SecretId, Bytes},
+		traits::{ShardStatusProvider, ShardAdmins, OnBlockConfirmed, CallHistoryProvider, ContractNaming, ReceiptRouter},
+	};
 	use frame_support::sp_runtime::SaturatedConversion;
-	use sp_std::vec::Vec;
+	use sp_std::{vec::Vec, collections::btree_map::BTreeMap};
+
+	/// lifecycle states of a shard
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum ShardState {
+		/// announced by the admin origin, operators can be added and the shard can be initialized
+		Proposed,
+		/// accepting calls and outcomes
+		Active,
+		/// not accepting new calls, outcomes of pushed calls are still accepted
+		Paused,
+		/// winding down, outcomes of pushed calls are still accepted until it is retired or migrated
+		Retiring,
+		/// no calls nor outcomes are accepted anymore
+		Retired,
+	}
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	#[pallet::config]
 	pub trait Config: 
@@ -43,6 +68,17 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxCallPerBlock: Get<u32>;
 
+		/// maximum number of calls waiting to be scheduled for a shard
+		#[pallet::constant]
+		type MaxQueuedCallsPerShard: Get<u32>;
+
+		/// currency used to pay tips to secret keepers
+		type Currency: Currency<Self::AccountId>;
+
+		/// number of blocks after which the tips of a block that has not been confirmed can be refunded
+		#[pallet::constant]
+		type TipRefundDelay: Get<Self::BlockNumber>;
+
 		/// minimum length of a contract name
 		#[pallet::constant]
 		type MinContractNameLength: Get<u32>;
@@ -53,10 +89,36 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type SContractRoot: Get<PalletId>;
+
+		/// number of blocks call records are kept after their block has been confirmed
+		#[pallet::constant]
+		type CallRecordPruneDepth: Get<Self::BlockNumber>;
+
+		/// maximum number of call records pruned in each block
+		#[pallet::constant]
+		type MaxPrunedCallsPerBlock: Get<u32>;
+
+		/// maximum number of code versions kept in the history of a contract
+		#[pallet::constant]
+		type MaxContractVersions: Get<u32>;
+
+		/// maximum number of keys a shard rotated away from that are kept with their last call index
+		#[pallet::constant]
+		type MaxPreviousShardKeys: Get<u32>;
+
+		/// name service contracts can claim a name on when they are deployed
+		type Naming: ContractNaming<Self::AccountId>;
+
+		/// origin allowed to administrate every shard and appoint shard admins
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// origin of shard admins, who can only administrate the shards they are appointed to
+		type ShardAdminOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// wasm_blob of a deployed contracts
@@ -65,17 +127,59 @@ pub mod pallet {
 	pub(super) type WasmBlob<T: Config> = StorageDoubleMap<_, Twox64Concat,
 		ShardId, Blake2_128Concat, BoundedVec<u8, T::MaxContractNameLength>, T::Hash>;
 
+	/// owner of a deployed contract, who is allowed to upgrade its code
+	#[pallet::storage]
+	#[pallet::getter(fn contract_owner_of)]
+	pub(super) type ContractOwner<T: Config> = StorageDoubleMap<_, Twox64Concat,
+		ShardId, Blake2_128Concat, BoundedVec<u8, T::MaxContractNameLength>, T::AccountId>;
+
+	/// code versions of a contract and the call index where each got activated, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn contract_versions_of)]
+	pub(super) type ContractVersions<T: Config> = StorageDoubleMap<_, Twox64Concat,
+		ShardId, Blake2_128Concat, BoundedVec<u8, T::MaxContractNameLength>,
+		BoundedVec<(T::Hash, CallIndex), T::MaxContractVersions>>;
+
+	/// number of contracts running a wasm blob
+	#[pallet::storage]
+	#[pallet::getter(fn code_ref_count_of)]
+	pub(super) type CodeRefCount<T: Config> = StorageMap<_, Identity, T::Hash, u32>;
+
 	/// call history of a block (ShardId, BlockNumber) -> Vec<CallIndex>
 	#[pallet::storage]
 	#[pallet::getter(fn call_history_of)]
 	pub(super) type CallHistory<T: Config> = StorageDoubleMap<_, Twox64Concat,
 		ShardId, Twox64Concat, T::BlockNumber, BoundedVec<CallIndex, T::MaxCallPerBlock> >;
 	
+	/// calls waiting to be scheduled in a block, highest tip first (BlockNumber, ShardId) -> Vec<(Tip, CallIndex)>.
+	/// Calls that do not fit in their block are deferred to the next one
+	#[pallet::storage]
+	#[pallet::getter(fn call_queue_of)]
+	pub(super) type CallQueue<T: Config> = StorageDoubleMap<_, Twox64Concat,
+		T::BlockNumber, Twox64Concat, ShardId, BoundedVec<(BalanceOf<T>, CallIndex), T::MaxQueuedCallsPerShard>>;
+
+	/// number of calls across all shards deferred from previous blocks
+	#[pallet::storage]
+	#[pallet::getter(fn queued_call_count)]
+	pub(super) type QueuedCallCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// tipped calls scheduled in a block (ShardId, BlockNumber) -> Vec<(CallIndex, Tip)>. The tips are paid to the
+	/// confirming secret keepers, or refunded to whoever pushed the calls when the block is never confirmed
+	#[pallet::storage]
+	#[pallet::getter(fn block_tips_of)]
+	pub(super) type BlockTips<T: Config> = StorageDoubleMap<_, Twox64Concat,
+		ShardId, Twox64Concat, T::BlockNumber, BoundedVec<(CallIndex, BalanceOf<T>), T::MaxCallPerBlock>>;
+
 	/// call content of a call (ShardId, CallIndex) -> EncodedCall
 	#[pallet::storage]
 	#[pallet::getter(fn call_record_of)]
 	pub(super) type CallRecord<T: Config> = StorageMap<_, Twox64Concat, CallIndex, (BoundedVec<u8, T::MaxCallLength>, T::AccountId) >;
 
+	/// (shard, call index) of the call whose outcome pushed a call through a receipt
+	#[pallet::storage]
+	#[pallet::getter(fn receipt_source_of)]
+	pub(super) type ReceiptSource<T: Config> = StorageMap<_, Twox64Concat, CallIndex, (ShardId, CallIndex)>;
+
 	/// the callIndex that will be assigned to the next calls
 	#[pallet::type_value]
 	pub(super) fn DefaultId<T: Config>() -> CallIndex { 0u32 }
@@ -95,6 +199,26 @@ pub mod pallet {
 	pub(super) type ShardPublicKey<T: Config> = StorageMap<_, Twox64Concat,
 		ShardId, PublicKey>;
 	
+	/// keys a shard rotated away from and the call index the next key is required from, oldest first.
+	/// Calls encrypted to a previous key are accepted before its call index only
+	#[pallet::storage]
+	#[pallet::getter(fn previous_shard_keys_of)]
+	pub(super) type PreviousShardKeys<T: Config> = StorageMap<_, Twox64Concat,
+		ShardId, BoundedVec<(PublicKey, CallIndex), T::MaxPreviousShardKeys>, ValueQuery>;
+
+	/// key rotation waiting for the approval of the shard operators:
+	/// (new public key, effective call index, hash of the re-sealed state metadata, approvals)
+	#[pallet::storage]
+	#[pallet::getter(fn pending_key_rotation_of)]
+	pub(super) type PendingKeyRotation<T: Config> = StorageMap<_, Twox64Concat,
+		ShardId, (PublicKey, CallIndex, T::Hash, u32)>;
+
+	/// operators who approved the pending key rotation of a shard
+	#[pallet::storage]
+	#[pallet::getter(fn key_rotation_approval_of)]
+	pub(super) type KeyRotationApproval<T: Config> = StorageDoubleMap<_, Twox64Concat,
+		ShardId, Twox64Concat, T::AccountId, bool>;
+
 	/// the highest call index of a shard as of the latest state rollup
 	#[pallet::storage]
 	#[pallet::getter(fn shard_high_call_index)]
@@ -106,7 +230,80 @@ pub mod pallet {
 	#[pallet::getter(fn shard_operator)]
 	pub(super) type ShardOperator<T: Config> = StorageDoubleMap<_, Twox64Concat,
 		ShardId, Twox64Concat, T::AccountId, bool>;
-		
+
+	/// lifecycle state of a shard
+	#[pallet::storage]
+	#[pallet::getter(fn shard_status)]
+	pub(super) type ShardStatus<T: Config> = StorageMap<_, Twox64Concat,
+		ShardId, ShardState>;
+
+	/// confirmed blocks waiting to be pruned: queue position -> (ShardId, BlockNumber, prunable at)
+	#[pallet::storage]
+	#[pallet::getter(fn prune_queue_at)]
+	pub(super) type PruneQueue<T: Config> = StorageMap<_, Twox64Concat,
+		u32, (ShardId, T::BlockNumber, T::BlockNumber)>;
+
+	/// (head, tail) of the PruneQueue
+	#[pallet::storage]
+	#[pallet::getter(fn prune_queue_range)]
+	pub(super) type PruneQueueRange<T: Config> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	/// merkle root of the call records pruned for a block (ShardId, BlockNumber) -> Hash
+	#[pallet::storage]
+	#[pallet::getter(fn archived_call_root_of)]
+	pub(super) type ArchivedCallRoot<T: Config> = StorageDoubleMap<_, Twox64Concat,
+		ShardId, Twox64Concat, T::BlockNumber, T::Hash>;
+
+	/// the shard a retired shard has been migrated to
+	#[pallet::storage]
+	#[pallet::getter(fn shard_successor)]
+	pub(super) type ShardSuccessor<T: Config> = StorageMap<_, Twox64Concat,
+		ShardId, ShardId>;
+
+	/// the account appointed to administrate a shard
+	#[pallet::storage]
+	#[pallet::getter(fn shard_admin_of)]
+	pub(super) type ShardAdmin<T: Config> = StorageMap<_, Twox64Concat,
+		ShardId, T::AccountId>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// (shard, operator) authorized at genesis
+		pub shard_operators: Vec<(ShardId, T::AccountId)>,
+		/// (shard, initializing operator, initial state metadata, shard public key) of shards active from genesis
+		pub shards: Vec<(ShardId, T::AccountId, Vec<u8>, PublicKey)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				shard_operators: Vec::new(),
+				shards: Vec::new(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (shard_id, operator) in self.shard_operators.iter() {
+				<ShardOperator<T>>::insert(shard_id, operator, true);
+			}
+
+			for (shard_id, operator, initial_state_metadata, public_key) in self.shards.iter() {
+				<ShardOperator<T>>::insert(shard_id, operator, true);
+				<ShardStatus<T>>::insert(shard_id, ShardState::Proposed);
+				Pallet::<T>::initialize_shard(
+					frame_system::RawOrigin::Signed(operator.clone()).into(),
+					*shard_id,
+					initial_state_metadata.clone(),
+					*public_key,
+				).expect("genesis shards should be valid");
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -114,6 +311,20 @@ pub mod pallet {
 		ShardInitialized(ShardId),
 		ShardRolluped(ShardId, CallIndex),
 		SecretContractRegistered(ShardId, Vec<u8>, CallIndex),
+		SecretContractUpgraded(ShardId, Vec<u8>, T::Hash, CallIndex),
+		ShardStatusChanged(ShardId, ShardState),
+		ShardMigrated(ShardId, ShardId),
+		CallRecordsArchived(ShardId, T::BlockNumber, T::Hash),
+		CallsScheduled(ShardId, T::BlockNumber, u32),
+		TipsPaid(ShardId, T::BlockNumber, BalanceOf<T>),
+		TipsRefunded(ShardId, T::BlockNumber, BalanceOf<T>),
+		ShardAdminChanged(ShardId, Option<T::AccountId>),
+		ShardOperatorRemoved(ShardId, T::AccountId),
+		ShardKeyRotationProposed(ShardId, CallIndex),
+		ShardKeyRotationApproved(ShardId, T::AccountId),
+		ShardKeyRotationCancelled(ShardId),
+		ShardKeyRotated(ShardId, CallIndex),
+		ReceiptReceived(ShardId, CallIndex, ShardId, CallIndex),
 	}
 
 	#[pallet::error]
@@ -129,6 +340,73 @@ pub mod pallet {
 		InvalidWasmBlob,	
 		Unauthorized, 
 		Unexpected,
+		ShardNotActive,
+		ShardAlreadyProposed,
+		InvalidShardStatusTransition,
+		ContractNotFound,
+		ContractCodeUnchanged,
+		CallQueueFull,
+		KeyRotationInProgress,
+		NoPendingKeyRotation,
+		KeyRotationAlreadyApproved,
+		InvalidEffectiveCallIndex,
+		StaleShardKey,
+		ShardKeyRequired,
+		ShardSecretNotShared,
+		InvalidOperatorCount,
+		TipsNotRefundable,
+		InvalidContractCount,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// prune call records of confirmed blocks that are old enough
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let (blocks, calls) = Self::prune_call_records(now, T::MaxPrunedCallsPerBlock::get());
+
+			// calls pushed in this block pay for their scheduling in push_call
+			<T as Config>::WeightInfo::on_initialize(blocks, calls)
+				.saturating_add(<T as Config>::WeightInfo::schedule_calls(Self::queued_call_count()))
+		}
+
+		/// move queued calls into the CallHistory of this block, highest tip first
+		fn on_finalize(now: T::BlockNumber) {
+			Self::schedule_queued_calls(now);
+		}
+
+		/// every block has to fit in the pruning budget, otherwise pruning gets stuck on it
+		fn integrity_test() {
+			assert!(T::MaxPrunedCallsPerBlock::get() >= T::MaxCallPerBlock::get());
+			assert!(T::MaxPreviousShardKeys::get() >= 1);
+		}
+
+		/// shards initialized before lifecycle states existed are marked as Active, their
+		/// secrets are bound to the shard and the blobs of existing contracts are ref counted
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return 0;
+			}
+
+			let mut migrated: Weight = 0;
+			for (shard_id, secret_id) in <ShardSecretIndex<T>>::iter() {
+				if !<ShardStatus<T>>::contains_key(&shard_id) {
+					<ShardStatus<T>>::insert(&shard_id, ShardState::Active);
+					migrated += 1;
+				}
+				pallet_secrets::Pallet::<T>::do_bind_shard(secret_id, shard_id);
+				migrated += 1;
+			}
+
+			// contracts deployed before code ref counting. Their blobs were noted without a
+			// reference in pallet-secrets, so releasing them never unnotes the bytes
+			for code_hash in <WasmBlob<T>>::iter_values() {
+				<CodeRefCount<T>>::mutate(&code_hash, |c| *c = Some(c.unwrap_or(0).saturating_add(1)));
+				migrated += 1;
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+		}
 	}
 
 	#[pallet::call]
@@ -136,6 +414,7 @@ pub mod pallet {
 
 		/// register a contract with a deployment encoded call
 		#[pallet::weight(<T as Config>::WeightInfo::register_contract())]
+		#[transactional]
 		pub fn register_contract(
 			origin: OriginFor<T>, 
 			contract_name: Vec<u8>,
@@ -149,27 +428,71 @@ pub mod pallet {
 			// ("action_deploy") will be automatically appended by the offchain bridge
 
 
-			let hash = pallet_secrets::Pallet::<T>::maybe_note_bytes(wasm_blob)?;
-			let bounded_contract_name = BoundedVec::<u8, T::MaxContractNameLength>::try_from(contract_name.clone())
-				.map_err(|_| Error::<T>::InvalidContractName)?;
+			let hash = Self::note_code(wasm_blob)?;
+			Self::do_register_contract(deployer, shard_id, contract_name, hash, deployment_call)
+		}
 
-			ensure!(
-				Self::validate_name(shard_id, &bounded_contract_name),
-				Error::<T>::InvalidContractName
-			);
+		/// register a contract and claim a name for `period` periods on the name service resolving to it
+		#[pallet::weight(<T as Config>::WeightInfo::register_contract_with_name())]
+		#[transactional]
+		pub fn register_contract_with_name(
+			origin: OriginFor<T>, 
+			contract_name: Vec<u8>,
+			wasm_blob: Bytes,
+			deployment_call: EncodedCall,
+			shard_id: ShardId,
+			name: Vec<u8>,
+			period: u32,
+		) -> DispatchResult {
+			let deployer = ensure_signed(origin.clone())?;
 
-			let call_index = Self::maybe_push_calls(
-				deployer, 
-				shard_id, 
-				&deployment_call, 
-				Some(contract_name.clone()),
-				false,
-			)?;
+			let hash = Self::note_code(wasm_blob)?;
+			Self::do_register_contract(deployer.clone(), shard_id, contract_name.clone(), hash, deployment_call)?;
+			T::Naming::claim_contract_name(&deployer, &name, period, shard_id, &contract_name)
+		}
 
-			// No error below this line 
-			<WasmBlob<T>>::insert(&shard_id, &bounded_contract_name, hash);
-			Self::deposit_event(Event::<T>::SecretContractRegistered(shard_id, contract_name, call_index));
-			Ok(())
+		/// register a contract with a wasm blob that has already been uploaded by another contract
+		#[pallet::weight(<T as Config>::WeightInfo::register_contract_with_code_hash())]
+		pub fn register_contract_with_code_hash(
+			origin: OriginFor<T>, 
+			contract_name: Vec<u8>,
+			code_hash: T::Hash,
+			deployment_call: EncodedCall,
+			shard_id: ShardId,
+		) -> DispatchResult {
+			let deployer = ensure_signed(origin.clone())?;
+			ensure!(Self::is_known_code(&code_hash), Error::<T>::InvalidWasmBlob);
+
+			Self::do_register_contract(deployer, shard_id, contract_name, code_hash, deployment_call)
+		}
+
+		/// (CONTRACT OWNER ONLY) upgrade the code of a contract with a new wasm blob
+		#[pallet::weight(<T as Config>::WeightInfo::upgrade_contract())]
+		#[transactional]
+		pub fn upgrade_contract(
+			origin: OriginFor<T>,
+			contract_name: Vec<u8>,
+			wasm_blob: Bytes,
+			shard_id: ShardId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
+
+			let hash = Self::note_code(wasm_blob)?;
+			Self::do_upgrade_contract(who, shard_id, contract_name, hash)
+		}
+
+		/// (CONTRACT OWNER ONLY) upgrade the code of a contract to an already uploaded wasm blob
+		#[pallet::weight(<T as Config>::WeightInfo::upgrade_contract_with_code_hash())]
+		pub fn upgrade_contract_with_code_hash(
+			origin: OriginFor<T>,
+			contract_name: Vec<u8>,
+			code_hash: T::Hash,
+			shard_id: ShardId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
+			ensure!(Self::is_known_code(&code_hash), Error::<T>::InvalidWasmBlob);
+
+			Self::do_upgrade_contract(who, shard_id, contract_name, code_hash)
 		}
 
 		/// push a batch of calls for a shard
@@ -182,15 +505,100 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 
 			if  who.clone() == Self::get_pallet_account_id() {
-				let call_index = Self::maybe_push_calls(who, shard_id, &call, None, true)?;
+				let call_index = Self::maybe_push_calls(who, shard_id, &call, None, None, true, Zero::zero())?;
 				Self::deposit_event(Event::<T>::CallReceived(shard_id, call_index));
 			} else {
-				let call_index = Self::maybe_push_calls(who, shard_id, &call, None, false)?;
+				let call_index = Self::maybe_push_calls(who, shard_id, &call, None, None, false, Zero::zero())?;
+				Self::deposit_event(Event::<T>::CallReceived(shard_id, call_index));
+			}
+			Ok(())
+		}
+
+		/// push batches of calls for several shards at once. Batches for the same shard are merged
+		/// into one call, and either every shard accepts its calls or none does
+		#[pallet::weight(<T as Config>::WeightInfo::push_calls(calls.len() as u32))]
+		#[transactional]
+		pub fn push_calls(
+			origin: OriginFor<T>,
+			calls: Vec<(ShardId, EncodedCall)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(calls.len() <= T::MaxCallPerBlock::get() as usize, Error::<T>::TooManyCallsInCurrentBlock);
+
+			for (shard_id, call) in Self::split_calls_by_shard(&calls)? {
+				let call_index = Self::maybe_push_calls(who.clone(), shard_id, &call, None, None, false, Zero::zero())?;
 				Self::deposit_event(Event::<T>::CallReceived(shard_id, call_index));
 			}
 			Ok(())
 		}
 
+		/// push a batch of calls encrypted to `public_key`. The previous key of a rotated
+		/// shard is only accepted before the call index the rotation is effective from
+		#[pallet::weight(<T as Config>::WeightInfo::push_call_with_key())]
+		pub fn push_call_with_key(
+			origin: OriginFor<T>, 
+			shard_id: ShardId,
+			call: EncodedCall,
+			public_key: PublicKey,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let call_index = Self::maybe_push_calls(who, shard_id, &call, None, Some(&public_key), false, Zero::zero())?;
+			Self::deposit_event(Event::<T>::CallReceived(shard_id, call_index));
+			Ok(())
+		}
+
+		/// push a batch of calls for a shard with a tip for the secret keepers, optionally encrypted
+		/// to `public_key` like `push_call_with_key`.
+		/// Calls with higher tips are scheduled first when a block is full
+		#[pallet::weight(<T as Config>::WeightInfo::push_call_with_tip())]
+		#[transactional]
+		pub fn push_call_with_tip(
+			origin: OriginFor<T>, 
+			shard_id: ShardId,
+			call: EncodedCall,
+			tip: BalanceOf<T>,
+			public_key: Option<PublicKey>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if !tip.is_zero() {
+				T::Currency::transfer(&who, &Self::get_pallet_account_id(), tip, KeepAlive)?;
+			}
+
+			let call_index = Self::maybe_push_calls(who, shard_id, &call, None, public_key.as_ref(), false, tip)?;
+			Self::deposit_event(Event::<T>::CallReceived(shard_id, call_index));
+			Ok(())
+		}
+
+		/// refund the tips of a block that has not been confirmed within TipRefundDelay blocks
+		/// to the accounts that pushed the tipped calls
+		#[pallet::weight(<T as Config>::WeightInfo::refund_tips(T::MaxCallPerBlock::get()))]
+		#[transactional]
+		pub fn refund_tips(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+			block_number: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() > block_number.saturating_add(T::TipRefundDelay::get()),
+				Error::<T>::TipsNotRefundable
+			);
+			let tips = <BlockTips<T>>::take(&shard_id, &block_number).ok_or(Error::<T>::TipsNotRefundable)?;
+
+			// call records of a block are only pruned once it has been confirmed
+			let pallet_account = Self::get_pallet_account_id();
+			for (call_index, tip) in tips.iter() {
+				if let Some((_, pusher)) = Self::call_record_of(call_index) {
+					T::Currency::transfer(&pallet_account, &pusher, *tip, AllowDeath)?;
+				}
+			}
+
+			Self::deposit_event(Event::<T>::TipsRefunded(shard_id, block_number, Self::total_tips(&tips)));
+			Ok(Some(<T as Config>::WeightInfo::refund_tips(tips.len() as u32)).into())
+		}
+
 		/// (SHARD OPERATOR ONLY) initialize a shard with initial state file and initial calls
 		#[pallet::weight(<T as Config>::WeightInfo::initialize_shard())]
 		pub fn initialize_shard(
@@ -201,6 +609,12 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin.clone())?;
 			ensure!(Self::shard_operator(&shard_id, &who).is_some(), Error::<T>::Unauthorized);
+			match Self::shard_status(shard_id) {
+				Some(ShardState::Proposed) => {},
+				// the shard has to be announced with propose_shard first
+				None => return Err(Error::<T>::InvalidShardIndex.into()),
+				Some(_) => return Err(Error::<T>::ShardHasBeenInitialized.into()),
+			}
 
 			match pallet_secrets::Pallet::<T>::register_secret(origin, initial_state_metadata) {
 				Ok(()) => {
@@ -208,8 +622,10 @@ pub mod pallet {
 					let secret_id = pallet_secrets::Pallet::<T>::current_secret_id().saturating_sub(1);
 
 					<ShardSecretIndex<T>>::insert(&shard_id, secret_id);
+					pallet_secrets::Pallet::<T>::do_bind_shard(secret_id, shard_id);
 					<ShardPublicKey<T>>::insert(&shard_id, public_key);
 					<ShardHighCallIndex<T>>::insert(&shard_id, 0);
+					<ShardStatus<T>>::insert(&shard_id, ShardState::Active);
 					Self::deposit_event(Event::<T>::ShardInitialized(shard_id));
 					Ok(())
 				},
@@ -242,19 +658,82 @@ pub mod pallet {
 			}
 		}
 		
-		/// (ROOT ONLY) nominate a shard operator
+		/// (SHARD OPERATOR ONLY) propose a new shard key, with the latest state re-sealed under it.
+		/// The key is required from `effective_from` once enough operators approved it. The proposal
+		/// counts as the approval of the proposer, which rotates the key at a share threshold of 1
+		#[pallet::weight(
+			<T as Config>::WeightInfo::propose_key_rotation(metadata.len() as u32)
+				.saturating_add(<T as Config>::WeightInfo::approve_key_rotation())
+		)]
+		pub fn propose_key_rotation(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+			public_key: PublicKey,
+			effective_from: CallIndex,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::shard_operator(&shard_id, &who).is_some(), Error::<T>::Unauthorized);
+			ensure!(Self::shard_secret_id(shard_id).is_some(), Error::<T>::ShardNotInitialized);
+			ensure!(Self::pending_key_rotation_of(shard_id).is_none(), Error::<T>::KeyRotationInProgress);
+			ensure!(Self::key_rotation_threshold(shard_id).is_some(), Error::<T>::ShardSecretNotShared);
+			ensure!(effective_from >= Self::min_effective_call_index(shard_id), Error::<T>::InvalidEffectiveCallIndex);
+
+			let hash = pallet_secrets::Pallet::<T>::maybe_note_bytes(metadata)?;
+			<PendingKeyRotation<T>>::insert(&shard_id, (public_key, effective_from, hash, 0));
+			Self::deposit_event(Event::<T>::ShardKeyRotationProposed(shard_id, effective_from));
+
+			Self::do_approve_key_rotation(shard_id, who)
+		}
+
+		/// (SHARD OPERATOR ONLY) approve the pending key rotation of a shard
+		#[pallet::weight(<T as Config>::WeightInfo::approve_key_rotation())]
+		pub fn approve_key_rotation(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+			public_key: PublicKey,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::shard_operator(&shard_id, &who).is_some(), Error::<T>::Unauthorized);
+
+			let (pending_key, _, _, _) = Self::pending_key_rotation_of(shard_id)
+				.ok_or(Error::<T>::NoPendingKeyRotation)?;
+			// guards against approving a rotation that got replaced in the meantime
+			ensure!(pending_key == public_key, Error::<T>::NoPendingKeyRotation);
+
+			Self::do_approve_key_rotation(shard_id, who)
+		}
+
+		/// (SHARD ADMIN ONLY) drop the pending key rotation of a shard
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_key_rotation())]
+		pub fn cancel_key_rotation(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+		) -> DispatchResult {
+			Self::ensure_shard_admin(origin, shard_id)?;
+
+			let (_, _, hash, _) = <PendingKeyRotation<T>>::take(&shard_id)
+				.ok_or(Error::<T>::NoPendingKeyRotation)?;
+			pallet_secrets::Pallet::<T>::maybe_remove_bytes(&hash);
+			<KeyRotationApproval<T>>::remove_prefix(&shard_id, None);
+
+			Self::deposit_event(Event::<T>::ShardKeyRotationCancelled(shard_id));
+			Ok(())
+		}
+
+		/// (SHARD ADMIN ONLY) nominate a shard operator
 		#[pallet::weight(<T as Config>::WeightInfo::add_authorized_shard_operator())]
 		pub fn add_authorized_shard_operator(
 			origin: OriginFor<T>,
 			shard_id: ShardId,
 			operator: T::AccountId,
 		) -> DispatchResult {
-			ensure_root(origin.clone())?;
+			Self::ensure_shard_admin(origin, shard_id)?;
 
 			let secret_id = Self::shard_secret_id(shard_id);
 			match secret_id {
 				Some(id) => {
-					pallet_secrets::Pallet::<T>::force_nominate_member(origin, id, operator.clone())?;
+					pallet_secrets::Pallet::<T>::do_nominate_member(id, operator.clone());
 					<ShardOperator<T>>::mutate(&shard_id, &operator, |status| {
 						* status = Some(true);
 					});
@@ -274,6 +753,163 @@ pub mod pallet {
 			}
 		}
 
+		/// (SHARD ADMIN ONLY) remove a shard operator
+		#[pallet::weight(<T as Config>::WeightInfo::remove_authorized_shard_operator())]
+		pub fn remove_authorized_shard_operator(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+			operator: T::AccountId,
+		) -> DispatchResult {
+			Self::ensure_shard_admin(origin, shard_id)?;
+			ensure!(<ShardOperator<T>>::take(&shard_id, &operator).is_some(), Error::<T>::Unauthorized);
+
+			if let Some(id) = Self::shard_secret_id(shard_id) {
+				pallet_secrets::Pallet::<T>::do_remove_member(id, operator.clone());
+			}
+
+			Self::deposit_event(Event::<T>::ShardOperatorRemoved(shard_id, operator));
+			Ok(())
+		}
+
+		/// (ADMIN ONLY) appoint or remove the admin of a shard
+		#[pallet::weight(<T as Config>::WeightInfo::set_shard_admin())]
+		pub fn set_shard_admin(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+			admin: Option<T::AccountId>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<ShardAdmin<T>>::set(&shard_id, admin.clone());
+			Self::deposit_event(Event::<T>::ShardAdminChanged(shard_id, admin));
+			Ok(())
+		}
+
+		/// (ADMIN ONLY) announce a new shard
+		#[pallet::weight(<T as Config>::WeightInfo::propose_shard())]
+		pub fn propose_shard(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::shard_status(shard_id).is_none(), Error::<T>::ShardAlreadyProposed);
+
+			<ShardStatus<T>>::insert(&shard_id, ShardState::Proposed);
+			Self::deposit_event(Event::<T>::ShardStatusChanged(shard_id, ShardState::Proposed));
+			Ok(())
+		}
+
+		/// (SHARD ADMIN ONLY) move a shard to another lifecycle state
+		#[pallet::weight(<T as Config>::WeightInfo::set_shard_status())]
+		pub fn set_shard_status(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+			status: ShardState,
+		) -> DispatchResult {
+			Self::ensure_shard_admin(origin, shard_id)?;
+
+			let current = Self::shard_status(shard_id).ok_or(Error::<T>::InvalidShardIndex)?;
+			ensure!(
+				Self::is_valid_status_transition(current, status),
+				Error::<T>::InvalidShardStatusTransition
+			);
+
+			<ShardStatus<T>>::insert(&shard_id, status);
+			Self::deposit_event(Event::<T>::ShardStatusChanged(shard_id, status));
+			Ok(())
+		}
+
+		/// (ADMIN ONLY) move the secret, operators and contracts of a retiring shard to a proposed
+		/// shard id. `operator_count` and `contract_count` are upper bounds of the number of
+		/// operators and contracts of the retiring shard
+		#[pallet::weight(<T as Config>::WeightInfo::migrate_shard(*operator_count, *contract_count))]
+		pub fn migrate_shard(
+			origin: OriginFor<T>,
+			shard_id: ShardId,
+			new_shard_id: ShardId,
+			operator_count: u32,
+			contract_count: u32,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				Self::shard_status(shard_id) == Some(ShardState::Retiring),
+				Error::<T>::InvalidShardStatusTransition
+			);
+			match Self::shard_status(new_shard_id) {
+				Some(ShardState::Proposed) => {},
+				// the shard has to be announced with propose_shard first
+				None => return Err(Error::<T>::InvalidShardIndex.into()),
+				Some(_) => return Err(Error::<T>::ShardHasBeenInitialized.into()),
+			}
+
+			let secret_id = Self::shard_secret_id(shard_id).ok_or(Error::<T>::ShardNotInitialized)?;
+			ensure!(
+				<ShardOperator<T>>::iter_prefix(&shard_id).take(operator_count as usize + 1).count()
+					<= operator_count as usize,
+				Error::<T>::InvalidOperatorCount
+			);
+			ensure!(
+				<WasmBlob<T>>::iter_prefix(&shard_id).take(contract_count as usize + 1).count()
+					<= contract_count as usize,
+				Error::<T>::InvalidContractCount
+			);
+
+			// No error below this line
+			<ShardSecretIndex<T>>::remove(&shard_id);
+			<ShardSecretIndex<T>>::insert(&new_shard_id, secret_id);
+			// the shares are re-shared to the keepers running the new shard
+			pallet_secrets::Pallet::<T>::do_bind_shard(secret_id, new_shard_id);
+
+			if let Some(public_key) = <ShardPublicKey<T>>::take(&shard_id) {
+				<ShardPublicKey<T>>::insert(&new_shard_id, public_key);
+			}
+
+			let previous_keys = <PreviousShardKeys<T>>::take(&shard_id);
+			if !previous_keys.is_empty() {
+				<PreviousShardKeys<T>>::insert(&new_shard_id, previous_keys);
+			}
+
+			// operators have to agree on a key rotation again on the new shard
+			if let Some((_, _, hash, _)) = <PendingKeyRotation<T>>::take(&shard_id) {
+				pallet_secrets::Pallet::<T>::maybe_remove_bytes(&hash);
+			}
+			<KeyRotationApproval<T>>::remove_prefix(&shard_id, None);
+
+			// calls pushed to the new shard id are indexed globally, the rollup index carries over
+			if let Some(high_call_index) = <ShardHighCallIndex<T>>::take(&shard_id) {
+				<ShardHighCallIndex<T>>::insert(&new_shard_id, high_call_index);
+			}
+
+			for (operator, status) in <ShardOperator<T>>::drain_prefix(&shard_id) {
+				<ShardOperator<T>>::insert(&new_shard_id, &operator, status);
+			}
+
+			if let Some(admin) = <ShardAdmin<T>>::take(&shard_id) {
+				<ShardAdmin<T>>::insert(&new_shard_id, admin);
+			}
+
+			// contracts keep their names and versions, the code references move along
+			for (contract_name, code_hash) in <WasmBlob<T>>::drain_prefix(&shard_id) {
+				<WasmBlob<T>>::insert(&new_shard_id, &contract_name, code_hash);
+			}
+			for (contract_name, owner) in <ContractOwner<T>>::drain_prefix(&shard_id) {
+				<ContractOwner<T>>::insert(&new_shard_id, &contract_name, owner);
+			}
+			for (contract_name, versions) in <ContractVersions<T>>::drain_prefix(&shard_id) {
+				<ContractVersions<T>>::insert(&new_shard_id, &contract_name, versions);
+			}
+
+			<ShardStatus<T>>::insert(&shard_id, ShardState::Retired);
+			<ShardStatus<T>>::insert(&new_shard_id, ShardState::Active);
+			<ShardSuccessor<T>>::insert(&shard_id, new_shard_id);
+
+			Self::deposit_event(Event::<T>::ShardStatusChanged(shard_id, ShardState::Retired));
+			Self::deposit_event(Event::<T>::ShardStatusChanged(new_shard_id, ShardState::Active));
+			Self::deposit_event(Event::<T>::ShardMigrated(shard_id, new_shard_id));
+			Ok(())
+		}
+
 		/// (ROOT ONLY/TEST ONLY) WILL BE REMOVED Force Update call_record
 		#[pallet::weight(0)]
 		pub fn force_update_call_record(
@@ -316,17 +952,300 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// the admin origin, or the shard admin origin of the account appointed to the shard
+		pub fn ensure_shard_admin(origin: OriginFor<T>, shard_id: ShardId) -> DispatchResult {
+			let origin = match T::AdminOrigin::try_origin(origin) {
+				Ok(_) => return Ok(()),
+				Err(origin) => origin,
+			};
+
+			let who = T::ShardAdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::shard_admin_of(shard_id) == Some(who), Error::<T>::Unauthorized);
+			Ok(())
+		}
+
+		/// merge the batches of calls of each shard into a single encoded call per shard, by shard id
+		pub fn split_calls_by_shard(
+			calls: &[(ShardId, EncodedCall)],
+		) -> Result<Vec<(ShardId, EncodedCall)>, Error<T>> {
+			let mut by_shard: BTreeMap<ShardId, skw_blockchain_primitives::types::Calls> = BTreeMap::new();
+			for (shard_id, call) in calls.iter() {
+				let decoded: skw_blockchain_primitives::types::Calls =
+					skw_blockchain_primitives::BorshDeserialize::try_from_slice(&call[..])
+						.map_err(|_| Error::<T>::InvalidEncodedCall)?;
+
+				by_shard.entry(*shard_id)
+					.or_insert_with(Default::default)
+					.ops.extend(decoded.ops);
+			}
+
+			by_shard.into_iter()
+				.map(|(shard_id, calls)| {
+					skw_blockchain_primitives::BorshSerialize::try_to_vec(&calls)
+						.map(|encoded| (shard_id, encoded))
+						.map_err(|_| Error::<T>::InvalidEncodedCall)
+				})
+				.collect()
+		}
+
+		/// number of operator approvals a key rotation needs - the share threshold of the shard secret.
+		/// Keys are not rotated before the shard secret is shared
+		pub fn key_rotation_threshold(shard_id: ShardId) -> Option<u32> {
+			Self::shard_secret_id(shard_id)
+				.and_then(|secret_id| pallet_secrets::Pallet::<T>::share_threshold_of(secret_id))
+				.map(|(threshold, _)| threshold)
+		}
+
+		/// record the approval of an operator and rotate the key once the threshold is reached
+		pub fn do_approve_key_rotation(shard_id: ShardId, who: T::AccountId) -> DispatchResult {
+			ensure!(
+				Self::key_rotation_approval_of(&shard_id, &who).is_none(),
+				Error::<T>::KeyRotationAlreadyApproved
+			);
+
+			let (public_key, effective_from, hash, approvals) = Self::pending_key_rotation_of(shard_id)
+				.ok_or(Error::<T>::NoPendingKeyRotation)?;
+			let threshold = Self::key_rotation_threshold(shard_id).ok_or(Error::<T>::ShardSecretNotShared)?;
+			let approvals = approvals.saturating_add(1);
+			let rotates = approvals >= threshold;
+
+			// calls pushed while the rotation was waiting for approvals may have passed its call index
+			ensure!(
+				!rotates || effective_from >= Self::min_effective_call_index(shard_id),
+				Error::<T>::InvalidEffectiveCallIndex
+			);
+
+			<KeyRotationApproval<T>>::insert(&shard_id, &who, true);
+			Self::deposit_event(Event::<T>::ShardKeyRotationApproved(shard_id, who.clone()));
+
+			if !rotates {
+				<PendingKeyRotation<T>>::insert(&shard_id, (public_key, effective_from, hash, approvals));
+				return Ok(());
+			}
+
+			let secret_id = Self::shard_secret_id(shard_id).ok_or(Error::<T>::ShardNotInitialized)?;
+			pallet_secrets::Pallet::<T>::do_set_metadata(secret_id, hash, who);
+
+			if let Some(old_key) = Self::shard_public_key(shard_id) {
+				<PreviousShardKeys<T>>::mutate(&shard_id, |keys| {
+					if keys.len() >= T::MaxPreviousShardKeys::get() as usize {
+						// keep the most recent keys only
+						keys.remove(0);
+					}
+					let _ = keys.try_push((old_key, effective_from));
+				});
+			}
+			<ShardPublicKey<T>>::insert(&shard_id, public_key);
+			<PendingKeyRotation<T>>::remove(&shard_id);
+			<KeyRotationApproval<T>>::remove_prefix(&shard_id, None);
+
+			Self::deposit_event(Event::<T>::ShardKeyRotated(shard_id, effective_from));
+			Ok(())
+		}
+
+		/// whether calls encrypted to `public_key` are accepted by the shard at the current call index.
+		/// Only the key the call will be read with is accepted, so the new key of a rotation is
+		/// refused until the call index the rotation is effective from
+		pub fn is_valid_call_key(shard_id: ShardId, public_key: &PublicKey) -> bool {
+			Self::public_key_of_call(shard_id, Self::current_call_index_of()).as_ref() == Some(public_key)
+		}
+
+		/// calls pushed without a key are taken as encrypted to the current key of the shard, which
+		/// cannot be told apart from a previous key that is still accepted
+		pub fn ensure_call_key(shard_id: ShardId, public_key: Option<&PublicKey>) -> Result<(), Error<T>> {
+			match public_key {
+				Some(public_key) =>
+					ensure!(Self::is_valid_call_key(shard_id, public_key), Error::<T>::StaleShardKey),
+				None =>
+					ensure!(
+						Self::current_call_index_of() >= Self::min_effective_call_index(shard_id),
+						Error::<T>::ShardKeyRequired
+					),
+			}
+			Ok(())
+		}
+
+		/// lowest call index the next key of a shard can be required from - the call indexes of
+		/// previous keys never decrease
+		pub fn min_effective_call_index(shard_id: ShardId) -> CallIndex {
+			let call_index = Self::current_call_index_of();
+			match Self::previous_shard_keys_of(shard_id).last() {
+				Some((_, effective_from)) => call_index.max(*effective_from),
+				None => call_index,
+			}
+		}
+
+		/// the key a call of the shard is encrypted to
+		pub fn public_key_of_call(shard_id: ShardId, call_index: CallIndex) -> Option<PublicKey> {
+			match Self::previous_shard_keys_of(shard_id).iter().find(|(_, effective_from)| call_index < *effective_from) {
+				Some((old_key, _)) => Some(*old_key),
+				None => Self::shard_public_key(shard_id),
+			}
+		}
+
 		pub fn validate_name(shard_id: ShardId, name: &BoundedVec::<u8, T::MaxContractNameLength>) -> bool {
 			name.len() >= T::MinContractNameLength::get() as usize
 				&& 
 			Self::wasm_blob_of(shard_id, name).is_none() // as name is not taken
 		}
 
+		/// wasm blob run by at least one contract
+		pub fn is_known_code(code_hash: &T::Hash) -> bool {
+			<CodeRefCount<T>>::contains_key(code_hash)
+		}
+
+		/// note a wasm blob unless a contract already runs it - the pallet holds a single
+		/// reference on each blob in pallet-secrets, dropped by `release_code`
+		pub fn note_code(wasm_blob: Bytes) -> Result<T::Hash, DispatchError> {
+			let hash = T::Hashing::hash(&wasm_blob[..]);
+			if !Self::is_known_code(&hash) {
+				pallet_secrets::Pallet::<T>::maybe_note_bytes(wasm_blob)?;
+			}
+			Ok(hash)
+		}
+
+		/// empty batch of calls - used when a call only needs the deploy op
+		pub fn empty_calls() -> EncodedCall {
+			skw_blockchain_primitives::BorshSerialize::try_to_vec(
+				&skw_blockchain_primitives::types::Calls::default()
+			).unwrap()
+		}
+
+		pub fn do_register_contract(
+			deployer: T::AccountId,
+			shard_id: ShardId,
+			contract_name: Vec<u8>,
+			code_hash: T::Hash,
+			deployment_call: EncodedCall,
+		) -> DispatchResult {
+			let bounded_contract_name = BoundedVec::<u8, T::MaxContractNameLength>::try_from(contract_name.clone())
+				.map_err(|_| Error::<T>::InvalidContractName)?;
+
+			ensure!(
+				Self::validate_name(shard_id, &bounded_contract_name),
+				Error::<T>::InvalidContractName
+			);
+
+			let call_index = Self::maybe_push_calls(
+				deployer.clone(), 
+				shard_id, 
+				&deployment_call, 
+				Some(contract_name.clone()),
+				None,
+				false,
+				Zero::zero(),
+			)?;
+
+			// No error below this line 
+			let mut versions = BoundedVec::<(T::Hash, CallIndex), T::MaxContractVersions>::default();
+			// MaxContractVersions is at least 1 - an empty history can always take one version
+			let _ = versions.try_push((code_hash, call_index));
+
+			<WasmBlob<T>>::insert(&shard_id, &bounded_contract_name, code_hash);
+			<ContractOwner<T>>::insert(&shard_id, &bounded_contract_name, deployer);
+			<ContractVersions<T>>::insert(&shard_id, &bounded_contract_name, versions);
+			<CodeRefCount<T>>::mutate(&code_hash, |c| *c = Some(c.unwrap_or(0).saturating_add(1)));
+
+			Self::deposit_event(Event::<T>::SecretContractRegistered(shard_id, contract_name, call_index));
+			Ok(())
+		}
+
+		pub fn do_upgrade_contract(
+			who: T::AccountId,
+			shard_id: ShardId,
+			contract_name: Vec<u8>,
+			code_hash: T::Hash,
+		) -> DispatchResult {
+			let bounded_contract_name = BoundedVec::<u8, T::MaxContractNameLength>::try_from(contract_name.clone())
+				.map_err(|_| Error::<T>::InvalidContractName)?;
+
+			let old_hash = Self::wasm_blob_of(shard_id, &bounded_contract_name)
+				.ok_or(Error::<T>::ContractNotFound)?;
+			ensure!(
+				Self::contract_owner_of(shard_id, &bounded_contract_name) == Some(who.clone()),
+				Error::<T>::Unauthorized
+			);
+			ensure!(old_hash != code_hash, Error::<T>::ContractCodeUnchanged);
+
+			// the deploy op is pushed by the pallet account - the owner has been checked above
+			let call_index = Self::maybe_push_calls(
+				Self::get_pallet_account_id(),
+				shard_id,
+				&Self::empty_calls(),
+				Some(contract_name.clone()),
+				None,
+				false,
+				Zero::zero(),
+			)?;
+
+			// No error below this line 
+			let mut versions = Self::contract_versions_of(shard_id, &bounded_contract_name).unwrap_or_default();
+			if versions.len() >= T::MaxContractVersions::get() as usize {
+				// keep the most recent versions only
+				versions.remove(0);
+			}
+			let _ = versions.try_push((code_hash, call_index));
+
+			<WasmBlob<T>>::insert(&shard_id, &bounded_contract_name, code_hash);
+			<ContractVersions<T>>::insert(&shard_id, &bounded_contract_name, versions);
+			<CodeRefCount<T>>::mutate(&code_hash, |c| *c = Some(c.unwrap_or(0).saturating_add(1)));
+			Self::release_code(&old_hash);
+
+			Self::deposit_event(Event::<T>::SecretContractUpgraded(shard_id, contract_name, code_hash, call_index));
+			Ok(())
+		}
+
+		/// drop a reference to a wasm blob and remove the blob once no contract runs it
+		pub fn release_code(code_hash: &T::Hash) {
+			if let Some(count) = Self::code_ref_count_of(code_hash) {
+				if count <= 1 {
+					<CodeRefCount<T>>::remove(code_hash);
+					pallet_secrets::Pallet::<T>::maybe_remove_bytes(code_hash);
+				} else {
+					<CodeRefCount<T>>::insert(code_hash, count - 1);
+				}
+			}
+		}
+
+		/// call history of a shard for blocks in [from, to]; blocks without calls are skipped
+		pub fn calls_in_range(
+			shard_id: ShardId,
+			from: T::BlockNumber,
+			to: T::BlockNumber,
+		) -> Vec<(T::BlockNumber, Vec<CallIndex>)> {
+			let mut calls = Vec::new();
+			let mut block_number = from;
+			while block_number <= to {
+				if let Some(history) = Self::call_history_of(shard_id, block_number) {
+					calls.push((block_number, history.into_inner()));
+				}
+				if block_number == to {
+					break;
+				}
+				block_number += One::one();
+			}
+
+			calls
+		}
+
 		pub fn is_shard_running(shard_id: ShardId) -> bool {
 			// or we can use any of the shard initialization param
 			Self::shard_secret_id(shard_id).is_some()
 		}
 
+		pub fn is_valid_status_transition(from: ShardState, to: ShardState) -> bool {
+			match (from, to) {
+				// Proposed -> Active only happens through initialize_shard
+				(ShardState::Proposed, ShardState::Retired) => true,
+				(ShardState::Active, ShardState::Paused) => true,
+				(ShardState::Paused, ShardState::Active) => true,
+				(ShardState::Active, ShardState::Retiring) => true,
+				(ShardState::Paused, ShardState::Retiring) => true,
+				(ShardState::Retiring, ShardState::Retired) => true,
+				_ => false,
+			}
+		}
+
 		pub fn add_contract_creation_call (
 			calls: &EncodedCall,
 			contract_name: Bytes,
@@ -357,7 +1276,9 @@ pub mod pallet {
 				Err(_) => Err(Error::<T>::InvalidEncodedCall),	
 			}
 		}
-	
+
+		/// check the origin of each op and mark the calls with the shard and the block they are pushed in.
+		/// Calls deferred to a later block keep that mark - CallHistory records the block they are scheduled in
 		pub fn validate_and_mark_usual_call(
 			origin: &T::AccountId,
 			calls: &EncodedCall, 
@@ -403,17 +1324,27 @@ pub mod pallet {
 			}
 		}
 		
+		/// every call but the system calls has to be encrypted to the key the shard accepts at the
+		/// current call index, `public_key` is the key the caller says the call is encrypted to
 		pub fn maybe_push_calls(
 			who: T::AccountId,
 			shard_id: ShardId, 
 			call: &EncodedCall,
 			contract_name: Option<Bytes>,
+			public_key: Option<&PublicKey>,
 			force_push: bool,
+			tip: BalanceOf<T>,
 		) -> Result<CallIndex, Error::<T> > {
-			ensure!(
-				force_push || Self::is_shard_running(shard_id), 
-				Error::<T>::ShardNotInitialized
-			);
+			match Self::shard_status(shard_id) {
+				Some(ShardState::Active) => {},
+				// system calls can be pushed before the shard is initialized
+				None | Some(ShardState::Proposed) => ensure!(force_push, Error::<T>::ShardNotInitialized),
+				_ => return Err(Error::<T>::ShardNotActive),
+			}
+
+			if !force_push {
+				Self::ensure_call_key(shard_id, public_key)?;
+			}
 
 			let marked_call = Self::validate_and_mark_usual_call(
 				&who,
@@ -441,22 +1372,291 @@ pub mod pallet {
 				BoundedVec::<u8, T::MaxCallLength>::try_from(marked_call)
 				.map_err(|_| Error::<T>::InvalidEncodedCall)?;	
 
+			// calls with the same tip keep their submission order
 			let now = frame_system::Pallet::<T>::block_number();
+			let mut queue = Self::call_queue_of(&now, &shard_id).unwrap_or_default().into_inner();
+			let position = queue.iter().position(|(t, _)| *t < tip).unwrap_or(queue.len());
+			queue.insert(position, (tip, call_index));
+			let queue = BoundedVec::<(BalanceOf<T>, CallIndex), T::MaxQueuedCallsPerShard>::try_from(queue)
+				.map_err(|_| Error::<T>::CallQueueFull)?;
+
 			<CallRecord<T>>::insert(&call_index, (bounded_encoded_call, who));
-			
-			let history = Self::call_history_of(&shard_id, now);
-			let mut content = history.unwrap_or_default();
-			content.try_push(call_index).map_err(|_| Error::<T>::TooManyCallsInCurrentBlock.into())?;
-			<CallHistory<T>>::mutate(&shard_id, &now,
-				|c| *c = Some(content.clone()));
+			<CallQueue<T>>::insert(&now, &shard_id, queue);
 			<CurrentCallIndex<T>>::set( call_index.saturating_add(1) );
 
 			Ok(call_index)
 		}
 
+		/// schedule up to MaxCallPerBlock calls queued for `now` of each shard into the CallHistory of `now`.
+		/// The rest is deferred to the next block. Returns the number of calls scheduled
+		pub fn schedule_queued_calls(now: T::BlockNumber) -> u32 {
+			let max_calls = T::MaxCallPerBlock::get() as usize;
+			let next = now.saturating_add(One::one());
+			let mut scheduled = 0u32;
+			let mut deferred = 0u32;
+
+			// only shards with calls queued for this block are visited
+			for (shard_id, queue) in <CallQueue<T>>::drain_prefix(&now) {
+				let mut queue = queue.into_inner();
+				let mut history = Self::call_history_of(&shard_id, now).unwrap_or_default();
+				let count = max_calls.saturating_sub(history.len()).min(queue.len());
+				let mut tips = Self::block_tips_of(&shard_id, now).unwrap_or_default();
+
+				for (tip, call_index) in queue.drain(..count) {
+					// room has been checked above - BlockTips is bounded like CallHistory
+					let _ = history.try_push(call_index);
+					if !tip.is_zero() {
+						let _ = tips.try_push((call_index, tip));
+					}
+				}
+
+				if count > 0 {
+					<CallHistory<T>>::insert(&shard_id, &now, history);
+					if !tips.is_empty() {
+						<BlockTips<T>>::insert(&shard_id, &now, tips);
+					}
+					Self::deposit_event(Event::<T>::CallsScheduled(shard_id, now, count as u32));
+				}
+
+				if !queue.is_empty() {
+					deferred = deferred.saturating_add(queue.len() as u32);
+					// the queue only got shorter, and nothing is queued for the next block yet
+					let queue = BoundedVec::<(BalanceOf<T>, CallIndex), T::MaxQueuedCallsPerShard>::try_from(queue)
+						.unwrap_or_default();
+					<CallQueue<T>>::insert(&next, &shard_id, queue);
+				}
+
+				scheduled = scheduled.saturating_add(count as u32);
+			}
+
+			<QueuedCallCount<T>>::put(deferred);
+			scheduled
+		}
+
+		/// split the tips of a block evenly among the secret keepers that confirmed it.
+		/// The remainder of the division goes to the first keeper. Either every keeper
+		/// is paid or the tips are left in BlockTips, where they can be refunded
+		#[transactional]
+		pub fn pay_tips(
+			shard_id: ShardId,
+			block_number: T::BlockNumber,
+			keepers: &[T::AccountId],
+		) -> DispatchResult {
+			if keepers.is_empty() {
+				return Ok(());
+			}
+
+			let total = match <BlockTips<T>>::take(&shard_id, &block_number) {
+				Some(tips) => Self::total_tips(&tips),
+				None => return Ok(()),
+			};
+
+			let pallet_account = Self::get_pallet_account_id();
+			let count: BalanceOf<T> = (keepers.len() as u32).into();
+			let share = total / count;
+			let remainder = total.saturating_sub(share.saturating_mul(count));
+
+			for (i, keeper) in keepers.iter().enumerate() {
+				let amount = if i == 0 { share.saturating_add(remainder) } else { share };
+				if !amount.is_zero() {
+					T::Currency::transfer(&pallet_account, keeper, amount, AllowDeath)?;
+				}
+			}
+
+			Self::deposit_event(Event::<T>::TipsPaid(shard_id, block_number, total));
+			Ok(())
+		}
+
+		pub fn total_tips(tips: &[(CallIndex, BalanceOf<T>)]) -> BalanceOf<T> {
+			tips.iter().fold(Zero::zero(), |total: BalanceOf<T>, (_, tip)| total.saturating_add(*tip))
+		}
+
 		pub fn get_pallet_account_id() -> T::AccountId {
 			T::SContractRoot::get().into_account()
 		}
+
+		/// leaf of a call record in the merkle tree committed by ArchivedCallRoot
+		pub fn call_record_leaf(
+			call_index: CallIndex,
+			record: &(BoundedVec<u8, T::MaxCallLength>, T::AccountId),
+		) -> T::Hash {
+			T::Hashing::hash_of(&(call_index, record))
+		}
+
+		/// verify a pruned call record against the ArchivedCallRoot of its block.
+		/// `index` is the position of the call in the CallHistory of the block
+		pub fn verify_archived_call(
+			shard_id: ShardId,
+			block_number: T::BlockNumber,
+			call_index: CallIndex,
+			record: &(BoundedVec<u8, T::MaxCallLength>, T::AccountId),
+			index: u32,
+			call_count: u32,
+			proof: &[T::Hash],
+		) -> bool {
+			match Self::archived_call_root_of(shard_id, block_number) {
+				Some(root) => crate::merkle::verify_merkle_proof::<T::Hashing>(
+					&root,
+					Self::call_record_leaf(call_index, record),
+					index as usize,
+					call_count as usize,
+					proof,
+				),
+				None => false,
+			}
+		}
+
+		/// prune the calls of confirmed blocks whose prune depth has passed, removing at most
+		/// `max_calls` call records. Returns (number of blocks pruned, number of calls pruned)
+		pub fn prune_call_records(
+			now: T::BlockNumber,
+			max_calls: u32,
+		) -> (u32, u32) {
+			let (mut head, tail) = Self::prune_queue_range();
+			let mut blocks = 0u32;
+			let mut calls = 0u32;
+
+			while head != tail && calls < max_calls {
+				let (shard_id, block_number, prunable_at) = match Self::prune_queue_at(head) {
+					Some(entry) => entry,
+					None => {
+						head = head.wrapping_add(1);
+						continue;
+					}
+				};
+
+				// the queue is ordered by confirmation time
+				if prunable_at > now {
+					break;
+				}
+
+				// blocks are pruned as a whole, a block that does not fit waits for the next call
+				let history = Self::call_history_of(&shard_id, &block_number).unwrap_or_default();
+				if calls.saturating_add(history.len() as u32) > max_calls {
+					break;
+				}
+
+				<PruneQueue<T>>::remove(head);
+				head = head.wrapping_add(1);
+				blocks += 1;
+
+				if history.is_empty() {
+					continue;
+				}
+				<CallHistory<T>>::remove(&shard_id, &block_number);
+
+				let leaves: Vec<T::Hash> = history.iter()
+					.filter_map(|call_index| {
+						<ReceiptSource<T>>::remove(call_index);
+						<CallRecord<T>>::take(call_index)
+							.map(|record| Self::call_record_leaf(*call_index, &record))
+					})
+					.collect();
+
+				calls = calls.saturating_add(history.len() as u32);
+
+				if !leaves.is_empty() {
+					let root = crate::merkle::merkle_root::<T::Hashing>(&leaves[..]);
+					<ArchivedCallRoot<T>>::insert(&shard_id, &block_number, root);
+					Self::deposit_event(Event::<T>::CallRecordsArchived(shard_id, block_number, root));
+				}
+			}
+
+			<PruneQueueRange<T>>::put((head, tail));
+			(blocks, calls)
+		}
 	}
-}
 
+	impl<T: Config> OnBlockConfirmed<T::AccountId, T::BlockNumber> for Pallet<T> {
+		/// pay the tips of a confirmed block and queue its calls for pruning
+		fn on_block_confirmed(shard_id: ShardId, block_number: T::BlockNumber, keepers: &[T::AccountId]) {
+			// tips that cannot be paid stay refundable
+			let _ = Self::pay_tips(shard_id, block_number, keepers);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let (head, tail) = Self::prune_queue_range();
+
+			<PruneQueue<T>>::insert(tail, (shard_id, block_number, now + T::CallRecordPruneDepth::get()));
+			<PruneQueueRange<T>>::put((head, tail.wrapping_add(1)));
+		}
+	}
+
+	impl<T: Config> ReceiptRouter<T::BlockNumber> for Pallet<T> {
+		/// receipts are pushed on behalf of the account that pushed the source call, and go through
+		/// the same origin checks as the calls that account pushes itself. The source call has to be
+		/// scheduled on the source shard, otherwise a shard could push calls as any account
+		fn route_receipt(
+			source_shard_id: ShardId,
+			source_block_number: T::BlockNumber,
+			source_call_index: CallIndex,
+			target_shard_id: ShardId,
+			calls: Vec<u8>,
+		) -> bool {
+			if Self::shard_status(target_shard_id) != Some(ShardState::Active) {
+				return false;
+			}
+
+			if !<Self as CallHistoryProvider<T::BlockNumber>>::is_call_of(source_shard_id, source_block_number, source_call_index) {
+				return false;
+			}
+
+			let origin = match Self::call_record_of(source_call_index) {
+				Some((_, origin)) => origin,
+				None => return false,
+			};
+
+			match Self::maybe_push_calls(origin, target_shard_id, &calls, None, None, false, Zero::zero()) {
+				Ok(call_index) => {
+					<ReceiptSource<T>>::insert(&call_index, (source_shard_id, source_call_index));
+					Self::deposit_event(Event::<T>::CallReceived(target_shard_id, call_index));
+					Self::deposit_event(Event::<T>::ReceiptReceived(target_shard_id, call_index, source_shard_id, source_call_index));
+					true
+				},
+				Err(_) => false,
+			}
+		}
+	}
+
+	impl<T: Config> ShardAdmins<T::AccountId> for Pallet<T> {
+		fn is_shard_admin(shard_id: ShardId, who: &T::AccountId) -> bool {
+			Self::shard_admin_of(shard_id).as_ref() == Some(who)
+		}
+	}
+
+	impl<T: Config> ShardStatusProvider for Pallet<T> {
+		fn is_accepting_calls(shard_id: ShardId) -> bool {
+			Self::shard_status(shard_id) == Some(ShardState::Active)
+		}
+
+		fn is_accepting_outcomes(shard_id: ShardId) -> bool {
+			matches!(
+				Self::shard_status(shard_id),
+				Some(ShardState::Active) | Some(ShardState::Paused) | Some(ShardState::Retiring)
+			)
+		}
+	}
+
+	impl<T: Config> CallHistoryProvider<T::BlockNumber> for Pallet<T> {
+		fn calls_of(shard_id: ShardId, block_number: T::BlockNumber) -> Vec<(CallIndex, Vec<u8>)> {
+			Self::call_history_of(shard_id, block_number)
+				.unwrap_or_default()
+				.into_iter()
+				.filter_map(|call_index| Self::call_record_of(call_index)
+					.map(|(calls, _)| (call_index, calls.into_inner()))
+				)
+				.collect()
+		}
+
+		fn is_call_of(shard_id: ShardId, block_number: T::BlockNumber, call_index: CallIndex) -> bool {
+			Self::call_history_of(shard_id, block_number)
+				.map_or(false, |history| history.contains(&call_index))
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn set_calls_of(shard_id: ShardId, block_number: T::BlockNumber, call_indexes: Vec<CallIndex>) {
+			let history = BoundedVec::<CallIndex, T::MaxCallPerBlock>::try_from(call_indexes)
+				.expect("benchmarks stay within MaxCallPerBlock");
+			<CallHistory<T>>::insert(&shard_id, &block_number, history);
+		}
+	}
+}
@@ -0,0 +1,108 @@
+use frame_support::sp_runtime::traits::Hash;
+use sp_std::vec::Vec;
+
+/// prefixes of the hashed nodes, so that a leaf can never be taken for an internal node
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// hash of a leaf of the tree
+fn hash_leaf<H: Hash>(leaf: &H::Output) -> H::Output {
+	let mut buf = Vec::with_capacity(1 + leaf.as_ref().len());
+	buf.push(LEAF_PREFIX);
+	buf.extend_from_slice(leaf.as_ref());
+	H::hash(&buf[..])
+}
+
+/// hash of an internal node with one or two children
+fn hash_node<H: Hash>(left: &H::Output, right: Option<&H::Output>) -> H::Output {
+	let mut buf = Vec::with_capacity(1 + 2 * left.as_ref().len());
+	buf.push(NODE_PREFIX);
+	buf.extend_from_slice(left.as_ref());
+	if let Some(right) = right {
+		buf.extend_from_slice(right.as_ref());
+	}
+	H::hash(&buf[..])
+}
+
+/// hash every pair of nodes of a level - an odd node at the end is hashed on its own
+fn next_level<H: Hash>(level: &[H::Output]) -> Vec<H::Output> {
+	level.chunks(2)
+		.map(|pair| match pair {
+			[left, right] => hash_node::<H>(left, Some(right)),
+			[single] => hash_node::<H>(single, None),
+			_ => unreachable!("chunks(2) yields one or two items"),
+		})
+		.collect()
+}
+
+/// root of the binary merkle tree built on top of `leaves`
+pub fn merkle_root<H: Hash>(leaves: &[H::Output]) -> H::Output {
+	if leaves.is_empty() {
+		return H::hash(&[]);
+	}
+
+	let mut level: Vec<H::Output> = leaves.iter().map(hash_leaf::<H>).collect();
+	while level.len() > 1 {
+		level = next_level::<H>(&level);
+	}
+	level[0]
+}
+
+/// siblings of the leaf at `index` from the bottom up
+pub fn merkle_proof<H: Hash>(leaves: &[H::Output], index: usize) -> Vec<H::Output> {
+	let mut proof = Vec::new();
+	if index >= leaves.len() {
+		return proof;
+	}
+
+	let mut level: Vec<H::Output> = leaves.iter().map(hash_leaf::<H>).collect();
+	let mut index = index;
+	while level.len() > 1 {
+		let sibling = index ^ 1;
+		if sibling < level.len() {
+			proof.push(level[sibling]);
+		}
+		level = next_level::<H>(&level);
+		index /= 2;
+	}
+	proof
+}
+
+/// verify that `leaf` is the `index`th of `leaf_count` leaves of the tree with `root`
+pub fn verify_merkle_proof<H: Hash>(
+	root: &H::Output,
+	leaf: H::Output,
+	index: usize,
+	leaf_count: usize,
+	proof: &[H::Output],
+) -> bool {
+	if index >= leaf_count {
+		return false;
+	}
+
+	let mut hash = hash_leaf::<H>(&leaf);
+	let mut index = index;
+	let mut width = leaf_count;
+	let mut proof = proof.iter();
+
+	while width > 1 {
+		if index % 2 == 1 {
+			match proof.next() {
+				Some(sibling) => hash = hash_node::<H>(sibling, Some(&hash)),
+				None => return false,
+			}
+		} else if index + 1 < width {
+			match proof.next() {
+				Some(sibling) => hash = hash_node::<H>(&hash, Some(sibling)),
+				None => return false,
+			}
+		} else {
+			hash = hash_node::<H>(&hash, None);
+		}
+
+		index /= 2;
+		width = (width + 1) / 2;
+	}
+
+	proof.next().is_none() && hash == *root
+}
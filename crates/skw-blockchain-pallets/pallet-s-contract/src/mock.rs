@@ -6,8 +6,9 @@ use frame_support::{
 	traits::{Nothing, ConstU32, ConstU64},
 	PalletId,
 };
-use frame_system::{EnsureRoot};
+use frame_system::{EnsureRoot, EnsureSigned};
 use sp_core::H256;
+use skw_blockchain_primitives::{traits::SecretKeeperSet, types::ShardId};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
@@ -24,10 +25,11 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Balances: pallet_balances::{Pallet, Call, Event<T>, Storage},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Event<T>, Storage},
 		Preimage: pallet_preimage::{Pallet, Call, Event<T>, Storage},
 		Secrets: pallet_secrets::{Pallet, Call, Storage, Event<T>},
 		SContract: pallet_s_contract::{Pallet, Call, Storage, Event<T>},
+		Naming: pallet_naming::{Pallet, Call, Storage, Event<T>},
 	}
 );
 pub type AccountId = <<sp_runtime::MultiSignature as sp_runtime::traits::Verify>::Signer as sp_runtime::traits::IdentifyAccount>::AccountId;
@@ -80,14 +82,30 @@ impl pallet_preimage::Config for Test {
 	type WeightInfo = ();
 }
 
+frame_support::parameter_types! {
+	pub static Keepers: Vec<AccountId> = vec![AccountId::from([1u8; 32]), AccountId::from([2u8; 32])];
+}
+
+impl SecretKeeperSet<AccountId> for Keepers {
+	fn secret_keepers_of(_shard_id: ShardId) -> Vec<AccountId> { Keepers::get() }
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_secret_keepers_of(_shard_id: ShardId, keepers: Vec<AccountId>) { Keepers::set(keepers) }
+}
+
 impl pallet_secrets::Config for Test {
 	type WeightInfo = ();
 	type Event = Event;
 	type Preimage = Preimage;
+	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type SecretKeepers = Keepers;
+	type MaxAuditLogLength = frame_support::traits::ConstU32<3>;
 }
 
 frame_support::parameter_types! {
 	pub const SContractPalletId: PalletId = PalletId(*b"scontrac");
+	/// tests of a full block lower it with `MaxCallPerBlock::set`
+	pub static MaxCallPerBlock: u32 = 1_000;
 }
 impl pallet_s_contract::Config for Test {
 	type WeightInfo = ();
@@ -95,11 +113,40 @@ impl pallet_s_contract::Config for Test {
 	type MaxCallLength = ConstU32<100_1000>;
 	type MinContractNameLength = ConstU32<1>;
 	type MaxContractNameLength = ConstU32<32>;
-	type MaxCallPerBlock = ConstU32<1_000>;
+	type MaxCallPerBlock = MaxCallPerBlock;
+	type MaxQueuedCallsPerShard = ConstU32<5_000>;
+	type Currency = Balances;
+	type TipRefundDelay = ConstU64<10>;
 	type SContractRoot = SContractPalletId;
+	type CallRecordPruneDepth = ConstU64<10>;
+	type MaxPrunedCallsPerBlock = ConstU32<1_000>;
+	type MaxContractVersions = ConstU32<3>;
+	type MaxPreviousShardKeys = ConstU32<2>;
+	type Naming = Naming;
+	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type ShardAdminOrigin = EnsureSigned<Self::AccountId>;
+}
+
+impl pallet_naming::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type WeightInfo = ();
+	type ReservationFee = ConstU64<2>;
+	type BlockPerPeriod = ConstU64<5>;
+	type MaxPeriod = ConstU32<20>;
+	type MaxContractNameLength = ConstU32<32>;
+	type ForceOrigin = EnsureRoot<Self::AccountId>;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let account1: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let account2: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(account1, 100), (account2, 20)],
+	}.assimilate_storage(&mut t).unwrap();
+
+	t.into()
 }
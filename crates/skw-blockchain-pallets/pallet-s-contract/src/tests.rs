@@ -1,7 +1,10 @@
 use pallet_secrets::Event as SecretsEvent;
-use crate::{Event as SContractEvent };
-use frame_support::{assert_ok};
+use crate::{Event as SContractEvent, Error as SContractError, ShardState};
+use frame_support::{assert_ok, assert_noop, traits::{GenesisBuild, Hooks}};
+use skw_blockchain_primitives::traits::{OnBlockConfirmed, ReceiptRouter};
 use crate::mock::{Event, *};
+use sp_core::H256;
+use sp_runtime::traits::BadOrigin;
 
 const WASM_BLOB: &str = "123123123123123123123123";
 
@@ -26,6 +29,17 @@ fn it_register_secret_contracts() {
 		};
 
 		let encoded_calls = skw_blockchain_primitives::BorshSerialize::try_to_vec(&calls).unwrap();
+
+		// only proposed shards are initialized
+		assert_noop!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			),
+			SContractError::<Test>::InvalidShardIndex
+		);
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
 		assert_ok!(
 			SContract::initialize_shard(
 				Origin::signed(account.clone()), 0,
@@ -56,6 +70,7 @@ fn it_register_secret_contracts() {
 			0,
 		)));
 
+		SContract::on_finalize(1);
 		let history = SContract::call_history_of(0, 1).unwrap();
 		assert_eq! (history.len(), 1);
 
@@ -64,3 +79,922 @@ fn it_register_secret_contracts() {
 		assert_eq! (call_record.1, account.clone());
 	});
 }
+
+/// one share sealed to each of the two secret keepers of the mock
+fn sealed_shares() -> Vec<u8> {
+	(0..2u8).flat_map(|i| [&64u32.to_be_bytes()[..], &[i; 64][..]].concat()).collect()
+}
+
+fn empty_calls() -> Vec<u8> {
+	let calls = skw_blockchain_primitives::types::Calls {
+		ops: Vec::new(),
+		block_number: Some(1),
+		shard_id: 0
+	};
+	skw_blockchain_primitives::BorshSerialize::try_to_vec(&calls).unwrap()
+}
+
+#[test]
+fn it_follows_shard_lifecycle() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_eq! (SContract::shard_status(0), Some(ShardState::Proposed));
+		assert_noop!(
+			SContract::propose_shard(Origin::root(), 0),
+			SContractError::<Test>::ShardAlreadyProposed
+		);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, account.clone()) );
+
+		// no calls before the shard is initialized
+		assert_noop!(
+			SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()),
+			SContractError::<Test>::ShardNotInitialized
+		);
+
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+		assert_eq! (SContract::shard_status(0), Some(ShardState::Active));
+		assert_ok!( SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()) );
+
+		// Proposed -> Active only happens through initialize_shard
+		assert_noop!(
+			SContract::set_shard_status(Origin::root(), 0, ShardState::Proposed),
+			SContractError::<Test>::InvalidShardStatusTransition
+		);
+
+		assert_ok!( SContract::set_shard_status(Origin::root(), 0, ShardState::Paused) );
+		assert_noop!(
+			SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()),
+			SContractError::<Test>::ShardNotActive
+		);
+		assert_noop!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			),
+			SContractError::<Test>::ShardHasBeenInitialized
+		);
+
+		assert_ok!( SContract::set_shard_status(Origin::root(), 0, ShardState::Active) );
+		assert_ok!( SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()) );
+
+		assert_ok!( SContract::set_shard_status(Origin::root(), 0, ShardState::Retiring) );
+		assert_ok!( SContract::set_shard_status(Origin::root(), 0, ShardState::Retired) );
+		assert_noop!(
+			SContract::set_shard_status(Origin::root(), 0, ShardState::Active),
+			SContractError::<Test>::InvalidShardStatusTransition
+		);
+		assert_noop!(
+			SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()),
+			SContractError::<Test>::ShardNotActive
+		);
+	});
+}
+
+#[test]
+fn it_migrates_shard() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, account.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+		assert_ok!(
+			SContract::register_contract(
+				Origin::signed(account.clone()),
+				"contract_name".as_bytes().to_vec(),
+				WASM_BLOB.as_bytes().to_vec(),
+				empty_calls(),
+				0,
+			)
+		);
+		let code_hash = SContract::wasm_blob_of(0, "contract_name".as_bytes().to_vec()).unwrap();
+
+		// only retiring shards can be migrated
+		assert_noop!(
+			SContract::migrate_shard(Origin::root(), 0, 1, 1, 1),
+			SContractError::<Test>::InvalidShardStatusTransition
+		);
+
+		assert_ok!( SContract::set_shard_status(Origin::root(), 0, ShardState::Retiring) );
+
+		// the new shard has to be proposed first
+		assert_noop!(
+			SContract::migrate_shard(Origin::root(), 0, 1, 1, 1),
+			SContractError::<Test>::InvalidShardIndex
+		);
+		assert_ok!( SContract::propose_shard(Origin::root(), 1) );
+
+		// the operator count bounds the operators moved
+		assert_noop!(
+			SContract::migrate_shard(Origin::root(), 0, 1, 0, 1),
+			SContractError::<Test>::InvalidOperatorCount
+		);
+		// and the contract count bounds the contracts moved
+		assert_noop!(
+			SContract::migrate_shard(Origin::root(), 0, 1, 1, 0),
+			SContractError::<Test>::InvalidContractCount
+		);
+		assert_ok!( SContract::migrate_shard(Origin::root(), 0, 1, 1, 1) );
+
+		assert_eq! (SContract::shard_status(0), Some(ShardState::Retired));
+		assert_eq! (SContract::shard_status(1), Some(ShardState::Active));
+		assert_eq! (SContract::shard_successor(0), Some(1));
+
+		assert_eq! (SContract::shard_secret_id(0), None);
+		assert_eq! (SContract::shard_secret_id(1), Some(0));
+		assert_eq! (Secrets::shard_of(0), Some(1));
+		assert_eq! (SContract::shard_public_key(1), Some(SContract::get_pallet_account_id().into()));
+		assert_eq! (SContract::shard_operator(0, &account), None);
+		assert_eq! (SContract::shard_operator(1, &account), Some(true));
+
+		assert_eq! (SContract::wasm_blob_of(0, "contract_name".as_bytes().to_vec()), None);
+		assert_eq! (SContract::wasm_blob_of(1, "contract_name".as_bytes().to_vec()), Some(code_hash));
+		assert_eq! (SContract::contract_owner_of(0, "contract_name".as_bytes().to_vec()), None);
+		assert_eq! (SContract::contract_owner_of(1, "contract_name".as_bytes().to_vec()), Some(account.clone()));
+		assert_eq! (SContract::contract_versions_of(1, "contract_name".as_bytes().to_vec()).map(|v| v.len()), Some(1));
+
+		assert! (System::events().iter().any(|evt| {
+			evt.event == Event::SContract(SContractEvent::ShardMigrated(0, 1))
+		}));
+
+		assert_ok!( SContract::push_call(Origin::signed(account.clone()), 1, empty_calls()) );
+	});
+}
+
+#[test]
+fn it_prunes_and_archives_confirmed_call_records() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, account.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+
+		for _ in 0..3 {
+			assert_ok!( SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()) );
+		}
+		SContract::on_finalize(1);
+
+		let history = SContract::call_history_of(0, 1).unwrap();
+		let records: Vec<_> = history.iter().map(|i| SContract::call_record_of(i).unwrap()).collect();
+		let leaves: Vec<_> = history.iter().zip(records.iter())
+			.map(|(i, r)| SContract::call_record_leaf(*i, r))
+			.collect();
+
+		System::set_block_number(2);
+		<SContract as OnBlockConfirmed<AccountId, u64>>::on_block_confirmed(0, 1, &[]);
+
+		// CallRecordPruneDepth = 10 in the mock runtime
+		SContract::on_initialize(11);
+		assert_eq! (SContract::call_history_of(0, 1).unwrap().len(), 3);
+
+		SContract::on_initialize(12);
+		assert_eq! (SContract::call_history_of(0, 1), None);
+		for i in history.iter() {
+			assert_eq! (SContract::call_record_of(i), None);
+		}
+
+		let root = SContract::archived_call_root_of(0, 1).unwrap();
+		assert_eq! (root, crate::merkle::merkle_root::<sp_runtime::traits::BlakeTwo256>(&leaves[..]));
+		assert! (System::events().iter().any(|evt| {
+			evt.event == Event::SContract(SContractEvent::CallRecordsArchived(0, 1, root))
+		}));
+
+		for (index, call_index) in history.iter().enumerate() {
+			let proof = crate::merkle::merkle_proof::<sp_runtime::traits::BlakeTwo256>(&leaves[..], index);
+			assert! (SContract::verify_archived_call(0, 1, *call_index, &records[index], index as u32, 3, &proof[..]));
+		}
+
+		// a record at the wrong position does not verify
+		let proof = crate::merkle::merkle_proof::<sp_runtime::traits::BlakeTwo256>(&leaves[..], 0);
+		assert! (!SContract::verify_archived_call(0, 1, history[1], &records[1], 0, 3, &proof[..]));
+		assert_eq! (SContract::prune_queue_range(), (1, 1));
+	});
+}
+
+#[test]
+fn it_proves_every_leaf_of_uneven_merkle_trees() {
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	for count in 1..=9u32 {
+		let leaves: Vec<_> = (0..count).map(|i| BlakeTwo256::hash_of(&i)).collect();
+		let root = crate::merkle::merkle_root::<BlakeTwo256>(&leaves[..]);
+
+		for index in 0..leaves.len() {
+			let proof = crate::merkle::merkle_proof::<BlakeTwo256>(&leaves[..], index);
+			assert! (crate::merkle::verify_merkle_proof::<BlakeTwo256>(&root, leaves[index], index, leaves.len(), &proof[..]));
+			// the leaf count is committed by the tree shape
+			assert! (!crate::merkle::verify_merkle_proof::<BlakeTwo256>(&root, leaves[index], index, leaves.len() + 1, &proof[..]));
+		}
+	}
+}
+
+#[test]
+fn it_does_not_take_internal_nodes_for_leaves() {
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	let leaves: Vec<_> = (0..4u32).map(|i| BlakeTwo256::hash_of(&i)).collect();
+	let root = crate::merkle::merkle_root::<BlakeTwo256>(&leaves[..]);
+
+	// the root of the left half is an internal node of the tree, not a leaf of a 2 leaf tree
+	let left = crate::merkle::merkle_root::<BlakeTwo256>(&leaves[..2]);
+	let right = crate::merkle::merkle_root::<BlakeTwo256>(&leaves[2..]);
+	assert! (!crate::merkle::verify_merkle_proof::<BlakeTwo256>(&root, left, 0, 2, &[right][..]));
+}
+
+#[test]
+fn it_prunes_whole_blocks_within_the_budget() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, account.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+
+		for block_number in 1..=2u64 {
+			System::set_block_number(block_number);
+			for _ in 0..3 {
+				assert_ok!( SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()) );
+			}
+			SContract::on_finalize(block_number);
+			<SContract as OnBlockConfirmed<AccountId, u64>>::on_block_confirmed(0, block_number, &[]);
+		}
+
+		// the second block does not fit in the budget
+		assert_eq! (SContract::prune_call_records(100, 5), (1, 3));
+		assert_eq! (SContract::call_history_of(0, 1), None);
+		assert_eq! (SContract::call_history_of(0, 2).unwrap().len(), 3);
+
+		// a block larger than the budget is not pruned partially
+		assert_eq! (SContract::prune_call_records(100, 2), (0, 0));
+		assert_eq! (SContract::prune_call_records(100, 3), (1, 3));
+		assert_eq! (SContract::call_history_of(0, 2), None);
+	});
+}
+
+#[test]
+fn it_upgrades_contracts_and_shares_code() {
+	let owner: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let stranger: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let contract_a = "contract_a".as_bytes().to_vec();
+	let contract_b = "contract_b".as_bytes().to_vec();
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, owner.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(owner.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+
+		assert_ok!(
+			SContract::register_contract(
+				Origin::signed(owner.clone()), contract_a.clone(),
+				"first version of the code".as_bytes().to_vec(), empty_calls(), 0,
+			)
+		);
+		let v1 = SContract::wasm_blob_of(0, contract_a.clone()).unwrap();
+
+		// a second contract runs the same blob without uploading it again
+		assert_ok!(
+			SContract::register_contract_with_code_hash(
+				Origin::signed(stranger.clone()), contract_b.clone(), v1, empty_calls(), 0,
+			)
+		);
+		assert_eq! (SContract::wasm_blob_of(0, contract_b.clone()), Some(v1));
+		assert_eq! (SContract::code_ref_count_of(v1), Some(2));
+		assert_noop!(
+			SContract::register_contract_with_code_hash(
+				Origin::signed(stranger.clone()), "contract_c".as_bytes().to_vec(), H256::repeat_byte(1), empty_calls(), 0,
+			),
+			SContractError::<Test>::InvalidWasmBlob
+		);
+
+		// only the owner can upgrade
+		assert_noop!(
+			SContract::upgrade_contract(
+				Origin::signed(stranger.clone()), contract_a.clone(),
+				"second version of the code".as_bytes().to_vec(), 0,
+			),
+			SContractError::<Test>::Unauthorized
+		);
+		assert_noop!(
+			SContract::upgrade_contract_with_code_hash(Origin::signed(owner.clone()), contract_a.clone(), v1, 0),
+			SContractError::<Test>::ContractCodeUnchanged
+		);
+
+		assert_ok!(
+			SContract::upgrade_contract(
+				Origin::signed(owner.clone()), contract_a.clone(),
+				"second version of the code".as_bytes().to_vec(), 0,
+			)
+		);
+		let v2 = SContract::wasm_blob_of(0, contract_a.clone()).unwrap();
+		let upgrade_call_index = SContract::current_call_index_of() - 1;
+
+		assert_eq! (
+			SContract::contract_versions_of(0, contract_a.clone()).unwrap().into_inner(),
+			vec![(v1, 0), (v2, upgrade_call_index)]
+		);
+		assert! (System::events().iter().any(|evt| {
+			evt.event == Event::SContract(SContractEvent::SecretContractUpgraded(0, contract_a.clone(), v2, upgrade_call_index))
+		}));
+
+		// the upgrade pushes a deploy op for the contract
+		let (call, origin) = SContract::call_record_of(upgrade_call_index).unwrap();
+		let calls: skw_blockchain_primitives::types::Calls =
+			skw_blockchain_primitives::BorshDeserialize::try_from_slice(&call[..]).unwrap();
+		assert_eq! (origin, SContract::get_pallet_account_id());
+		assert_eq! (calls.ops.len(), 1);
+		assert_eq! (calls.ops[0].transaction_action, 4);
+		assert_eq! (calls.ops[0].contract_name, Some(contract_a.clone()));
+
+		// v1 is still used by contract_b
+		assert_eq! (SContract::code_ref_count_of(v1), Some(1));
+		assert! (SContract::is_known_code(&v1));
+
+		assert_ok!( SContract::upgrade_contract_with_code_hash(Origin::signed(stranger.clone()), contract_b.clone(), v2, 0) );
+		assert_eq! (SContract::code_ref_count_of(v1), None);
+		assert_eq! (SContract::code_ref_count_of(v2), Some(2));
+		assert! (!SContract::is_known_code(&v1));
+	});
+}
+
+#[test]
+fn it_keeps_contract_code_apart_from_secrets() {
+	let owner: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let stranger: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let contract_a = "contract_a".as_bytes().to_vec();
+	let shared_bytes = "bytes of a secret and of a contract".as_bytes().to_vec();
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, owner.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(owner.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+
+		// the metadata of a secret is not code a contract can be registered with
+		let secret_id = Secrets::current_secret_id();
+		assert_ok!( Secrets::register_secret(Origin::signed(stranger.clone()), shared_bytes.clone()) );
+		let hash = Secrets::metadata_of(secret_id).unwrap();
+		assert! (!SContract::is_known_code(&hash));
+		assert_noop!(
+			SContract::register_contract_with_code_hash(
+				Origin::signed(owner.clone()), contract_a.clone(), hash, empty_calls(), 0,
+			),
+			SContractError::<Test>::InvalidWasmBlob
+		);
+
+		// uploading the same bytes as code and upgrading away from them keeps the metadata
+		assert_ok!(
+			SContract::register_contract(
+				Origin::signed(owner.clone()), contract_a.clone(), shared_bytes.clone(), empty_calls(), 0,
+			)
+		);
+		assert_eq! (SContract::wasm_blob_of(0, contract_a.clone()), Some(hash));
+		assert_ok!(
+			SContract::upgrade_contract(
+				Origin::signed(owner.clone()), contract_a.clone(),
+				"second version of the code".as_bytes().to_vec(), 0,
+			)
+		);
+		assert_eq! (SContract::code_ref_count_of(hash), None);
+		assert_eq! (Secrets::try_get_bytes(&hash).unwrap(), shared_bytes);
+	});
+}
+
+#[test]
+fn it_schedules_calls_by_tip_and_pays_keepers() {
+	let alice: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let bob: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let keepers: Vec<AccountId> = (10u8..13).map(|i| AccountId::from([i; 32])).collect();
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MaxCallPerBlock::set(3);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, alice.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(alice.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+
+		assert_ok!( SContract::push_call(Origin::signed(alice.clone()), 0, empty_calls()) );
+		assert_ok!( SContract::push_call_with_tip(Origin::signed(bob.clone()), 0, empty_calls(), 5, None) );
+		assert_ok!( SContract::push_call_with_tip(Origin::signed(alice.clone()), 0, empty_calls(), 10, None) );
+		assert_ok!( SContract::push_call_with_tip(Origin::signed(bob.clone()), 0, empty_calls(), 5, None) );
+
+		assert_eq! (Balances::free_balance(&SContract::get_pallet_account_id()), 20);
+		assert_eq! (SContract::call_queue_of(1, 0).unwrap().into_inner(), vec![(10, 2), (5, 1), (5, 3), (0, 0)]);
+
+		// the call without a tip does not fit in the block and is deferred to the next one
+		SContract::on_finalize(1);
+		assert_eq! (SContract::call_history_of(0, 1).unwrap().into_inner(), vec![2, 1, 3]);
+		assert_eq! (SContract::call_queue_of(1, 0), None);
+		assert_eq! (SContract::call_queue_of(2, 0).unwrap().into_inner(), vec![(0, 0)]);
+		assert_eq! (SContract::queued_call_count(), 1);
+		assert_eq! (SContract::block_tips_of(0, 1).unwrap().into_inner(), vec![(2, 10), (1, 5), (3, 5)]);
+
+		System::set_block_number(2);
+		SContract::on_finalize(2);
+		assert_eq! (SContract::call_history_of(0, 2).unwrap().into_inner(), vec![0]);
+		assert_eq! (SContract::call_queue_of(2, 0), None);
+		assert_eq! (SContract::queued_call_count(), 0);
+
+		// the deferred call is left as it was pushed
+		let (call, _) = SContract::call_record_of(0).unwrap();
+		let calls: skw_blockchain_primitives::types::Calls =
+			skw_blockchain_primitives::BorshDeserialize::try_from_slice(&call[..]).unwrap();
+		assert_eq! (calls.block_number, Some(1));
+
+		<SContract as OnBlockConfirmed<AccountId, u64>>::on_block_confirmed(0, 1, &keepers[..]);
+		assert_eq! (Balances::free_balance(&keepers[0]), 8);
+		assert_eq! (Balances::free_balance(&keepers[1]), 6);
+		assert_eq! (Balances::free_balance(&keepers[2]), 6);
+		assert_eq! (SContract::block_tips_of(0, 1), None);
+		assert! (System::events().iter().any(|evt| {
+			evt.event == Event::SContract(SContractEvent::TipsPaid(0, 1, 20))
+		}));
+	});
+}
+
+#[test]
+fn it_refunds_tips_of_unconfirmed_blocks() {
+	let alice: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let bob: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let keepers: Vec<AccountId> = (10u8..13).map(|i| AccountId::from([i; 32])).collect();
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, alice.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(alice.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+
+		assert_ok!( SContract::push_call_with_tip(Origin::signed(alice.clone()), 0, empty_calls(), 10, None) );
+		assert_ok!( SContract::push_call_with_tip(Origin::signed(bob.clone()), 0, empty_calls(), 5, None) );
+		SContract::on_finalize(1);
+		assert_eq! (Balances::free_balance(&alice), 90);
+		assert_eq! (Balances::free_balance(&bob), 15);
+
+		// TipRefundDelay = 10 in the mock runtime
+		System::set_block_number(11);
+		assert_noop!(
+			SContract::refund_tips(Origin::signed(bob.clone()), 0, 1),
+			SContractError::<Test>::TipsNotRefundable
+		);
+
+		System::set_block_number(12);
+		assert_ok!( SContract::refund_tips(Origin::signed(bob.clone()), 0, 1) );
+		assert_eq! (Balances::free_balance(&alice), 100);
+		assert_eq! (Balances::free_balance(&bob), 20);
+		assert_eq! (SContract::block_tips_of(0, 1), None);
+		System::assert_last_event(Event::SContract(SContractEvent::TipsRefunded(0, 1, 15)));
+
+		// tips are refunded once, and a late confirmation has nothing left to pay
+		assert_noop!(
+			SContract::refund_tips(Origin::signed(bob.clone()), 0, 1),
+			SContractError::<Test>::TipsNotRefundable
+		);
+		<SContract as OnBlockConfirmed<AccountId, u64>>::on_block_confirmed(0, 1, &keepers[..]);
+		assert_eq! (Balances::free_balance(&keepers[0]), 0);
+	});
+}
+
+#[test]
+fn it_claims_a_name_on_deploy() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let account2: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, account.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0,
+				WASM_BLOB.as_bytes().to_vec(),
+				SContract::get_pallet_account_id().into(),
+			)
+		);
+
+		assert_ok!(
+			SContract::register_contract_with_name(
+				Origin::signed(account.clone()),
+				"contract_name".as_bytes().to_vec(),
+				WASM_BLOB.as_bytes().to_vec(),
+				empty_calls(),
+				0,
+				"my_contract".as_bytes().to_vec(),
+				10,
+			)
+		);
+		assert_eq!(
+			Naming::resolve_name(b"my_contract"),
+			Some(pallet_naming::NameTarget::Contract(0, "contract_name".as_bytes().to_vec().try_into().unwrap()))
+		);
+
+		// the name is taken - the whole deployment is reverted
+		assert_noop!(
+			SContract::register_contract_with_name(
+				Origin::signed(account2.clone()),
+				"another_contract".as_bytes().to_vec(),
+				WASM_BLOB.as_bytes().to_vec(),
+				empty_calls(),
+				0,
+				"my_contract".as_bytes().to_vec(),
+				1,
+			),
+			pallet_naming::Error::<Test>::NameTaken
+		);
+		assert!( SContract::wasm_blob_of(0, "another_contract".as_bytes().to_vec()).is_none() );
+	});
+}
+
+#[test]
+fn it_initializes_shards_at_genesis() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let account2: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		shard_operators: vec![(0, account2.clone())],
+		shards: vec![(0, account.clone(), "initial_state".as_bytes().to_vec(), [0u8; 32])],
+	}.assimilate_storage(&mut t).unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		System::set_block_number(1);
+
+		assert_eq!( SContract::shard_status(0), Some(ShardState::Active) );
+		assert_eq!( SContract::shard_secret_id(0), Some(0) );
+		assert_eq!( SContract::shard_public_key(0), Some([0u8; 32]) );
+		assert_eq!( SContract::shard_high_call_index(0), Some(0) );
+		assert_eq!( SContract::shard_operator(0, &account), Some(true) );
+		assert_eq!( SContract::shard_operator(0, &account2), Some(true) );
+		assert_eq!( Secrets::owner_of(0), Some(account.clone()) );
+
+		// ready to take calls right away
+		assert_ok!( SContract::push_call( Origin::signed(account2.clone()), 0, empty_calls() ) );
+	});
+}
+
+#[test]
+fn it_scopes_shard_admins_to_their_shard() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let admin: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// only the admin origin appoints shard admins
+		assert_noop!(
+			SContract::set_shard_admin(Origin::signed(admin.clone()), 0, Some(admin.clone())),
+			BadOrigin
+		);
+		assert_ok!( SContract::set_shard_admin(Origin::root(), 0, Some(admin.clone())) );
+		assert_eq! (SContract::shard_admin_of(0), Some(admin.clone()));
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::signed(admin.clone()), 0, account.clone()) );
+		assert_noop!(
+			SContract::add_authorized_shard_operator(Origin::signed(admin.clone()), 1, account.clone()),
+			SContractError::<Test>::Unauthorized
+		);
+		assert_noop!(
+			SContract::add_authorized_shard_operator(Origin::signed(account.clone()), 0, account.clone()),
+			SContractError::<Test>::Unauthorized
+		);
+
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0, "initial_state".as_bytes().to_vec(), [0u8; 32]
+			)
+		);
+		assert_ok!( SContract::set_shard_status(Origin::signed(admin.clone()), 0, ShardState::Paused) );
+
+		// proposing and migrating shards stays with the admin origin
+		assert_noop!( SContract::propose_shard(Origin::signed(admin.clone()), 1), BadOrigin );
+
+		assert_ok!( SContract::remove_authorized_shard_operator(Origin::signed(admin.clone()), 0, account.clone()) );
+		assert_eq! (SContract::shard_operator(0, &account), None);
+		System::assert_last_event(Event::SContract(SContractEvent::ShardOperatorRemoved(0, account.clone())));
+
+		assert_ok!( SContract::set_shard_admin(Origin::root(), 0, None) );
+		assert_noop!(
+			SContract::set_shard_status(Origin::signed(admin.clone()), 0, ShardState::Active),
+			SContractError::<Test>::Unauthorized
+		);
+	});
+}
+
+#[test]
+fn it_rotates_shard_keys_once_operators_agree() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let operator: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let old_key = [1u8; 32];
+	let new_key = [2u8; 32];
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, account.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0, "initial_state".as_bytes().to_vec(), old_key
+			)
+		);
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, operator.clone()) );
+
+		// keys are not rotated before the shard state is shared
+		assert_noop!(
+			SContract::propose_key_rotation(Origin::signed(account.clone()), 0, new_key, 3, "resealed".as_bytes().to_vec()),
+			SContractError::<Test>::ShardSecretNotShared
+		);
+
+		// the shard state is shared 2 out of 2 between the operators
+		assert_ok!( Secrets::update_shares(Origin::signed(account.clone()), 0, 2, 2, sealed_shares()) );
+
+		assert_noop!(
+			SContract::push_call_with_key(Origin::signed(account.clone()), 0, empty_calls(), new_key),
+			SContractError::<Test>::StaleShardKey
+		);
+		assert_ok!( SContract::push_call_with_key(Origin::signed(account.clone()), 0, empty_calls(), old_key) );
+
+		assert_noop!(
+			SContract::propose_key_rotation(Origin::signed(account.clone()), 0, new_key, 0, "resealed".as_bytes().to_vec()),
+			SContractError::<Test>::InvalidEffectiveCallIndex
+		);
+		assert_ok!(
+			SContract::propose_key_rotation(Origin::signed(account.clone()), 0, new_key, 3, "resealed".as_bytes().to_vec())
+		);
+		assert_noop!(
+			SContract::propose_key_rotation(Origin::signed(operator.clone()), 0, new_key, 3, "resealed".as_bytes().to_vec()),
+			SContractError::<Test>::KeyRotationInProgress
+		);
+
+		// one approval is not enough
+		assert_eq! (SContract::shard_public_key(0), Some(old_key));
+		assert_noop!(
+			SContract::approve_key_rotation(Origin::signed(account.clone()), 0, new_key),
+			SContractError::<Test>::KeyRotationAlreadyApproved
+		);
+		assert_noop!(
+			SContract::approve_key_rotation(Origin::signed(operator.clone()), 0, old_key),
+			SContractError::<Test>::NoPendingKeyRotation
+		);
+
+		assert_ok!( SContract::approve_key_rotation(Origin::signed(operator.clone()), 0, new_key) );
+		System::assert_last_event(Event::SContract(SContractEvent::ShardKeyRotated(0, 3)));
+		assert_eq! (SContract::shard_public_key(0), Some(new_key));
+		assert_eq! (SContract::previous_shard_keys_of(0).into_inner(), vec![(old_key, 3)]);
+		assert_eq! (SContract::pending_key_rotation_of(0), None);
+
+		let metadata = Secrets::metadata_of(0).unwrap();
+		assert_eq! (Secrets::try_get_bytes(&metadata).unwrap(), "resealed".as_bytes().to_vec());
+
+		// the old key is accepted up to call index 3, the new key from call index 3 only
+		assert_ok!( SContract::push_call_with_key(Origin::signed(account.clone()), 0, empty_calls(), old_key) );
+		assert_noop!(
+			SContract::push_call_with_key(Origin::signed(account.clone()), 0, empty_calls(), new_key),
+			SContractError::<Test>::StaleShardKey
+		);
+		assert_noop!(
+			SContract::push_call_with_tip(Origin::signed(account.clone()), 0, empty_calls(), 0, Some(new_key)),
+			SContractError::<Test>::StaleShardKey
+		);
+		assert_ok!( SContract::push_call_with_tip(Origin::signed(account.clone()), 0, empty_calls(), 0, Some(old_key)) );
+		assert_noop!(
+			SContract::push_call_with_key(Origin::signed(account.clone()), 0, empty_calls(), old_key),
+			SContractError::<Test>::StaleShardKey
+		);
+		assert_ok!( SContract::push_call_with_key(Origin::signed(account.clone()), 0, empty_calls(), new_key) );
+
+		assert_eq! (SContract::public_key_of_call(0, 2), Some(old_key));
+		assert_eq! (SContract::public_key_of_call(0, 3), Some(new_key));
+	});
+}
+
+#[test]
+fn it_keeps_previous_shard_keys_and_rechecks_the_call_index() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let operator: AccountId = AccountId::from([2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let keys = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, account.clone()) );
+		assert_ok!( SContract::propose_shard(Origin::root(), 0) );
+		assert_ok!(
+			SContract::initialize_shard(
+				Origin::signed(account.clone()), 0, "initial_state".as_bytes().to_vec(), keys[0]
+			)
+		);
+		assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), 0, operator.clone()) );
+		assert_ok!( Secrets::update_shares(Origin::signed(account.clone()), 0, 2, 2, sealed_shares()) );
+
+		// 1. calls pushed before the last approval make the rotation stale
+		assert_ok!(
+			SContract::propose_key_rotation(Origin::signed(account.clone()), 0, keys[1], 2, "initial_state".as_bytes().to_vec())
+		);
+		for _ in 0..3 {
+			assert_ok!( SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()) );
+		}
+		assert_noop!(
+			SContract::approve_key_rotation(Origin::signed(operator.clone()), 0, keys[1]),
+			SContractError::<Test>::InvalidEffectiveCallIndex
+		);
+		assert_ok!( SContract::cancel_key_rotation(Origin::root(), 0) );
+
+		// 2. re-sealing the same state does not leak a reference on its preimage
+		let metadata = Secrets::metadata_of(0).unwrap();
+		assert_ok!(
+			SContract::propose_key_rotation(Origin::signed(account.clone()), 0, keys[1], 4, "initial_state".as_bytes().to_vec())
+		);
+		assert_ok!( SContract::approve_key_rotation(Origin::signed(operator.clone()), 0, keys[1]) );
+		assert_eq! (Secrets::metadata_of(0), Some(metadata));
+		assert_eq! (Secrets::preimage_ref_count_of(metadata), Some(1));
+
+		// 3. calls without a key are refused while the previous key is still accepted
+		assert_noop!(
+			SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()),
+			SContractError::<Test>::ShardKeyRequired
+		);
+		assert_noop!(
+			SContract::push_calls(Origin::signed(account.clone()), vec![(0, empty_calls())]),
+			SContractError::<Test>::ShardKeyRequired
+		);
+		assert_noop!(
+			SContract::push_call_with_tip(Origin::signed(account.clone()), 0, empty_calls(), 0, None),
+			SContractError::<Test>::ShardKeyRequired
+		);
+		assert_noop!(
+			SContract::register_contract(
+				Origin::signed(account.clone()), "contract_name".as_bytes().to_vec(), WASM_BLOB.as_bytes().to_vec(), empty_calls(), 0
+			),
+			SContractError::<Test>::ShardKeyRequired
+		);
+		assert_ok!( SContract::push_call_with_key(Origin::signed(account.clone()), 0, empty_calls(), keys[0]) );
+		assert_ok!( SContract::push_call(Origin::signed(account.clone()), 0, empty_calls()) );
+
+		// 4. the next key cannot be required before the previous one stopped being accepted
+		assert_ok!(
+			SContract::propose_key_rotation(Origin::signed(account.clone()), 0, keys[2], 7, "state_2".as_bytes().to_vec())
+		);
+		assert_ok!( SContract::approve_key_rotation(Origin::signed(operator.clone()), 0, keys[2]) );
+		assert_noop!(
+			SContract::propose_key_rotation(Origin::signed(account.clone()), 0, keys[3], 6, "state_3".as_bytes().to_vec()),
+			SContractError::<Test>::InvalidEffectiveCallIndex
+		);
+		assert_ok!(
+			SContract::propose_key_rotation(Origin::signed(account.clone()), 0, keys[3], 7, "state_3".as_bytes().to_vec())
+		);
+		assert_ok!( SContract::approve_key_rotation(Origin::signed(operator.clone()), 0, keys[3]) );
+
+		// 5. the two most recent previous keys are kept
+		assert_eq! (SContract::previous_shard_keys_of(0).into_inner(), vec![(keys[1], 7), (keys[2], 7)]);
+		assert!( SContract::is_valid_call_key(0, &keys[1]) );
+		assert!( !SContract::is_valid_call_key(0, &keys[0]) );
+		assert_eq! (SContract::public_key_of_call(0, 6), Some(keys[1]));
+		assert_eq! (SContract::public_key_of_call(0, 7), Some(keys[3]));
+	});
+}
+
+#[test]
+fn it_splits_batched_calls_and_routes_receipts() {
+	let account: AccountId = AccountId::from([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	let call = |method: &str| skw_blockchain_primitives::types::Call {
+		origin_public_key: account.clone().into(),
+		transaction_action: 2,
+		contract_name: Some("contract_name".as_bytes().to_vec()),
+		method: Some(method.as_bytes().to_vec()),
+		..Default::default()
+	};
+	let encode = |ops| skw_blockchain_primitives::BorshSerialize::try_to_vec(
+		&skw_blockchain_primitives::types::Calls { ops, shard_id: 0, block_number: None }
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for shard_id in 0..2 {
+			assert_ok!( SContract::add_authorized_shard_operator(Origin::root(), shard_id, account.clone()) );
+			assert_ok!( SContract::propose_shard(Origin::root(), shard_id) );
+			assert_ok!(
+				SContract::initialize_shard(
+					Origin::signed(account.clone()), shard_id, "initial_state".as_bytes().to_vec(), [0u8; 32]
+				)
+			);
+		}
+
+		// the batches of shard 1 are merged into a single call
+		assert_ok!(
+			SContract::push_calls(Origin::signed(account.clone()), vec![
+				(1, encode(vec![call("a")])),
+				(0, encode(vec![call("b")])),
+				(1, encode(vec![call("c")])),
+			])
+		);
+		assert_eq! (SContract::current_call_index_of(), 2);
+
+		let calls: skw_blockchain_primitives::types::Calls =
+			skw_blockchain_primitives::BorshDeserialize::try_from_slice(&SContract::call_record_of(0).unwrap().0[..]).unwrap();
+		assert_eq! (calls.shard_id, 0);
+		assert_eq! (calls.ops.len(), 1);
+		let calls: skw_blockchain_primitives::types::Calls =
+			skw_blockchain_primitives::BorshDeserialize::try_from_slice(&SContract::call_record_of(1).unwrap().0[..]).unwrap();
+		assert_eq! (calls.shard_id, 1);
+		assert_eq! (calls.ops.iter().map(|op| op.method.clone().unwrap()).collect::<Vec<_>>(), vec![b"a".to_vec(), b"c".to_vec()]);
+
+		// nothing is pushed when one of the shards rejects its calls
+		assert_ok!( SContract::set_shard_status(Origin::root(), 1, ShardState::Paused) );
+		assert_noop!(
+			SContract::push_calls(Origin::signed(account.clone()), vec![
+				(0, encode(vec![call("d")])),
+				(1, encode(vec![call("e")])),
+			]),
+			SContractError::<Test>::ShardNotActive
+		);
+
+		// receipts need the source call to be scheduled on the source shard
+		assert! (!<SContract as ReceiptRouter<u64>>::route_receipt(1, 1, 1, 0, encode(vec![call("f")])));
+		SContract::on_finalize(1);
+
+		// receipts are only routed to active shards
+		assert! (!<SContract as ReceiptRouter<u64>>::route_receipt(0, 1, 0, 1, encode(vec![call("f")])));
+
+		// receipts carry calls of the account that pushed the source call only
+		let mut foreign_call = call("f");
+		foreign_call.origin_public_key = AccountId::from([9u8; 32]).into();
+		assert! (!<SContract as ReceiptRouter<u64>>::route_receipt(1, 1, 1, 0, encode(vec![foreign_call])));
+		assert! (!<SContract as ReceiptRouter<u64>>::route_receipt(1, 1, 99, 0, encode(vec![call("f")])));
+
+		// call 1 is scheduled on shard 1, shard 0 cannot push calls on its behalf
+		assert! (!<SContract as ReceiptRouter<u64>>::route_receipt(0, 1, 1, 0, encode(vec![call("f")])));
+		assert! (!<SContract as ReceiptRouter<u64>>::route_receipt(1, 2, 1, 0, encode(vec![call("f")])));
+
+		assert! (<SContract as ReceiptRouter<u64>>::route_receipt(1, 1, 1, 0, encode(vec![call("f")])));
+		assert_eq! (SContract::receipt_source_of(2), Some((1, 1)));
+		System::assert_last_event(Event::SContract(SContractEvent::ReceiptReceived(0, 2, 1, 1)));
+	});
+}
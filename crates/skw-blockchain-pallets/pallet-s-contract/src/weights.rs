@@ -42,20 +42,54 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_s_contract.
 pub trait WeightInfo {
 	fn add_authorized_shard_operator() -> Weight;
+	fn remove_authorized_shard_operator() -> Weight;
+	fn set_shard_admin() -> Weight;
 	fn initialize_shard() -> Weight;
 	fn register_contract() -> Weight;
+	fn register_contract_with_code_hash() -> Weight;
+	fn register_contract_with_name() -> Weight;
+	fn upgrade_contract() -> Weight;
+	fn upgrade_contract_with_code_hash() -> Weight;
 	fn push_call() -> Weight;
+	fn push_call_with_tip() -> Weight;
+	fn push_call_with_key() -> Weight;
+	fn push_calls(c: u32, ) -> Weight;
+	fn schedule_calls(c: u32, ) -> Weight;
+	fn refund_tips(c: u32, ) -> Weight;
 	fn shard_rollup() -> Weight;
+	fn propose_key_rotation(s: u32, ) -> Weight;
+	fn approve_key_rotation() -> Weight;
+	fn cancel_key_rotation() -> Weight;
+	fn propose_shard() -> Weight;
+	fn set_shard_status() -> Weight;
+	fn migrate_shard(o: u32, c: u32, ) -> Weight;
+	fn on_initialize(b: u32, c: u32, ) -> Weight;
 }
 
 /// Weights for pallet_s_contract using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: SContract ShardAdmin (r:1 w:0)
 	// Storage: SContract ShardSecretIndex (r:1 w:0)
+	// Storage: Secrets Operator (r:0 w:1)
 	// Storage: SContract ShardOperator (r:1 w:1)
 	fn add_authorized_shard_operator() -> Weight {
-		(4_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+		(6_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: SContract ShardAdmin (r:1 w:0)
+	// Storage: SContract ShardOperator (r:1 w:1)
+	// Storage: SContract ShardSecretIndex (r:1 w:0)
+	// Storage: Secrets Operator (r:1 w:1)
+	fn remove_authorized_shard_operator() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: SContract ShardAdmin (r:0 w:1)
+	fn set_shard_admin() -> Weight {
+		(3_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: SContract ShardOperator (r:1 w:0)
@@ -65,32 +99,164 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Secrets Metadata (r:0 w:1)
 	// Storage: Secrets Owner (r:0 w:1)
 	// Storage: SContract ShardSecretIndex (r:0 w:1)
+	// Storage: Secrets SecretShard (r:0 w:1)
 	// Storage: SContract ShardPublicKey (r:0 w:1)
 	// Storage: SContract ShardHighCallIndex (r:0 w:1)
 	fn initialize_shard() -> Weight {
 		(27_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
 	}
 	// Storage: Preimage PreimageFor (r:1 w:0)
 	// Storage: SContract WasmBlob (r:1 w:1)
-	// Storage: SContract ShardSecretIndex (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
 	// Storage: SContract CurrentCallIndex (r:1 w:1)
 	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:1 w:1)
 	// Storage: SContract CallRecord (r:0 w:1)
+	// Storage: SContract ContractOwner (r:0 w:1)
+	// Storage: SContract ContractVersions (r:0 w:1)
 	fn register_contract() -> Weight {
-		(25_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
-	// Storage: SContract ShardSecretIndex (r:1 w:0)
+	// Storage: Preimage PreimageFor (r:1 w:0)
+	// Storage: SContract WasmBlob (r:1 w:1)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:1 w:1)
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SContract CallRecord (r:0 w:1)
+	// Storage: SContract ContractOwner (r:0 w:1)
+	// Storage: SContract ContractVersions (r:0 w:1)
+	fn register_contract_with_name() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Preimage PreimageFor (r:1 w:0)
+	// Storage: SContract WasmBlob (r:1 w:1)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
 	// Storage: SContract CurrentCallIndex (r:1 w:1)
 	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:1 w:1)
 	// Storage: SContract CallRecord (r:0 w:1)
+	// Storage: SContract ContractOwner (r:0 w:1)
+	// Storage: SContract ContractVersions (r:0 w:1)
+	fn register_contract_with_code_hash() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Preimage StatusFor (r:2 w:2)
+	// Storage: Preimage PreimageFor (r:1 w:2)
+	// Storage: SContract WasmBlob (r:1 w:1)
+	// Storage: SContract ContractOwner (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract ContractVersions (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:2 w:2)
+	// Storage: SContract CallRecord (r:0 w:1)
+	fn upgrade_contract() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:1 w:1)
+	// Storage: SContract WasmBlob (r:1 w:1)
+	// Storage: SContract ContractOwner (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract ContractVersions (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:2 w:2)
+	// Storage: SContract CallRecord (r:0 w:1)
+	fn upgrade_contract_with_code_hash() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CallQueue (r:2 w:2)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
 	fn push_call() -> Weight {
-		(18_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract ShardPublicKey (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CallQueue (r:2 w:2)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
+	fn push_call_with_tip() -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: SContract ShardPublicKey (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallQueue (r:2 w:2)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
+	fn push_call_with_key() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallQueue (r:1 w:1)
+	// Storage: SContract CallRecord (r:0 w:1)
+	fn push_calls(c: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((19_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: SContract CallQueue (r:2 w:2)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract QueuedCallCount (r:0 w:1)
+	fn schedule_calls(c: u32, ) -> Weight {
+		(1_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn refund_tips(c: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((23_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
 	// Storage: SContract ShardOperator (r:1 w:0)
 	// Storage: SContract ShardSecretIndex (r:1 w:0)
@@ -105,15 +271,128 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(7 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Storage: SContract ShardOperator (r:1 w:0)
+	// Storage: SContract ShardSecretIndex (r:2 w:0)
+	// Storage: SContract PendingKeyRotation (r:2 w:2)
+	// Storage: SContract CurrentCallIndex (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: SContract KeyRotationApproval (r:1 w:1)
+	// Storage: Secrets ShareThreshold (r:1 w:0)
+	fn propose_key_rotation(s: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: SContract ShardOperator (r:1 w:0)
+	// Storage: SContract PendingKeyRotation (r:2 w:1)
+	// Storage: SContract KeyRotationApproval (r:1 w:2)
+	// Storage: SContract ShardSecretIndex (r:2 w:0)
+	// Storage: Secrets ShareThreshold (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:2 w:1)
+	// Storage: Secrets Metadata (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: Secrets AuditLog (r:1 w:1)
+	// Storage: SContract ShardPublicKey (r:1 w:1)
+	fn approve_key_rotation() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: SContract ShardAdmin (r:1 w:0)
+	// Storage: SContract PendingKeyRotation (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: SContract KeyRotationApproval (r:1 w:1)
+	fn cancel_key_rotation() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: SContract ShardStatus (r:1 w:1)
+	fn propose_shard() -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SContract ShardAdmin (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:1)
+	fn set_shard_status() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SContract ShardStatus (r:2 w:2)
+	// Storage: SContract ShardSecretIndex (r:1 w:2)
+	// Storage: Secrets SecretShard (r:0 w:1)
+	// Storage: SContract ShardPublicKey (r:1 w:2)
+	// Storage: SContract ShardHighCallIndex (r:1 w:2)
+	// Storage: SContract ShardOperator (r:1 w:2)
+	// Storage: SContract ShardAdmin (r:1 w:2)
+	// Storage: SContract PreviousShardKeys (r:1 w:2)
+	// Storage: SContract PendingKeyRotation (r:1 w:1)
+	// Storage: SContract KeyRotationApproval (r:1 w:1)
+	// Storage: SContract ShardSuccessor (r:0 w:1)
+	// Storage: SContract WasmBlob (r:1 w:2)
+	// Storage: SContract ContractOwner (r:1 w:2)
+	// Storage: SContract ContractVersions (r:1 w:2)
+	fn migrate_shard(o: u32, c: u32, ) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: SContract PruneQueueRange (r:1 w:1)
+	// Storage: SContract PruneQueue (r:1 w:1)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
+	// Storage: SContract ReceiptSource (r:0 w:1)
+	// Storage: SContract ArchivedCallRoot (r:0 w:1)
+	fn on_initialize(b: u32, c: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((8_000_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((3_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// Storage: SContract ShardAdmin (r:1 w:0)
 	// Storage: SContract ShardSecretIndex (r:1 w:0)
+	// Storage: Secrets Operator (r:0 w:1)
 	// Storage: SContract ShardOperator (r:1 w:1)
 	fn add_authorized_shard_operator() -> Weight {
-		(4_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+		(6_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: SContract ShardAdmin (r:1 w:0)
+	// Storage: SContract ShardOperator (r:1 w:1)
+	// Storage: SContract ShardSecretIndex (r:1 w:0)
+	// Storage: Secrets Operator (r:1 w:1)
+	fn remove_authorized_shard_operator() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: SContract ShardAdmin (r:0 w:1)
+	fn set_shard_admin() -> Weight {
+		(3_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	// Storage: SContract ShardOperator (r:1 w:0)
@@ -123,32 +402,164 @@ impl WeightInfo for () {
 	// Storage: Secrets Metadata (r:0 w:1)
 	// Storage: Secrets Owner (r:0 w:1)
 	// Storage: SContract ShardSecretIndex (r:0 w:1)
+	// Storage: Secrets SecretShard (r:0 w:1)
 	// Storage: SContract ShardPublicKey (r:0 w:1)
 	// Storage: SContract ShardHighCallIndex (r:0 w:1)
 	fn initialize_shard() -> Weight {
 		(27_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
 	}
 	// Storage: Preimage PreimageFor (r:1 w:0)
 	// Storage: SContract WasmBlob (r:1 w:1)
-	// Storage: SContract ShardSecretIndex (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
 	// Storage: SContract CurrentCallIndex (r:1 w:1)
 	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:1 w:1)
 	// Storage: SContract CallRecord (r:0 w:1)
+	// Storage: SContract ContractOwner (r:0 w:1)
+	// Storage: SContract ContractVersions (r:0 w:1)
 	fn register_contract() -> Weight {
-		(25_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+		(29_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
-	// Storage: SContract ShardSecretIndex (r:1 w:0)
+	// Storage: Preimage PreimageFor (r:1 w:0)
+	// Storage: SContract WasmBlob (r:1 w:1)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:1 w:1)
+	// Storage: Naming Naming (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SContract CallRecord (r:0 w:1)
+	// Storage: SContract ContractOwner (r:0 w:1)
+	// Storage: SContract ContractVersions (r:0 w:1)
+	fn register_contract_with_name() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Preimage PreimageFor (r:1 w:0)
+	// Storage: SContract WasmBlob (r:1 w:1)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
 	// Storage: SContract CurrentCallIndex (r:1 w:1)
 	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:1 w:1)
 	// Storage: SContract CallRecord (r:0 w:1)
+	// Storage: SContract ContractOwner (r:0 w:1)
+	// Storage: SContract ContractVersions (r:0 w:1)
+	fn register_contract_with_code_hash() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Preimage StatusFor (r:2 w:2)
+	// Storage: Preimage PreimageFor (r:1 w:2)
+	// Storage: SContract WasmBlob (r:1 w:1)
+	// Storage: SContract ContractOwner (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract ContractVersions (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:2 w:2)
+	// Storage: SContract CallRecord (r:0 w:1)
+	fn upgrade_contract() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:1 w:1)
+	// Storage: SContract WasmBlob (r:1 w:1)
+	// Storage: SContract ContractOwner (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract ContractVersions (r:1 w:1)
+	// Storage: SContract CodeRefCount (r:2 w:2)
+	// Storage: SContract CallRecord (r:0 w:1)
+	fn upgrade_contract_with_code_hash() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CallQueue (r:2 w:2)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
 	fn push_call() -> Weight {
-		(18_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract ShardPublicKey (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CallQueue (r:2 w:2)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
+	fn push_call_with_tip() -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: SContract ShardPublicKey (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallQueue (r:2 w:2)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
+	fn push_call_with_key() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:1)
+	// Storage: SContract CallQueue (r:1 w:1)
+	// Storage: SContract CallRecord (r:0 w:1)
+	fn push_calls(c: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((19_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: SContract CallQueue (r:2 w:2)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract QueuedCallCount (r:0 w:1)
+	fn schedule_calls(c: u32, ) -> Weight {
+		(1_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: SContract BlockTips (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn refund_tips(c: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((23_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
 	// Storage: SContract ShardOperator (r:1 w:0)
 	// Storage: SContract ShardSecretIndex (r:1 w:0)
@@ -163,4 +574,101 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	// Storage: SContract ShardOperator (r:1 w:0)
+	// Storage: SContract ShardSecretIndex (r:2 w:0)
+	// Storage: SContract PendingKeyRotation (r:2 w:2)
+	// Storage: SContract CurrentCallIndex (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:1 w:0)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: SContract KeyRotationApproval (r:1 w:1)
+	// Storage: Secrets ShareThreshold (r:1 w:0)
+	fn propose_key_rotation(s: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: SContract ShardOperator (r:1 w:0)
+	// Storage: SContract PendingKeyRotation (r:2 w:1)
+	// Storage: SContract KeyRotationApproval (r:1 w:2)
+	// Storage: SContract ShardSecretIndex (r:2 w:0)
+	// Storage: Secrets ShareThreshold (r:1 w:0)
+	// Storage: SContract CurrentCallIndex (r:1 w:0)
+	// Storage: SContract PreviousShardKeys (r:2 w:1)
+	// Storage: Secrets Metadata (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: Secrets AuditLog (r:1 w:1)
+	// Storage: SContract ShardPublicKey (r:1 w:1)
+	fn approve_key_rotation() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	// Storage: SContract ShardAdmin (r:1 w:0)
+	// Storage: SContract PendingKeyRotation (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: SContract KeyRotationApproval (r:1 w:1)
+	fn cancel_key_rotation() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: SContract ShardStatus (r:1 w:1)
+	fn propose_shard() -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SContract ShardAdmin (r:1 w:0)
+	// Storage: SContract ShardStatus (r:1 w:1)
+	fn set_shard_status() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SContract ShardStatus (r:2 w:2)
+	// Storage: SContract ShardSecretIndex (r:1 w:2)
+	// Storage: Secrets SecretShard (r:0 w:1)
+	// Storage: SContract ShardPublicKey (r:1 w:2)
+	// Storage: SContract ShardHighCallIndex (r:1 w:2)
+	// Storage: SContract ShardOperator (r:1 w:2)
+	// Storage: SContract ShardAdmin (r:1 w:2)
+	// Storage: SContract PreviousShardKeys (r:1 w:2)
+	// Storage: SContract PendingKeyRotation (r:1 w:1)
+	// Storage: SContract KeyRotationApproval (r:1 w:1)
+	// Storage: SContract ShardSuccessor (r:0 w:1)
+	// Storage: SContract WasmBlob (r:1 w:2)
+	// Storage: SContract ContractOwner (r:1 w:2)
+	// Storage: SContract ContractVersions (r:1 w:2)
+	fn migrate_shard(o: u32, c: u32, ) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: SContract PruneQueueRange (r:1 w:1)
+	// Storage: SContract PruneQueue (r:1 w:1)
+	// Storage: SContract CallHistory (r:1 w:1)
+	// Storage: SContract CallRecord (r:1 w:1)
+	// Storage: SContract ReceiptSource (r:0 w:1)
+	// Storage: SContract ArchivedCallRoot (r:0 w:1)
+	fn on_initialize(b: u32, c: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((8_000_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((3_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
 }
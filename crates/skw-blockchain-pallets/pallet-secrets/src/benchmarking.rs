@@ -1,10 +1,12 @@
 use super::*;
 
 use frame_system::RawOrigin;
-use frame_benchmarking::{benchmarks, whitelisted_caller, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, impl_benchmark_test_suite};
 #[allow(unused)]
 use crate::Pallet as Secrets;
 use sp_std::vec::Vec;
+use skw_blockchain_primitives::traits::SecretKeeperSet;
 
 fn sized_preimage<T: Config>(size: u32) -> Vec<u8> {
 	let mut preimage = Vec::new();
@@ -12,6 +14,21 @@ fn sized_preimage<T: Config>(size: u32) -> Vec<u8> {
 	preimage
 }
 
+/// `count` sealed shares taking `size` bytes once encoded
+fn sized_sealed_shares(count: u32, size: u32) -> Vec<u8> {
+	let mut shares = Vec::new();
+	for i in 0..count {
+		let len = if i == count - 1 { size - shares.len() as u32 - 4 } else { size / count - 4 };
+		shares.extend_from_slice(&len.to_be_bytes());
+		shares.resize(shares.len() + len as usize, i as u8);
+	}
+	shares
+}
+
+fn set_keepers<T: Config>(count: u32) {
+	T::SecretKeepers::set_secret_keepers_of(0, (0..count).map(|i| account("keeper", i, 0)).collect());
+}
+
 benchmarks! {
 	register_secret {
 		let s in 0 .. 4194304; 
@@ -33,6 +50,44 @@ benchmarks! {
 	}: nominate_member(RawOrigin::Signed(caller), secret_id, caller2)
 	verify {	}
 
+	nominate_member_until {
+		const METADATA1: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+		let caller: T::AccountId = whitelisted_caller();
+		Secrets::<T>::register_secret(RawOrigin::Signed(caller.clone()).into(), METADATA1[..].to_vec())?;
+		let secret_id = Secrets::<T>::current_secret_id() - 1;
+
+		let caller2: T::AccountId = account("member", 0, 0);
+		let expiration = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	}: nominate_member_until(RawOrigin::Signed(caller), secret_id, caller2.clone(), expiration)
+	verify {
+		assert_eq! (Secrets::<T>::operator_expiration_of(secret_id, caller2), Some(expiration));
+	}
+
+	transfer_ownership {
+		const METADATA1: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+		let caller: T::AccountId = whitelisted_caller();
+		Secrets::<T>::register_secret(RawOrigin::Signed(caller.clone()).into(), METADATA1[..].to_vec())?;
+		let secret_id = Secrets::<T>::current_secret_id() - 1;
+
+		let caller2: T::AccountId = account("new_owner", 0, 0);
+	}: transfer_ownership(RawOrigin::Signed(caller), secret_id, caller2.clone())
+	verify {
+		assert_eq! (Secrets::<T>::pending_owner_of(secret_id), Some(caller2));
+	}
+
+	accept_ownership {
+		const METADATA1: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+		let caller: T::AccountId = whitelisted_caller();
+		Secrets::<T>::register_secret(RawOrigin::Signed(caller.clone()).into(), METADATA1[..].to_vec())?;
+		let secret_id = Secrets::<T>::current_secret_id() - 1;
+
+		let caller2: T::AccountId = account("new_owner", 0, 0);
+		Secrets::<T>::transfer_ownership(RawOrigin::Signed(caller).into(), secret_id, caller2.clone())?;
+	}: accept_ownership(RawOrigin::Signed(caller2.clone()), secret_id)
+	verify {
+		assert_eq! (Secrets::<T>::owner_of(secret_id), Some(caller2));
+	}
+
 	remove_member {
 		const METADATA1: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
 		let caller: T::AccountId = whitelisted_caller();
@@ -50,7 +105,8 @@ benchmarks! {
 		Secrets::<T>::register_secret(RawOrigin::Signed(caller.clone()).into(), METADATA1[..].to_vec())?;
 		let secret_id = Secrets::<T>::current_secret_id() - 1;
 		let caller2: T::AccountId = whitelisted_caller();
-	}: force_nominate_member(RawOrigin::Root, secret_id, caller2)
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, secret_id, caller2)
 	verify { }
 
 	force_remove_member {
@@ -60,7 +116,8 @@ benchmarks! {
 		let secret_id = Secrets::<T>::current_secret_id() - 1;
 		let caller2: T::AccountId = whitelisted_caller();
 		Secrets::<T>::nominate_member(RawOrigin::Signed(caller.clone()).into(), secret_id, caller2.clone())?;
-	}: force_remove_member(RawOrigin::Root, secret_id, caller2)
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, secret_id, caller2)
 	verify { }
 
 	force_change_owner {
@@ -69,7 +126,8 @@ benchmarks! {
 		Secrets::<T>::register_secret(RawOrigin::Signed(caller.clone()).into(), METADATA1[..].to_vec())?;
 		let secret_id = Secrets::<T>::current_secret_id() - 1;
 		let caller2: T::AccountId = whitelisted_caller();
-	}: force_change_owner(RawOrigin::Root, secret_id, caller2)
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, secret_id, caller2)
 	verify { }
 
 	update_metadata {
@@ -87,6 +145,27 @@ benchmarks! {
 		// assert_eq! (Secrets::<T>::metadata_of(secret_id), Some(hash));
 	}
 
+	update_shares {
+		// three shares of more than the sealing header each
+		let s in 183 .. 4194304;
+
+		const METADATA1: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+		let caller: T::AccountId = whitelisted_caller();
+		Secrets::<T>::register_secret(RawOrigin::Signed(caller.clone()).into(), METADATA1[..].to_vec())?;
+		let secret_id = Secrets::<T>::current_secret_id() - 1;
+		Secrets::<T>::do_bind_shard(secret_id, 0);
+
+		// the shares of the previous keeper set are replaced
+		set_keepers::<T>(2);
+		Secrets::<T>::update_shares(RawOrigin::Signed(caller.clone()).into(), secret_id, 2, 2, sized_sealed_shares(2, 122))?;
+
+		set_keepers::<T>(3);
+		let shares = sized_sealed_shares(3, s);
+	}: update_shares(RawOrigin::Signed(caller), secret_id, 2, 3, shares)
+	verify {
+		assert_eq! (Secrets::<T>::share_threshold_of(secret_id), Some((2, 3)));
+	}
+
 	burn_secret {
 		const METADATA1: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
 		let caller: T::AccountId = whitelisted_caller();
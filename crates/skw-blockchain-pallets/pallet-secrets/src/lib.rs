@@ -15,9 +15,9 @@ pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{ pallet_prelude::*, traits::{PreimageRecipient, PreimageProvider}};
+	use frame_support::{ pallet_prelude::*, traits::{PreimageRecipient, PreimageProvider, EnsureOrigin}};
 	use frame_system::pallet_prelude::*;
-	use skw_blockchain_primitives::types::{SecretId};
+	use skw_blockchain_primitives::{types::{SecretId, ShardId}, traits::SecretKeeperSet};
 	use super::{WeightInfo};
 	
 	use sp_std::vec::Vec;
@@ -30,11 +30,26 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		type Preimage: PreimageRecipient<Self::Hash> + PreimageProvider<Self::Hash>;
-		// type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// origin allowed to manage the membership and ownership of any secret
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// secret keepers the shares of shard secrets are sealed to
+		type SecretKeepers: SecretKeeperSet<Self::AccountId>;
+
+		/// number of metadata updates kept in the audit log of each secret, 0 disables the log
+		#[pallet::constant]
+		type MaxAuditLogLength: Get<u32>;
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	/// public key and nonce in front of every share sealed to a secret keeper
+	const SEALED_SHARE_HEADER_LEN: usize = 32 + 24;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Secret Metadata of generic secrets & contracts
@@ -51,6 +66,47 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type Operator<T: Config> = StorageDoubleMap<_, Twox64Concat, SecretId, Twox64Concat, T::AccountId, bool>;
 
+	/// last block an operator grant is valid for - grants without an entry never expire
+	#[pallet::storage]
+	#[pallet::getter(fn operator_expiration_of)]
+	pub(super) type OperatorExpiration<T: Config> = StorageDoubleMap<_, Twox64Concat, SecretId, Twox64Concat, T::AccountId, T::BlockNumber>;
+
+	/// account a secret is being transferred to, waiting for it to accept the ownership
+	#[pallet::storage]
+	#[pallet::getter(fn pending_owner_of)]
+	pub(super) type PendingOwner<T: Config> = StorageMap<_, Twox64Concat, SecretId, T::AccountId>;
+
+	/// (account, block number) of the latest metadata updates of a secret, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn audit_log_of)]
+	pub(super) type AuditLog<T: Config> = StorageMap<_, Twox64Concat, SecretId,
+		BoundedVec<(T::AccountId, T::BlockNumber), T::MaxAuditLogLength>>;
+
+	/// hash of the encoded shares of a secret, each encrypted to one secret keeper
+	#[pallet::storage]
+	#[pallet::getter(fn share_metadata_of)]
+	pub(super) type ShareMetadata<T: Config> = StorageMap<_, Twox64Concat, SecretId, T::Hash>;
+
+	/// (threshold, number of shares) of the latest shares of a secret
+	#[pallet::storage]
+	#[pallet::getter(fn share_threshold_of)]
+	pub(super) type ShareThreshold<T: Config> = StorageMap<_, Twox64Concat, SecretId, (u32, u32)>;
+
+	/// hash of the encoded secret keepers the latest shares of a secret are sealed to
+	#[pallet::storage]
+	#[pallet::getter(fn share_keepers_of)]
+	pub(super) type ShareKeepers<T: Config> = StorageMap<_, Twox64Concat, SecretId, T::Hash>;
+
+	/// shard a secret holds the state of, its shares are sealed to the secret keepers running the shard
+	#[pallet::storage]
+	#[pallet::getter(fn shard_of)]
+	pub(super) type SecretShard<T: Config> = StorageMap<_, Twox64Concat, SecretId, ShardId>;
+
+	/// number of references on a preimage noted through this pallet - preimages without an entry are never unnoted
+	#[pallet::storage]
+	#[pallet::getter(fn preimage_ref_count_of)]
+	pub(super) type PreimageRefCount<T: Config> = StorageMap<_, Identity, T::Hash, u32>;
+
 	/// the secret ID of the next registered secret
 	#[pallet::type_value]
 	pub(super) fn DefaultId<T: Config>() -> SecretId { 0u32 }
@@ -58,6 +114,31 @@ pub mod pallet {
 	#[pallet::getter(fn current_secret_id)]
 	pub(super) type CurrentSecretId<T: Config> = StorageValue<_, SecretId, ValueQuery, DefaultId<T>>;
 
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// (owner, metadata) of secrets registered at genesis, assigned secret IDs in order
+		pub secrets: Vec<(T::AccountId, Vec<u8>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { secrets: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (owner, metadata) in self.secrets.iter() {
+				Pallet::<T>::register_secret(
+					frame_system::RawOrigin::Signed(owner.clone()).into(),
+					metadata.clone(),
+				).expect("genesis secrets should be valid");
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -66,6 +147,9 @@ pub mod pallet {
 		MembershipGranted(SecretId, T::AccountId),
 		MembershipRevoked(SecretId, T::AccountId),
 		SecretBurnt(SecretId),
+		OwnershipTransferProposed(SecretId, T::AccountId),
+		OwnershipTransferred(SecretId, T::AccountId),
+		SharesUpdated(SecretId, u32, u32),
 	}
 
 	#[pallet::error]
@@ -77,6 +161,30 @@ pub mod pallet {
 		SecretNotExecutable,
 		NotAllowedForSecretContracts,
 		InvalidShardId,
+		NoPendingTransfer,
+		InvalidExpiration,
+		InvalidThreshold,
+		InvalidShares,
+		SharesUnchanged,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// metadata and shares noted before ref counting hold one reference each
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return 0;
+			}
+
+			let mut migrated: Weight = 0;
+			for hash in <Metadata<T>>::iter_values().chain(<ShareMetadata<T>>::iter_values()) {
+				<PreimageRefCount<T>>::mutate(&hash, |c| *c = Some(c.unwrap_or(0).saturating_add(1)));
+				migrated += 1;
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(2 * migrated + 1, migrated + 1)
+		}
 	}
 
 	#[pallet::call]
@@ -110,9 +218,25 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::authorize_owner(who, secret_id) == true, Error::<T>::AccessDenied);
 
+			Self::do_nominate_member(secret_id, member);
+			Ok(())
+		}
+
+		/// nominate an operator to a secret until the `expiration` block (inclusive)
+		#[pallet::weight(<T as Config>::WeightInfo::nominate_member_until())]
+		pub fn nominate_member_until(
+			origin: OriginFor<T>,
+			secret_id: SecretId,
+			member: T::AccountId,
+			expiration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::authorize_owner(who, secret_id) == true, Error::<T>::AccessDenied);
+			ensure!(expiration >= frame_system::Pallet::<T>::block_number(), Error::<T>::InvalidExpiration);
+
 			<Operator<T>>::insert(secret_id, &member, true);
+			<OperatorExpiration<T>>::insert(secret_id, &member, expiration);
 			Self::deposit_event(Event::<T>::MembershipGranted(secret_id, member));
-			
 			Ok(())
 		}
 
@@ -126,9 +250,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::authorize_owner(who, secret_id) == true, Error::<T>::AccessDenied);
 
-			<Operator<T>>::take(&secret_id, &member);
-			Self::deposit_event(Event::<T>::MembershipRevoked(secret_id, member));
-			
+			Self::do_remove_member(secret_id, member);
 			Ok(())
 		}
 
@@ -140,7 +262,7 @@ pub mod pallet {
 			metadata: Vec<u8>
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;	
-			ensure!(Self::authorize_access(who, secret_id) == true, Error::<T>::AccessDenied);
+			ensure!(Self::authorize_access(who.clone(), secret_id) == true, Error::<T>::AccessDenied);
 
 
 			// so far, it is garenteed the secret_id is valid 
@@ -150,9 +272,7 @@ pub mod pallet {
 			};
 
 			let hash = Self::maybe_note_bytes(metadata.clone())?;
-			<Metadata<T>>::insert(&secret_id, &hash);
-
-			Self::deposit_event(Event::<T>::SecretUpdated(secret_id));
+			Self::do_set_metadata(secret_id, hash, who);
 			
 			Ok(())
 		}
@@ -166,56 +286,147 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::authorize_owner(who, secret_id) == true, Error::<T>::AccessDenied);
 
-			
+			if let Some(hash) = <Metadata<T>>::take(&secret_id) {
+				Self::maybe_remove_bytes(&hash);
+			}
+
+			if let Some(hash) = <ShareMetadata<T>>::take(&secret_id) {
+				Self::maybe_remove_bytes(&hash);
+			}
 
 			<Owner<T>>::remove(&secret_id);
+			<ShareThreshold<T>>::remove(&secret_id);
+			<ShareKeepers<T>>::remove(&secret_id);
+			<SecretShard<T>>::remove(&secret_id);
+			<PendingOwner<T>>::remove(&secret_id);
+			<AuditLog<T>>::remove(&secret_id);
 			<Operator<T>>::remove_prefix(&secret_id, None);
+			<OperatorExpiration<T>>::remove_prefix(&secret_id, None);
 			
 			Self::deposit_event(Event::<T>::SecretBurnt(secret_id));
 			
 			Ok(())
 		}
 
-		/// (ROOT ONLY) forcefuly nominate an operator to a secret
+		/// record the threshold shares of a shard secret after the keepers running the shard or the
+		/// threshold changed, one sealed share per keeper. A threshold of 1 is only allowed when
+		/// there is a single share
+		#[pallet::weight(<T as Config>::WeightInfo::update_shares(shares.len() as u32))]
+		pub fn update_shares(
+			origin: OriginFor<T>,
+			secret_id: SecretId,
+			threshold: u32,
+			shares_count: u32,
+			shares: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::authorize_access(who, secret_id) == true, Error::<T>::AccessDenied);
+			ensure!(
+				threshold > 0 && threshold <= shares_count && shares_count <= u8::MAX as u32 &&
+					(threshold > 1 || shares_count == 1),
+				Error::<T>::InvalidThreshold
+			);
+
+			let shard_id = Self::shard_of(&secret_id).ok_or(Error::<T>::InvalidShardId)?;
+			let keepers = T::SecretKeepers::secret_keepers_of(shard_id);
+			ensure!(keepers.len() as u32 == shares_count, Error::<T>::InvalidShares);
+			ensure!(Self::sealed_shares_count(&shares) == Some(shares_count), Error::<T>::InvalidShares);
+
+			let keepers_hash = T::Hashing::hash_of(&keepers);
+			ensure!(
+				Self::share_keepers_of(&secret_id) != Some(keepers_hash) ||
+					Self::share_threshold_of(&secret_id) != Some((threshold, shares_count)),
+				Error::<T>::SharesUnchanged
+			);
+
+			if let Some(h) = <ShareMetadata<T>>::take(&secret_id) {
+				Self::maybe_remove_bytes(&h);
+			}
+
+			let hash = Self::maybe_note_bytes(shares)?;
+			<ShareMetadata<T>>::insert(&secret_id, &hash);
+			<ShareThreshold<T>>::insert(&secret_id, (threshold, shares_count));
+			<ShareKeepers<T>>::insert(&secret_id, keepers_hash);
+
+			Self::deposit_event(Event::<T>::SharesUpdated(secret_id, threshold, shares_count));
+			Ok(())
+		}
+
+		/// (OWNER ONLY) offer the ownership of a secret to another account, which has to accept it.
+		/// Offering it back to the owner cancels a pending transfer
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_ownership())]
+		pub fn transfer_ownership(
+			origin: OriginFor<T>,
+			secret_id: SecretId,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::authorize_owner(who.clone(), secret_id) == true, Error::<T>::AccessDenied);
+
+			if new_owner == who {
+				<PendingOwner<T>>::remove(&secret_id);
+			} else {
+				<PendingOwner<T>>::insert(&secret_id, &new_owner);
+				Self::deposit_event(Event::<T>::OwnershipTransferProposed(secret_id, new_owner));
+			}
+			Ok(())
+		}
+
+		/// accept the ownership of a secret offered through `transfer_ownership`
+		#[pallet::weight(<T as Config>::WeightInfo::accept_ownership())]
+		pub fn accept_ownership(
+			origin: OriginFor<T>,
+			secret_id: SecretId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let pending = Self::pending_owner_of(&secret_id).ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(pending == who, Error::<T>::AccessDenied);
+			// the secret could have been burnt in the meantime
+			ensure!(<Owner<T>>::contains_key(&secret_id), Error::<T>::InvalidSecretId);
+
+			<PendingOwner<T>>::remove(&secret_id);
+			<Owner<T>>::insert(&secret_id, &who);
+			Self::deposit_event(Event::<T>::OwnershipTransferred(secret_id, who));
+			Ok(())
+		}
+
+		/// (ADMIN ONLY) forcefuly nominate an operator to a secret
 		#[pallet::weight(<T as Config>::WeightInfo::force_nominate_member())]
 		pub fn force_nominate_member(
 			origin: OriginFor<T>,
 			secret_id: SecretId,
 			member: T::AccountId
 		) -> DispatchResult {
-			ensure_root(origin)?;
+			T::AdminOrigin::ensure_origin(origin)?;
 
 			// no checks here!
-			<Operator<T>>::insert(secret_id, &member, true);
-			Self::deposit_event(Event::<T>::MembershipGranted(secret_id, member));
-
+			Self::do_nominate_member(secret_id, member);
 			Ok(())
 		}
 
-		/// (ROOT ONLY) forcefuly remove an operator to a secret
+		/// (ADMIN ONLY) forcefuly remove an operator to a secret
 		#[pallet::weight(<T as Config>::WeightInfo::force_remove_member())]
 		pub fn force_remove_member(
 			origin: OriginFor<T>,
 			secret_id: SecretId,
 			member: T::AccountId
 		) -> DispatchResult {
-			ensure_root(origin)?;
+			T::AdminOrigin::ensure_origin(origin)?;
 
-			<Operator<T>>::take(&secret_id, &member);
-			Self::deposit_event(Event::<T>::MembershipRevoked(secret_id, member));
-			
+			Self::do_remove_member(secret_id, member);
 			Ok(())
 		}
 
-		/// (ROOT ONLY) forcefuly change owner of a secret
+		/// (ADMIN ONLY) forcefuly change owner of a secret
 		#[pallet::weight(<T as Config>::WeightInfo::force_change_owner())]
 		pub fn force_change_owner(
 			origin: OriginFor<T>,
 			secret_id: SecretId,
 			member: T::AccountId
 		) -> DispatchResult {
-			ensure_root(origin)?;
+			T::AdminOrigin::ensure_origin(origin)?;
 
+			<PendingOwner<T>>::remove(&secret_id);
 			<Owner<T>>::mutate(&secret_id, |owner| {
 				* owner = Some(member);
 			});
@@ -224,6 +435,37 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// grant membership without any permission check - callers are responsible for authorization
+		pub fn do_nominate_member(secret_id: SecretId, member: T::AccountId) {
+			<Operator<T>>::insert(secret_id, &member, true);
+			<OperatorExpiration<T>>::remove(secret_id, &member);
+			Self::deposit_event(Event::<T>::MembershipGranted(secret_id, member));
+		}
+
+		/// mark a secret as the state of a shard, the shares of the previous shard have to be replaced
+		pub fn do_bind_shard(secret_id: SecretId, shard_id: ShardId) {
+			<SecretShard<T>>::insert(&secret_id, shard_id);
+		}
+
+		/// revoke membership without any permission check - callers are responsible for authorization
+		pub fn do_remove_member(secret_id: SecretId, member: T::AccountId) {
+			<Operator<T>>::take(&secret_id, &member);
+			<OperatorExpiration<T>>::remove(&secret_id, &member);
+			Self::deposit_event(Event::<T>::MembershipRevoked(secret_id, member));
+		}
+
+		/// replace the metadata of a secret with already noted bytes, on behalf of `who`.
+		/// The reference taken when noting `hash` is handed over to the secret
+		pub fn do_set_metadata(secret_id: SecretId, hash: T::Hash, who: T::AccountId) {
+			if let Some(old) = <Metadata<T>>::get(&secret_id) {
+				Self::maybe_remove_bytes(&old);
+			}
+
+			<Metadata<T>>::insert(&secret_id, &hash);
+			Self::log_update(secret_id, who);
+			Self::deposit_event(Event::<T>::SecretUpdated(secret_id));
+		}
+
 		pub fn authorize_owner(
 			who: T::AccountId,
 			secret_id: SecretId
@@ -235,7 +477,55 @@ pub mod pallet {
 			who: T::AccountId,
 			secret_id: SecretId
 		) -> bool {
-			<Operator<T>>::get(&secret_id, &who) == Some(true) || <Owner<T>>::get(&secret_id) == Some(who)
+			Self::is_active_operator(&who, secret_id) || <Owner<T>>::get(&secret_id) == Some(who)
+		}
+
+		/// operator of the secret whose grant has not expired
+		pub fn is_active_operator(
+			who: &T::AccountId,
+			secret_id: SecretId
+		) -> bool {
+			<Operator<T>>::get(&secret_id, who) == Some(true) &&
+				<OperatorExpiration<T>>::get(&secret_id, who)
+					.map_or(true, |expiration| expiration >= frame_system::Pallet::<T>::block_number())
+		}
+
+		/// record a metadata update in the audit log of the secret, dropping the oldest entry when full
+		pub fn log_update(secret_id: SecretId, who: T::AccountId) {
+			if T::MaxAuditLogLength::get() == 0 {
+				return;
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			<AuditLog<T>>::mutate(&secret_id, |log| {
+				let log = log.get_or_insert_with(Default::default);
+				if log.len() as u32 >= T::MaxAuditLogLength::get() {
+					log.remove(0);
+				}
+				// cannot fail - there is room for at least one entry
+				let _ = log.try_push((who, now));
+			});
+		}
+
+		/// number of sealed shares in the encoded share metadata, each prefixed by its big endian
+		/// u32 length. None when the encoding is broken or a share is too short to be sealed
+		pub fn sealed_shares_count(encoded: &[u8]) -> Option<u32> {
+			let mut count = 0u32;
+			let mut rest = encoded;
+			while !rest.is_empty() {
+				if rest.len() < 4 {
+					return None;
+				}
+
+				let size = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+				if size <= SEALED_SHARE_HEADER_LEN || rest.len() - 4 < size {
+					return None;
+				}
+
+				rest = &rest[4 + size..];
+				count = count.saturating_add(1);
+			}
+			Some(count)
 		}
 
 		pub fn compress_hex_key(s: &Vec<u8>) -> Vec<u8> {
@@ -246,18 +536,36 @@ pub mod pallet {
 		}
 
 		// Preimage func are dumped here ... for now
+
+		/// note the bytes and take a reference on them, to be dropped with `maybe_remove_bytes`.
+		/// Bytes already noted outside of this pallet are left to whoever noted them
 		pub fn maybe_note_bytes(bytes: Vec<u8>) -> Result<T::Hash, DispatchError> {
 
 			let bounded_bytes= BoundedVec::<u8, <<T as crate::pallet::Config>::Preimage as PreimageRecipient<T::Hash>>::MaxSize>::try_from(bytes.clone())
 				.map_err(|_| Error::<T>::MetadataNotValid)?;
 			let hash = T::Hashing::hash(&bounded_bytes);
 
-			T::Preimage::note_preimage(bounded_bytes);
+			match <PreimageRefCount<T>>::get(&hash) {
+				Some(count) => <PreimageRefCount<T>>::insert(&hash, count.saturating_add(1)),
+				None if !T::Preimage::have_preimage(&hash) => {
+					T::Preimage::note_preimage(bounded_bytes);
+					<PreimageRefCount<T>>::insert(&hash, 1);
+				},
+				None => {},
+			}
 			Ok(hash)
 		}
 
+		/// drop a reference taken by `maybe_note_bytes` and unnote the bytes once nothing refers to them
 		pub fn maybe_remove_bytes(hash: &T::Hash) -> () {
-			T::Preimage::unnote_preimage(hash);
+			match <PreimageRefCount<T>>::get(hash) {
+				Some(count) if count > 1 => <PreimageRefCount<T>>::insert(hash, count - 1),
+				Some(_) => {
+					<PreimageRefCount<T>>::remove(hash);
+					T::Preimage::unnote_preimage(hash);
+				},
+				None => {},
+			}
 		}
 
 		pub fn try_get_bytes(hash: &T::Hash) -> Option<Vec<u8>> {
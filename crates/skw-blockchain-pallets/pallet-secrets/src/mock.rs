@@ -1,8 +1,9 @@
 use crate as pallet_secrets;
 
-use frame_support::traits::{ConstU32, ConstU64, Nothing};
+use frame_support::{parameter_types, traits::{ConstU32, ConstU64, Nothing}};
 use frame_system::{EnsureRoot};
 use sp_core::H256;
+use skw_blockchain_primitives::{traits::SecretKeeperSet, types::ShardId};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
@@ -73,10 +74,27 @@ impl pallet_preimage::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	/// keepers running shard 0, the other shards have none
+	pub static Keepers: Vec<u64> = vec![1, 2, 3];
+}
+
+impl SecretKeeperSet<u64> for Keepers {
+	fn secret_keepers_of(shard_id: ShardId) -> Vec<u64> {
+		if shard_id == 0 { Keepers::get() } else { Vec::new() }
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_secret_keepers_of(_shard_id: ShardId, keepers: Vec<u64>) { Keepers::set(keepers) }
+}
+
 impl pallet_secrets::Config for Test {
 	type WeightInfo = ();
 	type Event = Event;
 	type Preimage = Preimage;
+	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type SecretKeepers = Keepers;
+	type MaxAuditLogLength = frame_support::traits::ConstU32<3>;
 }
 
 // Build genesis storage according to the mock runtime.
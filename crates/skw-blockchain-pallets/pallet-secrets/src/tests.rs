@@ -1,5 +1,5 @@
 use super::Event as SecretsEvent;
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{assert_ok, assert_noop, traits::{Hooks, StorageVersion}};
 use crate::{mock::{Event, *}, Error};
 
 const METADATA1: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
@@ -9,6 +9,7 @@ type AccountId = u64;
 
 const ALICE: AccountId = 1;
 const BOB: AccountId = 2;
+const CHARLIE: AccountId = 3;
 
 #[test]
 fn it_register_secrets() {
@@ -143,3 +144,292 @@ fn owner_can_burn_secret() {
 		);
 	});
 }
+
+#[test]
+fn ownership_is_transferred_in_two_steps() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// 1. Alice register a secret w/ID = 0
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(ALICE), METADATA1[..].to_vec() )
+		);
+
+		// 2. Alice offers the secret to Bob, only Bob can accept it
+		assert_ok!(
+			Secrets::transfer_ownership( Origin::signed(ALICE), 0, BOB )
+		);
+		assert_noop!(
+			Secrets::accept_ownership( Origin::signed(CHARLIE), 0 ),
+			Error::<Test>::AccessDenied
+		);
+		assert_eq! (Secrets::owner_of(0), Some(ALICE));
+
+		// 3. Alice cancels the offer by offering it to herself
+		assert_ok!(
+			Secrets::transfer_ownership( Origin::signed(ALICE), 0, ALICE )
+		);
+		assert_noop!(
+			Secrets::accept_ownership( Origin::signed(BOB), 0 ),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		// 4. Alice offers it again and Bob accepts
+		assert_ok!(
+			Secrets::transfer_ownership( Origin::signed(ALICE), 0, BOB )
+		);
+		assert_ok!(
+			Secrets::accept_ownership( Origin::signed(BOB), 0 )
+		);
+		assert_eq! (Secrets::owner_of(0), Some(BOB));
+		assert_eq! (Secrets::pending_owner_of(0), None);
+		System::assert_last_event(Event::Secrets(SecretsEvent::OwnershipTransferred(0, BOB)));
+	});
+}
+
+#[test]
+fn operator_grants_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+
+		// 1. Alice register a secret w/ID = 0
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(ALICE), METADATA1[..].to_vec() )
+		);
+
+		// 2. expiration cannot be in the past
+		assert_noop!(
+			Secrets::nominate_member_until( Origin::signed(ALICE), 0, BOB, 4 ),
+			Error::<Test>::InvalidExpiration
+		);
+
+		// 3. Bob is an operator up to block 10
+		assert_ok!(
+			Secrets::nominate_member_until( Origin::signed(ALICE), 0, BOB, 10 )
+		);
+		System::set_block_number(10);
+		assert_eq! (Secrets::authorize_access(BOB, 0), true);
+
+		System::set_block_number(11);
+		assert_eq! (Secrets::authorize_access(BOB, 0), false);
+		assert_noop!(
+			Secrets::update_metadata( Origin::signed(BOB), 0, METADATA2[..].to_vec() ),
+			Error::<Test>::AccessDenied
+		);
+
+		// 4. a plain nomination does not expire
+		assert_ok!(
+			Secrets::nominate_member( Origin::signed(ALICE), 0, BOB )
+		);
+		assert_eq! (Secrets::operator_expiration_of(0, BOB), None);
+		assert_eq! (Secrets::authorize_access(BOB, 0), true);
+	});
+}
+
+#[test]
+fn metadata_updates_are_logged_and_burnt() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// 1. Alice register a secret w/ID = 0
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(ALICE), METADATA1[..].to_vec() )
+		);
+
+		// 2. the audit log keeps only the 3 latest updates
+		for block in 2..=5 {
+			System::set_block_number(block);
+			assert_ok!(
+				Secrets::update_metadata( Origin::signed(ALICE), 0, vec![block as u8; 16] )
+			);
+		}
+		assert_eq! (Secrets::audit_log_of(0).unwrap().into_inner(), vec![(ALICE, 3), (ALICE, 4), (ALICE, 5)]);
+
+		// 3. burning the secret unnotes the metadata and clears the log
+		let hash = Secrets::metadata_of(0).unwrap();
+		assert_ok!(
+			Secrets::burn_secret( Origin::signed(ALICE), 0 )
+		);
+		assert_eq! (Secrets::try_get_bytes(&hash), None);
+		assert_eq! (Secrets::audit_log_of(0), None);
+	});
+}
+
+/// `count` shares encoded like the share metadata of the sgx protocol, each 64 bytes of `fill`
+fn sealed_shares(count: usize, fill: u8) -> Vec<u8> {
+	(0..count)
+		.flat_map(|_| [&64u32.to_be_bytes()[..], &[fill; 64][..]].concat())
+		.collect()
+}
+
+#[test]
+fn members_can_update_shares() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// 1. Alice register a secret w/ID = 0 and nominate Bob
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(ALICE), METADATA1[..].to_vec() )
+		);
+		assert_ok!(
+			Secrets::nominate_member( Origin::signed(ALICE), 0, BOB )
+		);
+
+		// only the secret of a shard is shared, to the keepers running the shard
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 2, 3, sealed_shares(3, 1) ),
+			Error::<Test>::InvalidShardId
+		);
+		Secrets::do_bind_shard(0, 0);
+
+		// 2. a single keeper cannot hold enough shares alone
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 1, 3, sealed_shares(3, 1) ),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 4, 3, sealed_shares(3, 1) ),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(CHARLIE), 0, 2, 3, sealed_shares(3, 1) ),
+			Error::<Test>::AccessDenied
+		);
+
+		// 3. there must be exactly one sealed share per secret keeper
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 2, 4, sealed_shares(4, 1) ),
+			Error::<Test>::InvalidShares
+		);
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 2, 3, sealed_shares(2, 1) ),
+			Error::<Test>::InvalidShares
+		);
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 2, 3, METADATA1[..].to_vec() ),
+			Error::<Test>::InvalidShares
+		);
+		let mut truncated = sealed_shares(3, 1);
+		truncated.pop();
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 2, 3, truncated ),
+			Error::<Test>::InvalidShares
+		);
+
+		// 4. Bob records the shares for the keeper set
+		assert_ok!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 2, 3, sealed_shares(3, 1) )
+		);
+		let old_hash = Secrets::share_metadata_of(0).unwrap();
+		assert_eq! (Secrets::share_threshold_of(0), Some((2, 3)));
+
+		// 5. shares are only replaced once the keepers running the shard or the threshold changed
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 2, 3, sealed_shares(3, 2) ),
+			Error::<Test>::SharesUnchanged
+		);
+		assert_ok!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 3, 3, sealed_shares(3, 2) )
+		);
+		assert_eq! (Secrets::share_threshold_of(0), Some((3, 3)));
+
+		Keepers::set(vec![1, 2, 3, 4]);
+		assert_ok!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 3, 4, sealed_shares(4, 2) )
+		);
+		let hash = Secrets::share_metadata_of(0).unwrap();
+		assert_eq! (Secrets::try_get_bytes(&old_hash), None);
+		assert_eq! (Secrets::try_get_bytes(&hash).unwrap(), sealed_shares(4, 2));
+		assert_eq! (Secrets::share_threshold_of(0), Some((3, 4)));
+		System::assert_last_event(Event::Secrets(SecretsEvent::SharesUpdated(0, 3, 4)));
+
+		// 6. the shares follow the shard the secret is bound to
+		Secrets::do_bind_shard(0, 1);
+		assert_noop!(
+			Secrets::update_shares( Origin::signed(BOB), 0, 3, 4, sealed_shares(4, 3) ),
+			Error::<Test>::InvalidShares
+		);
+
+		// 7. burning the secret drops the shares
+		assert_ok!( Secrets::burn_secret( Origin::signed(ALICE), 0 ) );
+		assert_eq! (Secrets::try_get_bytes(&hash), None);
+		assert_eq! (Secrets::share_keepers_of(0), None);
+		assert_eq! (Secrets::shard_of(0), None);
+	});
+}
+
+#[test]
+fn shared_preimages_are_ref_counted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// 1. Alice and Bob register secrets with the same metadata
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(ALICE), METADATA1[..].to_vec() )
+		);
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(BOB), METADATA1[..].to_vec() )
+		);
+		let hash = Secrets::metadata_of(0).unwrap();
+		assert_eq! (Secrets::preimage_ref_count_of(&hash), Some(2));
+
+		// 2. burning Alice's secret keeps the metadata of Bob's
+		assert_ok!(
+			Secrets::burn_secret( Origin::signed(ALICE), 0 )
+		);
+		assert_eq! (Secrets::try_get_bytes(&hash).unwrap(), METADATA1[..].to_vec());
+		assert_eq! (Secrets::preimage_ref_count_of(&hash), Some(1));
+
+		// 3. the last reference unnotes it
+		assert_ok!(
+			Secrets::burn_secret( Origin::signed(BOB), 1 )
+		);
+		assert_eq! (Secrets::try_get_bytes(&hash), None);
+		assert_eq! (Secrets::preimage_ref_count_of(&hash), None);
+	});
+}
+
+#[test]
+fn preimages_noted_elsewhere_are_never_unnoted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(
+			Preimage::note_preimage( Origin::root(), METADATA2[..].to_vec() )
+		);
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(ALICE), METADATA2[..].to_vec() )
+		);
+		let hash = Secrets::metadata_of(0).unwrap();
+		assert_eq! (Secrets::preimage_ref_count_of(&hash), None);
+
+		assert_ok!(
+			Secrets::burn_secret( Origin::signed(ALICE), 0 )
+		);
+		assert_eq! (Secrets::try_get_bytes(&hash).unwrap(), METADATA2[..].to_vec());
+	});
+}
+
+#[test]
+fn it_migrates_preimage_ref_counts() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(ALICE), METADATA1[..].to_vec() )
+		);
+		assert_ok!(
+			Secrets::register_secret( Origin::signed(BOB), METADATA1[..].to_vec() )
+		);
+
+		// secrets registered before ref counting
+		let hash = Secrets::metadata_of(0).unwrap();
+		crate::PreimageRefCount::<Test>::remove(&hash);
+		StorageVersion::new(0).put::<Secrets>();
+
+		Secrets::on_runtime_upgrade();
+
+		assert_eq! (Secrets::preimage_ref_count_of(&hash), Some(2));
+		assert_eq! (StorageVersion::get::<Secrets>(), 1);
+	});
+}
@@ -43,7 +43,11 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn register_secret(s: u32, ) -> Weight;
 	fn nominate_member() -> Weight;
+	fn nominate_member_until() -> Weight;
 	fn remove_member() -> Weight;
+	fn update_shares(s: u32, ) -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn accept_ownership() -> Weight;
 	fn force_nominate_member() -> Weight;
 	fn force_remove_member() -> Weight;
 	fn force_change_owner() -> Weight;
@@ -59,61 +63,121 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Preimage StatusFor (r:1 w:1)
 	// Storage: Secrets Metadata (r:0 w:1)
 	// Storage: Secrets Owner (r:0 w:1)
+	// Storage: Secrets PreimageRefCount (r:1 w:1)
 	fn register_secret(s: u32, ) -> Weight {
 		(109_154_000 as Weight)
 			// Standard Error: 0
 			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
 	// Storage: Secrets Owner (r:1 w:0)
 	// Storage: Secrets Operator (r:0 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
 	fn nominate_member() -> Weight {
-		(10_000_000 as Weight)
+		(11_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Secrets Owner (r:1 w:0)
+	// Storage: Secrets Operator (r:0 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
+	fn nominate_member_until() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 	// Storage: Secrets Owner (r:1 w:0)
 	// Storage: Secrets Operator (r:1 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
 	fn remove_member() -> Weight {
-		(12_000_000 as Weight)
+		(13_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Secrets Operator (r:1 w:0)
+	// Storage: Secrets OperatorExpiration (r:1 w:0)
+	// Storage: Secrets Owner (r:1 w:0)
+	// Storage: Secrets SecretShard (r:1 w:0)
+	// Storage: Registry ShardMembers (r:1 w:0)
+	// Storage: Secrets ShareKeepers (r:1 w:1)
+	// Storage: Secrets ShareThreshold (r:1 w:1)
+	// Storage: Secrets ShareMetadata (r:1 w:1)
+	// Storage: Preimage StatusFor (r:2 w:2)
+	// Storage: Preimage PreimageFor (r:1 w:2)
+	// Storage: Secrets PreimageRefCount (r:2 w:2)
+	fn update_shares(s: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 0
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Secrets Owner (r:1 w:0)
+	// Storage: Secrets PendingOwner (r:0 w:1)
+	fn transfer_ownership() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Secrets PendingOwner (r:1 w:1)
+	// Storage: Secrets Owner (r:1 w:1)
+	fn accept_ownership() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 	// Storage: Secrets Operator (r:0 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
 	fn force_nominate_member() -> Weight {
-		(8_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 	// Storage: Secrets Operator (r:1 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
 	fn force_remove_member() -> Weight {
-		(10_000_000 as Weight)
+		(11_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Storage: Secrets PendingOwner (r:0 w:1)
 	// Storage: Secrets Owner (r:1 w:1)
 	fn force_change_owner() -> Weight {
-		(3_000_000 as Weight)
+		(4_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 	// Storage: Secrets Operator (r:1 w:0)
+	// Storage: Secrets OperatorExpiration (r:1 w:0)
 	// Storage: Secrets Owner (r:1 w:0)
 	// Storage: Secrets Metadata (r:1 w:1)
 	// Storage: Preimage StatusFor (r:2 w:2)
 	// Storage: Preimage PreimageFor (r:1 w:2)
+	// Storage: Secrets AuditLog (r:1 w:1)
+	// Storage: Secrets PreimageRefCount (r:2 w:2)
 	fn update_metadata(s: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 0
 			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
 	// Storage: Secrets Owner (r:1 w:1)
+	// Storage: Secrets Metadata (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: Secrets ShareMetadata (r:1 w:1)
+	// Storage: Secrets ShareThreshold (r:0 w:1)
+	// Storage: Secrets SecretShard (r:0 w:1)
+	// Storage: Secrets PendingOwner (r:0 w:1)
+	// Storage: Secrets AuditLog (r:0 w:1)
+	// Storage: Secrets Operator (r:1 w:1)
+	// Storage: Secrets OperatorExpiration (r:1 w:1)
+	// Storage: Secrets PreimageRefCount (r:2 w:2)
 	fn burn_secret() -> Weight {
-		(12_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
 	}
 }
 
@@ -124,60 +188,120 @@ impl WeightInfo for () {
 	// Storage: Preimage StatusFor (r:1 w:1)
 	// Storage: Secrets Metadata (r:0 w:1)
 	// Storage: Secrets Owner (r:0 w:1)
+	// Storage: Secrets PreimageRefCount (r:1 w:1)
 	fn register_secret(s: u32, ) -> Weight {
 		(109_154_000 as Weight)
 			// Standard Error: 0
 			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
 	// Storage: Secrets Owner (r:1 w:0)
 	// Storage: Secrets Operator (r:0 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
 	fn nominate_member() -> Weight {
-		(10_000_000 as Weight)
+		(11_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Secrets Owner (r:1 w:0)
+	// Storage: Secrets Operator (r:0 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
+	fn nominate_member_until() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 	// Storage: Secrets Owner (r:1 w:0)
 	// Storage: Secrets Operator (r:1 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
 	fn remove_member() -> Weight {
-		(12_000_000 as Weight)
+		(13_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Secrets Operator (r:1 w:0)
+	// Storage: Secrets OperatorExpiration (r:1 w:0)
+	// Storage: Secrets Owner (r:1 w:0)
+	// Storage: Secrets SecretShard (r:1 w:0)
+	// Storage: Registry ShardMembers (r:1 w:0)
+	// Storage: Secrets ShareKeepers (r:1 w:1)
+	// Storage: Secrets ShareThreshold (r:1 w:1)
+	// Storage: Secrets ShareMetadata (r:1 w:1)
+	// Storage: Preimage StatusFor (r:2 w:2)
+	// Storage: Preimage PreimageFor (r:1 w:2)
+	// Storage: Secrets PreimageRefCount (r:2 w:2)
+	fn update_shares(s: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 0
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Secrets Owner (r:1 w:0)
+	// Storage: Secrets PendingOwner (r:0 w:1)
+	fn transfer_ownership() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Secrets PendingOwner (r:1 w:1)
+	// Storage: Secrets Owner (r:1 w:1)
+	fn accept_ownership() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 	// Storage: Secrets Operator (r:0 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
 	fn force_nominate_member() -> Weight {
-		(8_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+		(9_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 	// Storage: Secrets Operator (r:1 w:1)
+	// Storage: Secrets OperatorExpiration (r:0 w:1)
 	fn force_remove_member() -> Weight {
-		(10_000_000 as Weight)
+		(11_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Storage: Secrets PendingOwner (r:0 w:1)
 	// Storage: Secrets Owner (r:1 w:1)
 	fn force_change_owner() -> Weight {
-		(3_000_000 as Weight)
+		(4_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 	// Storage: Secrets Operator (r:1 w:0)
+	// Storage: Secrets OperatorExpiration (r:1 w:0)
 	// Storage: Secrets Owner (r:1 w:0)
 	// Storage: Secrets Metadata (r:1 w:1)
 	// Storage: Preimage StatusFor (r:2 w:2)
 	// Storage: Preimage PreimageFor (r:1 w:2)
+	// Storage: Secrets AuditLog (r:1 w:1)
+	// Storage: Secrets PreimageRefCount (r:2 w:2)
 	fn update_metadata(s: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 0
 			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
 	// Storage: Secrets Owner (r:1 w:1)
+	// Storage: Secrets Metadata (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: Secrets ShareMetadata (r:1 w:1)
+	// Storage: Secrets ShareThreshold (r:0 w:1)
+	// Storage: Secrets SecretShard (r:0 w:1)
+	// Storage: Secrets PendingOwner (r:0 w:1)
+	// Storage: Secrets AuditLog (r:0 w:1)
+	// Storage: Secrets Operator (r:1 w:1)
+	// Storage: Secrets OperatorExpiration (r:1 w:1)
+	// Storage: Secrets PreimageRefCount (r:2 w:2)
 	fn burn_secret() -> Weight {
-		(12_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+		(32_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
 	}
 }
@@ -3,4 +3,5 @@
 pub mod util;
 pub mod sig;
 pub mod types;
+pub mod traits;
 pub use borsh::{BorshDeserialize, BorshSerialize};
\ No newline at end of file
@@ -0,0 +1,123 @@
+use sp_std::vec::Vec;
+use sp_runtime::{DispatchError, DispatchResult};
+use crate::types::{CallIndex, ShardId};
+
+/// lifecycle status of shards, queried by pallets that do not own the shard records
+pub trait ShardStatusProvider {
+	/// whether new calls can be pushed to the shard
+	fn is_accepting_calls(shard_id: ShardId) -> bool;
+
+	/// whether secret keepers can submit outcomes for the shard
+	fn is_accepting_outcomes(shard_id: ShardId) -> bool;
+}
+
+/// every shard is treated as active
+impl ShardStatusProvider for () {
+	fn is_accepting_calls(_shard_id: ShardId) -> bool { true }
+	fn is_accepting_outcomes(_shard_id: ShardId) -> bool { true }
+}
+
+/// per-shard administrators, who can manage their own shard without the admin origin
+pub trait ShardAdmins<AccountId> {
+	fn is_shard_admin(shard_id: ShardId, who: &AccountId) -> bool;
+}
+
+/// no shard has an administrator
+impl<AccountId> ShardAdmins<AccountId> for () {
+	fn is_shard_admin(_shard_id: ShardId, _who: &AccountId) -> bool { false }
+}
+
+/// hook for pallets that act on a block of a shard being confirmed by the secret keepers
+pub trait OnBlockConfirmed<AccountId, BlockNumber> {
+	/// `keepers` are the secret keepers whose outcomes confirmed the block
+	fn on_block_confirmed(shard_id: ShardId, block_number: BlockNumber, keepers: &[AccountId]);
+}
+
+impl<AccountId, BlockNumber> OnBlockConfirmed<AccountId, BlockNumber> for () {
+	fn on_block_confirmed(_shard_id: ShardId, _block_number: BlockNumber, _keepers: &[AccountId]) {}
+}
+
+/// the secret keepers the shares of a shard secret are sealed to
+pub trait SecretKeeperSet<AccountId> {
+	/// secret keepers running the shard, the i-th share of its secret is sealed to the i-th keeper
+	fn secret_keepers_of(shard_id: ShardId) -> Vec<AccountId>;
+
+	/// replace the secret keepers of a shard, so that benchmarks can change the keeper set
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_secret_keepers_of(shard_id: ShardId, keepers: Vec<AccountId>);
+}
+
+/// there are no secret keepers
+impl<AccountId> SecretKeeperSet<AccountId> for () {
+	fn secret_keepers_of(_shard_id: ShardId) -> Vec<AccountId> { Vec::new() }
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_secret_keepers_of(_shard_id: ShardId, _keepers: Vec<AccountId>) {}
+}
+
+/// calls scheduled for a shard, read by the offchain worker of secret keepers
+pub trait CallHistoryProvider<BlockNumber> {
+	/// (call index, encoded calls) of every call scheduled for the shard in a block
+	fn calls_of(shard_id: ShardId, block_number: BlockNumber) -> Vec<(CallIndex, Vec<u8>)>;
+
+	/// whether the call is scheduled for the shard in a block
+	fn is_call_of(shard_id: ShardId, block_number: BlockNumber, call_index: CallIndex) -> bool {
+		Self::calls_of(shard_id, block_number).iter().any(|(index, _)| *index == call_index)
+	}
+
+	/// schedule calls for the shard in a block, so that benchmarks can submit receipts for them
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_calls_of(shard_id: ShardId, block_number: BlockNumber, call_indexes: Vec<CallIndex>);
+}
+
+impl<BlockNumber> CallHistoryProvider<BlockNumber> for () {
+	fn calls_of(_shard_id: ShardId, _block_number: BlockNumber) -> Vec<(CallIndex, Vec<u8>)> { Vec::new() }
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_calls_of(_shard_id: ShardId, _block_number: BlockNumber, _call_indexes: Vec<CallIndex>) {}
+}
+
+/// enqueues the calls a confirmed outcome of a shard asks for on another shard
+pub trait ReceiptRouter<BlockNumber> {
+	/// push `calls` to `target_shard_id` on behalf of the call `source_call_index` scheduled in block
+	/// `source_block_number` of `source_shard_id`, returns false when the source call is not part of
+	/// that block or the target shard does not accept the calls
+	fn route_receipt(
+		source_shard_id: ShardId,
+		source_block_number: BlockNumber,
+		source_call_index: CallIndex,
+		target_shard_id: ShardId,
+		calls: Vec<u8>,
+	) -> bool;
+}
+
+/// receipts are dropped
+impl<BlockNumber> ReceiptRouter<BlockNumber> for () {
+	fn route_receipt(
+		_source_shard_id: ShardId,
+		_source_block_number: BlockNumber,
+		_source_call_index: CallIndex,
+		_target_shard_id: ShardId,
+		_calls: Vec<u8>,
+	) -> bool { false }
+}
+
+/// name service a secret contract can claim a name on when it is deployed
+pub trait ContractNaming<AccountId> {
+	/// claim or renew `name` for `period` periods on behalf of `who` and resolve it to the contract.
+	/// Fails when the name is taken by someone else, the period is invalid or the deposit cannot be reserved
+	fn claim_contract_name(
+		who: &AccountId,
+		name: &[u8],
+		period: u32,
+		shard_id: ShardId,
+		contract_name: &[u8],
+	) -> DispatchResult;
+}
+
+/// no name service - names can never be claimed
+impl<AccountId> ContractNaming<AccountId> for () {
+	fn claim_contract_name(_who: &AccountId, _name: &[u8], _period: u32, _shard_id: ShardId, _contract_name: &[u8]) -> DispatchResult {
+		Err(DispatchError::Other("no name service"))
+	}
+}
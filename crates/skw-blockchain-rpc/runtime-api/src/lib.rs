@@ -0,0 +1,55 @@
+//! Runtime API definition for querying the secret contract state kept by
+//! `pallet-s-contract`, `pallet-parentchain` and `pallet-registry`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use sp_std::vec::Vec;
+use skw_blockchain_primitives::types::{CallIndex, ShardId, PublicKey};
+
+/// Submission status of the outcome of a call
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct OutcomeStatus<BlockNumber> {
+	/// the shard the call was pushed to
+	pub shard_id: ShardId,
+	/// the block the outcome was submitted for
+	pub block_number: BlockNumber,
+	/// matching submissions received so far
+	pub confirmations: u64,
+	/// submissions needed to confirm the block
+	pub threshold: u64,
+	/// the submitted outcome
+	pub outcome: Vec<u8>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait SecretContractApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// call indexes pushed to a shard for each block in [from, to], blocks without calls are skipped
+		fn calls_in_range(shard_id: ShardId, from: BlockNumber, to: BlockNumber) -> Vec<(BlockNumber, Vec<CallIndex>)>;
+
+		/// encoded calls of a call index and the account that pushed them
+		fn call_record(call_index: CallIndex) -> Option<(Vec<u8>, AccountId)>;
+
+		/// outcome of a call index, once the block it was submitted for is confirmed
+		fn confirmed_outcome(call_index: CallIndex) -> Option<Vec<u8>>;
+
+		/// submission status of the outcome of a call index, confirmed or not
+		fn outcome_status(call_index: CallIndex) -> Option<OutcomeStatus<BlockNumber>>;
+
+		/// the latest confirmed block of a shard and its state root
+		fn latest_state_root(shard_id: ShardId) -> Option<(BlockNumber, [u8; 32])>;
+
+		/// secret keepers running a shard
+		fn shard_members(shard_id: ShardId) -> Vec<AccountId>;
+
+		/// beacon count of a shard and the beacon index of each member
+		fn beacon_schedule(shard_id: ShardId) -> (u64, Vec<(AccountId, u64)>);
+
+		/// the public key calls to a shard are encrypted with
+		fn shard_public_key(shard_id: ShardId) -> Option<PublicKey>;
+	}
+}
@@ -0,0 +1,358 @@
+//! RPC interface for querying the secret contract state of the SkyeKiwi Network,
+//! so that clients and the relayer do not need to decode raw storage keys of
+//! `SContract`, `Parentchain` and `Registry` themselves.
+
+use std::{fmt::Debug, sync::Arc};
+
+use codec::Codec;
+use futures::{future, stream, FutureExt, Stream, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+	SubscriptionSink,
+};
+use serde::{Deserialize, Serialize};
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{AtLeast32BitUnsigned, Block as BlockT},
+	SaturatedConversion,
+};
+
+use skw_blockchain_primitives::types::{CallIndex, ShardId};
+pub use skw_blockchain_rpc_runtime_api::{OutcomeStatus, SecretContractApi as SecretContractRuntimeApi};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of blocks a single `sContract_callsInRange` query may cover
+pub const MAX_BLOCK_RANGE: u64 = 1_000;
+
+/// Maximum number of new best blocks a `sContract_subscribeOutcome` subscription is kept open
+/// for, about an hour of 6 second blocks
+pub const MAX_OUTCOME_SUBSCRIPTION_BLOCKS: usize = 600;
+
+/// Calls pushed to a shard in a block
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockCalls<BlockNumber> {
+	pub block_number: BlockNumber,
+	pub call_indexes: Vec<CallIndex>,
+}
+
+/// Encoded calls of a call index and the account that pushed them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallRecord<AccountId> {
+	pub calls: Bytes,
+	pub origin: AccountId,
+}
+
+/// The latest confirmed block of a shard and its state root
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmedStateRoot<BlockNumber> {
+	pub block_number: BlockNumber,
+	pub state_root: H256,
+}
+
+/// Beacon count of a shard and the beacon index of each member
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BeaconSchedule<AccountId> {
+	pub beacon_count: u64,
+	pub beacon_index: Vec<(AccountId, u64)>,
+}
+
+/// Outcome of a call pushed to subscribers of `sContract_subscribeOutcome`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OutcomeNotification<BlockNumber> {
+	pub shard_id: ShardId,
+	pub block_number: BlockNumber,
+	pub confirmations: u64,
+	pub threshold: u64,
+	/// final status - no more notifications are sent once the block is confirmed
+	pub confirmed: bool,
+	pub outcome: Bytes,
+}
+
+impl<BlockNumber> From<OutcomeStatus<BlockNumber>> for OutcomeNotification<BlockNumber> {
+	fn from(status: OutcomeStatus<BlockNumber>) -> Self {
+		Self {
+			shard_id: status.shard_id,
+			block_number: status.block_number,
+			confirmations: status.confirmations,
+			threshold: status.threshold,
+			confirmed: status.confirmations >= status.threshold,
+			outcome: status.outcome.into(),
+		}
+	}
+}
+
+#[rpc(client, server)]
+pub trait SecretContractApi<BlockHash, AccountId, BlockNumber> {
+	#[method(name = "sContract_callsInRange")]
+	fn calls_in_range(
+		&self,
+		shard_id: ShardId,
+		from: BlockNumber,
+		to: BlockNumber,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<BlockCalls<BlockNumber>>>;
+
+	#[method(name = "sContract_callRecord")]
+	fn call_record(&self, call_index: CallIndex, at: Option<BlockHash>) -> RpcResult<Option<CallRecord<AccountId>>>;
+
+	#[method(name = "sContract_confirmedOutcome")]
+	fn confirmed_outcome(&self, call_index: CallIndex, at: Option<BlockHash>) -> RpcResult<Option<Bytes>>;
+
+	#[method(name = "sContract_latestStateRoot")]
+	fn latest_state_root(
+		&self,
+		shard_id: ShardId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ConfirmedStateRoot<BlockNumber>>>;
+
+	#[method(name = "sContract_shardMembers")]
+	fn shard_members(&self, shard_id: ShardId, at: Option<BlockHash>) -> RpcResult<Vec<AccountId>>;
+
+	#[method(name = "sContract_beaconSchedule")]
+	fn beacon_schedule(&self, shard_id: ShardId, at: Option<BlockHash>) -> RpcResult<BeaconSchedule<AccountId>>;
+
+	#[method(name = "sContract_shardPublicKey")]
+	fn shard_public_key(&self, shard_id: ShardId, at: Option<BlockHash>) -> RpcResult<Option<Bytes>>;
+
+	/// Notifies when the outcome of a call is synced and every time its confirmations change,
+	/// the subscription ends once the block it was submitted for is confirmed, or after
+	/// `MAX_OUTCOME_SUBSCRIPTION_BLOCKS` new best blocks.
+	#[subscription(
+		name = "sContract_subscribeOutcome",
+		unsubscribe = "sContract_unsubscribeOutcome",
+		item = OutcomeNotification<BlockNumber>
+	)]
+	fn subscribe_outcome(&self, call_index: CallIndex);
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The requested block range is empty or too large.
+	InvalidRange,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidRange => 2,
+		}
+	}
+}
+
+/// Notifications for the outcome status of a call at up to `max_blocks` best blocks. Blocks
+/// without a submitted outcome and unchanged statuses are skipped, the stream ends after the
+/// first confirmed status or on the first runtime error.
+fn outcome_notifications<BlockNumber, E>(
+	statuses: impl Stream<Item = Result<Option<OutcomeStatus<BlockNumber>>, E>>,
+	max_blocks: usize,
+) -> impl Stream<Item = OutcomeNotification<BlockNumber>>
+where
+	BlockNumber: Clone + PartialEq,
+{
+	statuses
+		.take(max_blocks)
+		.take_while(|status| future::ready(status.is_ok()))
+		// outcome not submitted yet
+		.filter_map(|status| future::ready(status.ok().flatten().map(OutcomeNotification::from)))
+		.scan(None, |last: &mut Option<OutcomeNotification<BlockNumber>>, notification| {
+			// nothing is sent after a confirmed outcome
+			if last.as_ref().map_or(false, |last| last.confirmed) {
+				return future::ready(None)
+			}
+
+			let changed = last.as_ref() != Some(&notification);
+			*last = Some(notification.clone());
+			future::ready(Some(changed.then(|| notification)))
+		})
+		.filter_map(future::ready)
+}
+
+fn runtime_error(message: &str, e: impl Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		message,
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+/// Provides RPC methods to query the secret contract state.
+pub struct SecretContractRpc<C, Block> {
+	client: Arc<C>,
+	executor: SubscriptionTaskExecutor,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> SecretContractRpc<C, Block> {
+	/// Create new `SecretContractRpc` with the given reference to the client and
+	/// the executor subscriptions are spawned on.
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, executor, _marker: Default::default() }
+	}
+
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block>
+	where
+		Block: BlockT,
+		C: HeaderBackend<Block>,
+	{
+		// If the block hash is not supplied assume the best block.
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId, BlockNumber>
+	SecretContractApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber>
+	for SecretContractRpc<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block> + Send + Sync + 'static,
+	C::Api: SecretContractRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec + Clone + Send + Sync + 'static,
+	BlockNumber: Codec + AtLeast32BitUnsigned + Copy + Serialize + Send + Sync + 'static,
+{
+	fn calls_in_range(
+		&self,
+		shard_id: ShardId,
+		from: BlockNumber,
+		to: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<BlockCalls<BlockNumber>>> {
+		if from > to || (to - from).saturated_into::<u64>() >= MAX_BLOCK_RANGE {
+			return Err(CallError::Custom(ErrorObject::owned(
+				Error::InvalidRange.into(),
+				"Invalid block range.",
+				Some(format!("a range must be non-empty and cover less than {} blocks", MAX_BLOCK_RANGE)),
+			))
+			.into())
+		}
+
+		let api = self.client.runtime_api();
+		let calls = api
+			.calls_in_range(&self.block_id(at), shard_id, from, to)
+			.map_err(|e| runtime_error("Unable to query calls in range.", e))?;
+
+		Ok(calls
+			.into_iter()
+			.map(|(block_number, call_indexes)| BlockCalls { block_number, call_indexes })
+			.collect())
+	}
+
+	fn call_record(
+		&self,
+		call_index: CallIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<CallRecord<AccountId>>> {
+		let api = self.client.runtime_api();
+		let record = api
+			.call_record(&self.block_id(at), call_index)
+			.map_err(|e| runtime_error("Unable to query call record.", e))?;
+
+		Ok(record.map(|(calls, origin)| CallRecord { calls: calls.into(), origin }))
+	}
+
+	fn confirmed_outcome(
+		&self,
+		call_index: CallIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let api = self.client.runtime_api();
+		let outcome = api
+			.confirmed_outcome(&self.block_id(at), call_index)
+			.map_err(|e| runtime_error("Unable to query confirmed outcome.", e))?;
+
+		Ok(outcome.map(Into::into))
+	}
+
+	fn latest_state_root(
+		&self,
+		shard_id: ShardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<ConfirmedStateRoot<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let state_root = api
+			.latest_state_root(&self.block_id(at), shard_id)
+			.map_err(|e| runtime_error("Unable to query latest state root.", e))?;
+
+		Ok(state_root.map(|(block_number, state_root)| ConfirmedStateRoot {
+			block_number,
+			state_root: H256::from(state_root),
+		}))
+	}
+
+	fn shard_members(
+		&self,
+		shard_id: ShardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<AccountId>> {
+		let api = self.client.runtime_api();
+		api.shard_members(&self.block_id(at), shard_id)
+			.map_err(|e| runtime_error("Unable to query shard members.", e))
+	}
+
+	fn beacon_schedule(
+		&self,
+		shard_id: ShardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<BeaconSchedule<AccountId>> {
+		let api = self.client.runtime_api();
+		let (beacon_count, beacon_index) = api
+			.beacon_schedule(&self.block_id(at), shard_id)
+			.map_err(|e| runtime_error("Unable to query beacon schedule.", e))?;
+
+		Ok(BeaconSchedule { beacon_count, beacon_index })
+	}
+
+	fn shard_public_key(
+		&self,
+		shard_id: ShardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let api = self.client.runtime_api();
+		let public_key = api
+			.shard_public_key(&self.block_id(at), shard_id)
+			.map_err(|e| runtime_error("Unable to query shard public key.", e))?;
+
+		Ok(public_key.map(|pk| pk.to_vec().into()))
+	}
+
+	fn subscribe_outcome(&self, mut sink: SubscriptionSink, call_index: CallIndex) -> RpcResult<()> {
+		let client = self.client.clone();
+
+		// check the current best block first, then every new best block
+		let best_hashes = stream::iter(Some(client.info().best_hash)).chain(
+			client
+				.import_notification_stream()
+				.filter(|notification| future::ready(notification.is_new_best))
+				.map(|notification| notification.hash),
+		);
+		let statuses = best_hashes
+			.map(move |hash| client.runtime_api().outcome_status(&BlockId::hash(hash), call_index));
+		let notifications = outcome_notifications(statuses, MAX_OUTCOME_SUBSCRIPTION_BLOCKS).boxed();
+
+		// piping stops as soon as the subscriber unsubscribes or disconnects
+		let fut = async move {
+			sink.pipe_from_stream(notifications).await;
+		};
+
+		self.executor.spawn("skw-rpc-outcome-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+use futures::executor::block_on;
+
+fn status(confirmations: u64) -> Result<Option<OutcomeStatus<u32>>, ()> {
+	Ok(Some(OutcomeStatus {
+		shard_id: 0,
+		block_number: 10,
+		confirmations,
+		threshold: 2,
+		outcome: vec![1, 2, 3],
+	}))
+}
+
+fn collect(
+	statuses: Vec<Result<Option<OutcomeStatus<u32>>, ()>>,
+	max_blocks: usize,
+) -> Vec<u64> {
+	block_on(outcome_notifications(stream::iter(statuses), max_blocks).collect::<Vec<_>>())
+		.into_iter()
+		.map(|notification| notification.confirmations)
+		.collect()
+}
+
+#[test]
+fn it_notifies_confirmation_changes_until_confirmed() {
+	let statuses = vec![Ok(None), status(1), status(1), status(2), status(2), status(3)];
+	assert_eq!(collect(statuses, MAX_OUTCOME_SUBSCRIPTION_BLOCKS), vec![1, 2]);
+}
+
+#[test]
+fn it_marks_the_last_notification_confirmed() {
+	let notifications = block_on(
+		outcome_notifications(stream::iter(vec![status(1), status(2)]), MAX_OUTCOME_SUBSCRIPTION_BLOCKS)
+			.collect::<Vec<_>>(),
+	);
+
+	assert_eq!(notifications.len(), 2);
+	assert!(!notifications[0].confirmed);
+	assert!(notifications[1].confirmed);
+	assert_eq!(notifications[1].outcome, Bytes(vec![1, 2, 3]));
+}
+
+#[test]
+fn it_ends_on_runtime_errors() {
+	let statuses = vec![status(1), Err(()), status(2)];
+	assert_eq!(collect(statuses, MAX_OUTCOME_SUBSCRIPTION_BLOCKS), vec![1]);
+}
+
+#[test]
+fn it_ends_after_max_blocks() {
+	// blocks without an outcome count towards the limit
+	let statuses = vec![Ok(None), Ok(None), status(1), status(2)];
+	assert_eq!(collect(statuses.clone(), 2), Vec::<u64>::new());
+	assert_eq!(collect(statuses, 3), vec![1]);
+}
+
+#[test]
+fn it_ends_on_a_never_ending_chain_without_outcome() {
+	let statuses = stream::repeat(()).map(|_| Ok::<_, ()>(None::<OutcomeStatus<u32>>));
+	let notifications = block_on(
+		outcome_notifications(statuses, MAX_OUTCOME_SUBSCRIPTION_BLOCKS).collect::<Vec<_>>(),
+	);
+	assert!(notifications.is_empty());
+}
@@ -42,7 +42,7 @@ use pallet_transaction_payment::CurrencyAdapter;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
-use frame_system::EnsureRoot;
+use frame_system::{EnsureRoot, EnsureSigned};
 /// An index to a block.
 pub type BlockNumber = skw_blockchain_primitives::types::BlockNumber;
 
@@ -98,10 +98,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 105,
+	spec_version: 106,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -331,6 +331,7 @@ impl pallet_registry::Config for Runtime {
 	type RegistrationDuration = ConstU32<100_000>;
 	type MaxActiveShards = ConstU32<1_000>;
 	type MaxSecretKeepers = ConstU32<5_000>;
+	type MaxExpirationChecksPerBlock = ConstU32<50>;
 }
 
 impl pallet_parentchain::Config for Runtime {
@@ -339,12 +340,76 @@ impl pallet_parentchain::Config for Runtime {
 	type DelayThreshold = ConstU32<10_000>;
 	type MaxOutcomePerSubmission = ConstU32<1_000>;
 	type MaxSizePerOutcome = ConstU32<100_000>;
+	type Shards = SContract;
+	type OnBlockConfirmed = SContract;
+	type Calls = SContract;
+	type Receipts = SContract;
+	type AuthorityId = pallet_parentchain::offchain::crypto::OutcomeAuthId;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type ShardAdminOrigin = EnsureSigned<AccountId>;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+		use codec::Encode;
+		use sp_runtime::{traits::StaticLookup, SaturatedConversion};
+
+		let tip = 0;
+		// take the biggest period possible.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number()
+			.saturated_into::<u64>()
+			// The `System::block_number` is initialized with `n+1`,
+			// so the actual block number is `n`.
+			.saturating_sub(1);
+		let era = generic::Era::mortal(period, current_block);
+		let extra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(era),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let address = <Runtime as frame_system::Config>::Lookup::unlookup(account);
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (address, signature, extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
 }
 
 impl pallet_secrets::Config for Runtime {
 	type WeightInfo = ();
 	type Event = Event;
 	type Preimage = Preimage;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type SecretKeepers = Registry;
+	type MaxAuditLogLength = ConstU32<16>;
 }
 
 impl pallet_s_contract::Config for Runtime {
@@ -354,7 +419,33 @@ impl pallet_s_contract::Config for Runtime {
 	type MinContractNameLength = ConstU32<1>;
 	type MaxContractNameLength = ConstU32<32>;
 	type MaxCallPerBlock = ConstU32<1_000>;
+	type MaxQueuedCallsPerShard = ConstU32<10_000>;
+	type Currency = Balances;
+	type TipRefundDelay = ConstU32<14_400>;
 	type SContractRoot = SContractPalletId;
+	type CallRecordPruneDepth = ConstU32<14_400>;
+	type MaxPrunedCallsPerBlock = ConstU32<1_000>;
+	type MaxContractVersions = ConstU32<16>;
+	type MaxPreviousShardKeys = ConstU32<8>;
+	type Naming = Naming;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type ShardAdminOrigin = EnsureSigned<AccountId>;
+}
+
+parameter_types! {
+	pub const NameReservationFee: Balance = 1_000_000_000_000;
+}
+
+impl pallet_naming::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type WeightInfo = ();
+	type ReservationFee = NameReservationFee;
+	type BlockPerPeriod = ConstU32<DAYS>;
+	// reserve a name no more than 5 years.
+	type MaxPeriod = ConstU32<1825>;
+	type MaxContractNameLength = ConstU32<32>;
+	type ForceOrigin = EnsureRoot<AccountId>;
 }
 
 impl pallet_s_account::Config for Runtime {
@@ -383,11 +474,12 @@ construct_runtime!(
 		Scheduler: pallet_scheduler::{Pallet, Call, Event<T>, Storage},
 		Preimage: pallet_preimage::{Pallet, Call, Event<T>, Storage},
 
-		Secrets: pallet_secrets::{Pallet, Call, Storage, Event<T>},
-		SContract: pallet_s_contract::{Pallet, Call, Storage, Event<T>},
-		Registry: pallet_registry::{Pallet, Call, Storage, Event<T>},
-		Parentchain: pallet_parentchain::{Pallet, Call, Storage, Event<T>},
-		SAccount: pallet_s_account::{Pallet, Call, Storage, Event<T>},
+		Secrets: pallet_secrets::{Pallet, Call, Storage, Event<T>, Config<T>},
+		SContract: pallet_s_contract::{Pallet, Call, Storage, Event<T>, Config<T>},
+		Registry: pallet_registry::{Pallet, Call, Storage, Event<T>, Config<T>},
+		Parentchain: pallet_parentchain::{Pallet, Call, Storage, Event<T>, Config},
+		SAccount: pallet_s_account::{Pallet, Call, Storage, Event<T>, Config<T>},
+		Naming: pallet_naming::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -436,6 +528,7 @@ mod benches {
 		[pallet_s_account, SAccount]
 		[pallet_secrets, Secrets]
 		[pallet_registry, Registry]
+		[pallet_naming, Naming]
 		[pallet_parentchain, Parentchain]
 	);
 }
@@ -556,6 +649,63 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl skw_blockchain_rpc_runtime_api::SecretContractApi<Block, AccountId, BlockNumber> for Runtime {
+		fn calls_in_range(
+			shard_id: skw_blockchain_primitives::types::ShardId,
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Vec<(BlockNumber, Vec<skw_blockchain_primitives::types::CallIndex>)> {
+			SContract::calls_in_range(shard_id, from, to)
+		}
+
+		fn call_record(
+			call_index: skw_blockchain_primitives::types::CallIndex,
+		) -> Option<(Vec<u8>, AccountId)> {
+			SContract::call_record_of(call_index).map(|(calls, origin)| (calls.into_inner(), origin))
+		}
+
+		fn confirmed_outcome(
+			call_index: skw_blockchain_primitives::types::CallIndex,
+		) -> Option<Vec<u8>> {
+			Parentchain::confirmed_outcome_of(call_index)
+		}
+
+		fn outcome_status(
+			call_index: skw_blockchain_primitives::types::CallIndex,
+		) -> Option<skw_blockchain_rpc_runtime_api::OutcomeStatus<BlockNumber>> {
+			let (shard_id, block_number) = Parentchain::outcome_block_of(call_index)?;
+			let outcome = Parentchain::outcome_of(call_index)?;
+
+			Some(skw_blockchain_rpc_runtime_api::OutcomeStatus {
+				shard_id,
+				block_number,
+				confirmations: Parentchain::confirmation_of(shard_id, block_number).unwrap_or(0),
+				threshold: Parentchain::confirmation_threshold_at(shard_id, block_number),
+				outcome: outcome.into_inner(),
+			})
+		}
+
+		fn latest_state_root(
+			shard_id: skw_blockchain_primitives::types::ShardId,
+		) -> Option<(BlockNumber, [u8; 32])> {
+			Parentchain::latest_confirmed_state_root_of(shard_id)
+		}
+
+		fn shard_members(shard_id: skw_blockchain_primitives::types::ShardId) -> Vec<AccountId> {
+			Registry::shard_members_of(shard_id).map(|m| m.into_inner()).unwrap_or_default()
+		}
+
+		fn beacon_schedule(shard_id: skw_blockchain_primitives::types::ShardId) -> (u64, Vec<(AccountId, u64)>) {
+			Registry::beacon_schedule_of(shard_id)
+		}
+
+		fn shard_public_key(
+			shard_id: skw_blockchain_primitives::types::ShardId,
+		) -> Option<skw_blockchain_primitives::types::PublicKey> {
+			SContract::shard_public_key(shard_id)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -23,6 +23,8 @@ const REGISTER_EXPECTED_ERR: &str =
 const ATOMIC_OP_REGISTER: u64 = std::u64::MAX - 2;
 /// Register used to record evicted values from the storage.
 const EVICTED_REGISTER: u64 = std::u64::MAX - 1;
+/// Register used to read the values returned by storage iterators, keys go to `ATOMIC_OP_REGISTER`.
+const ITER_VALUE_REGISTER: u64 = std::u64::MAX - 3;
 
 /// Key used to store the state of the contract.
 const STATE_KEY: &[u8] = b"STATE";
@@ -229,6 +231,31 @@ pub fn random_seed_array() -> [u8; 32] {
     }
 }
 
+/// Returns `len` bytes of confidential randomness. Unlike `random_seed`, the bytes are derived
+/// from the shard secret and cannot be predicted outside the enclave, and every call within the
+/// same method returns fresh bytes.
+pub fn random_bytes(len: u64) -> Vec<u8> {
+    unsafe { sys::random_bytes(len, ATOMIC_OP_REGISTER) };
+    expect_register(read_register(ATOMIC_OP_REGISTER))
+}
+
+/// Encrypts `value` under a key that only the current contract on the current shard can use.
+/// The result can be kept in contract state and decrypted with [`unseal`].
+pub fn seal(value: &[u8]) -> Vec<u8> {
+    unsafe { sys::seal(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER) };
+    expect_register(read_register(ATOMIC_OP_REGISTER))
+}
+
+/// Decrypts a value produced by [`seal`]. Returns `None` if the value was not sealed by the
+/// current contract or was tampered with.
+pub fn unseal(sealed: &[u8]) -> Option<Vec<u8>> {
+    match unsafe { sys::unseal(sealed.len() as _, sealed.as_ptr() as _, ATOMIC_OP_REGISTER) } {
+        0 => None,
+        1 => Some(expect_register(read_register(ATOMIC_OP_REGISTER))),
+        _ => abort(),
+    }
+}
+
 /// Hashes the random sequence of bytes using sha256.
 pub fn sha256(value: &[u8]) -> Vec<u8> {
     sha256_array(value).to_vec()
@@ -288,6 +315,72 @@ pub fn ripemd160_array(value: &[u8]) -> [u8; 20] {
     }
 }
 
+/// Hashes the random sequence of bytes using blake2b with a 32 byte output.
+pub fn blake2b_256(value: &[u8]) -> Vec<u8> {
+    blake2b_256_array(value).to_vec()
+}
+
+/// Hashes the bytes using the BLAKE2b hash function with a 32 byte output, the same hash as
+/// `Blake2_256` on the parentchain.
+pub fn blake2b_256_array(value: &[u8]) -> [u8; 32] {
+    //* SAFETY: blake2b_256 syscall will always generate 32 bytes inside of the atomic op register
+    //*         so the read will have a sufficient buffer of 32, and can transmute from uninit
+    //*         because all bytes are filled. This assumes a valid blake2b_256 implementation.
+    unsafe {
+        sys::blake2b_256(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
+        read_register_fixed_32(ATOMIC_OP_REGISTER)
+    }
+}
+
+/// Verifies an ed25519 `signature` of `message` against `public_key`.
+pub fn ed25519_verify(signature: &[u8; 64], message: &[u8], public_key: &[u8; 32]) -> bool {
+    unsafe {
+        sys::ed25519_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        ) == 1
+    }
+}
+
+/// Verifies an sr25519 `signature` of `message` against `public_key`. The signature must be made
+/// with the same signing context as transaction signatures.
+pub fn sr25519_verify(signature: &[u8; 64], message: &[u8], public_key: &[u8; 32]) -> bool {
+    unsafe {
+        sys::sr25519_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        ) == 1
+    }
+}
+
+/// Computes the x25519 shared secret of `secret_key` and `public_key`.
+///
+/// Returns `None` if `public_key` is a low order point and the shared secret would be all zeros.
+pub fn x25519_shared_secret(secret_key: &[u8; 32], public_key: &[u8; 32]) -> Option<[u8; 32]> {
+    unsafe {
+        let return_code = sys::x25519_shared_secret(
+            secret_key.len() as _,
+            secret_key.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+            ATOMIC_OP_REGISTER,
+        );
+        if return_code == 0 {
+            None
+        } else {
+            Some(read_register_fixed_32(ATOMIC_OP_REGISTER))
+        }
+    }
+}
+
 /// Recovers an ECDSA signer address from a 32-byte message `hash` and a corresponding `signature`
 /// along with `v` recovery byte.
 ///
@@ -579,6 +672,33 @@ pub fn storage_has_key(key: &[u8]) -> bool {
         _ => abort(),
     }
 }
+/// Creates an iterator over the storage keys starting with `prefix`, in lexicographic order.
+/// Writing to or removing from the storage invalidates all existing iterators.
+pub fn storage_iter_prefix(prefix: &[u8]) -> u64 {
+    unsafe { sys::storage_iter_prefix(prefix.len() as _, prefix.as_ptr() as _) }
+}
+/// Creates an iterator over the storage keys `start <= key < end`, in lexicographic order.
+pub fn storage_iter_range(start: &[u8], end: &[u8]) -> u64 {
+    unsafe {
+        sys::storage_iter_range(
+            start.len() as _,
+            start.as_ptr() as _,
+            end.len() as _,
+            end.as_ptr() as _,
+        )
+    }
+}
+/// Advances the iterator and returns the next key-value pair, or `None` once it is exhausted.
+pub fn storage_iter_next(iterator_id: u64) -> Option<(Vec<u8>, Vec<u8>)> {
+    match unsafe { sys::storage_iter_next(iterator_id, ATOMIC_OP_REGISTER, ITER_VALUE_REGISTER) } {
+        0 => None,
+        1 => Some((
+            expect_register(read_register(ATOMIC_OP_REGISTER)),
+            expect_register(read_register(ITER_VALUE_REGISTER)),
+        )),
+        _ => abort(),
+    }
+}
 
 // ############################################
 // # Saving and loading of the contract state #
@@ -641,6 +761,85 @@ mod tests {
             &super::ripemd160_array(b"some value"),
             base64::decode("CfAl/tcE4eysj4iyvaPlaHbaA6w=").unwrap().as_slice()
         );
+
+        assert_eq!(
+            &super::blake2b_256_array(b"some value"),
+            base64::decode("BkIadcLEOv7GE6Opx4rpaTnkdKMNeFZvznEME5P11L4=").unwrap().as_slice()
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn crypto_smoke_tests() {
+        // RFC 8032 test vector 1
+        let public_key: [u8; 32] =
+            hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let signature: [u8; 64] = hex::decode(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bac\
+                c61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        assert!(super::ed25519_verify(&signature, b"", &public_key));
+        assert!(!super::ed25519_verify(&signature, b"tampered", &public_key));
+        assert!(!super::sr25519_verify(&signature, b"", &public_key));
+
+        // RFC 7748 section 6.1
+        let secret_key: [u8; 32] =
+            hex::decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let public_key: [u8; 32] =
+            hex::decode("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        assert_eq!(
+            super::x25519_shared_secret(&secret_key, &public_key).unwrap().to_vec(),
+            hex::decode("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742")
+                .unwrap()
+        );
+        assert_eq!(super::x25519_shared_secret(&secret_key, &[0; 32]), None);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn storage_iter_smoke_test() {
+        use crate::test_utils::test_env;
+
+        test_env::setup_free();
+        super::storage_write(b"a1", b"x");
+        super::storage_write(b"a2", b"y");
+        super::storage_write(b"b1", b"z");
+
+        let iter = super::storage_iter_prefix(b"a");
+        assert_eq!(super::storage_iter_next(iter), Some((b"a1".to_vec(), b"x".to_vec())));
+        assert_eq!(super::storage_iter_next(iter), Some((b"a2".to_vec(), b"y".to_vec())));
+        assert_eq!(super::storage_iter_next(iter), None);
+
+        let iter = super::storage_iter_range(b"a2", b"b2");
+        assert_eq!(super::storage_iter_next(iter), Some((b"a2".to_vec(), b"y".to_vec())));
+        assert_eq!(super::storage_iter_next(iter), Some((b"b1".to_vec(), b"z".to_vec())));
+        assert_eq!(super::storage_iter_next(iter), None);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn seal_smoke_test() {
+        crate::testing_env!(crate::test_utils::VMContextBuilder::new().build());
+
+        let sealed = super::seal(b"secret");
+        assert_ne!(&sealed[sealed.len() - 6..], b"secret");
+        assert_eq!(super::unseal(&sealed), Some(b"secret".to_vec()));
+        assert_eq!(super::unseal(&sealed[1..]), None);
+
+        assert_eq!(super::random_bytes(40).len(), 40);
+        assert_ne!(super::random_bytes(32), super::random_bytes(32));
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -1,7 +1,7 @@
 use super::{Receipt, VmAction};
 use crate::{Balance};
 
-use skw_vm_host::types::AccountId as VmAccountId;
+use skw_vm_host::types::{AccountId as VmAccountId, IteratorIndex};
 use skw_vm_host::{RuntimeExternal as External, HostError, ValuePtr};
 use std::{ collections::HashMap };
 
@@ -12,6 +12,8 @@ type Result<T> = ::core::result::Result<T, skw_vm_host::VMLogicError>;
 /// `MockedExternal` from `skw_vm_host`.
 pub(crate) struct SdkExternal {
     pub fake_trie: HashMap<Vec<u8>, Vec<u8>>,
+    iterators: HashMap<IteratorIndex, std::vec::IntoIter<(Vec<u8>, Vec<u8>)>>,
+    next_iterator_index: IteratorIndex,
     pub receipts: Vec<Receipt>,
     pub validators: HashMap<String, Balance>,
 }
@@ -34,10 +36,25 @@ impl SdkExternal {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn create_iterator<F: Fn(&[u8]) -> bool>(&mut self, filter: F) -> IteratorIndex {
+        let mut entries: Vec<_> = self
+            .fake_trie
+            .iter()
+            .filter(|(key, _)| filter(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        entries.sort();
+        let iterator_index = self.next_iterator_index;
+        self.iterators.insert(iterator_index, entries.into_iter());
+        self.next_iterator_index += 1;
+        iterator_index
+    }
 }
 
 impl External for SdkExternal {
     fn storage_set(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.iterators.clear();
         self.fake_trie.insert(key.to_vec(), value.to_vec());
         Ok(())
     }
@@ -50,11 +67,13 @@ impl External for SdkExternal {
     }
 
     fn storage_remove(&mut self, key: &[u8]) -> Result<()> {
+        self.iterators.clear();
         self.fake_trie.remove(key);
         Ok(())
     }
 
     fn storage_remove_subtree(&mut self, prefix: &[u8]) -> Result<()> {
+        self.iterators.clear();
         self.fake_trie.retain(|key, _| !key.starts_with(prefix));
         Ok(())
     }
@@ -63,6 +82,37 @@ impl External for SdkExternal {
         Ok(self.fake_trie.contains_key(key))
     }
 
+    fn storage_iter(&mut self, prefix: &[u8]) -> Result<IteratorIndex> {
+        Ok(self.create_iterator(|key| key.starts_with(prefix)))
+    }
+
+    fn storage_iter_range(&mut self, start: &[u8], end: &[u8]) -> Result<IteratorIndex> {
+        Ok(self.create_iterator(|key| key >= start && key < end))
+    }
+
+    fn storage_iter_next<'a>(
+        &'a mut self,
+        iterator_idx: IteratorIndex,
+    ) -> Result<Option<(Vec<u8>, Box<dyn ValuePtr + 'a>)>> {
+        let next = self
+            .iterators
+            .get_mut(&iterator_idx)
+            .ok_or(HostError::InvalidIteratorIndex { iterator_index: iterator_idx })?
+            .next();
+        match next {
+            Some((key, value)) => Ok(Some((key, Box::new(MockedValuePtr { value }) as Box<_>))),
+            None => {
+                self.iterators.remove(&iterator_idx);
+                Ok(None)
+            }
+        }
+    }
+
+    fn storage_iter_drop(&mut self, iterator_idx: IteratorIndex) -> Result<()> {
+        self.iterators.remove(&iterator_idx);
+        Ok(())
+    }
+
     fn create_receipt(
         &mut self,
         receipt_indices: Vec<u64>,
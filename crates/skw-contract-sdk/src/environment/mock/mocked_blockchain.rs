@@ -166,6 +166,18 @@ mod mock_chain {
         with_mock_interface(|b| b.random_seed(register_id))
     }
     #[no_mangle]
+    extern "C" fn random_bytes(len: u64, register_id: u64) {
+        with_mock_interface(|b| b.random_bytes(len, register_id))
+    }
+    #[no_mangle]
+    extern "C" fn seal(value_len: u64, value_ptr: u64, register_id: u64) {
+        with_mock_interface(|b| b.seal(value_len, value_ptr, register_id))
+    }
+    #[no_mangle]
+    extern "C" fn unseal(value_len: u64, value_ptr: u64, register_id: u64) -> u64 {
+        with_mock_interface(|b| b.unseal(value_len, value_ptr, register_id))
+    }
+    #[no_mangle]
     extern "C" fn sha256(value_len: u64, value_ptr: u64, register_id: u64) {
         with_mock_interface(|b| b.sha256(value_len, value_ptr, register_id))
     }
@@ -196,6 +208,68 @@ mod mock_chain {
         })
     }
     #[no_mangle]
+    extern "C" fn ed25519_verify(
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> u64 {
+        with_mock_interface(|b| {
+            b.ed25519_verify(
+                signature_len,
+                signature_ptr,
+                message_len,
+                message_ptr,
+                public_key_len,
+                public_key_ptr,
+            )
+        })
+    }
+    #[no_mangle]
+    extern "C" fn sr25519_verify(
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> u64 {
+        with_mock_interface(|b| {
+            b.sr25519_verify(
+                signature_len,
+                signature_ptr,
+                message_len,
+                message_ptr,
+                public_key_len,
+                public_key_ptr,
+            )
+        })
+    }
+    #[no_mangle]
+    extern "C" fn blake2b_256(value_len: u64, value_ptr: u64, register_id: u64) {
+        with_mock_interface(|b| b.blake2b_256(value_len, value_ptr, register_id))
+    }
+    #[no_mangle]
+    extern "C" fn x25519_shared_secret(
+        secret_key_len: u64,
+        secret_key_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+        register_id: u64,
+    ) -> u64 {
+        with_mock_interface(|b| {
+            b.x25519_shared_secret(
+                secret_key_len,
+                secret_key_ptr,
+                public_key_len,
+                public_key_ptr,
+                register_id,
+            )
+        })
+    }
+    #[no_mangle]
     extern "C" fn value_return(value_len: u64, value_ptr: u64) {
         with_mock_interface(|b| b.value_return(value_len, value_ptr))
     }
@@ -373,4 +447,25 @@ mod mock_chain {
     extern "C" fn storage_has_key(key_len: u64, key_ptr: u64) -> u64 {
         with_mock_interface(|b| b.storage_has_key(key_len, key_ptr))
     }
+    #[no_mangle]
+    extern "C" fn storage_iter_prefix(prefix_len: u64, prefix_ptr: u64) -> u64 {
+        with_mock_interface(|b| b.storage_iter_prefix(prefix_len, prefix_ptr))
+    }
+    #[no_mangle]
+    extern "C" fn storage_iter_range(
+        start_len: u64,
+        start_ptr: u64,
+        end_len: u64,
+        end_ptr: u64,
+    ) -> u64 {
+        with_mock_interface(|b| b.storage_iter_range(start_len, start_ptr, end_len, end_ptr))
+    }
+    #[no_mangle]
+    extern "C" fn storage_iter_next(
+        iterator_id: u64,
+        key_register_id: u64,
+        value_register_id: u64,
+    ) -> u64 {
+        with_mock_interface(|b| b.storage_iter_next(iterator_id, key_register_id, value_register_id))
+    }
 }
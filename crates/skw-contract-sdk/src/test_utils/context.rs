@@ -32,8 +32,10 @@ impl VMContextBuilder {
                 attached_deposit: 0,
                 prepaid_gas: 300 * 10u64.pow(12),
                 random_seed: vec![0u8; 32],
+                shard_secret: Some([0u8; 32]),
                 view_config: None,
                 output_data_receivers: vec![],
+                trace: false,
             },
         }
     }
@@ -88,6 +90,11 @@ impl VMContextBuilder {
         self
     }
 
+    pub fn shard_secret(&mut self, secret: Option<[u8; 32]>) -> &mut Self {
+        self.context.shard_secret = secret;
+        self
+    }
+
     pub fn is_view(&mut self, is_view: bool) -> &mut Self {
         self.context.view_config =
             if is_view { Some(ViewConfig { max_gas_burnt: 200000000000000 }) } else { None };
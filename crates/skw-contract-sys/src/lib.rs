@@ -30,6 +30,9 @@ extern "C" {
     // # Math API #
     // ############
     pub fn random_seed(register_id: u64);
+    pub fn random_bytes(len: u64, register_id: u64);
+    pub fn seal(value_len: u64, value_ptr: u64, register_id: u64);
+    pub fn unseal(value_len: u64, value_ptr: u64, register_id: u64) -> u64;
     pub fn sha256(value_len: u64, value_ptr: u64, register_id: u64);
     pub fn keccak256(value_len: u64, value_ptr: u64, register_id: u64);
     pub fn keccak512(value_len: u64, value_ptr: u64, register_id: u64);
@@ -43,6 +46,30 @@ extern "C" {
         malleability_flag: u64,
         register_id: u64,
     ) -> u64;
+    pub fn ed25519_verify(
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> u64;
+    pub fn sr25519_verify(
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> u64;
+    pub fn blake2b_256(value_len: u64, value_ptr: u64, register_id: u64);
+    pub fn x25519_shared_secret(
+        secret_key_len: u64,
+        secret_key_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+        register_id: u64,
+    ) -> u64;
     // #####################
     // # Miscellaneous API #
     // #####################
@@ -118,4 +145,7 @@ extern "C" {
     pub fn storage_read(key_len: u64, key_ptr: u64, register_id: u64) -> u64;
     pub fn storage_remove(key_len: u64, key_ptr: u64, register_id: u64) -> u64;
     pub fn storage_has_key(key_len: u64, key_ptr: u64) -> u64;
+    pub fn storage_iter_prefix(prefix_len: u64, prefix_ptr: u64) -> u64;
+    pub fn storage_iter_range(start_len: u64, start_ptr: u64, end_len: u64, end_ptr: u64) -> u64;
+    pub fn storage_iter_next(iterator_id: u64, key_register_id: u64, value_register_id: u64) -> u64;
 }
\ No newline at end of file
@@ -11,6 +11,7 @@ extern crate core;
 #[macro_use] pub mod crypto;
 pub mod file;
 pub mod metadata;
+pub mod tss;
 pub mod types;
 pub mod utils;
 pub mod test;
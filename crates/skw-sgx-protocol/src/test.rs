@@ -18,6 +18,11 @@ pub fn skw_unit_test() {
 		crypto_test::secret_box_encrypt_decrypt,
 		crypto_test::box_encrypt_decrypt,
 
+		// tss
+		tss_test::split_recover,
+		tss_test::insufficient_shares,
+		tss_test::seal_unseal_shares,
+
 		// utils
 		utils_test::encode_decode_hex,
 		utils_test::pad_uszie,
@@ -163,6 +168,67 @@ pub mod crypto_test {
 	}
 }
 
+pub mod tss_test {
+
+	use super::*;
+	use crate::crypto::NaClBox;
+	use crate::tss::ShamirSecretSharing;
+	use crate::types::crypto::CryptoError;
+
+	pub fn split_recover() {
+		let secret = random_bytes!(32);
+		let shares = ShamirSecretSharing::split(&secret, 3, 5).unwrap();
+		assert_eq!(shares.len(), 5);
+
+		// any 3 shares recover the secret
+		let recovered = ShamirSecretSharing::recover(&shares[0..3]).unwrap();
+		assert_eq!(&recovered[..], &secret[..]);
+
+		let picked = [shares[4].clone(), shares[1].clone(), shares[3].clone()].to_vec();
+		let recovered = ShamirSecretSharing::recover(&picked).unwrap();
+		assert_eq!(&recovered[..], &secret[..]);
+	}
+
+	pub fn insufficient_shares() {
+		let secret = random_bytes!(32);
+		let shares = ShamirSecretSharing::split(&secret, 3, 5).unwrap();
+
+		// duplicated shares do not count
+		let picked = [shares[0].clone(), shares[0].clone(), shares[1].clone()].to_vec();
+		match ShamirSecretSharing::recover(&picked) {
+			Err(CryptoError::InsufficientShares) => {},
+			_ => panic!("recovered a secret from 2 distinct shares"),
+		}
+
+		match ShamirSecretSharing::split(&secret, 3, 2) {
+			Err(CryptoError::InsufficientShares) => {},
+			_ => panic!("split into fewer shares than the threshold"),
+		}
+	}
+
+	pub fn seal_unseal_shares() {
+		let sealing_key = random_bytes!(32);
+		let keepers: Vec<_> = (0..3)
+			.map(|_| NaClBox::keypair_from_seed(random_bytes!(32)))
+			.collect();
+		let public_keys: Vec<_> = keepers.iter().map(|k| k.public_key).collect();
+
+		let sealed = ShamirSecretSharing::seal_shares(&sealing_key, 2, &public_keys).unwrap();
+		let encoded = ShamirSecretSharing::encode_sealed_shares(&sealed);
+		let decoded = ShamirSecretSharing::decode_sealed_shares(&encoded).unwrap();
+		assert_eq!(decoded, sealed);
+
+		// a keeper cannot open the share of another keeper
+		assert!(ShamirSecretSharing::unseal_share(&keepers[0].secret_key, &decoded[1]).is_err());
+
+		let shares: Vec<_> = [0, 2].iter()
+			.map(|i| ShamirSecretSharing::unseal_share(&keepers[*i].secret_key, &decoded[*i]).unwrap())
+			.collect();
+		let recovered = ShamirSecretSharing::recover_sealing_key(&shares).unwrap();
+		assert_eq!(recovered, sealing_key);
+	}
+}
+
 pub mod metadata_test {
 	
 	use crate::metadata::{
@@ -0,0 +1,209 @@
+// Copyright 2021 @skyekiwi authors & contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::types::crypto::*;
+use crate::crypto::NaClBox;
+use crate::metadata::{encode_box_cipher, decode_box_cipher};
+use crate::utils::{pad_usize, padded_slice_to_usize};
+
+use std::{vec::Vec, convert::TryInto};
+
+/// Shamir secret sharing over GF(2^8)
+pub struct ShamirSecretSharing();
+
+// multiplication in GF(2^8) reduced by the AES polynomial x^8 + x^4 + x^3 + x + 1
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+	let mut result = 0u8;
+	while b != 0 {
+		if b & 1 != 0 {
+			result ^= a;
+		}
+		let carry = a & 0x80;
+		a <<= 1;
+		if carry != 0 {
+			a ^= 0x1b;
+		}
+		b >>= 1;
+	}
+	result
+}
+
+// a^254 is the multiplicative inverse of a in GF(2^8)
+fn gf_inv(a: u8) -> u8 {
+	let mut result = 1u8;
+	let mut base = a;
+	let mut exp = 254u8;
+	while exp != 0 {
+		if exp & 1 != 0 {
+			result = gf_mul(result, base);
+		}
+		base = gf_mul(base, base);
+		exp >>= 1;
+	}
+	result
+}
+
+fn decode_share_header(share: &[u8]) -> Result<(u8, u8), CryptoError> {
+	if share.len() <= SHARE_HEADER_LEN {
+		return Err(CryptoError::HeaderError);
+	}
+
+	let (threshold, x) = (share[0], share[1]);
+	if threshold == 0 || x == 0 {
+		return Err(CryptoError::HeaderError);
+	}
+	Ok((threshold, x))
+}
+
+impl ShamirSecretSharing {
+
+	/// split the secret into `shares` shares, any `threshold` of which recover it.
+	/// Each share is encoded as [threshold, x, y_0 .. y_n]
+	pub fn split(
+		secret: &[u8],
+		threshold: u8,
+		shares: u8,
+	) -> Result<Vec<Share>, CryptoError> {
+		if threshold == 0 || shares < threshold || secret.is_empty() {
+			return Err(CryptoError::InsufficientShares);
+		}
+
+		let mut result: Vec<Share> = (1..=shares)
+			.map(|x| {
+				let mut share = Vec::with_capacity(SHARE_HEADER_LEN + secret.len());
+				share.push(threshold);
+				share.push(x);
+				share
+			})
+			.collect();
+
+		let mut coefficients = Vec::with_capacity(threshold as usize);
+		for byte in secret {
+			// a random polynomial of degree threshold - 1 with the secret byte at x = 0
+			coefficients.clear();
+			coefficients.push(*byte);
+			for _ in 1..threshold {
+				coefficients.push(sgx_rand::random::<u8>());
+			}
+
+			for share in result.iter_mut() {
+				let x = share[1];
+				let y = coefficients.iter().rev()
+					.fold(0u8, |acc, coefficient| gf_mul(acc, x) ^ coefficient);
+				share.push(y);
+			}
+		}
+
+		Ok(result)
+	}
+
+	/// recover the secret from at least `threshold` distinct shares
+	pub fn recover(shares: &[Share]) -> Result<Vec<u8>, CryptoError> {
+		let first = shares.first().ok_or(CryptoError::InsufficientShares)?;
+		let (threshold, _) = decode_share_header(first)?;
+		let len = first.len();
+
+		let mut xs: Vec<u8> = Vec::with_capacity(threshold as usize);
+		let mut used: Vec<&Share> = Vec::with_capacity(threshold as usize);
+		for share in shares {
+			let (t, x) = decode_share_header(share)?;
+			if t != threshold || share.len() != len {
+				return Err(CryptoError::HeaderError);
+			}
+
+			// duplicated shares do not count towards the threshold
+			if xs.contains(&x) {
+				continue;
+			}
+
+			xs.push(x);
+			used.push(share);
+			if xs.len() == threshold as usize {
+				break;
+			}
+		}
+
+		if xs.len() < threshold as usize {
+			return Err(CryptoError::InsufficientShares);
+		}
+
+		// lagrange basis polynomials evaluated at x = 0
+		let basis: Vec<u8> = xs.iter().enumerate()
+			.map(|(i, xi)| {
+				xs.iter().enumerate()
+					.filter(|(j, _)| *j != i)
+					.fold(1u8, |acc, (_, xj)| gf_mul(acc, gf_mul(*xj, gf_inv(xj ^ xi))))
+			})
+			.collect();
+
+		let secret = (SHARE_HEADER_LEN..len)
+			.map(|offset| {
+				used.iter().zip(basis.iter())
+					.fold(0u8, |acc, (share, l)| acc ^ gf_mul(share[offset], *l))
+			})
+			.collect();
+
+		Ok(secret)
+	}
+
+	/// split a sealing key and encrypt the i-th share to the i-th keeper
+	pub fn seal_shares(
+		sealing_key: &SecretboxKey,
+		threshold: u8,
+		keepers: &[BoxPublicKey],
+	) -> Result<Vec<Vec<u8>>, CryptoError> {
+		let count: u8 = keepers.len().try_into().map_err(|_| CryptoError::HeaderError)?;
+		let shares = Self::split(&sealing_key[..], threshold, count)?;
+
+		shares.iter().zip(keepers.iter())
+			.map(|(share, keeper)| Ok(encode_box_cipher(&NaClBox::encrypt(&share, *keeper)?)))
+			.collect()
+	}
+
+	/// decrypt the share sealed to the keeper owning `key`
+	pub fn unseal_share(
+		key: &BoxSecretKey,
+		sealed_share: &[u8],
+	) -> Result<Share, CryptoError> {
+		if sealed_share.len() <= BOX_PUBLIC_KEY_LEN + SECRETBOX_NONCE_LEN {
+			return Err(CryptoError::HeaderError);
+		}
+
+		let share = NaClBox::decrypt(key, decode_box_cipher(sealed_share))?;
+		decode_share_header(&share)?;
+		Ok(share)
+	}
+
+	/// recover a sealing key from the shares unsealed by the keepers
+	pub fn recover_sealing_key(shares: &[Share]) -> Result<SecretboxKey, CryptoError> {
+		Self::recover(shares)?
+			.try_into()
+			.map_err(|_| CryptoError::HeaderError)
+	}
+
+	/// encode the sealed shares into the share metadata recorded on-chain
+	pub fn encode_sealed_shares(sealed_shares: &[Vec<u8>]) -> Vec<u8> {
+		sealed_shares.iter()
+			.flat_map(|sealed| [&pad_usize(sealed.len())[..], &sealed[..]].concat())
+			.collect()
+	}
+
+	pub fn decode_sealed_shares(encoded: &[u8]) -> Result<Vec<Vec<u8>>, CryptoError> {
+		let mut sealed_shares = Vec::new();
+		let mut offset = 0;
+		while offset < encoded.len() {
+			if offset + 4 > encoded.len() {
+				return Err(CryptoError::HeaderError);
+			}
+
+			let size = padded_slice_to_usize(&encoded[offset..offset + 4]);
+			if offset + 4 + size > encoded.len() {
+				return Err(CryptoError::HeaderError);
+			}
+
+			sealed_shares.push(encoded[offset + 4..offset + 4 + size].to_vec());
+			offset += 4 + size;
+		}
+		Ok(sealed_shares)
+	}
+}
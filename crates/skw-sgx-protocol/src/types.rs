@@ -41,6 +41,10 @@ pub mod crypto {
 
 	pub type SecretboxCipher = (Nonce, Vec<u8>);
 	pub type BoxCipher = (BoxPublicKey, Nonce, Vec<u8>);
+
+	// [threshold, x] in front of every secret share
+	pub const SHARE_HEADER_LEN: usize = 2;
+	pub type Share = Vec<u8>;
 }
 
 pub mod metadata {
@@ -2,6 +2,9 @@ mod script;
 
 use crate::script::Script;
 use clap::Parser;
+use num_rational::Ratio;
+use skw_vm_host::profile::{Cost, ProfileData};
+use skw_vm_host::{ActionCosts, ExtCosts};
 // use skw_vm_host::VMOutcome;
 // use skw_vm_host::{mocks::mock_external::Receipt};
 use serde::{
@@ -92,6 +95,37 @@ struct CliArgs {
     /// Prints execution times of various components.
     #[clap(long)]
     timings: bool,
+    /// Prints the trace of host calls, storage accesses and promises of the call as JSON.
+    #[clap(long)]
+    trace: bool,
+    /// Prints a breakdown of the gas burnt by wasm instructions, host functions and actions to
+    /// stderr.
+    #[clap(long)]
+    profile: bool,
+}
+
+fn print_profile(profile: &ProfileData) {
+    let wasm_gas = profile.wasm_gas();
+    let total = wasm_gas + profile.host_gas() + profile.action_gas();
+    let percent = |gas: u64| Ratio::new(gas * 100, core::cmp::max(total, 1)).to_integer();
+
+    eprintln!("{:<8} {:<40} {:>20} {:>5}", "category", "cost", "gas", "%");
+    eprintln!("{:<8} {:<40} {:>20} {:>4}%", "wasm", "wasm_instruction", wasm_gas, percent(wasm_gas));
+    for cost in Cost::ALL {
+        let (category, name, gas) = match cost {
+            Cost::ExtCost { ext_cost_kind: e } => {
+                ("host", ExtCosts::name_of(*e as usize), profile.get_ext_cost(*e))
+            }
+            Cost::ActionCost { action_cost_kind: a } => {
+                ("action", ActionCosts::name_of(*a as usize), profile.get_action_cost(*a))
+            }
+            Cost::WasmInstruction => continue,
+        };
+        if gas != 0 {
+            eprintln!("{:<8} {:<40} {:>20} {:>4}%", category, name, gas, percent(gas));
+        }
+    }
+    eprintln!("{:<8} {:<40} {:>20} {:>4}%", "total", "", total, 100);
 }
 
 // #[derive(Debug, Clone)]
@@ -144,6 +178,7 @@ fn main() {
     let promise_results =
         cli_args.promise_results.iter().map(|it| serde_json::from_str(it).unwrap()).collect();
     step.promise_results(promise_results);
+    step.trace(cli_args.trace);
 
     let mut results = script.run();
     let (outcome, err) = results.outcomes.pop().unwrap();
@@ -166,11 +201,22 @@ fn main() {
         serde_json::to_string(&State(results.state.fake_trie)).unwrap()
     );
 
+    if cli_args.trace {
+        let trace = outcome.as_ref().and_then(|outcome| outcome.trace.as_ref());
+        println!("\"trace\": {},", serde_json::to_string(&trace).unwrap());
+    }
+
     println!("\"error\": \"{:?}\"", err.map(|it| {
         it.to_string()
     }));
 
-    println!("{}", "}")
+    println!("{}", "}");
+
+    if cli_args.profile {
+        if let Some(outcome) = &outcome {
+            print_profile(&outcome.profile);
+        }
+    }
     // match &outcome {
     //     Some(outcome) => {
     //         println!("{:#?}", outcome.profile);
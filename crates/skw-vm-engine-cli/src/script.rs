@@ -8,7 +8,6 @@ use skw_vm_host::mocks::mock_external::MockedExternal;
 use skw_vm_host::types::{PromiseResult, AccountId};
 use skw_vm_host::{VMConfig, VMContext, VMOutcome};
 use skw_vm_engine::{VMError};
-use skw_vm_engine::WasmiVM;
 
 use crate::State;
 
@@ -95,7 +94,7 @@ impl Script {
         let mut outcomes = Vec::new();
         for step in &self.steps {
             for _ in 0..step.repeat {
-                let res = WasmiVM::run(
+                let res = skw_vm_engine::run(
                     &self.contracts[step.contract.0],
                     &step.method,
                     &mut external,
@@ -142,6 +141,10 @@ impl Step {
         self.promise_results = promise_results;
         self
     }
+    pub(crate) fn trace(&mut self, enabled: bool) -> &mut Step {
+        self.vm_context.trace = enabled;
+        self
+    }
     #[allow(unused)]
     pub(crate) fn repeat(&mut self, n: u32) -> &mut Step {
         self.repeat = n;
@@ -162,8 +165,10 @@ fn default_vm_context() -> VMContext {
         attached_deposit: 0,
         prepaid_gas: 10u64.pow(18),
         random_seed: vec![0, 1, 2],
+        shard_secret: None,
         view_config: None,
         output_data_receivers: vec![],
+        trace: false,
     }
 }
 
@@ -212,3 +217,42 @@ fn profile_data_is_per_outcome() {
             > res.outcomes[3].0.as_ref().unwrap().profile.host_gas()
     );
 }
+
+#[test]
+fn trace_records_host_calls_and_storage() {
+    use skw_vm_host::trace::TraceEvent;
+
+    let mut script = Script::default();
+    let contract = script.contract(near_test_contracts::rs_contract().to_vec());
+
+    let input = [10u64.to_le_bytes(), 20u64.to_le_bytes()].concat();
+    script.step(contract, "write_key_value").input(input.clone()).trace(true);
+    script.step(contract, "write_key_value").input(input);
+    let res = script.run();
+
+    let trace = res.outcomes[0].0.as_ref().unwrap().trace.as_ref().unwrap();
+    let names: Vec<_> = trace
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TraceEvent::HostCall { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, vec!["input", "register_len", "read_register", "storage_write", "value_return"]);
+
+    let write = trace.events.iter().position(|event| {
+        *event
+            == TraceEvent::StorageWrite {
+                key: 10u64.to_le_bytes().to_vec(),
+                value: 20u64.to_le_bytes().to_vec(),
+                evicted: None,
+            }
+    });
+    assert!(matches!(
+        trace.events[write.unwrap() - 1],
+        TraceEvent::HostCall { ref name, gas_burnt, .. } if name == "storage_write" && gas_burnt > 0
+    ));
+
+    assert!(res.outcomes[1].0.as_ref().unwrap().trace.is_none());
+}
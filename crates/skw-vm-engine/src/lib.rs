@@ -1,5 +1,8 @@
 mod cache;
 mod runner;
+mod vm;
+#[cfg(feature = "wasmtime_vm")]
+mod wasmtime_runner;
 pub mod prepare;
 #[cfg(test)]
 mod tests;
@@ -8,4 +11,7 @@ pub use skw_vm_primitives::errors::VMError;
 pub use crate::cache::create_module_instance;
 pub use skw_vm_host::with_ext_cost_counter;
 pub use runner::WasmiVM;
-pub use runner::WasmiMemory;
\ No newline at end of file
+pub use runner::WasmiMemory;
+pub use vm::{precompile, run, run_vm, VM};
+#[cfg(feature = "wasmtime_vm")]
+pub use wasmtime_runner::{WasmtimeMemory, WasmtimeVM};
@@ -1,5 +1,7 @@
 use parity_wasm::builder;
-use parity_wasm::elements::{self, External, MemorySection, Type, Module};
+use parity_wasm::elements::{
+    self, External, Instruction, Local, MemorySection, Module, Type, ValueType,
+};
 use pwasm_utils::{self, rules};
 
 use skw_vm_primitives::errors::PrepareError;
@@ -20,6 +22,24 @@ pub(crate) const WASM_FEATURES: wasmparser::WasmFeatures = wasmparser::WasmFeatu
     memory64: false,
 };
 
+/// The NaNs float arithmetic returns when all its NaN inputs are canonical, as defined by the
+/// wasm spec.
+const CANONICAL_NAN_F32: u32 = 0x7fc0_0000;
+const CANONICAL_NAN_F64: u64 = 0x7ff8_0000_0000_0000;
+
+/// Type of the float an instruction computes if the spec leaves the bits of a NaN result to the
+/// engine. Bitwise float instructions like `neg`, `abs` and `copysign` are deterministic.
+fn nan_result_type(instruction: &Instruction) -> Option<ValueType> {
+    use Instruction::*;
+    match instruction {
+        F32Add | F32Sub | F32Mul | F32Div | F32Min | F32Max | F32Sqrt | F32Ceil | F32Floor
+        | F32Trunc | F32Nearest | F32DemoteF64 => Some(ValueType::F32),
+        F64Add | F64Sub | F64Mul | F64Div | F64Min | F64Max | F64Sqrt | F64Ceil | F64Floor
+        | F64Trunc | F64Nearest | F64PromoteF32 => Some(ValueType::F64),
+        _ => None,
+    }
+}
+
 struct ContractModule<'a> {
     module: elements::Module,
     config: &'a VMConfig,
@@ -71,6 +91,68 @@ impl<'a> ContractModule<'a> {
         }
     }
 
+    /// Replaces a NaN computed by float arithmetic with the canonical NaN, so that engines which
+    /// return different NaN bits, e.g. wasmi returning the hardware NaN, behave the same. Every
+    /// function that does float arithmetic gets an `f32` and an `f64` local to check the result.
+    ///
+    /// Runs before gas metering, so the added instructions are charged on every engine alike.
+    fn canonicalize_nans(self) -> Result<Self, PrepareError> {
+        let Self { mut module, config } = self;
+
+        let types = module.type_section().map(elements::TypeSection::types).unwrap_or(&[]);
+        let params: Vec<u32> = module
+            .function_section()
+            .map(elements::FunctionSection::entries)
+            .unwrap_or(&[])
+            .iter()
+            .map(|func| match types.get(func.type_ref() as usize) {
+                Some(Type::Function(func_type)) => Ok(func_type.params().len() as u32),
+                None => Err(PrepareError::Deserialization),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let bodies = match module.code_section_mut() {
+            Some(code_section) => code_section.bodies_mut(),
+            None => return Ok(Self { module, config }),
+        };
+        for (body, params) in bodies.iter_mut().zip(params) {
+            if !body.code().elements().iter().any(|i| nan_result_type(i).is_some()) {
+                continue;
+            }
+            let f32_local = params + body.locals().iter().map(Local::count).sum::<u32>();
+            let f64_local = f32_local + 1;
+
+            let mut code = Vec::with_capacity(body.code().elements().len());
+            for instruction in body.code().elements() {
+                code.push(instruction.clone());
+                // `select` keeps the result unless it is not equal to itself, i.e. a NaN
+                match nan_result_type(instruction) {
+                    Some(ValueType::F32) => code.extend([
+                        Instruction::TeeLocal(f32_local),
+                        Instruction::F32Const(CANONICAL_NAN_F32),
+                        Instruction::GetLocal(f32_local),
+                        Instruction::GetLocal(f32_local),
+                        Instruction::F32Eq,
+                        Instruction::Select,
+                    ]),
+                    Some(_) => code.extend([
+                        Instruction::TeeLocal(f64_local),
+                        Instruction::F64Const(CANONICAL_NAN_F64),
+                        Instruction::GetLocal(f64_local),
+                        Instruction::GetLocal(f64_local),
+                        Instruction::F64Eq,
+                        Instruction::Select,
+                    ]),
+                    None => {}
+                }
+            }
+            *body.code_mut().elements_mut() = code;
+            body.locals_mut().push(Local::new(1, ValueType::F32));
+            body.locals_mut().push(Local::new(1, ValueType::F64));
+        }
+        Ok(Self { module, config })
+    }
+
     fn inject_gas_metering(self) -> Result<Self, PrepareError> {
         let Self { module, config } = self;
         // Free config, no need for gas metering.
@@ -164,6 +246,54 @@ impl<'a> ContractModule<'a> {
         Ok(self)
     }
 
+    fn validate_table_size(self) -> Result<Self, PrepareError> {
+        if let Some(max_table_elements) = self.config.limit_config.max_table_elements {
+            let imported = self
+                .module
+                .import_section()
+                .map(elements::ImportSection::entries)
+                .unwrap_or(&[])
+                .iter()
+                .filter_map(|import| match import.external() {
+                    External::Table(table_type) => Some(table_type),
+                    _ => None,
+                });
+            let declared =
+                self.module.table_section().map(elements::TableSection::entries).unwrap_or(&[]);
+            // Tables can't grow without the reference types proposal, so the initial size is
+            // the only one that matters.
+            if imported.chain(declared).any(|table| table.limits().initial() > max_table_elements)
+            {
+                return Err(PrepareError::TooLargeTable);
+            }
+        }
+        Ok(self)
+    }
+
+    fn validate_globals_number(self) -> Result<Self, PrepareError> {
+        if let Some(max_globals_number) = self.config.limit_config.max_globals_number_per_contract
+        {
+            let globals_number = self.module.globals_space() as u64;
+            if globals_number > max_globals_number {
+                return Err(PrepareError::TooManyGlobals);
+            }
+        }
+        Ok(self)
+    }
+
+    fn validate_locals_number(self) -> Result<Self, PrepareError> {
+        if let Some(max_locals) = self.config.limit_config.max_locals_per_function {
+            let bodies = self.module.code_section().map(elements::CodeSection::bodies).unwrap_or(&[]);
+            for body in bodies {
+                let locals: u64 = body.locals().iter().map(|local| local.count() as u64).sum();
+                if locals > max_locals {
+                    return Err(PrepareError::TooManyLocals);
+                }
+            }
+        }
+        Ok(self)
+    }
+
     fn get_module(self) -> Result<Module, PrepareError> {
         Ok(self.module)
     }
@@ -178,13 +308,19 @@ impl<'a> ContractModule<'a> {
 /// - imported memory (if any) doesn't reserve more memory than permitted by the `config`,
 /// - all imported functions from the external environment matches defined by `env` module,
 /// - functions number does not exceed limit specified in VMConfig,
+/// - tables, globals and locals of each function do not exceed limits specified in VMConfig,
 ///
-/// The preprocessing includes injecting code for gas metering and metering the height of stack.
+/// The preprocessing includes canonicalizing the NaNs of float arithmetic, injecting code for
+/// gas metering and metering the height of stack.
 pub fn prepare_contract(original_code: &[u8], config: &VMConfig) -> Result<Module, PrepareError> {
     ContractModule::init(original_code, config)?
         .validate_functions_number()?
+        .validate_table_size()?
+        .validate_globals_number()?
+        .validate_locals_number()?
         .standardize_mem()
         .ensure_no_internal_memory()?
+        .canonicalize_nans()?
         .inject_gas_metering()?
         .inject_stack_height_metering()?
         .scan_imports()?
@@ -255,6 +391,84 @@ mod tests {
         assert_matches!(r, Err(_));
     }
 
+    #[test]
+    fn table_size() {
+        assert_eq!(VMConfig::test().limit_config.max_table_elements, Some(10_000));
+
+        let r = parse_and_prepare_wat(r#"(module (table 10000 funcref))"#);
+        assert_matches!(r, Ok(_));
+
+        let r = parse_and_prepare_wat(r#"(module (table 10001 funcref))"#);
+        assert_matches!(r, Err(PrepareError::TooLargeTable));
+
+        let r = parse_and_prepare_wat(r#"(module (import "env" "table" (table 10001 funcref)))"#);
+        assert_matches!(r, Err(PrepareError::TooLargeTable));
+    }
+
+    #[test]
+    fn globals_number() {
+        assert_eq!(VMConfig::test().limit_config.max_globals_number_per_contract, Some(1_000));
+        let globals = |n: usize| format!("(module {})", "(global i32 (i32.const 0))".repeat(n));
+
+        let r = parse_and_prepare_wat(&globals(1_000));
+        assert_matches!(r, Ok(_));
+
+        let r = parse_and_prepare_wat(&globals(1_001));
+        assert_matches!(r, Err(PrepareError::TooManyGlobals));
+
+        let r = parse_and_prepare_wat(&format!(
+            r#"(module (import "env" "g" (global i32)) {})"#,
+            "(global i32 (i32.const 0))".repeat(1_000)
+        ));
+        assert_matches!(r, Err(PrepareError::TooManyGlobals));
+    }
+
+    #[test]
+    fn locals_number() {
+        assert_eq!(VMConfig::test().limit_config.max_locals_per_function, Some(10_000));
+        let locals = |i32s: usize, i64s: usize| {
+            format!(
+                "(module (func (local {}) (local {})) (func (local i32)))",
+                "i32 ".repeat(i32s),
+                "i64 ".repeat(i64s)
+            )
+        };
+
+        let r = parse_and_prepare_wat(&locals(5_000, 5_000));
+        assert_matches!(r, Ok(_));
+
+        let r = parse_and_prepare_wat(&locals(5_000, 5_001));
+        assert_matches!(r, Err(PrepareError::TooManyLocals));
+
+        let r = parse_and_prepare_wat(&locals(10_001, 0));
+        assert_matches!(r, Err(PrepareError::TooManyLocals));
+    }
+
+    #[test]
+    fn nans_canonicalized() {
+        let module = parse_and_prepare_wat(
+            r#"(module
+              (func (result f32) (f32.div (f32.const 0) (f32.const 0)))
+              (func (result f32) (f32.neg (f32.const 0))))"#,
+        )
+        .unwrap();
+        let bodies = module.code_section().unwrap().bodies();
+
+        assert_eq!(
+            bodies[0].locals(),
+            &[Local::new(1, ValueType::F32), Local::new(1, ValueType::F64)][..]
+        );
+        let code = bodies[0].code().elements();
+        let div = code.iter().position(|i| *i == Instruction::F32Div).unwrap();
+        assert_eq!(code[div + 1], Instruction::TeeLocal(0));
+        assert_eq!(code[div + 2], Instruction::F32Const(CANONICAL_NAN_F32));
+        assert_eq!(code[div + 6], Instruction::Select);
+
+        // bitwise float instructions are left alone
+        assert!(bodies[1].locals().is_empty());
+        assert!(!bodies[1].code().elements().contains(&Instruction::Select));
+    }
+
     #[test]
     fn imports() {
         // nothing can be imported from non-"env" module for now.
@@ -1,16 +1,17 @@
-use crate::{cache};
+use crate::{cache, prepare};
+use crate::vm::{run_vm, VM};
 
 use skw_vm_primitives::contract_runtime::{ContractCode};
 use skw_vm_primitives::fees::RuntimeFeesConfig;
 
 use skw_vm_primitives::errors::{
-    FunctionCallError, MethodResolveError, VMError, WasmTrap, HostError,
+    CompilationError, FunctionCallError, MethodResolveError, VMError, WasmTrap, HostError,
 };
 use skw_vm_host::types::{PromiseResult};
 use skw_vm_host::{MemoryLike, VMConfig, VMContext, VMLogic, VMOutcome, RuntimeExternal};
 
 use wasmi::{
-    MemoryInstance, MemoryRef, ModuleInstance,
+    MemoryInstance, MemoryRef, ModuleInstance, ModuleRef,
     memory_units::{Pages, Bytes, size_of}, TrapKind,
 };
 
@@ -112,6 +113,42 @@ fn map_invoke_err(error: wasmi::Error) -> VMError {
 }
 
 pub struct WasmiVM;
+
+impl VM for WasmiVM {
+    type Module = wasmi::Module;
+    type Memory = WasmiMemory;
+    type Instance = ModuleRef;
+
+    fn prepare(&self, code: &ContractCode, config: &VMConfig) -> Result<wasmi::Module, CompilationError> {
+        let prepared_module = prepare::prepare_contract(&code.code, config)
+            .map_err(|e| CompilationError::PrepareError(e))?;
+        wasmi::Module::from_parity_wasm_module(prepared_module)
+            .map_err(|_| CompilationError::WasmCompileError)
+    }
+
+    fn create_memory(&self, config: &VMConfig) -> WasmiMemory {
+        WasmiMemory::new(
+            config.limit_config.initial_memory_pages,
+            config.limit_config.max_memory_pages,
+        ).expect("Cannot create memory for a contract call")
+    }
+
+    fn instantiate(&self, module: &wasmi::Module, memory: &WasmiMemory) -> Result<ModuleRef, CompilationError> {
+        cache::create_instance(module, memory.0.clone())
+    }
+
+    fn check_method(&self, instance: &ModuleRef, method_name: &str) -> Result<(), VMError> {
+        check_method(instance, method_name)
+    }
+
+    fn invoke(&self, instance: &mut ModuleRef, method_name: &str, logic: &mut VMLogic) -> Result<(), VMError> {
+        instance
+            .invoke_export(method_name, &[], logic)
+            .map(|_| ())
+            .map_err(map_invoke_err)
+    }
+}
+
 impl WasmiVM {
     pub fn run(
         code: &ContractCode,
@@ -122,67 +159,6 @@ impl WasmiVM {
         fees_config: &RuntimeFeesConfig,
         promise_results: &[PromiseResult],
     ) -> (Option<VMOutcome>, Option<VMError>) {
-        let _span = tracing::debug_span!(
-            target: "vm",
-            "run_wasmi",
-            "code.len" = code.code.len(),
-            %method_name
-        )
-        .entered();
-
-        if method_name.is_empty() {
-            return (
-                None,
-                Some(VMError::FunctionCallError(FunctionCallError::MethodResolveError(
-                    MethodResolveError::MethodEmptyName,
-                ))),
-            );
-        }
-
-        let mut memory = WasmiMemory::new(
-            wasm_config.limit_config.initial_memory_pages,
-            wasm_config.limit_config.max_memory_pages,
-        ).expect("Cannot create memory for a contract call");
-        let memory_copy = memory.clone();
-
-        let mut logic = VMLogic::new(
-            ext, 
-            context, 
-            wasm_config, 
-            fees_config, 
-            promise_results, 
-            &mut memory
-        );
-
-        if logic.add_contract_compile_fee(code.code.len() as u64).is_err() {
-            return (
-                Some(logic.outcome()),
-                Some(VMError::FunctionCallError(FunctionCallError::HostError(
-                    HostError::GasExceeded
-                )))
-            )
-        }
-        
-        let module = cache::create_module_instance(&code, wasm_config, memory_copy);
-
-        let module = match module {
-            Ok(m) => m,
-            Err(e) => {
-                return (None, Some(VMError::FunctionCallError(FunctionCallError::CompilationError(e))));
-            }
-        };
-        
-        if let Err(e) = check_method(&module, method_name) {
-            return (None, Some(e));
-        }
-
-        let result = module
-            .invoke_export(&method_name, &[], &mut logic)
-            .map_err(map_invoke_err);
-        
-        (Some(logic.outcome()), match result {
-            Ok(_) => None,
-            Err(e) => Some(e)
-        })
+        run_vm(&WasmiVM, code, method_name, ext, context, wasm_config, fees_config, promise_results)
     }
 }
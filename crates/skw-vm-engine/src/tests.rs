@@ -1,4 +1,6 @@
 mod compile_errors;
+#[cfg(feature = "wasmtime_vm")]
+mod differential;
 mod rs_contract;
 mod runtime_errors;
 mod ts_contract;
@@ -32,8 +34,10 @@ fn create_context(input: Vec<u8>) -> VMContext {
         attached_deposit: 2u128,
         prepaid_gas: 10_u64.pow(14),
         random_seed: vec![0, 1, 2],
+        shard_secret: None,
         view_config: None,
         output_data_receivers: vec![],
+        trace: false,
     }
 }
 
@@ -184,13 +184,58 @@ fn test_limit_locals() {
         ),
     );
 
-    let wasm_ok = many_locals(50_000);
-    let res = make_simple_contract_call_vm(&wasm_ok, "main");
-
-    // behavior in the original near vm
+    let wasm_err = many_locals(10_001);
+    let res = make_simple_contract_call_vm(&wasm_err, "main");
     gas_and_error_match(
         res,
-        Some(47583963),
-        Some(VMError::FunctionCallError(FunctionCallError::WasmTrap(WasmTrap::Unreachable))),
+        None,
+        Some(
+            VMError::FunctionCallError(
+                FunctionCallError::CompilationError(
+                    CompilationError::PrepareError(PrepareError::TooManyLocals),
+                )
+            )
+        ),
+    );
+
+    // recursion is stopped by the stack height limit
+    let wasm_ok = many_locals(10_000);
+    let (_, err) = make_simple_contract_call_vm(&wasm_ok, "main");
+    assert_eq!(
+        err,
+        Some(VMError::FunctionCallError(FunctionCallError::WasmTrap(WasmTrap::Unreachable)))
+    );
+}
+
+#[cfg(not(feature = "wasmtime_vm"))]
+#[test]
+fn test_wasmtime_not_enabled() {
+    use skw_vm_host::mocks::mock_external::MockedExternal;
+    use skw_vm_host::{VMConfig, VMKind};
+    use skw_vm_primitives::contract_runtime::ContractCode;
+    use skw_vm_primitives::fees::RuntimeFeesConfig;
+
+    let code = ContractCode::new(near_test_contracts::rs_contract());
+    let config = VMConfig { vm_kind: VMKind::Wasmtime, ..VMConfig::test() };
+
+    assert_matches!(
+        crate::precompile(&code, &config),
+        Err(CompilationError::UnsupportedCompiler { .. })
+    );
+    let (outcome, err) = crate::run(
+        &code,
+        "log_something",
+        &mut MockedExternal::new(),
+        crate::tests::create_context(vec![]),
+        &config,
+        &RuntimeFeesConfig::test(),
+        &[],
+    );
+    assert!(outcome.is_none());
+    assert_matches!(
+        err,
+        Some(VMError::FunctionCallError(FunctionCallError::CompilationError(
+            CompilationError::UnsupportedCompiler { .. }
+        )))
     );
 }
@@ -0,0 +1,148 @@
+use skw_vm_primitives::contract_runtime::ContractCode;
+use skw_vm_primitives::fees::RuntimeFeesConfig;
+
+use skw_vm_host::mocks::mock_external::MockedExternal;
+use skw_vm_host::types::ReturnData;
+use skw_vm_host::VMConfig;
+
+use crate::runner::WasmiVM;
+use crate::vm::{run_vm, VM};
+use crate::wasmtime_runner::WasmtimeVM;
+
+use crate::tests::{create_context, make_simple_contract_call_vm};
+
+fn run_on<V: VM>(
+    vm: &V,
+    code: &ContractCode,
+    method_name: &str,
+    input: &[u8],
+    prepaid_gas: u64,
+) -> impl std::fmt::Debug + PartialEq {
+    let mut fake_external = MockedExternal::new();
+    let mut context = create_context(input.to_vec());
+    context.prepaid_gas = prepaid_gas;
+    let config = VMConfig::test();
+    let fees = RuntimeFeesConfig::test();
+
+    let (outcome, err) =
+        run_vm(vm, code, method_name, &mut fake_external, context, &config, &fees, &[]);
+    (outcome, err, fake_external.fake_trie)
+}
+
+/// Runs the call on both engines and checks that they return, burn and store the same.
+#[track_caller]
+fn assert_same(code: &[u8], method_name: &str, input: &[u8], prepaid_gas: u64) {
+    let code = ContractCode::new(code);
+    assert_eq!(
+        run_on(&WasmiVM, &code, method_name, input, prepaid_gas),
+        run_on(&WasmtimeVM::new(), &code, method_name, input, prepaid_gas),
+        "engines disagree on {}",
+        method_name
+    );
+}
+
+fn u64_input(values: &[u64]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+#[test]
+fn test_rs_contract_same_on_both_engines() {
+    let code = near_test_contracts::rs_contract();
+    let calls: &[(&str, Vec<u8>)] = &[
+        ("write_key_value", u64_input(&[10, 20])),
+        ("read_value", u64_input(&[10])),
+        ("log_something", vec![]),
+        ("run_test", vec![]),
+        ("sum_with_input", u64_input(&[1, 2, 3, 4])),
+        ("sum_n", u64_input(&[1000])),
+        ("fibonacci", vec![10]),
+        ("insert_strings", u64_input(&[0, 20])),
+        ("recurse", u64_input(&[100])),
+        ("recurse", u64_input(&[1_000_000])),
+        ("abort_with_zero", vec![]),
+        ("panic_with_message", vec![]),
+        ("panic_after_logging", vec![]),
+        ("out_of_memory", vec![]),
+        ("missing_method", vec![]),
+        ("", vec![]),
+    ];
+    for (method_name, input) in calls {
+        assert_same(code, method_name, input, 10u64.pow(14));
+    }
+    assert_same(code, "loop_forever", &[], 10u64.pow(12));
+    assert_same(code, "sum_n", &u64_input(&[1_000_000]), 10u64.pow(10));
+}
+
+#[test]
+fn test_ts_contract_same_on_both_engines() {
+    let code = near_test_contracts::ts_contract();
+    assert_same(code, "try_panic", &[], 10u64.pow(14));
+    assert_same(code, "try_storage_write", b"foo bar", 10u64.pow(14));
+}
+
+#[test]
+fn test_traps_same_on_both_engines() {
+    let modules = [
+        r#"(module (func (export "main") unreachable))"#,
+        r#"(module (func (export "main") (drop (i32.div_s (i32.const 1) (i32.const 0)))))"#,
+        r#"(module (func (export "main") (drop (i32.trunc_f32_s (f32.const nan)))))"#,
+        r#"(module (func (export "main") (drop (i64.load (i32.const 70000)))))"#,
+        r#"(module
+             (type $t (func))
+             (table 1 funcref)
+             (func (export "main") (call_indirect (type $t) (i32.const 0))))"#,
+        r#"(module (func (export "main") (param i32)))"#,
+    ];
+    for module in modules {
+        assert_same(&wat::parse_str(module).unwrap(), "main", &[], 10u64.pow(14));
+    }
+}
+
+#[test]
+fn test_nans_same_on_both_engines() {
+    // The hardware NaN of 0/0 on x86 is negative, and arithmetic on a NaN keeps its payload on
+    // wasmi, so every case returns the canonical NaN only if the prepared code canonicalizes it.
+    let cases: [(&str, Vec<u8>); 5] = [
+        (
+            "(i32.store (i32.const 0) (i32.reinterpret_f32 (f32.div (f32.const 0) (f32.const 0))))",
+            0x7fc0_0000u32.to_le_bytes().to_vec(),
+        ),
+        (
+            "(i32.store (i32.const 0)
+                (i32.reinterpret_f32 (f32.add (f32.reinterpret_i32 (i32.const 0xffc00001)) (f32.const 1))))",
+            0x7fc0_0000u32.to_le_bytes().to_vec(),
+        ),
+        (
+            "(i32.store (i32.const 0)
+                (i32.reinterpret_f32 (f32.demote_f64 (f64.reinterpret_i64 (i64.const 0xfff8000000000001)))))",
+            0x7fc0_0000u32.to_le_bytes().to_vec(),
+        ),
+        (
+            "(i64.store (i32.const 0) (i64.reinterpret_f64 (f64.sqrt (f64.const -1))))",
+            0x7ff8_0000_0000_0000u64.to_le_bytes().to_vec(),
+        ),
+        // bitwise float instructions keep the bits
+        (
+            "(i32.store (i32.const 0) (i32.reinterpret_f32 (f32.neg (f32.reinterpret_i32 (i32.const 0x7fc00001)))))",
+            0xffc0_0001u32.to_le_bytes().to_vec(),
+        ),
+    ];
+    for (body, expected) in cases {
+        let code = wat::parse_str(format!(
+            r#"(module
+                 (import "env" "value_return" (func $value_return (param i64 i64)))
+                 (memory 1)
+                 (func (export "main")
+                   {}
+                   (call $value_return (i64.const {}) (i64.const 0))))"#,
+            body,
+            expected.len()
+        ))
+        .unwrap();
+        assert_same(&code, "main", &[], 10u64.pow(14));
+
+        let (outcome, err) = make_simple_contract_call_vm(&code, "main");
+        assert_eq!(err, None);
+        assert_eq!(outcome.unwrap().return_data, ReturnData::Value(expected), "{}", body);
+    }
+}
@@ -0,0 +1,182 @@
+use skw_vm_primitives::contract_runtime::ContractCode;
+use skw_vm_primitives::errors::{
+    CompilationError, FunctionCallError, HostError, MethodResolveError, VMError,
+};
+use skw_vm_primitives::fees::RuntimeFeesConfig;
+
+use skw_vm_host::types::PromiseResult;
+use skw_vm_host::{
+    MemoryLike, RuntimeExternal, VMConfig, VMContext, VMKind, VMLogic, VMOutcome,
+};
+
+use crate::runner::WasmiVM;
+
+/// A wasm engine contracts can run on.
+///
+/// Every engine runs the code produced by `prepare::prepare_contract`, so gas metering, the
+/// stack height limit and the NaNs of float arithmetic are the same whichever engine is used.
+pub trait VM {
+    type Module;
+    /// Handle to the linear memory of a call; clones refer to the same memory.
+    type Memory: MemoryLike + Clone;
+    type Instance;
+
+    /// Validates and instruments `code` with `prepare::prepare_contract`, then compiles it.
+    fn prepare(&self, code: &ContractCode, config: &VMConfig)
+        -> Result<Self::Module, CompilationError>;
+
+    fn create_memory(&self, config: &VMConfig) -> Self::Memory;
+
+    /// Links the module with the host functions and `memory`.
+    fn instantiate(
+        &self,
+        module: &Self::Module,
+        memory: &Self::Memory,
+    ) -> Result<Self::Instance, CompilationError>;
+
+    /// Checks that `method_name` is exported and takes no arguments and returns nothing.
+    fn check_method(&self, instance: &Self::Instance, method_name: &str) -> Result<(), VMError>;
+
+    /// Calls `method_name`, dispatching host function calls to `logic`.
+    fn invoke(
+        &self,
+        instance: &mut Self::Instance,
+        method_name: &str,
+        logic: &mut VMLogic,
+    ) -> Result<(), VMError>;
+}
+
+/// Runs `method_name` of the contract on the given engine.
+#[allow(clippy::too_many_arguments)]
+pub fn run_vm<V: VM>(
+    vm: &V,
+    code: &ContractCode,
+    method_name: &str,
+    ext: &mut dyn RuntimeExternal,
+    context: VMContext,
+    wasm_config: &VMConfig,
+    fees_config: &RuntimeFeesConfig,
+    promise_results: &[PromiseResult],
+) -> (Option<VMOutcome>, Option<VMError>) {
+    let _span = tracing::debug_span!(
+        target: "vm",
+        "run_vm",
+        "code.len" = code.code.len(),
+        %method_name
+    )
+    .entered();
+
+    if method_name.is_empty() {
+        return (
+            None,
+            Some(VMError::FunctionCallError(FunctionCallError::MethodResolveError(
+                MethodResolveError::MethodEmptyName,
+            ))),
+        );
+    }
+
+    let mut memory = vm.create_memory(wasm_config);
+    let instance_memory = memory.clone();
+
+    let mut logic = VMLogic::new(
+        ext,
+        context,
+        wasm_config,
+        fees_config,
+        promise_results,
+        &mut memory
+    );
+
+    if logic.add_contract_compile_fee(code.code.len() as u64).is_err() {
+        return (
+            Some(logic.outcome()),
+            Some(VMError::FunctionCallError(FunctionCallError::HostError(
+                HostError::GasExceeded
+            )))
+        )
+    }
+
+    let instance = vm
+        .prepare(code, wasm_config)
+        .and_then(|module| vm.instantiate(&module, &instance_memory));
+    let mut instance = match instance {
+        Ok(instance) => instance,
+        Err(e) => {
+            return (None, Some(VMError::FunctionCallError(FunctionCallError::CompilationError(e))));
+        }
+    };
+
+    if let Err(e) = vm.check_method(&instance, method_name) {
+        return (None, Some(e));
+    }
+
+    let result = vm.invoke(&mut instance, method_name, &mut logic);
+
+    (Some(logic.outcome()), result.err())
+}
+
+/// Runs `method_name` of the contract on the engine selected by `wasm_config.vm_kind`.
+pub fn run(
+    code: &ContractCode,
+    method_name: &str,
+    ext: &mut dyn RuntimeExternal,
+    context: VMContext,
+    wasm_config: &VMConfig,
+    fees_config: &RuntimeFeesConfig,
+    promise_results: &[PromiseResult],
+) -> (Option<VMOutcome>, Option<VMError>) {
+    match wasm_config.vm_kind {
+        VMKind::Wasmi => run_vm(
+            &WasmiVM,
+            code,
+            method_name,
+            ext,
+            context,
+            wasm_config,
+            fees_config,
+            promise_results,
+        ),
+        #[cfg(feature = "wasmtime_vm")]
+        VMKind::Wasmtime => run_vm(
+            &crate::wasmtime_runner::WasmtimeVM::new(),
+            code,
+            method_name,
+            ext,
+            context,
+            wasm_config,
+            fees_config,
+            promise_results,
+        ),
+        #[cfg(not(feature = "wasmtime_vm"))]
+        VMKind::Wasmtime => (
+            None,
+            Some(VMError::FunctionCallError(FunctionCallError::CompilationError(
+                wasmtime_not_enabled(),
+            ))),
+        ),
+    }
+}
+
+/// Validates and compiles the contract on the engine selected by `wasm_config.vm_kind`,
+/// without running it.
+pub fn precompile(code: &ContractCode, wasm_config: &VMConfig) -> Result<(), CompilationError> {
+    match wasm_config.vm_kind {
+        VMKind::Wasmi => WasmiVM.prepare(code, wasm_config).map(|_| ()),
+        #[cfg(feature = "wasmtime_vm")]
+        VMKind::Wasmtime => {
+            crate::wasmtime_runner::WasmtimeVM::new().prepare(code, wasm_config).map(|_| ())
+        }
+        #[cfg(not(feature = "wasmtime_vm"))]
+        VMKind::Wasmtime => Err(wasmtime_not_enabled()),
+    }
+}
+
+/// `vm_kind` comes from a deserialized config, so an engine that is not built in fails the call
+/// rather than the node.
+#[cfg(not(feature = "wasmtime_vm"))]
+fn wasmtime_not_enabled() -> CompilationError {
+    CompilationError::UnsupportedCompiler {
+        msg: "the wasmtime engine is not enabled, build skw-vm-engine with the `wasmtime_vm` feature"
+            .to_string(),
+    }
+}
@@ -0,0 +1,292 @@
+use crate::prepare;
+use crate::vm::VM;
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::rc::Rc;
+
+use skw_vm_primitives::contract_runtime::ContractCode;
+use skw_vm_primitives::errors::{
+    CompilationError, FunctionCallError, HostError, MethodResolveError, VMError, WasmTrap,
+};
+use skw_vm_host::{resolve_host_function, HostFunctions, MemoryLike, VMConfig, VMLogic};
+
+use parity_wasm::elements;
+use wasmi::nan_preserving_float::{F32, F64};
+use wasmi::{Externals, RuntimeArgs, RuntimeValue, TrapKind, ValueType};
+use wasmtime::{
+    Caller, Config, Engine, Extern, FuncType, Instance, Linker, Memory, MemoryType, Module,
+    Store, Trap, TrapCode, Val, ValType,
+};
+
+/// Wasmtime can't carry a custom error through a trap, so a failing host function keeps its
+/// error in `HOST_ERROR` and exits the instance with this status.
+const HOST_ERROR_EXIT_STATUS: i32 = 239;
+
+thread_local! {
+    /// `VMLogic` of the call in progress.
+    static LOGIC: RefCell<Option<*mut c_void>> = RefCell::new(None);
+    /// Caller of the host function in progress, the memory is only reachable through it.
+    static CALLER: RefCell<Option<*mut c_void>> = RefCell::new(None);
+    static HOST_ERROR: RefCell<Option<HostError>> = RefCell::new(None);
+}
+
+fn with_logic<T>(f: impl FnOnce(&mut VMLogic) -> T) -> T {
+    let logic = LOGIC.with(|logic| logic.borrow().expect("host functions are only called by invoke"));
+    // Safety: `invoke` sets the pointer for the duration of the call and `logic` outlives it.
+    f(unsafe { &mut *(logic as *mut VMLogic) })
+}
+
+fn with_caller<T>(f: impl FnOnce(&mut Caller<()>) -> T) -> T {
+    let caller = CALLER.with(|caller| caller.borrow().expect("memory is only accessed by host functions"));
+    // Safety: the pointer is set only while the host function that owns the caller runs.
+    f(unsafe { &mut *(caller as *mut Caller<()>) })
+}
+
+#[derive(Clone)]
+pub struct WasmtimeMemory {
+    store: Rc<RefCell<Store<()>>>,
+    memory: Memory,
+}
+
+impl MemoryLike for WasmtimeMemory {
+    fn fits_memory(&self, offset: u64, len: u64) -> bool {
+        match offset.checked_add(len) {
+            None => false,
+            Some(end) => with_caller(|caller| self.memory.data_size(caller) as u64 >= end),
+        }
+    }
+
+    fn read_memory(&self, offset: u64, buffer: &mut [u8]) {
+        with_caller(|caller| self.memory.read(caller, offset as usize, buffer))
+            .expect("Memory read error");
+    }
+
+    fn read_memory_u8(&self, offset: u64) -> u8 {
+        let mut value = [0u8];
+        self.read_memory(offset, &mut value);
+        value[0]
+    }
+
+    fn write_memory(&mut self, offset: u64, buffer: &[u8]) {
+        with_caller(|caller| self.memory.write(caller, offset as usize, buffer))
+            .expect("Memory write error");
+    }
+}
+
+pub struct WasmtimeInstance {
+    store: Rc<RefCell<Store<()>>>,
+    instance: Instance,
+}
+
+fn to_val_type(value_type: ValueType) -> ValType {
+    match value_type {
+        ValueType::I32 => ValType::I32,
+        ValueType::I64 => ValType::I64,
+        ValueType::F32 => ValType::F32,
+        ValueType::F64 => ValType::F64,
+    }
+}
+
+fn to_runtime_value(value: &Val) -> RuntimeValue {
+    match value {
+        Val::I32(v) => RuntimeValue::I32(*v),
+        Val::I64(v) => RuntimeValue::I64(*v),
+        Val::F32(v) => RuntimeValue::F32(F32::from_bits(*v)),
+        Val::F64(v) => RuntimeValue::F64(F64::from_bits(*v)),
+        _ => unreachable!("host functions only take numbers"),
+    }
+}
+
+fn to_val(value: RuntimeValue) -> Val {
+    match value {
+        RuntimeValue::I32(v) => Val::I32(v),
+        RuntimeValue::I64(v) => Val::I64(v),
+        RuntimeValue::F32(v) => Val::F32(v.to_bits()),
+        RuntimeValue::F64(v) => Val::F64(v.to_bits()),
+    }
+}
+
+/// Calls the host function through the same `Externals` implementation wasmi uses, so both
+/// engines charge the same gas and fail with the same errors.
+fn call_host_function(
+    function: HostFunctions,
+    mut caller: Caller<'_, ()>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Trap> {
+    let args: Vec<RuntimeValue> = params.iter().map(to_runtime_value).collect();
+    CALLER.with(|c| *c.borrow_mut() = Some(&mut caller as *mut Caller<()> as *mut c_void));
+    let result = with_logic(|logic| logic.invoke_index(function as usize, RuntimeArgs::from(&args[..])));
+    CALLER.with(|c| *c.borrow_mut() = None);
+
+    match result {
+        Ok(value) => {
+            if let (Some(value), Some(result)) = (value, results.first_mut()) {
+                *result = to_val(value);
+            }
+            Ok(())
+        }
+        Err(trap) => match trap.kind() {
+            TrapKind::Host(err) => match err.downcast_ref::<HostError>() {
+                Some(err) => {
+                    HOST_ERROR.with(|e| *e.borrow_mut() = Some(err.clone()));
+                    Err(Trap::i32_exit(HOST_ERROR_EXIT_STATUS))
+                }
+                None => Err(Trap::new(format!("{:?}", err))),
+            },
+            kind => Err(Trap::new(format!("{:?}", kind))),
+        },
+    }
+}
+
+fn map_call_err(err: anyhow::Error) -> VMError {
+    let trap = match err.downcast::<Trap>() {
+        Ok(trap) => trap,
+        // same as wasmi, which reports any error that is not a trap this way
+        Err(_) => {
+            return VMError::FunctionCallError(FunctionCallError::WasmTrap(
+                WasmTrap::IncorrectCallIndirectSignature,
+            ))
+        }
+    };
+    if trap.i32_exit_status() == Some(HOST_ERROR_EXIT_STATUS) {
+        if let Some(err) = HOST_ERROR.with(|e| e.borrow_mut().take()) {
+            return VMError::FunctionCallError(FunctionCallError::HostError(err));
+        }
+    }
+    let wasm_trap = match trap.trap_code() {
+        Some(TrapCode::UnreachableCodeReached) => WasmTrap::Unreachable,
+        Some(TrapCode::MemoryOutOfBounds)
+        | Some(TrapCode::HeapMisaligned)
+        | Some(TrapCode::TableOutOfBounds) => WasmTrap::MemoryOutOfBounds,
+        Some(TrapCode::IndirectCallToNull) => WasmTrap::IndirectCallToNull,
+        Some(TrapCode::IntegerDivisionByZero)
+        | Some(TrapCode::IntegerOverflow)
+        | Some(TrapCode::BadConversionToInteger) => WasmTrap::IllegalArithmetic,
+        Some(TrapCode::StackOverflow) => WasmTrap::StackOverflow,
+        _ => WasmTrap::IncorrectCallIndirectSignature,
+    };
+    VMError::FunctionCallError(FunctionCallError::WasmTrap(wasm_trap))
+}
+
+pub struct WasmtimeVM {
+    engine: Engine,
+}
+
+impl WasmtimeVM {
+    pub fn new() -> Self {
+        let mut config = Config::new();
+        // the prepared code is already validated against these features, and its NaNs are
+        // canonicalized by `prepare::prepare_contract` like on wasmi
+        config
+            .wasm_reference_types(false)
+            .wasm_bulk_memory(false)
+            .wasm_multi_value(false)
+            .wasm_simd(false);
+        WasmtimeVM { engine: Engine::new(&config).expect("the wasmtime config is valid") }
+    }
+}
+
+impl Default for WasmtimeVM {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VM for WasmtimeVM {
+    type Module = Module;
+    type Memory = WasmtimeMemory;
+    type Instance = WasmtimeInstance;
+
+    fn prepare(&self, code: &ContractCode, config: &VMConfig) -> Result<Module, CompilationError> {
+        let prepared_module = prepare::prepare_contract(&code.code, config)
+            .map_err(CompilationError::PrepareError)?;
+        // wasmi refuses to instantiate such modules, wasmtime would run the start function
+        if prepared_module.start_section().is_some() {
+            return Err(CompilationError::StartFunctionError);
+        }
+        let prepared_code = elements::serialize(prepared_module)
+            .map_err(|_| CompilationError::WasmCompileError)?;
+        Module::new(&self.engine, prepared_code).map_err(|_| CompilationError::WasmCompileError)
+    }
+
+    fn create_memory(&self, config: &VMConfig) -> WasmtimeMemory {
+        let mut store = Store::new(&self.engine, ());
+        let memory = Memory::new(
+            &mut store,
+            MemoryType::new(
+                config.limit_config.initial_memory_pages,
+                Some(config.limit_config.max_memory_pages),
+            ),
+        )
+        .expect("Cannot create memory for a contract call");
+        WasmtimeMemory { store: Rc::new(RefCell::new(store)), memory }
+    }
+
+    fn instantiate(
+        &self,
+        module: &Module,
+        memory: &WasmtimeMemory,
+    ) -> Result<WasmtimeInstance, CompilationError> {
+        let mut store = memory.store.borrow_mut();
+        let mut linker = Linker::new(&self.engine);
+        linker
+            .define("env", "memory", memory.memory)
+            .map_err(|_| CompilationError::WasmCompileError)?;
+        for index in 0..HostFunctions::Unknown as usize {
+            let function = HostFunctions::from(index);
+            let (signature, _) = resolve_host_function(function.name())
+                .expect("every host function has a signature");
+            let func_type = FuncType::new(
+                signature.params().iter().cloned().map(to_val_type),
+                signature.return_type().map(to_val_type),
+            );
+            linker
+                .func_new("env", function.name(), func_type, move |caller, params, results| {
+                    call_host_function(function, caller, params, results)
+                })
+                .map_err(|_| CompilationError::WasmCompileError)?;
+        }
+        let instance = linker
+            .instantiate(&mut *store, module)
+            .map_err(|_| CompilationError::WasmCompileError)?;
+        Ok(WasmtimeInstance { store: memory.store.clone(), instance })
+    }
+
+    fn check_method(&self, instance: &WasmtimeInstance, method_name: &str) -> Result<(), VMError> {
+        let mut store = instance.store.borrow_mut();
+        match instance.instance.get_export(&mut *store, method_name) {
+            Some(Extern::Func(func)) => {
+                let ty = func.ty(&*store);
+                if ty.params().len() == 0 && ty.results().len() == 0 {
+                    Ok(())
+                } else {
+                    Err(VMError::FunctionCallError(FunctionCallError::MethodResolveError(
+                        MethodResolveError::MethodInvalidSignature,
+                    )))
+                }
+            }
+            _ => Err(VMError::FunctionCallError(FunctionCallError::MethodResolveError(
+                MethodResolveError::MethodNotFound,
+            ))),
+        }
+    }
+
+    fn invoke(
+        &self,
+        instance: &mut WasmtimeInstance,
+        method_name: &str,
+        logic: &mut VMLogic,
+    ) -> Result<(), VMError> {
+        let mut store = instance.store.borrow_mut();
+        let func = instance
+            .instance
+            .get_func(&mut *store, method_name)
+            .expect("checked by check_method");
+        LOGIC.with(|l| *l.borrow_mut() = Some(logic as *mut VMLogic as *mut c_void));
+        let result = func.call(&mut *store, &[], &mut []);
+        LOGIC.with(|l| *l.borrow_mut() = None);
+        result.map_err(map_call_err)
+    }
+}
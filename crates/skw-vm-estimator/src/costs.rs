@@ -0,0 +1,477 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use near_test_contracts::{loop_contract, many_functions_contract};
+
+use skw_vm_primitives::borsh::BorshSerialize;
+use skw_vm_primitives::crypto::{KeyType, SecretKey};
+
+use skw_vm_host::ExtCosts;
+
+use crate::testbed::{Measurement, Testbed};
+
+/// Size of the small and the large input of the host functions charged per byte.
+const SMALL_INPUT: u64 = 10;
+const LARGE_INPUT: u64 = 10 * 1024;
+
+/// Where the micro-contracts keep the inputs of the host functions in the first page of memory.
+/// Inputs that are not filled by a data segment are zeros.
+const KEY_PTR: u64 = 0;
+const VALUE_PTR: u64 = LARGE_INPUT;
+const SIGNATURE_PTR: u64 = 0;
+const PUBLIC_KEY_PTR: u64 = 64;
+const MESSAGE_PTR: u64 = 128;
+
+/// Number of functions of the small and the large contract measuring the compile cost.
+const SMALL_CONTRACT_FUNCTIONS: u32 = 100;
+const LARGE_CONTRACT_FUNCTIONS: u32 = 1000;
+
+/// Bodies of the loops that measure the wasm instruction cost. Instructions are charged at a
+/// flat rate, so the most expensive class sets `regular_op_cost`.
+const OP_CLASSES: &[(&str, &str)] = &[
+    (
+        "i64_arithmetic",
+        "(local.set $x (i64.add (i64.mul (local.get $x) (i64.const 3)) (i64.const 7)))",
+    ),
+    ("memory", "(i64.store (i32.const 8) (i64.add (i64.load (i32.const 8)) (local.get $x)))"),
+    ("call", "(local.set $x (call $id (local.get $x)))"),
+    (
+        "branch",
+        "(if (i64.eqz (local.get $x)) (then (local.set $x (i64.const 1))) (else (local.set $x (i64.const 0))))",
+    ),
+];
+
+/// Hash host functions with their base and per-unit costs. All of them take
+/// `(value_len, value_ptr, register_id)`.
+const HASHES: &[(&str, ExtCosts, ExtCosts)] = &[
+    ("sha256", ExtCosts::sha256_base, ExtCosts::sha256_byte),
+    ("keccak256", ExtCosts::keccak256_base, ExtCosts::keccak256_byte),
+    ("keccak512", ExtCosts::keccak512_base, ExtCosts::keccak512_byte),
+    ("ripemd160", ExtCosts::ripemd160_base, ExtCosts::ripemd160_block),
+    ("blake2b_256", ExtCosts::blake2b_256_base, ExtCosts::blake2b_256_byte),
+];
+
+/// Signature verification host functions with their key type, base and per-byte costs. All of
+/// them take `(signature_len, signature_ptr, message_len, message_ptr, key_len, key_ptr)`.
+const SIGNATURES: &[(&str, KeyType, ExtCosts, ExtCosts)] = &[
+    (
+        "ed25519_verify",
+        KeyType::ED25519,
+        ExtCosts::ed25519_verify_base,
+        ExtCosts::ed25519_verify_byte,
+    ),
+    (
+        "sr25519_verify",
+        KeyType::SR25519,
+        ExtCosts::sr25519_verify_base,
+        ExtCosts::sr25519_verify_byte,
+    ),
+];
+
+/// Measured costs, in nanoseconds.
+#[derive(Debug, Clone, Default)]
+pub struct Costs {
+    /// Cost of a wasm instruction, the maximum over `op_classes`.
+    pub regular_op: f64,
+    pub op_classes: BTreeMap<String, f64>,
+    /// Part of a call paid before the contract is compiled, see
+    /// `Testbed::measure_memory_allocation`.
+    pub memory_allocation: f64,
+    /// Ext costs in the order they were estimated.
+    pub ext_costs: Vec<(ExtCosts, f64)>,
+    /// Ext costs that are not measured but set equal to a measured one.
+    pub assumed: Vec<(ExtCosts, ExtCosts)>,
+}
+
+impl Costs {
+    pub fn ext_cost(&self, cost: ExtCosts) -> Option<f64> {
+        self.ext_costs.iter().find(|(c, _)| *c == cost).map(|(_, ns)| *ns)
+    }
+
+    /// Attributes the extra time `with` took over `reference` to `target`, after subtracting
+    /// the instructions and the already estimated ext costs in which the two runs differ.
+    pub fn estimate(&mut self, target: ExtCosts, with: &Measurement, reference: &Measurement) {
+        let ops = with.ops as f64 - reference.ops as f64;
+        let mut time = with.time_ns - reference.time_ns - ops * self.regular_op;
+        let mut target_count = 0.0;
+        let charged: BTreeSet<ExtCosts> =
+            with.counts.keys().chain(reference.counts.keys()).copied().collect();
+        for cost in charged {
+            let delta = with.count(cost) as f64 - reference.count(cost) as f64;
+            if delta == 0.0 {
+                continue;
+            }
+            if cost == target {
+                target_count = delta;
+                continue;
+            }
+            match self.ext_cost(cost) {
+                Some(ns) => time -= delta * ns,
+                None => panic!("{} has to be estimated before {}", cost, target),
+            }
+        }
+        assert!(target_count > 0.0, "the micro-contracts for {} are charged the same for it", target);
+        self.ext_costs.push((target, time / target_count));
+    }
+
+    /// Sets `cost` to the measured value of `like`, for costs that can't be told apart from
+    /// others by running contracts.
+    pub fn assume(&mut self, cost: ExtCosts, like: ExtCosts) {
+        let ns = self.ext_cost(like).unwrap_or_else(|| panic!("{} is not estimated", like));
+        self.ext_costs.push((cost, ns));
+        self.assumed.push((cost, like));
+    }
+}
+
+fn import(name: &str, params: usize, result: bool) -> String {
+    format!(
+        r#"(import "env" "{name}" (func ${name} (param{params}){result}))"#,
+        name = name,
+        params = " i64".repeat(params),
+        result = if result { " (result i64)" } else { "" },
+    )
+}
+
+/// Data segment that writes `bytes` to `offset` of the memory.
+fn data(offset: u64, bytes: &[u8]) -> String {
+    let escaped: String = bytes.iter().map(|byte| format!("\\{:02x}", byte)).collect();
+    format!(r#"(data (i32.const {}) "{}")"#, offset, escaped)
+}
+
+/// Runs the whole suite of micro-contracts. Every loop runs `iterations` times.
+pub fn run_suite(testbed: &Testbed, iterations: u32) -> Costs {
+    let mut costs = Costs::default();
+
+    for (class, body) in OP_CLASSES {
+        let short = testbed.measure(&loop_contract("", body, iterations));
+        let long = testbed.measure(&loop_contract("", body, 10 * iterations));
+        let ns = (long.time_ns - short.time_ns) / (long.ops as f64 - short.ops as f64);
+        costs.op_classes.insert(class.to_string(), ns);
+        costs.regular_op = costs.regular_op.max(ns);
+    }
+
+    // Every call allocates memory, compiles and instantiates the contract. The allocation is
+    // paid by the function call action, the rest by the contract compile costs.
+    costs.memory_allocation = testbed.measure_memory_allocation();
+    let small = testbed.measure(&many_functions_contract(SMALL_CONTRACT_FUNCTIONS));
+    let large = testbed.measure(&many_functions_contract(LARGE_CONTRACT_FUNCTIONS));
+    costs.estimate(ExtCosts::contract_compile_bytes, &large, &small);
+    let allocation = Measurement { time_ns: costs.memory_allocation, ..Default::default() };
+    costs.estimate(ExtCosts::contract_compile_base, &small, &allocation);
+
+    let imports = [
+        import("block_number", 0, true),
+        import("value_return", 2, false),
+        import("write_register", 3, false),
+        import("read_register", 2, false),
+    ]
+    .concat();
+    let measure = |body: &str| testbed.measure(&loop_contract(&imports, body, iterations));
+    let empty = measure("");
+
+    costs.estimate(ExtCosts::base, &measure("(drop (call $block_number))"), &empty);
+
+    let value_return = |len| format!("(call $value_return (i64.const {}) (i64.const 0))", len);
+    let small = measure(&value_return(SMALL_INPUT));
+    costs.estimate(ExtCosts::read_memory_byte, &measure(&value_return(LARGE_INPUT)), &small);
+    costs.estimate(ExtCosts::read_memory_base, &small, &empty);
+
+    let write_register =
+        |len| format!("(call $write_register (i64.const 0) (i64.const {}) (i64.const 0))", len);
+    let small = measure(&write_register(SMALL_INPUT));
+    costs.estimate(ExtCosts::write_register_byte, &measure(&write_register(LARGE_INPUT)), &small);
+    costs.estimate(ExtCosts::write_register_base, &small, &empty);
+
+    // Reading a register always writes the memory, so only the sum of both is measured.
+    costs.assume(ExtCosts::read_register_base, ExtCosts::write_register_base);
+    costs.assume(ExtCosts::read_register_byte, ExtCosts::write_register_byte);
+    let read_register = |len| {
+        format!("{} (call $read_register (i64.const 0) (i64.const 0))", write_register(len))
+    };
+    let written = small;
+    let small = measure(&read_register(SMALL_INPUT));
+    costs.estimate(ExtCosts::write_memory_byte, &measure(&read_register(LARGE_INPUT)), &small);
+    costs.estimate(ExtCosts::write_memory_base, &small, &written);
+
+    for (name, base, per_unit) in HASHES {
+        let imports = import(name, 3, false);
+        let hash = |len| {
+            let body = format!("(call ${} (i64.const {}) (i64.const 0) (i64.const 0))", name, len);
+            testbed.measure(&loop_contract(&imports, &body, iterations))
+        };
+        let empty = testbed.measure(&loop_contract(&imports, "", iterations));
+        let small = hash(SMALL_INPUT);
+        costs.estimate(*per_unit, &hash(LARGE_INPUT), &small);
+        costs.estimate(*base, &small, &empty);
+    }
+
+    estimate_storage(&mut costs, testbed, iterations);
+    estimate_crypto(&mut costs, testbed, iterations);
+
+    costs
+}
+
+/// Storage host functions on the in-memory storage of `MockedExternal`. The mock touches no trie
+/// nodes, so this measures the work of the host functions and `touching_trie_node` is left to
+/// the base config.
+fn estimate_storage(costs: &mut Costs, testbed: &Testbed, iterations: u32) {
+    let imports = [
+        import("storage_write", 5, true),
+        import("storage_read", 3, true),
+        import("storage_remove", 3, true),
+        import("storage_has_key", 2, true),
+        import("storage_iter_prefix", 2, true),
+        import("storage_iter_range", 4, true),
+        import("storage_iter_next", 3, true),
+    ]
+    .concat();
+    let measure = |body: &str| testbed.measure(&loop_contract(&imports, body, iterations));
+    let write = |key_len, value_len| {
+        format!(
+            "(drop (call $storage_write (i64.const {}) (i64.const {}) (i64.const {}) (i64.const {}) (i64.const 0)))",
+            key_len, KEY_PTR, value_len, VALUE_PTR
+        )
+    };
+
+    // A new key in every iteration, so that no value is evicted.
+    let next_key =
+        "(local.set $x (i64.add (local.get $x) (i64.const 1))) (i64.store (i32.const 0) (local.get $x))";
+    let fresh =
+        |key_len, value_len| measure(&format!("{} {}", next_key, write(key_len, value_len)));
+    let small = fresh(SMALL_INPUT, SMALL_INPUT);
+    costs.estimate(ExtCosts::storage_write_key_byte, &fresh(LARGE_INPUT, SMALL_INPUT), &small);
+    let large_value = fresh(SMALL_INPUT, LARGE_INPUT);
+    costs.estimate(ExtCosts::storage_write_value_byte, &large_value, &small);
+    costs.estimate(ExtCosts::storage_write_base, &small, &measure(next_key));
+    // The same key in every iteration evicts the value written by the previous one.
+    let evicting = measure(&write(SMALL_INPUT, LARGE_INPUT));
+    costs.estimate(ExtCosts::storage_write_evicted_byte, &evicting, &large_value);
+
+    // The other storage host functions need an entry, so it is written in every iteration.
+    let written = measure(&write(SMALL_INPUT, SMALL_INPUT));
+    let with_entry = |key_len, value_len, call: &str| {
+        measure(&format!("{} {}", write(key_len, value_len), call))
+    };
+
+    let read = |key_len| {
+        format!(
+            "(drop (call $storage_read (i64.const {}) (i64.const {}) (i64.const 0)))",
+            key_len, KEY_PTR
+        )
+    };
+    let small = with_entry(SMALL_INPUT, SMALL_INPUT, &read(SMALL_INPUT));
+    let large_key = with_entry(LARGE_INPUT, SMALL_INPUT, &read(LARGE_INPUT));
+    costs.estimate(ExtCosts::storage_read_key_byte, &large_key, &small);
+    let large_value = with_entry(SMALL_INPUT, LARGE_INPUT, &read(SMALL_INPUT));
+    costs.estimate(ExtCosts::storage_read_value_byte, &large_value, &small);
+    costs.estimate(ExtCosts::storage_read_base, &small, &written);
+
+    let has_key = |key_len| {
+        format!("(drop (call $storage_has_key (i64.const {}) (i64.const {})))", key_len, KEY_PTR)
+    };
+    let small = with_entry(SMALL_INPUT, SMALL_INPUT, &has_key(SMALL_INPUT));
+    let large_key = with_entry(LARGE_INPUT, SMALL_INPUT, &has_key(LARGE_INPUT));
+    costs.estimate(ExtCosts::storage_has_key_byte, &large_key, &small);
+    costs.estimate(ExtCosts::storage_has_key_base, &small, &written);
+
+    let remove = |key_len| {
+        format!(
+            "(drop (call $storage_remove (i64.const {}) (i64.const {}) (i64.const 0)))",
+            key_len, KEY_PTR
+        )
+    };
+    let small = with_entry(SMALL_INPUT, SMALL_INPUT, &remove(SMALL_INPUT));
+    let large_key = with_entry(LARGE_INPUT, SMALL_INPUT, &remove(LARGE_INPUT));
+    costs.estimate(ExtCosts::storage_remove_key_byte, &large_key, &small);
+    let large_value = with_entry(SMALL_INPUT, LARGE_INPUT, &remove(SMALL_INPUT));
+    costs.estimate(ExtCosts::storage_remove_ret_value_byte, &large_value, &small);
+    costs.estimate(ExtCosts::storage_remove_base, &small, &written);
+
+    let prefix = |len| {
+        format!("(drop (call $storage_iter_prefix (i64.const {}) (i64.const {})))", len, KEY_PTR)
+    };
+    let small = with_entry(SMALL_INPUT, SMALL_INPUT, &prefix(SMALL_INPUT));
+    let large = with_entry(SMALL_INPUT, SMALL_INPUT, &prefix(LARGE_INPUT));
+    costs.estimate(ExtCosts::storage_iter_create_prefix_byte, &large, &small);
+    costs.estimate(ExtCosts::storage_iter_create_prefix_base, &small, &written);
+
+    let range = |start_len, end_len| {
+        format!(
+            "(drop (call $storage_iter_range (i64.const {}) (i64.const {}) (i64.const {}) (i64.const {})))",
+            start_len, KEY_PTR, end_len, KEY_PTR
+        )
+    };
+    let small = with_entry(SMALL_INPUT, SMALL_INPUT, &range(SMALL_INPUT, SMALL_INPUT));
+    let large_start = with_entry(SMALL_INPUT, SMALL_INPUT, &range(LARGE_INPUT, SMALL_INPUT));
+    costs.estimate(ExtCosts::storage_iter_create_from_byte, &large_start, &small);
+    let large_end = with_entry(SMALL_INPUT, SMALL_INPUT, &range(SMALL_INPUT, LARGE_INPUT));
+    costs.estimate(ExtCosts::storage_iter_create_to_byte, &large_end, &small);
+    costs.estimate(ExtCosts::storage_iter_create_range_base, &small, &written);
+
+    // An empty prefix matches the entry, so `storage_iter_next` always returns it.
+    let next = "(drop (call $storage_iter_next (call $storage_iter_prefix (i64.const 0) (i64.const 0)) (i64.const 1) (i64.const 2)))";
+    let small = with_entry(SMALL_INPUT, SMALL_INPUT, next);
+    costs.estimate(
+        ExtCosts::storage_iter_next_key_byte,
+        &with_entry(LARGE_INPUT, SMALL_INPUT, next),
+        &small,
+    );
+    costs.estimate(
+        ExtCosts::storage_iter_next_value_byte,
+        &with_entry(SMALL_INPUT, LARGE_INPUT, next),
+        &small,
+    );
+    let created = with_entry(SMALL_INPUT, SMALL_INPUT, &prefix(0));
+    costs.estimate(ExtCosts::storage_iter_next_base, &small, &created);
+}
+
+/// Signature verification, key agreement and the host functions keyed by the shard secret.
+fn estimate_crypto(costs: &mut Costs, testbed: &Testbed, iterations: u32) {
+    let imports = [
+        import("ed25519_verify", 6, true),
+        import("sr25519_verify", 6, true),
+        import("x25519_shared_secret", 5, true),
+        import("random_bytes", 2, false),
+        import("seal", 3, false),
+        import("unseal", 3, true),
+    ]
+    .concat();
+    let measure = |segments: &str, body: &str| {
+        testbed.measure(&loop_contract(&format!("{}{}", imports, segments), body, iterations))
+    };
+    let empty = measure("", "");
+
+    for (name, key_type, base, per_byte) in SIGNATURES {
+        // a valid signature of a message of zeros, so that the whole verification is run
+        let verify = |len: u64| {
+            let secret_key = SecretKey::from_seed(*key_type, b"skw-vm-estimator");
+            let signature = secret_key.sign(&vec![0; len as usize]).try_to_vec().unwrap();
+            // borsh prefixes the signature with the key type
+            let segments = data(SIGNATURE_PTR, &signature[1..])
+                + &data(PUBLIC_KEY_PTR, secret_key.public_key().key_data());
+            let body = format!(
+                "(drop (call ${} (i64.const 64) (i64.const {}) (i64.const {}) (i64.const {}) (i64.const 32) (i64.const {})))",
+                name, SIGNATURE_PTR, len, MESSAGE_PTR, PUBLIC_KEY_PTR
+            );
+            measure(&segments, &body)
+        };
+        let small = verify(SMALL_INPUT);
+        costs.estimate(*per_byte, &verify(LARGE_INPUT), &small);
+        costs.estimate(*base, &small, &empty);
+    }
+
+    // The secret key is left as zeros, the public key is the base point.
+    let x25519 = format!(
+        "(drop (call $x25519_shared_secret (i64.const 32) (i64.const {}) (i64.const 32) (i64.const {}) (i64.const 0)))",
+        SIGNATURE_PTR, PUBLIC_KEY_PTR
+    );
+    let base_point = measure(&data(PUBLIC_KEY_PTR, &[9]), &x25519);
+    costs.estimate(ExtCosts::x25519_base, &base_point, &empty);
+
+    let random_bytes =
+        |len| measure("", &format!("(call $random_bytes (i64.const {}) (i64.const 0))", len));
+    let small = random_bytes(SMALL_INPUT);
+    costs.estimate(ExtCosts::random_bytes_byte, &random_bytes(LARGE_INPUT), &small);
+    costs.estimate(ExtCosts::random_bytes_base, &small, &empty);
+
+    let seal =
+        |len| format!("(call $seal (i64.const {}) (i64.const {}) (i64.const 0))", len, MESSAGE_PTR);
+    let sealed = measure("", &seal(SMALL_INPUT));
+    costs.estimate(ExtCosts::seal_byte, &measure("", &seal(LARGE_INPUT)), &sealed);
+    costs.estimate(ExtCosts::seal_base, &sealed, &empty);
+
+    // Unseals what was just sealed into register 0.
+    let unseal = |len| {
+        measure(
+            "",
+            &format!(
+                "{} (drop (call $unseal (i64.const -1) (i64.const 0) (i64.const 1)))",
+                seal(len)
+            ),
+        )
+    };
+    let small = unseal(SMALL_INPUT);
+    costs.estimate(ExtCosts::unseal_byte, &unseal(LARGE_INPUT), &small);
+    costs.estimate(ExtCosts::unseal_base, &small, &sealed);
+}
+
+/// Rounds to `digits` significant decimal digits, so that the jitter of the measurements does
+/// not show up in the proposed schedule.
+pub fn round_to_significant_digits(value: u64, digits: u32) -> u64 {
+    let len = value.to_string().len() as u32;
+    if digits == 0 || len <= digits {
+        return value;
+    }
+    let unit = 10u64.pow(len - digits);
+    value.saturating_add(unit / 2) / unit * unit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(time_ns: f64, ops: u64, counts: &[(ExtCosts, u64)]) -> Measurement {
+        Measurement { time_ns, ops, counts: counts.iter().cloned().collect() }
+    }
+
+    #[test]
+    fn test_estimate_subtracts_known_costs() {
+        let mut costs = Costs { regular_op: 2.0, ..Default::default() };
+        let empty = measurement(1000.0, 100, &[]);
+        let base = measurement(1000.0 + 10.0 * 5.0 + 20.0 * 2.0, 120, &[(ExtCosts::base, 10)]);
+        costs.estimate(ExtCosts::base, &base, &empty);
+        assert_eq!(costs.ext_cost(ExtCosts::base), Some(5.0));
+
+        let small = measurement(
+            2000.0,
+            100,
+            &[(ExtCosts::base, 10), (ExtCosts::read_memory_byte, 100)],
+        );
+        let large = measurement(
+            2000.0 + 900.0 * 3.0,
+            100,
+            &[(ExtCosts::base, 10), (ExtCosts::read_memory_byte, 1000)],
+        );
+        costs.estimate(ExtCosts::read_memory_byte, &large, &small);
+        assert_eq!(costs.ext_cost(ExtCosts::read_memory_byte), Some(3.0));
+
+        costs.assume(ExtCosts::read_register_byte, ExtCosts::read_memory_byte);
+        assert_eq!(costs.ext_cost(ExtCosts::read_register_byte), Some(3.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "read_memory_byte has to be estimated before read_memory_base")]
+    fn test_estimate_requires_order() {
+        let mut costs = Costs::default();
+        let empty = measurement(1000.0, 100, &[]);
+        let small = measurement(
+            2000.0,
+            100,
+            &[(ExtCosts::read_memory_base, 10), (ExtCosts::read_memory_byte, 100)],
+        );
+        costs.estimate(ExtCosts::read_memory_base, &small, &empty);
+    }
+
+    #[test]
+    fn test_round_to_significant_digits() {
+        assert_eq!(round_to_significant_digits(0, 2), 0);
+        assert_eq!(round_to_significant_digits(42, 2), 42);
+        assert_eq!(round_to_significant_digits(12_345_678, 2), 12_000_000);
+        assert_eq!(round_to_significant_digits(12_545_678, 2), 13_000_000);
+        assert_eq!(round_to_significant_digits(12_345_678, 0), 12_345_678);
+    }
+
+    /// Times the whole suite, run it with `cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn test_suite_runs() {
+        let costs = run_suite(&Testbed::new(0, 1), 10);
+        assert_eq!(costs.op_classes.len(), OP_CLASSES.len());
+        for (cost, _) in &costs.ext_costs {
+            assert_eq!(costs.ext_costs.iter().filter(|(c, _)| c == cost).count(), 1);
+        }
+        assert!(costs.ext_cost(ExtCosts::blake2b_256_byte).is_some());
+        assert!(costs.ext_cost(ExtCosts::storage_iter_next_base).is_some());
+        assert!(costs.ext_cost(ExtCosts::sr25519_verify_byte).is_some());
+        assert!(costs.ext_cost(ExtCosts::unseal_base).is_some());
+    }
+}
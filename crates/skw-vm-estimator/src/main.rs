@@ -0,0 +1,120 @@
+mod costs;
+mod schedule;
+mod testbed;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::Parser;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use skw_vm_primitives::fees::RuntimeFeesConfig;
+
+use skw_vm_host::VMConfig;
+
+use crate::schedule::GasConversion;
+use crate::testbed::Testbed;
+
+#[derive(clap::Parser)]
+struct CliArgs {
+    /// Number of measured runs of every micro-contract, the median is used.
+    #[clap(long, default_value = "11")]
+    iters: usize,
+    /// Number of runs of every micro-contract before measuring.
+    #[clap(long, default_value = "3")]
+    warmup: usize,
+    /// Number of times the whole suite is run. The measurements are wall-clock times, so a
+    /// schedule is only proposed if all runs agree on it after rounding.
+    #[clap(long, default_value = "2")]
+    runs: usize,
+    /// Number of loop iterations of the micro-contracts.
+    #[clap(long, default_value = "1000")]
+    loop_iterations: u32,
+    /// Gas charged per nanosecond of measured time.
+    #[clap(long, default_value = "1000000")]
+    gas_per_ns: u64,
+    /// Reserve for the variation of hardware, all measured costs are multiplied by it.
+    #[clap(long, default_value = "3")]
+    safety_multiplier: u64,
+    /// Measured costs are rounded to this many significant digits, so that repeated runs
+    /// propose the same schedule.
+    #[clap(long, default_value = "2")]
+    significant_digits: u32,
+    /// `VMConfig` to take the costs that are not measured from, `VMConfig::test()` by default.
+    #[clap(long)]
+    base_vm_config: Option<PathBuf>,
+    /// `RuntimeFeesConfig` to take the fees that are not measured from,
+    /// `RuntimeFeesConfig::test()` by default.
+    #[clap(long)]
+    base_fees_config: Option<PathBuf>,
+    /// Writes the proposed `VMConfig`, e.g. for `skw-vm-engine-cli --config-file`.
+    #[clap(long)]
+    vm_config_out: Option<PathBuf>,
+    /// Writes the proposed `RuntimeFeesConfig`.
+    #[clap(long)]
+    fees_config_out: Option<PathBuf>,
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> T {
+    let data = fs::read(path).unwrap();
+    serde_json::from_slice(&data).unwrap()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) {
+    fs::write(path, serde_json::to_string_pretty(value).unwrap()).unwrap();
+}
+
+fn main() {
+    let cli_args = CliArgs::parse();
+
+    let vm_config = cli_args.base_vm_config.as_deref().map_or_else(VMConfig::test, read_json);
+    let fees_config =
+        cli_args.base_fees_config.as_deref().map_or_else(RuntimeFeesConfig::test, read_json);
+
+    let testbed = Testbed::new(cli_args.warmup, cli_args.iters);
+    let conversion = GasConversion {
+        gas_per_ns: cli_args.gas_per_ns,
+        safety_multiplier: cli_args.safety_multiplier,
+        significant_digits: cli_args.significant_digits,
+    };
+
+    let mut schedule = None;
+    for run in 0..cli_args.runs.max(1) {
+        let costs = costs::run_suite(&testbed, cli_args.loop_iterations);
+
+        eprintln!("run {}", run + 1);
+        eprintln!("{:<32} {:>14}", "cost", "ns");
+        eprintln!("{:<32} {:>14.2}", "regular_op", costs.regular_op);
+        eprintln!("{:<32} {:>14.2}", "memory_allocation", costs.memory_allocation);
+        for (cost, ns) in &costs.ext_costs {
+            eprintln!("{:<32} {:>14.2}", cost, ns);
+        }
+
+        let proposed =
+            schedule::propose(&costs, &conversion, vm_config.clone(), fees_config.clone());
+        if let Some(first) = &schedule {
+            let differing = proposed.differing_costs(first);
+            if !differing.is_empty() {
+                eprintln!("runs 1 and {} propose different costs:", run + 1);
+                for path in differing {
+                    eprintln!("  {}", path);
+                }
+                eprintln!("rerun on an idle machine or with fewer --significant-digits");
+                process::exit(1);
+            }
+        } else {
+            schedule = Some(proposed);
+        }
+    }
+    let schedule = schedule.expect("the suite is run at least once");
+
+    if let Some(path) = &cli_args.vm_config_out {
+        write_json(path, &schedule.vm_config);
+    }
+    if let Some(path) = &cli_args.fees_config_out {
+        write_json(path, &schedule.runtime_fees_config);
+    }
+    println!("{}", serde_json::to_string_pretty(&schedule).unwrap());
+}
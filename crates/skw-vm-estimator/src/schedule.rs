@@ -0,0 +1,171 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use skw_vm_primitives::contract_runtime::Gas;
+use skw_vm_primitives::fees::RuntimeFeesConfig;
+
+use skw_vm_host::{ExtCosts, VMConfig};
+
+use crate::costs::{round_to_significant_digits, Costs};
+
+/// How measured time is turned into gas.
+pub struct GasConversion {
+    pub gas_per_ns: u64,
+    pub safety_multiplier: u64,
+    pub significant_digits: u32,
+}
+
+impl GasConversion {
+    pub fn gas(&self, ns: f64) -> Gas {
+        // noise can make a cheap cost come out slightly negative
+        let gas = ns.max(0.0) * self.gas_per_ns as f64 * self.safety_multiplier as f64;
+        round_to_significant_digits(gas.round() as u64, self.significant_digits)
+    }
+}
+
+/// Proposed gas schedule with the measurements it is computed from.
+#[derive(Serialize)]
+pub struct Schedule {
+    pub vm_config: VMConfig,
+    pub runtime_fees_config: RuntimeFeesConfig,
+    pub measurements: Value,
+}
+
+impl Schedule {
+    /// Paths of the costs in which the two schedules differ, e.g. because the timing noise of
+    /// the runs they were measured in exceeds the rounding.
+    pub fn differing_costs(&self, other: &Schedule) -> Vec<String> {
+        let mut paths = Vec::new();
+        collect_differences(
+            "vm_config".to_string(),
+            &serde_json::to_value(&self.vm_config).unwrap(),
+            &serde_json::to_value(&other.vm_config).unwrap(),
+            &mut paths,
+        );
+        collect_differences(
+            "runtime_fees_config".to_string(),
+            &serde_json::to_value(&self.runtime_fees_config).unwrap(),
+            &serde_json::to_value(&other.runtime_fees_config).unwrap(),
+            &mut paths,
+        );
+        paths
+    }
+}
+
+fn collect_differences(path: String, this: &Value, other: &Value, paths: &mut Vec<String>) {
+    match (this, other) {
+        (Value::Object(this), Value::Object(other)) => {
+            for (key, value) in this {
+                let other = other.get(key).unwrap_or(&Value::Null);
+                collect_differences(format!("{}.{}", path, key), value, other, paths);
+            }
+        }
+        _ if this != other => paths.push(path),
+        _ => {}
+    }
+}
+
+/// Replaces the measured costs of the base configs. Costs that are not measured, e.g.
+/// `touching_trie_node` which depends on the trie rather than the VM, are kept as they are.
+pub fn propose(
+    costs: &Costs,
+    conversion: &GasConversion,
+    mut vm_config: VMConfig,
+    mut fees_config: RuntimeFeesConfig,
+) -> Schedule {
+    vm_config.regular_op_cost =
+        u32::try_from(conversion.gas(costs.regular_op)).unwrap_or(u32::MAX);
+
+    let mut ext_costs = serde_json::to_value(&vm_config.ext_costs).unwrap();
+    let mut measured = Map::new();
+    for (cost, ns) in &costs.ext_costs {
+        let field = ext_costs
+            .get_mut(cost.to_string())
+            .unwrap_or_else(|| panic!("{} is not a field of ExtCostsConfig", cost));
+        *field = conversion.gas(*ns).into();
+        measured.insert(cost.to_string(), (*ns).into());
+    }
+    vm_config.ext_costs = serde_json::from_value(ext_costs).unwrap();
+
+    // The execution fee of a call also pays for the work outside the VM, so the measured memory
+    // allocation can only raise it.
+    let execution = &mut fees_config.action_creation_config.function_call_cost.execution;
+    *execution = (*execution).max(conversion.gas(costs.memory_allocation));
+
+    let unmeasured: Vec<_> = (0..ExtCosts::count())
+        .map(ExtCosts::name_of)
+        .filter(|name| !measured.contains_key(*name))
+        .collect();
+    let assumed: Map<_, _> = costs
+        .assumed
+        .iter()
+        .map(|(cost, like)| (cost.to_string(), like.to_string().into()))
+        .collect();
+    let measurements = json!({
+        "regular_op_ns": costs.regular_op,
+        "op_classes_ns": costs.op_classes,
+        "memory_allocation_ns": costs.memory_allocation,
+        "ext_costs_ns": measured,
+        "assumed": assumed,
+        "unmeasured": unmeasured,
+    });
+
+    Schedule { vm_config, runtime_fees_config: fees_config, measurements }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_propose_keeps_unmeasured_costs() {
+        let costs = Costs {
+            regular_op: 12.3,
+            memory_allocation: 1000.0,
+            ext_costs: vec![(ExtCosts::base, 45.6), (ExtCosts::sha256_byte, -0.1)],
+            ..Default::default()
+        };
+        let conversion =
+            GasConversion { gas_per_ns: 1_000_000, safety_multiplier: 3, significant_digits: 2 };
+        let base = VMConfig::test();
+        let schedule = propose(&costs, &conversion, base.clone(), RuntimeFeesConfig::test());
+
+        assert_eq!(schedule.vm_config.regular_op_cost, 37_000_000);
+        assert_eq!(schedule.vm_config.ext_costs.base, 140_000_000);
+        assert_eq!(schedule.vm_config.ext_costs.sha256_byte, 0);
+        assert_eq!(schedule.vm_config.ext_costs.sha256_base, base.ext_costs.sha256_base);
+        // the base fee is higher than the measured allocation
+        assert_eq!(
+            schedule.runtime_fees_config.action_creation_config.function_call_cost.execution,
+            RuntimeFeesConfig::test().action_creation_config.function_call_cost.execution
+        );
+        assert_eq!(schedule.measurements["unmeasured"].as_array().unwrap().len(), ExtCosts::count() - 2);
+
+        let free = propose(&costs, &conversion, base, RuntimeFeesConfig::free());
+        assert_eq!(
+            free.runtime_fees_config.action_creation_config.function_call_cost.execution,
+            3_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_differing_costs() {
+        let conversion =
+            GasConversion { gas_per_ns: 1_000_000, safety_multiplier: 3, significant_digits: 2 };
+        let schedule = |base_ns| {
+            let costs = Costs {
+                regular_op: 12.3,
+                ext_costs: vec![(ExtCosts::base, base_ns)],
+                ..Default::default()
+            };
+            propose(&costs, &conversion, VMConfig::test(), RuntimeFeesConfig::test())
+        };
+
+        // within the rounding
+        assert!(schedule(45.6).differing_costs(&schedule(45.4)).is_empty());
+        assert_eq!(
+            schedule(45.6).differing_costs(&schedule(52.0)),
+            vec!["vm_config.ext_costs.base"]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use skw_vm_primitives::contract_runtime::ContractCode;
+use skw_vm_primitives::fees::RuntimeFeesConfig;
+
+use skw_vm_engine::{with_ext_cost_counter, WasmiMemory, WasmiVM};
+use skw_vm_host::mocks::mock_external::MockedExternal;
+use skw_vm_host::types::AccountId;
+use skw_vm_host::{ExtCosts, VMConfig, VMContext, VMOutcome};
+
+/// What one micro-contract costs to run.
+#[derive(Debug, Clone, Default)]
+pub struct Measurement {
+    /// Median wall time of a call, in nanoseconds.
+    pub time_ns: f64,
+    /// Number of wasm instructions charged by the injected gas metering.
+    pub ops: u64,
+    /// How many units of each ext cost the call was charged.
+    pub counts: BTreeMap<ExtCosts, u64>,
+}
+
+impl Measurement {
+    pub fn count(&self, cost: ExtCosts) -> u64 {
+        self.counts.get(&cost).copied().unwrap_or_default()
+    }
+}
+
+/// Runs micro-contracts through `WasmiVM::run` with a config in which every ext cost is free
+/// and every wasm instruction costs exactly one gas, so the burnt gas is the instruction count.
+///
+/// Instruction and ext cost counts are exact, but the time is wall-clock time, which depends on
+/// the machine and its load. The median of several runs only reduces the noise, so a schedule
+/// is only proposed if repeated runs of the suite agree on it, see `Schedule::differing_costs`.
+pub struct Testbed {
+    vm_config: VMConfig,
+    fees_config: RuntimeFeesConfig,
+    warmup: usize,
+    iters: usize,
+}
+
+impl Testbed {
+    pub fn new(warmup: usize, iters: usize) -> Self {
+        assert!(iters > 0, "at least one measured run is needed");
+        Self {
+            vm_config: VMConfig { regular_op_cost: 1, ..VMConfig::free() },
+            fees_config: RuntimeFeesConfig::free(),
+            warmup,
+            iters,
+        }
+    }
+
+    pub fn measure(&self, code: &[u8]) -> Measurement {
+        let code = ContractCode::new(code);
+        for _ in 0..self.warmup {
+            self.run(&code, &mut MockedExternal::new());
+        }
+
+        let mut times = Vec::with_capacity(self.iters);
+        let mut res = Measurement::default();
+        for _ in 0..self.iters {
+            let mut ext = MockedExternal::new();
+            with_ext_cost_counter(|cc| cc.clear());
+            let start = Instant::now();
+            let outcome = self.run(&code, &mut ext);
+            times.push(start.elapsed().as_nanos() as f64);
+
+            res.ops = outcome.burnt_gas;
+            with_ext_cost_counter(|cc| res.counts = cc.drain().collect());
+        }
+        res.time_ns = median(times);
+        res
+    }
+
+    /// Time it takes to allocate the linear memory of a contract, which is paid on every call
+    /// before the contract is compiled.
+    pub fn measure_memory_allocation(&self) -> f64 {
+        let limits = &self.vm_config.limit_config;
+        let mut times = Vec::with_capacity(self.iters);
+        for i in 0..self.warmup + self.iters {
+            let start = Instant::now();
+            let memory = WasmiMemory::new(limits.initial_memory_pages, limits.max_memory_pages)
+                .expect("memory of the test config can be allocated");
+            let elapsed = start.elapsed().as_nanos() as f64;
+            drop(memory);
+            if i >= self.warmup {
+                times.push(elapsed);
+            }
+        }
+        median(times)
+    }
+
+    fn run(&self, code: &ContractCode, ext: &mut MockedExternal) -> VMOutcome {
+        let (outcome, err) = WasmiVM::run(
+            code,
+            "main",
+            ext,
+            create_context(),
+            &self.vm_config,
+            &self.fees_config,
+            &[],
+        );
+        if let Some(err) = err {
+            panic!("micro-contract failed: {:?}", err);
+        }
+        outcome.expect("a successful call has an outcome")
+    }
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).expect("measured times are not NaN"));
+    values[values.len() / 2]
+}
+
+fn create_context() -> VMContext {
+    VMContext {
+        current_account_id: AccountId::testn(1),
+        signer_account_id: AccountId::testn(2),
+        predecessor_account_id: AccountId::testn(3),
+        input: vec![],
+        block_number: 1,
+        block_timestamp: 1586796191203000000,
+        account_balance: 10u128.pow(25),
+        storage_usage: 100,
+        attached_deposit: 0,
+        prepaid_gas: 10u64.pow(18),
+        random_seed: vec![0, 1, 2],
+        // needed by `random_bytes`, `seal` and `unseal`
+        shard_secret: Some([7; 32]),
+        view_config: None,
+        output_data_receivers: vec![],
+        trace: false,
+    }
+}
@@ -1,4 +1,6 @@
-use skw_vm_primitives::contract_runtime::{AccountId, Balance, BlockNumber, Gas, StorageUsage};
+use skw_vm_primitives::contract_runtime::{
+    AccountId, Balance, BlockNumber, CryptoHash, Gas, StorageUsage,
+};
 use skw_vm_primitives::config::ViewConfig;
 
 use serde::{Deserialize, Serialize};
@@ -41,6 +43,11 @@ pub struct VMContext {
     #[serde(with = "crate::serde_with::bytes_as_base58")]
     /// Initial seed for randomness
     pub random_seed: Vec<u8>,
+    /// Secret of the shard the contract is executed on. It is never handed to the contract, only
+    /// used to derive the confidential randomness stream and the contract sealing key.
+    /// `None` when the runner has no shard secret, e.g. for view calls.
+    #[serde(skip)]
+    pub shard_secret: Option<CryptoHash>,
     /// If Some, it means that execution is made in a view mode and defines its configuration.
     /// View mode means that only read-only operations are allowed.
     /// See <https://nomicon.io/Proposals/0018-view-change-method.html> for more details.
@@ -48,6 +55,10 @@ pub struct VMContext {
     /// How many `DataReceipt`'s should receive this execution result. This should be empty if
     /// this function call is a part of a batch and it is not the last action.
     pub output_data_receivers: Vec<AccountId>,
+    /// Records an `ExecutionTrace` of the host calls, storage accesses and promises of the call
+    /// into the outcome. Ignored when `shard_secret` is set.
+    #[serde(default)]
+    pub trace: bool,
 }
 
 impl VMContext {
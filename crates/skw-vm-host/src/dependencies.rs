@@ -1,4 +1,4 @@
-use crate::types::{ReceiptIndex};
+use crate::types::{IteratorIndex, ReceiptIndex};
 use skw_vm_primitives::contract_runtime::{AccountId, Balance, Gas};
 use skw_vm_primitives::errors::VMLogicError;
 /// An abstraction over the memory of the smart contract.
@@ -157,6 +157,67 @@ pub trait RuntimeExternal {
     /// ```
     fn storage_has_key(&mut self, key: &[u8]) -> Result<bool>;
 
+    /// Creates an iterator over the keys of the storage trie associated with the current account
+    /// that start with `prefix`, in lexicographic order. Returns the index of the iterator.
+    ///
+    /// `VMLogic` drops all existing iterators on any write or removal, so an iterator never
+    /// observes a change made after its creation.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - a prefix of all keys returned by the iterator
+    ///
+    /// # Example
+    /// ```
+    /// # use skw_vm_host::mocks::mock_external::MockedExternal;
+    /// # use skw_vm_host::RuntimeExternal;
+    ///
+    /// # let mut external = MockedExternal::new();
+    /// external.storage_set(b"key1", b"value1").unwrap();
+    /// external.storage_set(b"other", b"value2").unwrap();
+    /// let iterator_index = external.storage_iter(b"key").unwrap();
+    /// assert_eq!(external.storage_iter_next(iterator_index).unwrap().map(|(key, _)| key), Some(b"key1".to_vec()));
+    /// assert!(external.storage_iter_next(iterator_index).unwrap().is_none());
+    /// ```
+    fn storage_iter(&mut self, prefix: &[u8]) -> Result<IteratorIndex>;
+
+    /// Creates an iterator over the keys `start <= key < end` of the storage trie associated with
+    /// the current account, in lexicographic order. Returns the index of the iterator.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - the first key of the range, inclusive
+    /// * `end` - the last key of the range, exclusive
+    ///
+    /// # Example
+    /// ```
+    /// # use skw_vm_host::mocks::mock_external::MockedExternal;
+    /// # use skw_vm_host::RuntimeExternal;
+    ///
+    /// # let mut external = MockedExternal::new();
+    /// external.storage_set(b"a", b"value1").unwrap();
+    /// external.storage_set(b"b", b"value2").unwrap();
+    /// external.storage_set(b"c", b"value3").unwrap();
+    /// let iterator_index = external.storage_iter_range(b"b", b"c").unwrap();
+    /// assert_eq!(external.storage_iter_next(iterator_index).unwrap().map(|(key, _)| key), Some(b"b".to_vec()));
+    /// assert!(external.storage_iter_next(iterator_index).unwrap().is_none());
+    /// ```
+    fn storage_iter_range(&mut self, start: &[u8], end: &[u8]) -> Result<IteratorIndex>;
+
+    /// Advances the iterator, returning the next key and a pointer to its value, or `None` once
+    /// the iterator is exhausted. An exhausted iterator is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HostError::InvalidIteratorIndex`] if the iterator does not exist.
+    fn storage_iter_next<'a>(
+        &'a mut self,
+        iterator_idx: IteratorIndex,
+    ) -> Result<Option<(Vec<u8>, Box<dyn ValuePtr + 'a>)>>;
+
+    /// Drops the iterator. Dropping an unknown iterator is a no-op.
+    fn storage_iter_drop(&mut self, iterator_idx: IteratorIndex) -> Result<()>;
+
     /// Create a receipt which will be executed after all the receipts identified by
     /// `receipt_indices` are complete.
     ///
@@ -338,6 +399,13 @@ pub trait RuntimeExternal {
         beneficiary_id: AccountId,
     ) -> Result<()>;
 
+    /// Returns the number of bytes the storage trie spends on the key and the value of a record
+    /// written with `storage_set`. It is larger than the plain lengths when the runtime encrypts
+    /// or blinds the contract storage, and storage usage is charged for these bytes.
+    fn storage_record_len(&self, key_len: u64, value_len: u64) -> u64 {
+        key_len + value_len
+    }
+
     /// Returns amount of touched trie nodes by storage operations
     fn get_touched_nodes_count(&self) -> u64;
 
@@ -6,7 +6,7 @@ use wasmi::{
   use crate::{VMLogic};
   use skw_vm_primitives::errors::{VMLogicError, HostError};
   
-  #[derive(PartialEq, Eq)]
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
   pub enum HostFunctions {
 	ReadRegister = 0,
 	RegisterLen = 1,
@@ -52,6 +52,16 @@ use wasmi::{
 	StorageRemove = 41,
 	StorageHasKey = 42,
 	Gas = 43,
+	StorageIterPrefix = 44,
+	StorageIterRange = 45,
+	StorageIterNext = 46,
+	Ed25519Verify = 47,
+	Sr25519Verify = 48,
+	Blake2b256 = 49,
+	X25519SharedSecret = 50,
+	RandomBytes = 51,
+	Seal = 52,
+	Unseal = 53,
 	Unknown,
   }
   
@@ -102,11 +112,84 @@ use wasmi::{
 		x if x == HostFunctions::StorageRemove as usize => HostFunctions::StorageRemove,
 		x if x == HostFunctions::StorageHasKey as usize => HostFunctions::StorageHasKey,
 		x if x == HostFunctions::Gas as usize => HostFunctions::Gas,
+		x if x == HostFunctions::StorageIterPrefix as usize => HostFunctions::StorageIterPrefix,
+		x if x == HostFunctions::StorageIterRange as usize => HostFunctions::StorageIterRange,
+		x if x == HostFunctions::StorageIterNext as usize => HostFunctions::StorageIterNext,
+		x if x == HostFunctions::Ed25519Verify as usize => HostFunctions::Ed25519Verify,
+		x if x == HostFunctions::Sr25519Verify as usize => HostFunctions::Sr25519Verify,
+		x if x == HostFunctions::Blake2b256 as usize => HostFunctions::Blake2b256,
+		x if x == HostFunctions::X25519SharedSecret as usize => HostFunctions::X25519SharedSecret,
+		x if x == HostFunctions::RandomBytes as usize => HostFunctions::RandomBytes,
+		x if x == HostFunctions::Seal as usize => HostFunctions::Seal,
+		x if x == HostFunctions::Unseal as usize => HostFunctions::Unseal,
 		  _ => HostFunctions::Unknown,
 	  }
 	}
   }
   
+  impl HostFunctions {
+	/// Name the host function is imported by.
+	pub fn name(&self) -> &'static str {
+	  match self {
+		  HostFunctions::ReadRegister => "read_register",
+		  HostFunctions::RegisterLen => "register_len",
+		  HostFunctions::WriteRegister => "write_register",
+		  HostFunctions::CurrentAccountId => "current_account_id",
+		  HostFunctions::SignerAccountId => "signer_account_id",
+		  HostFunctions::PredecessorAccountId => "predecessor_account_id",
+		  HostFunctions::Input => "input",
+		  HostFunctions::BlockNumber => "block_number",
+		  HostFunctions::BlockTimestamp => "block_timestamp",
+		  HostFunctions::StorageUsage => "storage_usage",
+		  HostFunctions::AccountBalance => "account_balance",
+		  HostFunctions::AttachedDeposit => "attached_deposit",
+		  HostFunctions::PrepaidGas => "prepaid_gas",
+		  HostFunctions::UsedGas => "used_gas",
+		  HostFunctions::RandomSeed => "random_seed",
+		  HostFunctions::Sha256 => "sha256",
+		  HostFunctions::Keccak256 => "keccak256",
+		  HostFunctions::Keccak512 => "keccak512",
+		  HostFunctions::Ripemd160 => "ripemd160",
+		  HostFunctions::Ecrecover => "ecrecover",
+		  HostFunctions::ValueReturn => "value_return",
+		  HostFunctions::Panic => "panic",
+		  HostFunctions::PanicUtf8 => "panic_utf8",
+		  HostFunctions::LogUtf8 => "log_utf8",
+		  HostFunctions::LogUtf16 => "log_utf16",
+		  HostFunctions::Abort => "abort",
+		  HostFunctions::PromiseCreate => "promise_create",
+		  HostFunctions::PromiseThen => "promise_then",
+		  HostFunctions::PromiseAnd => "promise_and",
+		  HostFunctions::PromiseBatchCreate => "promise_batch_create",
+		  HostFunctions::PromiseBatchThen => "promise_batch_then",
+		  HostFunctions::PromiseBatchActionCreateAccount => "promise_batch_action_create_account",
+		  HostFunctions::PromiseBatchActionDeployContract => "promise_batch_action_deploy_contract",
+		  HostFunctions::PromiseBatchActionFunctionCall => "promise_batch_action_function_call",
+		  HostFunctions::PromiseBatchActionTransfer => "promise_batch_action_transfer",
+		  HostFunctions::PromiseBatchActionDeleteAccount => "promise_batch_action_delete_account",
+		  HostFunctions::PromiseResultsCount => "promise_results_count",
+		  HostFunctions::PromiseResult => "promise_result",
+		  HostFunctions::PromiseReturn => "promise_return",
+		  HostFunctions::StorageWrite => "storage_write",
+		  HostFunctions::StorageRead => "storage_read",
+		  HostFunctions::StorageRemove => "storage_remove",
+		  HostFunctions::StorageHasKey => "storage_has_key",
+		  HostFunctions::Gas => "gas",
+		  HostFunctions::StorageIterPrefix => "storage_iter_prefix",
+		  HostFunctions::StorageIterRange => "storage_iter_range",
+		  HostFunctions::StorageIterNext => "storage_iter_next",
+		  HostFunctions::Ed25519Verify => "ed25519_verify",
+		  HostFunctions::Sr25519Verify => "sr25519_verify",
+		  HostFunctions::Blake2b256 => "blake2b_256",
+		  HostFunctions::X25519SharedSecret => "x25519_shared_secret",
+		  HostFunctions::RandomBytes => "random_bytes",
+		  HostFunctions::Seal => "seal",
+		  HostFunctions::Unseal => "unseal",
+		  HostFunctions::Unknown => "unknown",
+	  }
+	}
+  }
+  
   impl Into<usize> for HostFunctions {
 	fn into(self) -> usize {
 	  self as usize
@@ -121,23 +204,50 @@ use wasmi::{
 	  }
   }
   
+  fn trace_args(args: &RuntimeArgs) -> Vec<u64> {
+	  args.as_ref().iter()
+		  .map(|arg| match arg {
+			  RuntimeValue::I32(v) => *v as u32 as u64,
+			  RuntimeValue::I64(v) => *v as u64,
+			  RuntimeValue::F32(v) => v.to_bits() as u64,
+			  RuntimeValue::F64(v) => v.to_bits(),
+		  })
+		  .collect()
+  }
+  
   impl<'a> Externals for VMLogic<'a> {
 	fn invoke_index(
 	  &mut self,
 	  index: usize,
 	  args: RuntimeArgs,
 	) -> Result<Option<RuntimeValue>, Trap> {
-	  match HostFunctions::from(index) {
+	  let function = HostFunctions::from(index);
+	  // `gas` is injected by the instrumentation to charge for wasm instructions and is not traced
+	  if function == HostFunctions::Gas || !self.is_tracing() {
+		  return invoke_host_function(self, function, args);
+	  }
+	  self.trace_host_call(function.name(), trace_args(&args), |logic| {
+		  invoke_host_function(logic, function, args)
+	  })
+	}
+  }
+  
+  fn invoke_host_function(
+	logic: &mut VMLogic,
+	function: HostFunctions,
+	args: RuntimeArgs,
+  ) -> Result<Option<RuntimeValue>, Trap> {
+	  match function {
 		  HostFunctions::ReadRegister => {
 			  let register_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.read_register(register_id, ptr)
+			  logic.read_register(register_id, ptr)
 				  .map(|_| None)
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::RegisterLen => {
 			  let register_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.register_len(register_id)
+			  logic.register_len(register_id)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -145,74 +255,74 @@ use wasmi::{
 			  let register_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let data_len: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let data_ptr: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.write_register(register_id, data_len, data_ptr)
+			  logic.write_register(register_id, data_len, data_ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::CurrentAccountId => {
 			  let register_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.current_account_id(register_id)
+			  logic.current_account_id(register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::SignerAccountId => {
 			  let register_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.signer_account_id(register_id)
+			  logic.signer_account_id(register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PredecessorAccountId => {
 			  let register_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.predecessor_account_id(register_id)
+			  logic.predecessor_account_id(register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::Input => {
 			  let register_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.input(register_id)
+			  logic.input(register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::BlockNumber => {
-			  self.block_number()
+			  logic.block_number()
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::BlockTimestamp => {
-			  self.block_timestamp()
+			  logic.block_timestamp()
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::StorageUsage => {
-			  self.storage_usage()
+			  logic.storage_usage()
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::AccountBalance => {
 			  let balance_ptr: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.account_balance(balance_ptr)
+			  logic.account_balance(balance_ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::AttachedDeposit => {
 			  let balance_ptr: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.attached_deposit(balance_ptr)
+			  logic.attached_deposit(balance_ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PrepaidGas => {
-			  self.prepaid_gas()
+			  logic.prepaid_gas()
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::UsedGas => {
-			  self.used_gas()
+			  logic.used_gas()
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::RandomSeed => {
 			  let register_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.random_seed(register_id)
+			  logic.random_seed(register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -220,7 +330,7 @@ use wasmi::{
 			  let value_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let value_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.sha256(value_len, value_ptr, register_id)
+			  logic.sha256(value_len, value_ptr, register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -228,7 +338,7 @@ use wasmi::{
 			  let value_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let value_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.keccak256(value_len, value_ptr, register_id)
+			  logic.keccak256(value_len, value_ptr, register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -236,7 +346,7 @@ use wasmi::{
 			  let value_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let value_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.keccak512(value_len, value_ptr, register_id)
+			  logic.keccak512(value_len, value_ptr, register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -244,7 +354,7 @@ use wasmi::{
 			  let value_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let value_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.ripemd160(value_len, value_ptr, register_id)
+			  logic.ripemd160(value_len, value_ptr, register_id)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -256,40 +366,40 @@ use wasmi::{
 			  let v: u64 = args.nth_checked(4).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let malleability_flag: u64 = args.nth_checked(5).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(6).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.ecrecover(hash_len, hash_ptr, sign_len, sig_ptr, v, malleability_flag, register_id)
+			  logic.ecrecover(hash_len, hash_ptr, sign_len, sig_ptr, v, malleability_flag, register_id)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::ValueReturn => {
 			  let value_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let value_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.value_return(value_len, value_ptr)
+			  logic.value_return(value_len, value_ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::Panic => {
-			  self.panic()
+			  logic.panic()
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PanicUtf8 => {
 			  let len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.panic_utf8(len, ptr)
+			  logic.panic_utf8(len, ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::LogUtf8 => {
 			  let len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.log_utf8(len, ptr)
+			  logic.log_utf8(len, ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::LogUtf16 => {
 			  let len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.log_utf16(len, ptr)
+			  logic.log_utf16(len, ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -298,7 +408,7 @@ use wasmi::{
 			  let filename_ptr: u32 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let line: u32 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let col: u32 = args.nth_checked(3).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.abort(msg_ptr, filename_ptr, line, col)
+			  logic.abort(msg_ptr, filename_ptr, line, col)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -311,7 +421,7 @@ use wasmi::{
 			  let arguments_ptr: u64 = args.nth_checked(5).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let amount_ptr: u64 = args.nth_checked(6).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let gas: u64 = args.nth_checked(7).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_create(account_id_len, account_id_ptr, method_name_len, method_name_ptr, arguments_len, arguments_ptr, amount_ptr, gas)
+			  logic.promise_create(account_id_len, account_id_ptr, method_name_len, method_name_ptr, arguments_len, arguments_ptr, amount_ptr, gas)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -325,21 +435,21 @@ use wasmi::{
 			  let arguments_ptr: u64 = args.nth_checked(6).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let amount_ptr: u64 = args.nth_checked(7).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let gas: u64 = args.nth_checked(8).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_then(promise_index, account_id_len, account_id_ptr, method_name_len, method_name_ptr, arguments_len, arguments_ptr, amount_ptr, gas)
+			  logic.promise_then(promise_index, account_id_len, account_id_ptr, method_name_len, method_name_ptr, arguments_len, arguments_ptr, amount_ptr, gas)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PromiseAnd => {
 			  let promise_idx_ptr: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let promise_idx_count: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_and(promise_idx_ptr, promise_idx_count)
+			  logic.promise_and(promise_idx_ptr, promise_idx_count)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PromiseBatchCreate => {
 			  let account_id_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let account_id_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_batch_create(account_id_len, account_id_ptr)
+			  logic.promise_batch_create(account_id_len, account_id_ptr)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -347,13 +457,13 @@ use wasmi::{
 			  let promise_index: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let account_id_len: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let account_id_ptr: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_batch_then(promise_index, account_id_len, account_id_ptr)
+			  logic.promise_batch_then(promise_index, account_id_len, account_id_ptr)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PromiseBatchActionCreateAccount => {
 			  let promise_index: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_batch_action_create_account(promise_index)
+			  logic.promise_batch_action_create_account(promise_index)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -361,7 +471,7 @@ use wasmi::{
 			  let promise_index: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let code_len: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let code_ptr: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_batch_action_deploy_contract(promise_index, code_len, code_ptr)
+			  logic.promise_batch_action_deploy_contract(promise_index, code_len, code_ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -373,14 +483,14 @@ use wasmi::{
 			  let arguments_ptr: u64 = args.nth_checked(4).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let amount_ptr: u64 = args.nth_checked(5).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let gas: u64 = args.nth_checked(6).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_batch_action_function_call(promise_index, method_name_len, method_name_ptr, arguments_len, arguments_ptr, amount_ptr, gas)
+			  logic.promise_batch_action_function_call(promise_index, method_name_len, method_name_ptr, arguments_len, arguments_ptr, amount_ptr, gas)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PromiseBatchActionTransfer => {
 			  let promise_index: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let amount_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_batch_action_transfer(promise_index, amount_ptr)
+			  logic.promise_batch_action_transfer(promise_index, amount_ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -388,25 +498,25 @@ use wasmi::{
 			  let promise_index: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let beneficiary_id_len: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let beneficiary_id_ptr: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_batch_action_delete_account(promise_index, beneficiary_id_len, beneficiary_id_ptr)
+			  logic.promise_batch_action_delete_account(promise_index, beneficiary_id_len, beneficiary_id_ptr)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PromiseResultsCount => {
-			  self.promise_results_count()
+			  logic.promise_results_count()
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PromiseResult => {
 			  let result_idx: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_result(result_idx, register_id)
+			  logic.promise_result(result_idx, register_id)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::PromiseReturn => {
 			  let promise_idx: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.promise_return(promise_idx)
+			  logic.promise_return(promise_idx)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -416,7 +526,7 @@ use wasmi::{
 			  let value_len: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let value_ptr: u64 = args.nth_checked(3).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(4).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.storage_write(key_len, key_ptr, value_len, value_ptr, register_id)
+			  logic.storage_write(key_len, key_ptr, value_len, value_ptr, register_id)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -424,7 +534,7 @@ use wasmi::{
 			  let key_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let key_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.storage_read(key_len, key_ptr, register_id)
+			  logic.storage_read(key_len, key_ptr, register_id)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
@@ -432,27 +542,113 @@ use wasmi::{
 			  let key_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let key_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.storage_remove(key_len, key_ptr, register_id)
+			  logic.storage_remove(key_len, key_ptr, register_id)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::StorageHasKey => {
 			  let key_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
 			  let key_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.storage_has_key(key_len, key_ptr)
+			  logic.storage_has_key(key_len, key_ptr)
 				  .map(|ret| Some(ret.into()) )
 				  .map_err(vmlogicerr_to_trap)
 		  },
 		  HostFunctions::Gas => {
 			  let gas_amount: u32 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
-			  self.gas(gas_amount)
+			  logic.gas(gas_amount)
 				  .map(|_| None )
 				  .map_err(vmlogicerr_to_trap)
 		  },
+		  HostFunctions::StorageIterPrefix => {
+			  let prefix_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let prefix_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.storage_iter_prefix(prefix_len, prefix_ptr)
+				  .map(|ret| Some(ret.into()) )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::StorageIterRange => {
+			  let start_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let start_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let end_len: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let end_ptr: u64 = args.nth_checked(3).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.storage_iter_range(start_len, start_ptr, end_len, end_ptr)
+				  .map(|ret| Some(ret.into()) )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::StorageIterNext => {
+			  let iterator_id: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let key_register_id: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let value_register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.storage_iter_next(iterator_id, key_register_id, value_register_id)
+				  .map(|ret| Some(ret.into()) )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::Ed25519Verify => {
+			  let signature_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let signature_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let message_len: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let message_ptr: u64 = args.nth_checked(3).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let public_key_len: u64 = args.nth_checked(4).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let public_key_ptr: u64 = args.nth_checked(5).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.ed25519_verify(signature_len, signature_ptr, message_len, message_ptr, public_key_len, public_key_ptr)
+				  .map(|ret| Some(ret.into()) )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::Sr25519Verify => {
+			  let signature_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let signature_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let message_len: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let message_ptr: u64 = args.nth_checked(3).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let public_key_len: u64 = args.nth_checked(4).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let public_key_ptr: u64 = args.nth_checked(5).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.sr25519_verify(signature_len, signature_ptr, message_len, message_ptr, public_key_len, public_key_ptr)
+				  .map(|ret| Some(ret.into()) )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::Blake2b256 => {
+			  let value_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let value_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.blake2b_256(value_len, value_ptr, register_id)
+				  .map(|_| None )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::X25519SharedSecret => {
+			  let secret_key_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let secret_key_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let public_key_len: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let public_key_ptr: u64 = args.nth_checked(3).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let register_id: u64 = args.nth_checked(4).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.x25519_shared_secret(secret_key_len, secret_key_ptr, public_key_len, public_key_ptr, register_id)
+				  .map(|ret| Some(ret.into()) )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::RandomBytes => {
+			  let len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let register_id: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.random_bytes(len, register_id)
+				  .map(|_| None )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::Seal => {
+			  let value_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let value_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.seal(value_len, value_ptr, register_id)
+				  .map(|_| None )
+				  .map_err(vmlogicerr_to_trap)
+		  },
+		  HostFunctions::Unseal => {
+			  let value_len: u64 = args.nth_checked(0).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let value_ptr: u64 = args.nth_checked(1).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  let register_id: u64 = args.nth_checked(2).map_err(|_| TrapKind::UnexpectedSignature)?;
+			  logic.unseal(value_len, value_ptr, register_id)
+				  .map(|ret| Some(ret.into()) )
+				  .map_err(vmlogicerr_to_trap)
+		  },
   
 		  _ => {
 			  Err(Trap::new(TrapKind::Unreachable))
 		  }
 	  }
-	}
   }
\ No newline at end of file
@@ -19,190 +19,238 @@ use wasmi::{
 	  }
   }
   
-  impl ModuleImportResolver for WasmiImportResolver {
-	  fn resolve_func(
-		  &self, 
-		  func_name: &str,
-		  _signature: &Signature,
-	  ) -> Result<FuncRef, InterpeterError> {
+  /// Signature and index of the host function imported as `func_name`, shared by all engines.
+  pub fn resolve_host_function(func_name: &str) -> Option<(Signature, HostFunctions)> {
 		  match func_name {
-			  "read_register" => Ok(FuncInstance::alloc_host(
+			  "read_register" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::ReadRegister.into(),
+				  HostFunctions::ReadRegister,
 			  )),
-			  "register_len" => Ok(FuncInstance::alloc_host(
+			  "register_len" => Some((
 				  Signature::new(&[ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::RegisterLen.into(),
+				  HostFunctions::RegisterLen,
 			  )),
-			  "write_register" => Ok(FuncInstance::alloc_host(
+			  "write_register" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..] , None),
-				  HostFunctions::WriteRegister.into(),
+				  HostFunctions::WriteRegister,
 			  )),
-			  "current_account_id" => Ok(FuncInstance::alloc_host(
+			  "current_account_id" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::CurrentAccountId.into(),
+				  HostFunctions::CurrentAccountId,
 			  )),
-			  "signer_account_id" => Ok(FuncInstance::alloc_host(
+			  "signer_account_id" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::SignerAccountId.into(),
+				  HostFunctions::SignerAccountId,
 			  )),
-			  "predecessor_account_id" => Ok(FuncInstance::alloc_host(
+			  "predecessor_account_id" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::PredecessorAccountId.into(),
+				  HostFunctions::PredecessorAccountId,
 			  )),
-			  "input" => Ok(FuncInstance::alloc_host(
+			  "input" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::Input.into(),
+				  HostFunctions::Input,
 			  )),
-			  "block_number" => Ok(FuncInstance::alloc_host(
+			  "block_number" => Some((
 				  Signature::new(&[][..], Some(ValueType::I64)),
-				  HostFunctions::BlockNumber.into(),
+				  HostFunctions::BlockNumber,
 			  )),
-			  "block_timestamp" => Ok(FuncInstance::alloc_host(
+			  "block_timestamp" => Some((
 				  Signature::new(&[][..], Some(ValueType::I64)),
-				  HostFunctions::BlockTimestamp.into(),
+				  HostFunctions::BlockTimestamp,
 			  )),
-			  "storage_usage" => Ok(FuncInstance::alloc_host(
+			  "storage_usage" => Some((
 				  Signature::new(&[][..], Some(ValueType::I64)),
-				  HostFunctions::StorageUsage.into(),
+				  HostFunctions::StorageUsage,
 			  )),
-			  "account_balance" => Ok(FuncInstance::alloc_host(
+			  "account_balance" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::AccountBalance.into(),
+				  HostFunctions::AccountBalance,
 			  )),
-			  "attached_deposit" => Ok(FuncInstance::alloc_host(
+			  "attached_deposit" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::AttachedDeposit.into(),
+				  HostFunctions::AttachedDeposit,
 			  )),
-			  "prepaid_gas" => Ok(FuncInstance::alloc_host(
+			  "prepaid_gas" => Some((
 				  Signature::new(&[][..], Some(ValueType::I64)),
-				  HostFunctions::PrepaidGas.into(),
+				  HostFunctions::PrepaidGas,
 			  )),
-			  "used_gas" => Ok(FuncInstance::alloc_host(
+			  "used_gas" => Some((
 				  Signature::new(&[][..], Some(ValueType::I64)),
-				  HostFunctions::UsedGas.into(),
+				  HostFunctions::UsedGas,
 			  )),
-			  "random_seed" => Ok(FuncInstance::alloc_host(
+			  "random_seed" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::RandomSeed.into(),
+				  HostFunctions::RandomSeed,
 			  )),
-			  "sha256" => Ok(FuncInstance::alloc_host(
+			  "sha256" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::Sha256.into(),
+				  HostFunctions::Sha256,
 			  )),
-			  "keccak256" => Ok(FuncInstance::alloc_host(
+			  "keccak256" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::Keccak256.into(),
+				  HostFunctions::Keccak256,
 			  )),
-			  "keccak512" => Ok(FuncInstance::alloc_host(
+			  "keccak512" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::Keccak512.into(),
+				  HostFunctions::Keccak512,
 			  )),
-			  "ripemd160" => Ok(FuncInstance::alloc_host(
+			  "ripemd160" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::Ripemd160.into(),
+				  HostFunctions::Ripemd160,
 			  )),
-			  "ecrecover" => Ok(FuncInstance::alloc_host(
+			  "ecrecover" => Some((
 				  Signature::new(&[ValueType:: I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::Ecrecover.into(),
+				  HostFunctions::Ecrecover,
 			  )),
-			  "value_return" => Ok(FuncInstance::alloc_host(
+			  "value_return" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::ValueReturn.into(),
+				  HostFunctions::ValueReturn,
 			  )),
-			  "panic" => Ok(FuncInstance::alloc_host(
+			  "panic" => Some((
 				  Signature::new(&[][..], None),
-				  HostFunctions::Panic.into(),
+				  HostFunctions::Panic,
 			  )),
-			  "panic_utf8" => Ok(FuncInstance::alloc_host(
+			  "panic_utf8" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::PanicUtf8.into(),
+				  HostFunctions::PanicUtf8,
 			  )),
-			  "log_utf8" => Ok(FuncInstance::alloc_host(
+			  "log_utf8" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::LogUtf8.into(),
+				  HostFunctions::LogUtf8,
 			  )),
-			  "log_utf16" => Ok(FuncInstance::alloc_host(
+			  "log_utf16" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::LogUtf16.into(),
+				  HostFunctions::LogUtf16,
 			  )),
-			  "abort" => Ok(FuncInstance::alloc_host(
+			  "abort" => Some((
 				  Signature::new(&[ValueType::I32, ValueType::I32, ValueType::I32, ValueType::I32][..], None),
-				  HostFunctions::Abort.into(),
+				  HostFunctions::Abort,
 			  )),
-			  "promise_create" => Ok(FuncInstance::alloc_host(
+			  "promise_create" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::PromiseCreate.into(),
+				  HostFunctions::PromiseCreate,
 			  )),
-			  "promise_then" => Ok(FuncInstance::alloc_host(
+			  "promise_then" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::PromiseThen.into(),
+				  HostFunctions::PromiseThen,
 			  )),
-			  "promise_and" => Ok(FuncInstance::alloc_host(
+			  "promise_and" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::PromiseAnd.into(),
+				  HostFunctions::PromiseAnd,
 			  )),
-			  "promise_batch_create" => Ok(FuncInstance::alloc_host(
+			  "promise_batch_create" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::PromiseBatchCreate.into(),
+				  HostFunctions::PromiseBatchCreate,
 			  )),
-			  "promise_batch_then" => Ok(FuncInstance::alloc_host(
+			  "promise_batch_then" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::PromiseBatchThen.into(),
+				  HostFunctions::PromiseBatchThen,
 			  )),
-			  "promise_batch_action_create_account" => Ok(FuncInstance::alloc_host(
+			  "promise_batch_action_create_account" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::PromiseBatchActionCreateAccount.into(),
+				  HostFunctions::PromiseBatchActionCreateAccount,
 			  )),
-			  "promise_batch_action_deploy_contract" => Ok(FuncInstance::alloc_host(
+			  "promise_batch_action_deploy_contract" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::PromiseBatchActionDeployContract.into(),
+				  HostFunctions::PromiseBatchActionDeployContract,
 			  )),
-			  "promise_batch_action_function_call" => Ok(FuncInstance::alloc_host(
+			  "promise_batch_action_function_call" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::PromiseBatchActionFunctionCall.into(),
+				  HostFunctions::PromiseBatchActionFunctionCall,
 			  )),
-			  "promise_batch_action_transfer" => Ok(FuncInstance::alloc_host(
+			  "promise_batch_action_transfer" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::PromiseBatchActionTransfer.into(),
+				  HostFunctions::PromiseBatchActionTransfer,
 			  )),
-			  "promise_batch_action_delete_account" => Ok(FuncInstance::alloc_host(
+			  "promise_batch_action_delete_account" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], None),
-				  HostFunctions::PromiseBatchActionDeleteAccount.into(),
+				  HostFunctions::PromiseBatchActionDeleteAccount,
 			  )),
-			  "promise_results_count" => Ok(FuncInstance::alloc_host(
+			  "promise_results_count" => Some((
 				  Signature::new(&[][..], Some(ValueType::I64)),
-				  HostFunctions::PromiseResultsCount.into(),
+				  HostFunctions::PromiseResultsCount,
 			  )),
-			  "promise_result" => Ok(FuncInstance::alloc_host(
+			  "promise_result" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::PromiseResult.into(),
+				  HostFunctions::PromiseResult,
 			  )),
-			  "promise_return" => Ok(FuncInstance::alloc_host(
+			  "promise_return" => Some((
 				  Signature::new(&[ValueType::I64][..], None),
-				  HostFunctions::PromiseReturn.into(),
+				  HostFunctions::PromiseReturn,
 			  )),
-			  "storage_write" => Ok(FuncInstance::alloc_host(
+			  "storage_write" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::StorageWrite.into(),
+				  HostFunctions::StorageWrite,
 			  )),
-			  "storage_read" => Ok(FuncInstance::alloc_host(
+			  "storage_read" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::StorageRead.into(),
+				  HostFunctions::StorageRead,
 			  )),
-			  "storage_remove" => Ok(FuncInstance::alloc_host(
+			  "storage_remove" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::StorageRemove.into(),
+				  HostFunctions::StorageRemove,
 			  )),
-			  "storage_has_key" => Ok(FuncInstance::alloc_host(
+			  "storage_has_key" => Some((
 				  Signature::new(&[ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
-				  HostFunctions::StorageHasKey.into(),
+				  HostFunctions::StorageHasKey,
 			  )),
-			  "gas" => Ok(FuncInstance::alloc_host(
+			  "gas" => Some((
 				  Signature::new(&[ValueType::I32][..], None),
-				  HostFunctions::Gas.into(),
+				  HostFunctions::Gas,
+			  )),
+			  "storage_iter_prefix" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
+				  HostFunctions::StorageIterPrefix,
+			  )),
+			  "storage_iter_range" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
+				  HostFunctions::StorageIterRange,
+			  )),
+			  "storage_iter_next" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
+				  HostFunctions::StorageIterNext,
+			  )),
+			  "ed25519_verify" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
+				  HostFunctions::Ed25519Verify,
+			  )),
+			  "sr25519_verify" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
+				  HostFunctions::Sr25519Verify,
+			  )),
+			  "blake2b_256" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], None),
+				  HostFunctions::Blake2b256,
+			  )),
+			  "x25519_shared_secret" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
+				  HostFunctions::X25519SharedSecret,
+			  )),
+			  "random_bytes" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64][..], None),
+				  HostFunctions::RandomBytes,
+			  )),
+			  "seal" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], None),
+				  HostFunctions::Seal,
+			  )),
+			  "unseal" => Some((
+				  Signature::new(&[ValueType::I64, ValueType::I64, ValueType::I64][..], Some(ValueType::I64)),
+				  HostFunctions::Unseal,
 			  )),
-			  _ => Err(InterpeterError::Trap(HostError::InvalidMethodName.into()))
+			  _ => None,
+		  }
+  }
+  
+  impl ModuleImportResolver for WasmiImportResolver {
+	  fn resolve_func(
+		  &self, 
+		  func_name: &str,
+		  _signature: &Signature,
+	  ) -> Result<FuncRef, InterpeterError> {
+		  match resolve_host_function(func_name) {
+			  Some((signature, function)) => Ok(FuncInstance::alloc_host(signature, function.into())),
+			  None => Err(InterpeterError::Trap(HostError::InvalidMethodName.into())),
 		  }
 	  }
   
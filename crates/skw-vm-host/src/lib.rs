@@ -11,10 +11,12 @@ pub mod gas_counter;
 pub use context::VMContext;
 pub use dependencies::{RuntimeExternal, MemoryLike, ValuePtr};
 pub use logic::{VMLogic, VMOutcome};
-pub use imports::{WasmiImportResolver, create_builder};
+pub use imports::{WasmiImportResolver, create_builder, resolve_host_function};
+pub use externals::HostFunctions;
 
 pub use skw_vm_primitives::config::*;
 pub use skw_vm_primitives::profile;
+pub use skw_vm_primitives::trace;
 pub use skw_vm_primitives::contract_runtime::ProtocolVersion;
 pub use skw_vm_primitives::errors::{HostError, VMLogicError};
 pub use types::ReturnData;
@@ -1,7 +1,7 @@
 use crate::context::VMContext;
 use crate::dependencies::{RuntimeExternal, MemoryLike};
 use crate::gas_counter::{FastGasCounter, GasCounter};
-use crate::types::{PromiseIndex, PromiseResult, ReceiptIndex, ReturnData};
+use crate::types::{IteratorIndex, PromiseIndex, PromiseResult, ReceiptIndex, ReturnData};
 use crate::ValuePtr;
 use byteorder::ByteOrder;
 use primitive_types::U256;
@@ -11,6 +11,7 @@ use skw_vm_primitives::borsh::BorshSerialize;
 use skw_vm_primitives::config::ExtCosts::*;
 use skw_vm_primitives::config::{ActionCosts, ExtCosts, VMConfig, ViewConfig};
 use skw_vm_primitives::profile::ProfileData;
+use skw_vm_primitives::trace::{ExecutionTrace, TraceEvent};
 use skw_vm_primitives::fees::{
     transfer_exec_fee, transfer_send_fee, RuntimeFeesConfig,
 };
@@ -19,12 +20,18 @@ use skw_vm_primitives::contract_runtime::{
 };
 use skw_vm_primitives::errors::InconsistentStateError;
 use skw_vm_primitives::errors::{HostError, VMLogicError};
+use skw_vm_primitives::crypto::{
+    ED25519PublicKey, KeyType, PublicKey, SR25519PublicKey, Signature,
+};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::mem::size_of;
 
 pub type Result<T> = ::std::result::Result<T, VMLogicError>;
 
+/// Length of the xsalsa20 nonce prepended to sealed values.
+const SEAL_NONCE_LEN: usize = 24;
+
 pub struct VMLogic<'a> {
     /// Provides access to the components outside the Wasm runtime for operations on the trie and
     /// receipts creation.
@@ -62,6 +69,17 @@ pub struct VMLogic<'a> {
 
     /// Tracks the total log length. The sum of length of all logs.
     total_log_length: u64,
+
+    /// Iterators that can still be advanced.
+    valid_iterators: HashSet<IteratorIndex>,
+    /// Iterators that were dropped by a write or a removal since their creation.
+    invalid_iterators: HashSet<IteratorIndex>,
+
+    /// Number of draws taken from the shard secret in this call, including seal nonces.
+    secret_draws: u64,
+
+    /// Trace of the call, only recorded if `VMContext::trace` is set.
+    trace: Option<ExecutionTrace>,
 }
 
 /// Promises API allows to create a DAG-structure that defines dependencies between smart contract
@@ -107,6 +125,13 @@ impl<'a> VMLogic<'a> {
         // Overflow should be checked before calling VMLogic.
         let current_account_balance = context.account_balance + context.attached_deposit;
         let current_storage_usage = context.storage_usage;
+        // the trace holds plaintext storage keys and values, so it is never recorded for a call
+        // that can read the shard secret or encrypted contract storage
+        let trace = if context.trace && context.shard_secret.is_none() {
+            Some(ExecutionTrace::default())
+        } else {
+            None
+        };
         let max_gas_burnt = match context.view_config {
             Some(ViewConfig { max_gas_burnt: max_gas_burnt_view }) => max_gas_burnt_view,
             None => config.limit_config.max_gas_burnt,
@@ -134,6 +159,10 @@ impl<'a> VMLogic<'a> {
             promises: vec![],
             receipt_to_account: HashMap::new(),
             total_log_length: 0,
+            valid_iterators: HashSet::new(),
+            invalid_iterators: HashSet::new(),
+            secret_draws: 0,
+            trace,
         }
     }
 
@@ -466,6 +495,53 @@ impl<'a> VMLogic<'a> {
         Ok(())
     }
 
+    pub(crate) fn is_tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    fn trace_event(&mut self, event: TraceEvent) {
+        if let Some(trace) = &mut self.trace {
+            trace.push(event);
+        }
+    }
+
+    /// Runs a host function called by the contract and records it, together with the gas it
+    /// charged, into the trace. Events recorded by the host function itself follow the call.
+    pub(crate) fn trace_host_call<T>(
+        &mut self,
+        name: &str,
+        args: Vec<u64>,
+        host_call: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let trace = match &mut self.trace {
+            Some(trace) => trace,
+            None => return host_call(self),
+        };
+        let index = trace.events.len();
+        trace.push(TraceEvent::HostCall { name: name.to_string(), args, gas_burnt: 0, gas_used: 0 });
+        let burnt_before = self.gas_counter.burnt_gas();
+        let used_before = self.gas_counter.used_gas();
+        let res = host_call(self);
+        let burnt = self.gas_counter.burnt_gas().saturating_sub(burnt_before);
+        let used = self.gas_counter.used_gas().saturating_sub(used_before);
+        if let Some(trace) = &mut self.trace {
+            trace.set_host_call_gas(index, burnt, used);
+        }
+        res
+    }
+
+    /// Records a promise that created the receipt `receipt_idx`.
+    fn trace_promise(&mut self, promise_idx: PromiseIndex, receipt_idx: ReceiptIndex) {
+        if self.trace.is_some() {
+            let receiver_id = self.get_account_by_receipt(&receipt_idx).clone();
+            self.trace_event(TraceEvent::PromiseCreated {
+                promise_index: promise_idx,
+                receipt_index: receipt_idx,
+                receiver_id,
+            });
+        }
+    }
+
     // ###############
     // # Context API #
     // ###############
@@ -683,6 +759,148 @@ impl<'a> VMLogic<'a> {
         self.internal_write_register(register_id, self.context.random_seed.clone())
     }
 
+    /// Hashes `domain`, the shard secret and `data` together. The shard secret itself never
+    /// reaches the contract.
+    fn derive_from_shard_secret(&self, domain: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+        let shard_secret = self
+            .context
+            .shard_secret
+            .ok_or(VMLogicError::HostError(HostError::ShardSecretUnavailable))?;
+
+        use sha2::Digest;
+
+        let mut bytes = Vec::with_capacity(domain.len() + shard_secret.len() + data.len());
+        bytes.extend_from_slice(domain);
+        bytes.extend_from_slice(&shard_secret);
+        bytes.extend_from_slice(data);
+        Ok(sha2::Sha256::digest(&bytes).as_slice().to_vec())
+    }
+
+    /// Takes the next 32 bytes of the per-call secret stream under `domain`. The stream is keyed
+    /// by the shard secret and the call's `random_seed`, so it differs for every action.
+    fn next_secret_draw(&mut self, domain: &[u8]) -> Result<Vec<u8>> {
+        let mut data = self.context.random_seed.clone();
+        data.extend_from_slice(&self.secret_draws.to_le_bytes());
+        let draw = self.derive_from_shard_secret(domain, &data)?;
+        self.secret_draws += 1;
+        Ok(draw)
+    }
+
+    /// Writes `len` bytes of confidential randomness into `register_id`. The bytes are derived
+    /// from the shard secret and the block context, so unlike `random_seed` they cannot be
+    /// predicted outside the enclave. Every call continues the stream, so consecutive calls
+    /// return different bytes.
+    ///
+    /// # Errors
+    ///
+    /// * If the runner did not provide a shard secret returns `ShardSecretUnavailable`;
+    /// * If `len` exceeds the register size limit returns `MemoryAccessViolation`.
+    ///
+    /// # Cost
+    ///
+    /// `random_bytes_base + random_bytes_byte * len + write_register_base + write_register_byte * len`
+    pub fn random_bytes(&mut self, len: u64, register_id: u64) -> Result<()> {
+        self.gas_counter.pay_base(random_bytes_base)?;
+        self.gas_counter.pay_per(random_bytes_byte, len)?;
+        if len > self.config.limit_config.max_register_size {
+            return Err(HostError::MemoryAccessViolation.into());
+        }
+
+        let seed = self.next_secret_draw(b"skw-random")?;
+
+        use sha2::Digest;
+
+        let mut bytes = Vec::with_capacity(len as usize);
+        let mut block: u64 = 0;
+        while (bytes.len() as u64) < len {
+            let mut input = seed.clone();
+            input.extend_from_slice(&block.to_le_bytes());
+            let chunk = sha2::Sha256::digest(&input);
+            let take = std::cmp::min(chunk.len(), len as usize - bytes.len());
+            bytes.extend_from_slice(&chunk[..take]);
+            block += 1;
+        }
+        self.internal_write_register(register_id, bytes)
+    }
+
+    /// Key that only the current contract can seal and unseal with on this shard.
+    fn sealing_key(&self) -> Result<Vec<u8>> {
+        let account_id = self.context.current_account_id.as_ref().try_to_vec().unwrap();
+        self.derive_from_shard_secret(b"skw-seal", &account_id)
+    }
+
+    /// Encrypts the given value under a key derived from the shard secret and the current
+    /// account id and writes `nonce || ciphertext` into `register_id`. The result can be kept in
+    /// contract state and read back only by this contract with `unseal`.
+    ///
+    /// # Errors
+    ///
+    /// * If `value_len + value_ptr` points outside the memory or the registers use more memory
+    ///   than the limit returns `MemoryAccessViolation`;
+    /// * If the runner did not provide a shard secret returns `ShardSecretUnavailable`.
+    ///
+    /// # Cost
+    ///
+    /// `seal_base + seal_byte * num_bytes + write_register_base + write_register_byte * num_sealed_bytes`
+    pub fn seal(&mut self, value_len: u64, value_ptr: u64, register_id: u64) -> Result<()> {
+        self.gas_counter.pay_base(seal_base)?;
+        let value = self.get_vec_from_memory_or_register(value_ptr, value_len)?;
+        self.gas_counter.pay_per(seal_byte, value.len() as u64)?;
+
+        use xsalsa20poly1305::aead::{Aead, NewAead};
+
+        let key = self.sealing_key()?;
+        let nonce = self.next_secret_draw(b"skw-seal-nonce")?;
+        let nonce = xsalsa20poly1305::Nonce::from_slice(&nonce[..SEAL_NONCE_LEN]);
+        let cipher = xsalsa20poly1305::XSalsa20Poly1305::new(xsalsa20poly1305::Key::from_slice(&key));
+        let ciphertext = cipher.encrypt(nonce, value.as_slice()).map_err(|_| {
+            VMLogicError::HostError(HostError::SealError {
+                msg: "Failed to encrypt the value".to_string(),
+            })
+        })?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        self.internal_write_register(register_id, sealed)
+    }
+
+    /// Decrypts a value produced by `seal` of the current contract and writes it into
+    /// `register_id`.
+    ///
+    /// Returns `1` on success and `0` if the value was not sealed by this contract on this shard
+    /// or was tampered with. The register is not written in the latter case.
+    ///
+    /// # Errors
+    ///
+    /// * If `value_len + value_ptr` points outside the memory or the registers use more memory
+    ///   than the limit returns `MemoryAccessViolation`;
+    /// * If the runner did not provide a shard secret returns `ShardSecretUnavailable`.
+    ///
+    /// # Cost
+    ///
+    /// `unseal_base + unseal_byte * num_bytes + write_register_base + write_register_byte * num_unsealed_bytes`
+    pub fn unseal(&mut self, value_len: u64, value_ptr: u64, register_id: u64) -> Result<u64> {
+        self.gas_counter.pay_base(unseal_base)?;
+        let sealed = self.get_vec_from_memory_or_register(value_ptr, value_len)?;
+        self.gas_counter.pay_per(unseal_byte, sealed.len() as u64)?;
+
+        use xsalsa20poly1305::aead::{Aead, NewAead};
+
+        let key = self.sealing_key()?;
+        if sealed.len() < SEAL_NONCE_LEN {
+            return Ok(false as u64);
+        }
+        let (nonce, ciphertext) = sealed.split_at(SEAL_NONCE_LEN);
+        let cipher = xsalsa20poly1305::XSalsa20Poly1305::new(xsalsa20poly1305::Key::from_slice(&key));
+        match cipher.decrypt(xsalsa20poly1305::Nonce::from_slice(nonce), ciphertext) {
+            Ok(value) => {
+                self.internal_write_register(register_id, value)?;
+                Ok(true as u64)
+            }
+            Err(_) => Ok(false as u64),
+        }
+    }
+
     /// Hashes the given value using sha256 and returns it into `register_id`.
     ///
     /// # Errors
@@ -895,6 +1113,201 @@ impl<'a> VMLogic<'a> {
         Ok(false as u64)
     }
 
+    /// Verifies an ed25519 `signature` of `message` against `public_key`.
+    ///
+    /// Returns `1` if the signature is valid and `0` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// * If any of the inputs points outside the memory or to an unused register returns
+    ///   `MemoryAccessViolation`/`InvalidRegisterId`;
+    /// * If the signature is not 64 bytes or the public key is not 32 bytes returns
+    ///   `SignatureVerifyInvalidInput`.
+    ///
+    /// # Cost
+    ///
+    /// `base + ed25519_verify_base + ed25519_verify_byte * num_message_bytes`
+    pub fn ed25519_verify(
+        &mut self,
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> Result<u64> {
+        self.gas_counter.pay_base(base)?;
+        self.gas_counter.pay_base(ed25519_verify_base)?;
+        let (signature, message, public_key) = self.get_signature_verify_inputs(
+            signature_len,
+            signature_ptr,
+            message_len,
+            message_ptr,
+            public_key_len,
+            public_key_ptr,
+        )?;
+        self.gas_counter.pay_per(ed25519_verify_byte, message.len() as u64)?;
+
+        let signature = match Signature::from_parts(KeyType::ED25519, &signature) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false as u64),
+        };
+        let public_key = PublicKey::ED25519(ED25519PublicKey(public_key));
+        Ok(signature.verify(&message, &public_key) as u64)
+    }
+
+    /// Verifies an sr25519 `signature` of `message` against `public_key`, using the same
+    /// signing context as transaction signatures.
+    ///
+    /// Returns `1` if the signature is valid and `0` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// * If any of the inputs points outside the memory or to an unused register returns
+    ///   `MemoryAccessViolation`/`InvalidRegisterId`;
+    /// * If the signature is not 64 bytes or the public key is not 32 bytes returns
+    ///   `SignatureVerifyInvalidInput`.
+    ///
+    /// # Cost
+    ///
+    /// `base + sr25519_verify_base + sr25519_verify_byte * num_message_bytes`
+    pub fn sr25519_verify(
+        &mut self,
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> Result<u64> {
+        self.gas_counter.pay_base(base)?;
+        self.gas_counter.pay_base(sr25519_verify_base)?;
+        let (signature, message, public_key) = self.get_signature_verify_inputs(
+            signature_len,
+            signature_ptr,
+            message_len,
+            message_ptr,
+            public_key_len,
+            public_key_ptr,
+        )?;
+        self.gas_counter.pay_per(sr25519_verify_byte, message.len() as u64)?;
+
+        let signature = match Signature::from_parts(KeyType::SR25519, &signature) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false as u64),
+        };
+        let public_key = PublicKey::SR25519(SR25519PublicKey(public_key));
+        Ok(signature.verify(&message, &public_key) as u64)
+    }
+
+    /// Reads and length-checks the inputs shared by `ed25519_verify` and `sr25519_verify`.
+    fn get_signature_verify_inputs(
+        &mut self,
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>, [u8; 32])> {
+        let signature = self.get_vec_from_memory_or_register(signature_ptr, signature_len)?;
+        if signature.len() != 64 {
+            return Err(VMLogicError::HostError(HostError::SignatureVerifyInvalidInput {
+                msg: format!("The length of the signature: {}, should be 64 bytes", signature.len()),
+            }));
+        }
+
+        let public_key = self.get_vec_from_memory_or_register(public_key_ptr, public_key_len)?;
+        let public_key: [u8; 32] = public_key.try_into().map_err(|key: Vec<u8>| {
+            VMLogicError::HostError(HostError::SignatureVerifyInvalidInput {
+                msg: format!("The length of the public key: {}, should be 32 bytes", key.len()),
+            })
+        })?;
+
+        let message = self.get_vec_from_memory_or_register(message_ptr, message_len)?;
+        Ok((signature, message, public_key))
+    }
+
+    /// Hashes the given value using blake2b with a 32 bytes output and returns it into
+    /// `register_id`. This is the same hash the parentchain uses for `Blake2_256`.
+    ///
+    /// # Errors
+    ///
+    /// If `value_len + value_ptr` points outside the memory or the registers use more memory than
+    /// the limit with `MemoryAccessViolation`.
+    ///
+    /// # Cost
+    ///
+    /// `base + write_register_base + write_register_byte * num_bytes + blake2b_256_base + blake2b_256_byte * num_bytes`
+    pub fn blake2b_256(&mut self, value_len: u64, value_ptr: u64, register_id: u64) -> Result<()> {
+        self.gas_counter.pay_base(base)?;
+        self.gas_counter.pay_base(blake2b_256_base)?;
+        let value = self.get_vec_from_memory_or_register(value_ptr, value_len)?;
+        self.gas_counter.pay_per(blake2b_256_byte, value.len() as u64)?;
+
+        use blake2::Digest;
+
+        let value_hash = blake2::Blake2b::<blake2::digest::consts::U32>::digest(&value);
+        self.internal_write_register(register_id, value_hash.as_slice().to_vec())
+    }
+
+    /// Computes the x25519 shared secret of `secret_key` and `public_key` and writes it into
+    /// `register_id`. The secret key is clamped as described in RFC 7748.
+    ///
+    /// Returns `1` on success and `0` if the shared secret is all zeros, i.e. the public key is
+    /// a low order point. The register is not written in the latter case.
+    ///
+    /// # Errors
+    ///
+    /// * If any of the inputs points outside the memory or to an unused register returns
+    ///   `MemoryAccessViolation`/`InvalidRegisterId`;
+    /// * If either key is not 32 bytes returns `X25519InvalidInput`.
+    ///
+    /// # Cost
+    ///
+    /// `base + x25519_base + write_register_base + write_register_byte * 32`
+    pub fn x25519_shared_secret(
+        &mut self,
+        secret_key_len: u64,
+        secret_key_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+        register_id: u64,
+    ) -> Result<u64> {
+        self.gas_counter.pay_base(base)?;
+        self.gas_counter.pay_base(x25519_base)?;
+
+        let mut secret_key: [u8; 32] = self
+            .get_vec_from_memory_or_register(secret_key_ptr, secret_key_len)?
+            .try_into()
+            .map_err(|key: Vec<u8>| {
+                VMLogicError::HostError(HostError::X25519InvalidInput {
+                    msg: format!("The length of the secret key: {}, should be 32 bytes", key.len()),
+                })
+            })?;
+        let public_key: [u8; 32] = self
+            .get_vec_from_memory_or_register(public_key_ptr, public_key_len)?
+            .try_into()
+            .map_err(|key: Vec<u8>| {
+                VMLogicError::HostError(HostError::X25519InvalidInput {
+                    msg: format!("The length of the public key: {}, should be 32 bytes", key.len()),
+                })
+            })?;
+
+        secret_key[0] &= 248;
+        secret_key[31] &= 127;
+        secret_key[31] |= 64;
+
+        let shared_secret = curve25519_dalek::scalar::Scalar::from_bits(secret_key)
+            * curve25519_dalek::montgomery::MontgomeryPoint(public_key);
+        if shared_secret.0 == [0u8; 32] {
+            return Ok(false as u64);
+        }
+
+        self.internal_write_register(register_id, shared_secret.0.to_vec())?;
+        Ok(true as u64)
+    }
+
     /// Called by gas metering injected into Wasm. Counts both towards `burnt_gas` and `used_gas`.
     ///
     /// # Errors
@@ -1148,7 +1561,9 @@ impl<'a> VMLogic<'a> {
         let new_receipt_idx = self.ext.create_receipt(vec![], account_id.clone())?;
         self.receipt_to_account.insert(new_receipt_idx, account_id);
 
-        self.checked_push_promise(Promise::Receipt(new_receipt_idx))
+        let promise_idx = self.checked_push_promise(Promise::Receipt(new_receipt_idx))?;
+        self.trace_promise(promise_idx, new_receipt_idx);
+        Ok(promise_idx)
     }
 
     /// Creates a new promise towards given `account_id` without any actions attached, that is
@@ -1206,7 +1621,9 @@ impl<'a> VMLogic<'a> {
         let new_receipt_idx = self.ext.create_receipt(receipt_dependencies, account_id.clone())?;
         self.receipt_to_account.insert(new_receipt_idx, account_id);
 
-        self.checked_push_promise(Promise::Receipt(new_receipt_idx))
+        let promise_idx = self.checked_push_promise(Promise::Receipt(new_receipt_idx))?;
+        self.trace_promise(promise_idx, new_receipt_idx);
+        Ok(promise_idx)
     }
 
     /// Helper function to return the account id towards which the receipt is directed.
@@ -1847,19 +2264,30 @@ impl<'a> VMLogic<'a> {
             Self::deref_value(&mut self.gas_counter, storage_write_evicted_byte, evicted_ptr)?;
         self.gas_counter
             .pay_per(touching_trie_node, self.ext.get_touched_nodes_count() - nodes_before)?;
+        self.invalidate_iterators()?;
         self.ext.storage_set(&key, &value)?;
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEvent::StorageWrite {
+                key: key.clone(),
+                value: value.clone(),
+                evicted: evicted.clone(),
+            });
+        }
         let storage_config = &self.fees_config.storage_usage_config;
+        let record_len = self.ext.storage_record_len(key.len() as u64, value.len() as u64);
         match evicted {
             Some(old_value) => {
-                // Inner value can't overflow, because the value length is limited.
+                let old_record_len =
+                    self.ext.storage_record_len(key.len() as u64, old_value.len() as u64);
+                // Inner value can't overflow, because the key/value length is limited.
                 self.current_storage_usage = self
                     .current_storage_usage
-                    .checked_sub(old_value.len() as u64)
+                    .checked_sub(old_record_len)
                     .ok_or(InconsistentStateError::IntegerOverflow)?;
-                // Inner value can't overflow, because the value length is limited.
+                // Inner value can't overflow, because the key/value length is limited.
                 self.current_storage_usage = self
                     .current_storage_usage
-                    .checked_add(value.len() as u64)
+                    .checked_add(record_len)
                     .ok_or(InconsistentStateError::IntegerOverflow)?;
                 self.internal_write_register(register_id, old_value)?;
                 Ok(1)
@@ -1868,11 +2296,7 @@ impl<'a> VMLogic<'a> {
                 // Inner value can't overflow, because the key/value length is limited.
                 self.current_storage_usage = self
                     .current_storage_usage
-                    .checked_add(
-                        value.len() as u64
-                            + key.len() as u64
-                            + storage_config.num_extra_bytes_record,
-                    )
+                    .checked_add(record_len + storage_config.num_extra_bytes_record)
                     .ok_or(InconsistentStateError::IntegerOverflow)?;
                 Ok(0)
             }
@@ -1927,6 +2351,9 @@ impl<'a> VMLogic<'a> {
         self.gas_counter
             .pay_per(touching_trie_node, self.ext.get_touched_nodes_count() - nodes_before)?;
         let read = Self::deref_value(&mut self.gas_counter, storage_read_value_byte, read?)?;
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEvent::StorageRead { key, value: read.clone() });
+        }
         match read {
             Some(value) => {
                 self.internal_write_register(register_id, value)?;
@@ -1977,9 +2404,13 @@ impl<'a> VMLogic<'a> {
         let removed =
             Self::deref_value(&mut self.gas_counter, storage_remove_ret_value_byte, removed_ptr)?;
 
+        self.invalidate_iterators()?;
         self.ext.storage_remove(&key)?;
         self.gas_counter
             .pay_per(touching_trie_node, self.ext.get_touched_nodes_count() - nodes_before)?;
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEvent::StorageRemove { key: key.clone(), removed: removed.clone() });
+        }
         let storage_config = &self.fees_config.storage_usage_config;
         match removed {
             Some(value) => {
@@ -1987,8 +2418,7 @@ impl<'a> VMLogic<'a> {
                 self.current_storage_usage = self
                     .current_storage_usage
                     .checked_sub(
-                        value.len() as u64
-                            + key.len() as u64
+                        self.ext.storage_record_len(key.len() as u64, value.len() as u64)
                             + storage_config.num_extra_bytes_record,
                     )
                     .ok_or(InconsistentStateError::IntegerOverflow)?;
@@ -2027,11 +2457,183 @@ impl<'a> VMLogic<'a> {
         let res = self.ext.storage_has_key(&key);
         self.gas_counter
             .pay_per(touching_trie_node, self.ext.get_touched_nodes_count() - nodes_before)?;
-        Ok(res? as u64)
+        let found = res?;
+        self.trace_event(TraceEvent::StorageHasKey { key, found });
+        Ok(found as u64)
+    }
+
+    /// Drops all live iterators, so that advancing them reports `IteratorWasInvalidated`.
+    fn invalidate_iterators(&mut self) -> Result<()> {
+        for iterator_index in self.valid_iterators.drain() {
+            self.ext.storage_iter_drop(iterator_index)?;
+            self.invalid_iterators.insert(iterator_index);
+        }
+        Ok(())
+    }
+
+    /// Creates an iterator object inside the host. Returns the identifier that uniquely
+    /// differentiates the given iterator from other iterators that can be simultaneously created.
+    /// * It iterates over the keys that have the provided prefix. The order of iteration is defined
+    ///   by the lexicographic order of the bytes in the keys;
+    /// * If there are no keys, it creates an empty iterator, see below on empty iterators.
+    ///
+    /// # Errors
+    ///
+    /// * If `prefix_len + prefix_ptr` exceeds the memory container it returns
+    ///   `MemoryAccessViolation`.
+    /// * If the length of the prefix exceeds `max_length_storage_key` returns `KeyLengthExceeded`.
+    ///
+    /// # Cost
+    ///
+    /// `base + storage_iter_create_prefix_base + storage_iter_create_prefix_byte * num_prefix_bytes
+    ///  + cost of reading the prefix`.
+    pub fn storage_iter_prefix(&mut self, prefix_len: u64, prefix_ptr: u64) -> Result<u64> {
+        self.gas_counter.pay_base(base)?;
+        self.gas_counter.pay_base(storage_iter_create_prefix_base)?;
+        let prefix = self.get_vec_from_memory_or_register(prefix_ptr, prefix_len)?;
+        if prefix.len() as u64 > self.config.limit_config.max_length_storage_key {
+            return Err(HostError::KeyLengthExceeded {
+                length: prefix.len() as u64,
+                limit: self.config.limit_config.max_length_storage_key,
+            }
+            .into());
+        }
+        self.gas_counter.pay_per(storage_iter_create_prefix_byte, prefix.len() as u64)?;
+        let nodes_before = self.ext.get_touched_nodes_count();
+        let iterator_index = self.ext.storage_iter(&prefix);
+        self.gas_counter
+            .pay_per(touching_trie_node, self.ext.get_touched_nodes_count() - nodes_before)?;
+        let iterator_index = iterator_index?;
+        self.valid_iterators.insert(iterator_index);
+        Ok(iterator_index)
+    }
+
+    /// Iterates over all key-values such that keys are between `start` and `end`, where `start` is
+    /// inclusive and `end` is exclusive. Unless lexicographically `start < end`, it creates an
+    /// empty iterator. Note, this definition allows for `start` or `end` keys to not actually exist
+    /// on the given trie.
+    ///
+    /// # Errors
+    ///
+    /// * If `start_len + start_ptr` or `end_len + end_ptr` exceeds the memory container or points to
+    ///   an unused register it returns `MemoryAccessViolation`.
+    /// * If the length of `start` or `end` exceeds `max_length_storage_key` returns
+    ///   `KeyLengthExceeded`.
+    ///
+    /// # Cost
+    ///
+    /// `base + storage_iter_create_range_base + storage_iter_create_from_byte * num_from_bytes
+    ///  + storage_iter_create_to_byte * num_to_bytes + reading from prefix + reading to prefix`.
+    pub fn storage_iter_range(
+        &mut self,
+        start_len: u64,
+        start_ptr: u64,
+        end_len: u64,
+        end_ptr: u64,
+    ) -> Result<u64> {
+        self.gas_counter.pay_base(base)?;
+        self.gas_counter.pay_base(storage_iter_create_range_base)?;
+        let start_key = self.get_vec_from_memory_or_register(start_ptr, start_len)?;
+        let end_key = self.get_vec_from_memory_or_register(end_ptr, end_len)?;
+        for key in [&start_key, &end_key] {
+            if key.len() as u64 > self.config.limit_config.max_length_storage_key {
+                return Err(HostError::KeyLengthExceeded {
+                    length: key.len() as u64,
+                    limit: self.config.limit_config.max_length_storage_key,
+                }
+                .into());
+            }
+        }
+        self.gas_counter.pay_per(storage_iter_create_from_byte, start_key.len() as u64)?;
+        self.gas_counter.pay_per(storage_iter_create_to_byte, end_key.len() as u64)?;
+        let nodes_before = self.ext.get_touched_nodes_count();
+        let iterator_index = self.ext.storage_iter_range(&start_key, &end_key);
+        self.gas_counter
+            .pay_per(touching_trie_node, self.ext.get_touched_nodes_count() - nodes_before)?;
+        let iterator_index = iterator_index?;
+        self.valid_iterators.insert(iterator_index);
+        Ok(iterator_index)
+    }
+
+    /// Advances iterator and saves the next key and value in the register.
+    /// * If iterator is not empty (after calling next it points to a key-value), copies the key
+    ///   into `key_register_id` and value into `value_register_id` and returns `1`;
+    /// * If iterator is empty returns `0`;
+    /// This allows us to iterate over the keys that have zero bytes stored in values.
+    ///
+    /// # Errors
+    ///
+    /// * If `key_register_id == value_register_id` returns `MemoryAccessViolation`;
+    /// * If the registers exceed the memory limit returns `MemoryAccessViolation`;
+    /// * If `iterator_id` does not correspond to an existing iterator returns `InvalidIteratorIndex`;
+    /// * If `storage_write` or `storage_remove` was called after the creation of the iterator
+    ///   returns `IteratorWasInvalidated`.
+    ///
+    /// # Cost
+    ///
+    /// `base + storage_iter_next_base + storage_iter_next_key_byte * num_key_bytes
+    ///  + storage_iter_next_value_byte * num_value_bytes + writing key to register
+    ///  + writing value to register`.
+    pub fn storage_iter_next(
+        &mut self,
+        iterator_id: u64,
+        key_register_id: u64,
+        value_register_id: u64,
+    ) -> Result<u64> {
+        self.gas_counter.pay_base(base)?;
+        if self.invalid_iterators.contains(&iterator_id) {
+            return Err(HostError::IteratorWasInvalidated { iterator_index: iterator_id }.into());
+        } else if !self.valid_iterators.contains(&iterator_id) {
+            return Err(HostError::InvalidIteratorIndex { iterator_index: iterator_id }.into());
+        }
+        if key_register_id == value_register_id {
+            return Err(HostError::MemoryAccessViolation.into());
+        }
+
+        self.gas_counter.pay_base(storage_iter_next_base)?;
+        let nodes_before = self.ext.get_touched_nodes_count();
+        let next = self.ext.storage_iter_next(iterator_id)?;
+        let next = match next {
+            Some((key, value_ptr)) => {
+                self.gas_counter.pay_per(storage_iter_next_key_byte, key.len() as u64)?;
+                let value = Self::deref_value(
+                    &mut self.gas_counter,
+                    storage_iter_next_value_byte,
+                    Some(value_ptr),
+                )?
+                .unwrap_or_default();
+                Some((key, value))
+            }
+            None => None,
+        };
+        self.gas_counter
+            .pay_per(touching_trie_node, self.ext.get_touched_nodes_count() - nodes_before)?;
+        match next {
+            Some((key, value)) => {
+                if let Some(trace) = &mut self.trace {
+                    trace.push(TraceEvent::StorageRead { key: key.clone(), value: Some(value.clone()) });
+                }
+                self.internal_write_register(key_register_id, key)?;
+                self.internal_write_register(value_register_id, value)?;
+                Ok(1)
+            }
+            None => {
+                self.valid_iterators.remove(&iterator_id);
+                Ok(0)
+            }
+        }
+    }
+
+    /// Gas profile of the execution, with the gas burnt on wasm instructions filled in.
+    fn profile(&self) -> ProfileData {
+        let mut profile = self.gas_counter.profile_data();
+        profile.compute_wasm_instruction_cost(self.gas_counter.burnt_gas());
+        profile
     }
 
     /// Computes the outcome of execution.
     pub fn outcome(self) -> VMOutcome {
+        let profile = self.profile();
         VMOutcome {
             balance: self.current_account_balance,
             storage_usage: self.current_storage_usage,
@@ -2039,7 +2641,8 @@ impl<'a> VMLogic<'a> {
             burnt_gas: self.gas_counter.burnt_gas(),
             used_gas: self.gas_counter.used_gas(),
             logs: self.logs,
-            profile: self.gas_counter.profile_data(),
+            profile,
+            trace: self.trace,
         }
     }
 
@@ -2054,7 +2657,8 @@ impl<'a> VMLogic<'a> {
             burnt_gas: self.gas_counter.burnt_gas(),
             used_gas: self.gas_counter.used_gas(),
             logs,
-            profile: self.gas_counter.profile_data(),
+            profile: self.profile(),
+            trace: self.trace.clone(),
         }
     }
 
@@ -2087,6 +2691,8 @@ pub struct VMOutcome {
     pub logs: Vec<String>,
     /// Data collected from making a contract call
     pub profile: ProfileData,
+    /// Trace of the call, if it was made in trace mode
+    pub trace: Option<ExecutionTrace>,
 }
 
 impl std::fmt::Debug for VMOutcome {
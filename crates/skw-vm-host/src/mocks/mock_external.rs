@@ -1,5 +1,5 @@
 use crate::{RuntimeExternal, ValuePtr};
-use crate::types::{PublicKey};
+use crate::types::{IteratorIndex, PublicKey};
 use skw_vm_primitives::contract_runtime::{AccountId, Balance, Gas};
 use skw_vm_primitives::errors::HostError;
 use serde::{Deserialize, Serialize};
@@ -9,6 +9,8 @@ use std::collections::HashMap;
 /// Emulates the trie and the mock handling code.
 pub struct MockedExternal {
     pub fake_trie: HashMap<Vec<u8>, Vec<u8>>,
+    iterators: HashMap<IteratorIndex, std::vec::IntoIter<(Vec<u8>, Vec<u8>)>>,
+    next_iterator_index: IteratorIndex,
     receipts: Vec<Receipt>,
     pub validators: HashMap<AccountId, Balance>,
 }
@@ -45,11 +47,27 @@ impl MockedExternal {
     pub fn get_receipt_create_calls(&self) -> &Vec<Receipt> {
         &self.receipts
     }
+
+    /// Snapshot the matching entries of the fake trie in key order.
+    fn create_iterator<F: Fn(&[u8]) -> bool>(&mut self, filter: F) -> IteratorIndex {
+        let mut entries: Vec<_> = self
+            .fake_trie
+            .iter()
+            .filter(|(key, _)| filter(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        entries.sort();
+        let iterator_index = self.next_iterator_index;
+        self.iterators.insert(iterator_index, entries.into_iter());
+        self.next_iterator_index += 1;
+        iterator_index
+    }
 }
 
 use crate::dependencies::Result;
 impl RuntimeExternal for MockedExternal {
     fn storage_set(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.iterators.clear();
         self.fake_trie.insert(key.to_vec(), value.to_vec());
         Ok(())
     }
@@ -62,11 +80,13 @@ impl RuntimeExternal for MockedExternal {
     }
 
     fn storage_remove(&mut self, key: &[u8]) -> Result<()> {
+        self.iterators.clear();
         self.fake_trie.remove(key);
         Ok(())
     }
 
     fn storage_remove_subtree(&mut self, prefix: &[u8]) -> Result<()> {
+        self.iterators.clear();
         self.fake_trie.retain(|key, _| !key.starts_with(prefix));
         Ok(())
     }
@@ -75,6 +95,37 @@ impl RuntimeExternal for MockedExternal {
         Ok(self.fake_trie.contains_key(key))
     }
 
+    fn storage_iter(&mut self, prefix: &[u8]) -> Result<IteratorIndex> {
+        Ok(self.create_iterator(|key| key.starts_with(prefix)))
+    }
+
+    fn storage_iter_range(&mut self, start: &[u8], end: &[u8]) -> Result<IteratorIndex> {
+        Ok(self.create_iterator(|key| key >= start && key < end))
+    }
+
+    fn storage_iter_next<'a>(
+        &'a mut self,
+        iterator_idx: IteratorIndex,
+    ) -> Result<Option<(Vec<u8>, Box<dyn ValuePtr + 'a>)>> {
+        let next = self
+            .iterators
+            .get_mut(&iterator_idx)
+            .ok_or(HostError::InvalidIteratorIndex { iterator_index: iterator_idx })?
+            .next();
+        match next {
+            Some((key, value)) => Ok(Some((key, Box::new(MockedValuePtr { value }) as Box<_>))),
+            None => {
+                self.iterators.remove(&iterator_idx);
+                Ok(None)
+            }
+        }
+    }
+
+    fn storage_iter_drop(&mut self, iterator_idx: IteratorIndex) -> Result<()> {
+        self.iterators.remove(&iterator_idx);
+        Ok(())
+    }
+
     fn create_receipt(&mut self, receipt_indices: Vec<u64>, receiver_id: AccountId) -> Result<u64> {
         if let Some(index) = receipt_indices.iter().find(|&&el| el >= self.receipts.len() as u64) {
             return Err(HostError::InvalidReceiptIndex { receipt_index: *index }.into());
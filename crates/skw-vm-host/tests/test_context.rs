@@ -18,8 +18,10 @@ pub fn create_context() -> VMContext {
         attached_deposit: 2u128,
         prepaid_gas: 10_u64.pow(14),
         random_seed: vec![0, 1, 2],
+        shard_secret: None,
         view_config: None,
         output_data_receivers: vec![],
+        trace: false,
     }
 }
 
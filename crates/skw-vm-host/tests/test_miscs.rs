@@ -2,7 +2,9 @@ use fixtures::get_context;
 use helpers::*;
 use hex::FromHex;
 
-use skw_vm_primitives::errors::HostError;
+use skw_vm_primitives::borsh::BorshSerialize;
+use skw_vm_primitives::crypto::{KeyType, SecretKey};
+use skw_vm_primitives::errors::{HostError, VMLogicError};
 use skw_vm_host::{ExtCosts, types::AccountId};
 
 use serde::{de::Error, Deserialize, Deserializer};
@@ -867,3 +869,291 @@ fn test_contract_size_limit() {
         .into())
     );
 }
+
+#[test]
+fn test_seal_unseal() {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut context = get_context(vec![], false);
+    context.shard_secret = Some([1; 32]);
+    let mut logic = logic_builder.build(context.clone());
+    let data = b"a very secret value";
+
+    logic.seal(data.len() as _, data.as_ptr() as _, 0).unwrap();
+    let sealed = vec![0u8; logic.register_len(0).unwrap() as usize];
+    logic.read_register(0, sealed.as_ptr() as _).unwrap();
+    assert_eq!(sealed.len(), 24 + data.len() + 16);
+
+    assert_eq!(logic.unseal(u64::MAX, 0, 1), Ok(1));
+    let unsealed = vec![0u8; data.len()];
+    logic.read_register(1, unsealed.as_ptr() as _).unwrap();
+    assert_eq!(unsealed, data);
+
+    let mut tampered = sealed.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(logic.unseal(tampered.len() as _, tampered.as_ptr() as _, 2), Ok(0));
+
+    // another contract on the same shard cannot unseal the value
+    let mut logic_builder = VMLogicBuilder::default();
+    context.current_account_id = AccountId::system();
+    let mut logic = logic_builder.build(context);
+    assert_eq!(logic.unseal(sealed.len() as _, sealed.as_ptr() as _, 1), Ok(0));
+
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build(get_context(vec![], false));
+    assert_eq!(
+        logic.seal(data.len() as _, data.as_ptr() as _, 0),
+        Err(HostError::ShardSecretUnavailable.into())
+    );
+}
+
+#[test]
+fn test_random_bytes() {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut context = get_context(vec![], false);
+    context.shard_secret = Some([1; 32]);
+    let mut logic = logic_builder.build(context);
+
+    logic.random_bytes(40, 0).unwrap();
+    logic.random_bytes(40, 1).unwrap();
+    let first = vec![0u8; 40];
+    let second = vec![0u8; 40];
+    logic.read_register(0, first.as_ptr() as _).unwrap();
+    logic.read_register(1, second.as_ptr() as _).unwrap();
+    assert_ne!(first, second);
+
+    assert_costs(map! {
+        ExtCosts::base: 2,
+        ExtCosts::random_bytes_base: 2,
+        ExtCosts::random_bytes_byte: 80,
+        ExtCosts::write_register_base: 2,
+        ExtCosts::write_register_byte: 80,
+        ExtCosts::read_register_base: 2,
+        ExtCosts::read_register_byte: 80,
+        ExtCosts::write_memory_base: 2,
+        ExtCosts::write_memory_byte: 80,
+    });
+}
+
+/// Signs `message` with a fresh key and returns the raw signature and public key bytes.
+fn sign(key_type: KeyType, message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let secret_key = SecretKey::from_seed(key_type, b"test");
+    // borsh prefixes both with the key type
+    let signature = secret_key.sign(message).try_to_vec().unwrap()[1..].to_vec();
+    let public_key = secret_key.public_key().try_to_vec().unwrap()[1..].to_vec();
+    (signature, public_key)
+}
+
+fn is_invalid_signature_input<T>(res: Result<T, VMLogicError>) -> bool {
+    matches!(res, Err(VMLogicError::HostError(HostError::SignatureVerifyInvalidInput { .. })))
+}
+
+#[test]
+fn test_ed25519_verify() {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build(get_context(vec![], false));
+    let message = b"a message";
+    let (signature, public_key) = sign(KeyType::ED25519, message);
+
+    assert_eq!(
+        logic.ed25519_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        ),
+        Ok(1)
+    );
+    let len = message.len() as u64;
+    assert_costs(map! {
+        ExtCosts::base: 1,
+        ExtCosts::read_memory_base: 3,
+        ExtCosts::read_memory_byte: 64 + 32 + len,
+        ExtCosts::ed25519_verify_base: 1,
+        ExtCosts::ed25519_verify_byte: len,
+    });
+
+    let other = b"another message";
+    assert_eq!(
+        logic.ed25519_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            other.len() as _,
+            other.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        ),
+        Ok(0)
+    );
+    // an sr25519 signature does not pass as an ed25519 one
+    let (sr_signature, sr_public_key) = sign(KeyType::SR25519, message);
+    assert_eq!(
+        logic.ed25519_verify(
+            sr_signature.len() as _,
+            sr_signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            sr_public_key.len() as _,
+            sr_public_key.as_ptr() as _,
+        ),
+        Ok(0)
+    );
+
+    assert!(is_invalid_signature_input(logic.ed25519_verify(
+        63,
+        signature.as_ptr() as _,
+        message.len() as _,
+        message.as_ptr() as _,
+        public_key.len() as _,
+        public_key.as_ptr() as _,
+    )));
+    assert!(is_invalid_signature_input(logic.ed25519_verify(
+        signature.len() as _,
+        signature.as_ptr() as _,
+        message.len() as _,
+        message.as_ptr() as _,
+        31,
+        public_key.as_ptr() as _,
+    )));
+}
+
+#[test]
+fn test_sr25519_verify() {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build(get_context(vec![], false));
+    let message = b"a message";
+    let (signature, public_key) = sign(KeyType::SR25519, message);
+
+    assert_eq!(
+        logic.sr25519_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        ),
+        Ok(1)
+    );
+    let len = message.len() as u64;
+    assert_costs(map! {
+        ExtCosts::base: 1,
+        ExtCosts::read_memory_base: 3,
+        ExtCosts::read_memory_byte: 64 + 32 + len,
+        ExtCosts::sr25519_verify_base: 1,
+        ExtCosts::sr25519_verify_byte: len,
+    });
+
+    let mut tampered = signature.clone();
+    tampered[0] ^= 1;
+    assert_eq!(
+        logic.sr25519_verify(
+            tampered.len() as _,
+            tampered.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        ),
+        Ok(0)
+    );
+
+    assert!(is_invalid_signature_input(logic.sr25519_verify(
+        65,
+        signature.as_ptr() as _,
+        message.len() as _,
+        message.as_ptr() as _,
+        public_key.len() as _,
+        public_key.as_ptr() as _,
+    )));
+    assert!(is_invalid_signature_input(logic.sr25519_verify(
+        signature.len() as _,
+        signature.as_ptr() as _,
+        message.len() as _,
+        message.as_ptr() as _,
+        33,
+        public_key.as_ptr() as _,
+    )));
+}
+
+#[test]
+fn test_blake2b_256() {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build(get_context(vec![], false));
+    let data = b"tesdsst";
+
+    logic.blake2b_256(data.len() as _, data.as_ptr() as _, 0).unwrap();
+    let res = &vec![0u8; 32];
+    logic.read_register(0, res.as_ptr() as _).expect("OK");
+    assert_eq!(
+        res,
+        &[
+            95, 43, 222, 216, 153, 183, 57, 242, 166, 14, 219, 16, 169, 205, 255, 30, 42, 71, 70,
+            0, 109, 199, 50, 59, 126, 199, 2, 32, 101, 108, 210, 250,
+        ]
+    );
+    let len = data.len() as u64;
+    assert_costs(map! {
+        ExtCosts::base: 2,
+        ExtCosts::read_memory_base: 1,
+        ExtCosts::read_memory_byte: len,
+        ExtCosts::write_memory_base: 1,
+        ExtCosts::write_memory_byte: 32,
+        ExtCosts::read_register_base: 1,
+        ExtCosts::read_register_byte: 32,
+        ExtCosts::write_register_base: 1,
+        ExtCosts::write_register_byte: 32,
+        ExtCosts::blake2b_256_base: 1,
+        ExtCosts::blake2b_256_byte: len,
+    });
+}
+
+#[test]
+fn test_x25519_shared_secret() {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build(get_context(vec![], false));
+    // RFC 7748 section 6.1
+    let secret_key =
+        <[u8; 32]>::from_hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a")
+            .unwrap();
+    let public_key =
+        <[u8; 32]>::from_hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+            .unwrap();
+    let shared_secret =
+        <[u8; 32]>::from_hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742")
+            .unwrap();
+
+    assert_eq!(
+        logic.x25519_shared_secret(32, secret_key.as_ptr() as _, 32, public_key.as_ptr() as _, 0),
+        Ok(1)
+    );
+    let res = [0u8; 32];
+    logic.read_register(0, res.as_ptr() as _).unwrap();
+    assert_eq!(res, shared_secret);
+    assert_costs(map! {
+        ExtCosts::base: 2,
+        ExtCosts::read_memory_base: 2,
+        ExtCosts::read_memory_byte: 64,
+        ExtCosts::write_memory_base: 1,
+        ExtCosts::write_memory_byte: 32,
+        ExtCosts::read_register_base: 1,
+        ExtCosts::read_register_byte: 32,
+        ExtCosts::write_register_base: 1,
+        ExtCosts::write_register_byte: 32,
+        ExtCosts::x25519_base: 1,
+    });
+
+    // a low order point gives an all zero secret
+    let low_order = [0u8; 32];
+    assert_eq!(
+        logic.x25519_shared_secret(32, secret_key.as_ptr() as _, 32, low_order.as_ptr() as _, 1),
+        Ok(0)
+    );
+    assert_eq!(logic.register_len(1), Ok(u64::MAX));
+
+    assert!(matches!(
+        logic.x25519_shared_secret(31, secret_key.as_ptr() as _, 32, public_key.as_ptr() as _, 0),
+        Err(VMLogicError::HostError(HostError::X25519InvalidInput { .. }))
+    ));
+}
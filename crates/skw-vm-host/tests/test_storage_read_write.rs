@@ -2,7 +2,9 @@ mod fixtures;
 mod vm_logic_builder;
 
 use fixtures::get_context;
+use skw_vm_host::HostError;
 use skw_vm_host::RuntimeExternal;
+use skw_vm_primitives::trace::TraceEvent;
 use vm_logic_builder::VMLogicBuilder;
 
 #[test]
@@ -74,3 +76,72 @@ fn test_storage_has_key_with_register() {
 
     assert_eq!(logic.storage_has_key(u64::MAX, 1 as _), Ok(1));
 }
+
+#[test]
+fn test_storage_iter() {
+    let mut logic_builder = VMLogicBuilder::default();
+    logic_builder.ext.storage_set(b"a1", b"x").unwrap();
+    logic_builder.ext.storage_set(b"a2", b"y").unwrap();
+    logic_builder.ext.storage_set(b"b1", b"z").unwrap();
+
+    let mut logic = logic_builder.build(get_context(vec![], false));
+
+    let prefix: &[u8] = b"a";
+    let iter = logic.storage_iter_prefix(prefix.len() as _, prefix.as_ptr() as _).unwrap();
+    assert_eq!(logic.storage_iter_next(iter, 0, 1), Ok(1));
+    let (key, value) = ([0u8; 2], [0u8; 1]);
+    logic.read_register(0, key.as_ptr() as _).unwrap();
+    logic.read_register(1, value.as_ptr() as _).unwrap();
+    assert_eq!((&key, &value), (b"a1", b"x"));
+    assert_eq!(logic.storage_iter_next(iter, 0, 1), Ok(1));
+    assert_eq!(logic.storage_iter_next(iter, 0, 1), Ok(0));
+
+    let (start, end): (&[u8], &[u8]) = (b"a2", b"b");
+    let iter = logic
+        .storage_iter_range(start.len() as _, start.as_ptr() as _, end.len() as _, end.as_ptr() as _)
+        .unwrap();
+    assert_eq!(logic.storage_iter_next(iter, 0, 1), Ok(1));
+    assert_eq!(logic.storage_iter_next(iter, 0, 1), Ok(0));
+
+    // a write drops the live iterators
+    let iter = logic.storage_iter_prefix(prefix.len() as _, prefix.as_ptr() as _).unwrap();
+    logic
+        .storage_write(prefix.len() as _, prefix.as_ptr() as _, prefix.len() as _, prefix.as_ptr() as _, 0)
+        .unwrap();
+    assert_eq!(
+        logic.storage_iter_next(iter, 0, 1),
+        Err(HostError::IteratorWasInvalidated { iterator_index: iter }.into())
+    );
+    assert_eq!(
+        logic.storage_iter_next(iter + 1, 0, 1),
+        Err(HostError::InvalidIteratorIndex { iterator_index: iter + 1 }.into())
+    );
+}
+
+#[test]
+fn test_storage_trace_needs_no_shard_secret() {
+    let key: &[u8] = b"foo";
+    let val: &[u8] = b"bar";
+
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut context = get_context(vec![], false);
+    context.trace = true;
+    let mut logic = logic_builder.build(context.clone());
+    logic
+        .storage_write(key.len() as _, key.as_ptr() as _, val.len() as _, val.as_ptr() as _, 0)
+        .unwrap();
+    let trace = logic.outcome().trace.expect("trace is recorded");
+    assert_eq!(
+        trace.events,
+        vec![TraceEvent::StorageWrite { key: key.to_vec(), value: val.to_vec(), evicted: None }]
+    );
+
+    // plaintext contract data never leaves a call that can read the shard secret
+    let mut logic_builder = VMLogicBuilder::default();
+    context.shard_secret = Some([1; 32]);
+    let mut logic = logic_builder.build(context);
+    logic
+        .storage_write(key.len() as _, key.as_ptr() as _, val.len() as _, val.as_ptr() as _, 0)
+        .unwrap();
+    assert!(logic.outcome().trace.is_none());
+}
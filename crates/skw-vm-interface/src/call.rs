@@ -113,6 +113,11 @@ impl Caller {
         (*self.runtime).borrow().state_root()
     }
 
+    /// Records an execution trace for the following calls, see `ExecutionResult::trace_json`.
+    pub fn set_trace_execution(&self, enabled: bool) {
+        (*self.runtime).borrow_mut().set_trace_execution(enabled);
+    }
+
     pub fn set_account(&mut self, signer: AccountId) {
         self.account_id = signer.clone();
     }
@@ -133,9 +138,13 @@ impl Caller {
 
     fn submit_transaction(&self, transaction: Transaction) -> Result<ExecutionResult, RuntimeError> {
         let random_signer = InMemorySigner::from_seed(KeyType::SR25519, &[0]);
-        let res = (*self.runtime).borrow_mut().resolve_tx(transaction.sign(&random_signer))?;
+        let mut signed_transaction = transaction.sign(&random_signer);
+        signed_transaction.init();
+        let tx_hash = signed_transaction.get_hash();
+        let res = (*self.runtime).borrow_mut().resolve_tx(signed_transaction)?;
         (*self.runtime).borrow_mut().process_all()?;
-        Ok(outcome_into_result(res.1))
+        let profile = (*self.runtime).borrow().transaction_profile(&tx_hash).unwrap_or_default();
+        Ok(outcome_into_result(res.1).with_profile(profile))
     }
 
     pub fn account_id(&self) -> AccountId {
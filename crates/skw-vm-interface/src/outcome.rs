@@ -4,6 +4,8 @@ use core::fmt;
 use std::convert::TryFrom;
 use skw_vm_primitives::{
     contract_runtime::CryptoHash,
+    profile::ProfileData,
+    trace::ExecutionTrace,
     transaction::{ExecutionOutcome, ExecutionStatus},
 };
 use skw_vm_runtime::state_viewer::errors::CallFunctionError;
@@ -16,6 +18,7 @@ use std::fmt::Formatter;
 /// It wraps an ExecutionOutcome which is the same object returned from an RPC call.
 pub struct ExecutionResult {
     outcome: ExecutionOutcome,
+    profile: ProfileData,
 }
 
 impl Debug for ExecutionResult {
@@ -37,7 +40,20 @@ impl ExecutionResult {
     pub fn new(
         outcome: ExecutionOutcome,
     ) -> Self {
-        Self { outcome }
+        let profile = outcome.profile_data.clone().unwrap_or_default();
+        Self { outcome, profile }
+    }
+
+    /// Replaces the profile of the final receipt with the profile of the whole transaction.
+    pub(crate) fn with_profile(mut self, profile: ProfileData) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Gas profile of the transaction: wasm instructions, host functions and action costs of
+    /// all its receipts.
+    pub fn profile(&self) -> &ProfileData {
+        &self.profile
     }
 
     /// Interpret the SuccessValue as a JSON value
@@ -108,6 +124,18 @@ impl ExecutionResult {
         &self.outcome.receipt_ids
     }
 
+    /// Execution trace of the contract call, if it was made in trace mode.
+    pub fn trace(&self) -> Option<&ExecutionTrace> {
+        self.outcome.trace.as_ref()
+    }
+
+    /// Execution trace of the contract call as pretty printed JSON.
+    pub fn trace_json(&self) -> Option<String> {
+        self.trace().map(|trace| {
+            serde_json::to_string_pretty(trace).expect("trace is always serializable")
+        })
+    }
+
 }
 
 #[doc(hidden)]
@@ -12,11 +12,13 @@ use skw_vm_primitives::{
         CryptoHash, Balance, BlockNumber, Gas, Duration
     },
     profile::ProfileData,
+    trace::ExecutionTrace,
     receipt::Receipt,
     config::RuntimeConfig,
     state_record::{StateRecord},
     transaction::{ExecutionOutcome, ExecutionStatus, SignedTransaction},
     views::ViewApplyState,
+    utils::create_block_random_seed,
 };
 
 
@@ -67,6 +69,8 @@ pub struct GenesisConfig {
     pub genesis_height: u64,
     pub runtime_config: RuntimeConfig,
     pub state_records: Vec<StateRecord>,
+    /// Secret of the shard, used to derive confidential randomness and contract sealing keys.
+    pub shard_secret: Option<CryptoHash>,
 }
 
 impl Default for GenesisConfig {
@@ -80,6 +84,7 @@ impl Default for GenesisConfig {
             genesis_height: 0,
             runtime_config,
             state_records: vec![],
+            shard_secret: None,
         }
     }
 }
@@ -135,6 +140,8 @@ impl Block {
 
 pub struct RuntimeStandalone {
     runtime_config: RuntimeConfig,
+    shard_secret: Option<CryptoHash>,
+    trace_execution: bool,
     tx_pool: TransactionPool,
     transactions: HashMap<CryptoHash, SignedTransaction>,
     outcomes: HashMap<CryptoHash, ExecutionOutcome>,
@@ -171,6 +178,8 @@ impl RuntimeStandalone {
 
         Self {
             runtime_config: genesis.runtime_config.clone(),
+            shard_secret: genesis.shard_secret,
+            trace_execution: false,
             tries,
             runtime,
             transactions: HashMap::new(),
@@ -183,6 +192,38 @@ impl RuntimeStandalone {
         }
     }
 
+    /// Records an `ExecutionTrace` for the contract calls of the following blocks.
+    pub fn set_trace_execution(&mut self, enabled: bool) {
+        self.trace_execution = enabled;
+    }
+
+    /// Returns the trace of the transaction or receipt with the given hash, if it ran in trace mode.
+    pub fn trace(&self, hash: &CryptoHash) -> Option<&ExecutionTrace> {
+        self.outcomes.get(hash).and_then(|outcome| outcome.trace.as_ref())
+    }
+
+    /// Returns the gas profile of a single transaction or receipt.
+    pub fn profile(&self, hash: &CryptoHash) -> Option<&ProfileData> {
+        self.profile.get(hash)
+    }
+
+    /// Returns the gas profile of a transaction, merged over all the receipts it produced that
+    /// were applied so far.
+    pub fn transaction_profile(&self, tx_hash: &CryptoHash) -> Option<ProfileData> {
+        self.profile.get(tx_hash)?;
+        let mut profile = ProfileData::new();
+        let mut pending = vec![*tx_hash];
+        while let Some(hash) = pending.pop() {
+            if let Some(receipt_profile) = self.profile.get(&hash) {
+                profile.merge(receipt_profile);
+            }
+            if let Some(outcome) = self.outcomes.get(&hash) {
+                pending.extend(outcome.receipt_ids.iter().copied());
+            }
+        }
+        Some(profile)
+    }
+
     /// Processes blocks until the final value is produced
     pub fn resolve_tx(
         &mut self,
@@ -221,7 +262,6 @@ impl RuntimeStandalone {
 
     /// Processes one block. Populates outcomes and producining new pending_receipts.
     pub fn produce_block(&mut self) -> Result<(), RuntimeError> {
-        let profile_data = ProfileData::default();
         let apply_state = ApplyState {
             block_number: self.cur_block.block_number,
             prev_block_hash: Default::default(),
@@ -229,7 +269,15 @@ impl RuntimeStandalone {
             gas_price: self.cur_block.gas_price,
             block_timestamp: self.cur_block.block_timestamp,
             gas_limit: None,
-            random_seed: Default::default(),
+            random_seed: self.shard_secret
+                .map(|secret| create_block_random_seed(
+                    &secret,
+                    &self.cur_block.state_root,
+                    self.cur_block.block_number,
+                ))
+                .unwrap_or_default(),
+            shard_secret: self.shard_secret,
+            trace_execution: self.trace_execution,
             config: Arc::new(self.runtime_config.clone()),
         };
 
@@ -246,7 +294,10 @@ impl RuntimeStandalone {
         apply_result.outcomes.iter().for_each(|outcome| {
             self.last_outcomes.push(outcome.id);
             self.outcomes.insert(outcome.id, outcome.outcome.clone());
-            self.profile.insert(outcome.id, profile_data.clone());
+            self.profile.insert(
+                outcome.id,
+                outcome.outcome.profile_data.clone().unwrap_or_default(),
+            );
         });
         let (update, _) =
             self.tries.apply_all(&apply_result.trie_changes).expect("Unexpected Storage error");
@@ -379,6 +430,37 @@ mod tests {
         assert_eq!(runtime.view_account(AccountId::test()).unwrap().storage_usage(), 100);
     }
 
+    #[test]
+    fn profiles_match_burnt_gas() {
+        use skw_vm_primitives::config::ActionCosts;
+
+        let mut runtime = init_runtime(None, None, None);
+        let random_signer = InMemorySigner::from_seed(KeyType::SR25519, &[0]);
+        let tx_hash = runtime.send_tx(SignedTransaction::create_account(
+            1,
+            AccountId::root(),
+            AccountId::test(),
+            165437999999000,
+            &random_signer,
+            CryptoHash::default(),
+        ));
+        runtime.process_all().unwrap();
+
+        let tx_outcome = runtime.outcome(&tx_hash).unwrap();
+        let tx_profile = runtime.profile(&tx_hash).unwrap().clone();
+        assert!(tx_profile.get_action_cost(ActionCosts::create_account) > 0);
+        assert_eq!(tx_profile.action_gas(), tx_outcome.gas_burnt);
+
+        let receipt_id = tx_outcome.receipt_ids[0];
+        let receipt_profile = runtime.profile(&receipt_id).unwrap().clone();
+        assert!(receipt_profile.get_action_cost(ActionCosts::new_receipt) > 0);
+        assert_eq!(receipt_profile.action_gas(), runtime.outcome(&receipt_id).unwrap().gas_burnt);
+
+        let profile = runtime.transaction_profile(&tx_hash).unwrap();
+        assert!(profile.action_gas() >= tx_profile.action_gas() + receipt_profile.action_gas());
+        assert!(runtime.transaction_profile(&CryptoHash::default()).is_none());
+    }
+
     #[test]
     fn can_produce_many_blocks_without_stack_overflow() {
         let mut runtime = init_runtime(None, None, None);
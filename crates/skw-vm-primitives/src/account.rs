@@ -79,6 +79,23 @@ impl Account {
     }
 }
 
+/// How the contract data of an account is written to the state trie. Chosen when the contract is
+/// deployed and stored under `TrieKey::ContractStorageMode`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ContractStorageMode {
+    /// Keys and values are stored as they are.
+    Plain,
+    /// Values are encrypted under a key derived from the shard secret. With `blind_keys` the
+    /// storage keys are also replaced by their HMAC, which rules out prefix iteration.
+    Encrypted { blind_keys: bool },
+}
+
+impl Default for ContractStorageMode {
+    fn default() -> Self {
+        ContractStorageMode::Plain
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use borsh::BorshSerialize;
@@ -21,6 +21,12 @@ pub struct ApplyState {
     pub gas_limit: Option<Gas>,
     /// Current random seed (from current block vrf output).
     pub random_seed: CryptoHash,
+    /// Secret of the shard the block is applied on. Only known inside the enclave and never
+    /// exposed to contracts directly.
+    pub shard_secret: Option<CryptoHash>,
+    /// Whether contract calls record an `ExecutionTrace` into their outcomes. Traces hold plaintext
+    /// contract data, so they are not recorded while `shard_secret` is set.
+    pub trace_execution: bool,
     /// The Runtime config to use for the current transition.
     pub config: Arc<RuntimeConfig>,
 }
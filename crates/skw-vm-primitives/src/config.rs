@@ -19,6 +19,27 @@ pub struct VMConfig {
 
     /// Describes limits for VM and Runtime.
     pub limit_config: VMLimitConfig,
+
+    /// Engine the contracts are run on.
+    #[serde(default)]
+    pub vm_kind: VMKind,
+}
+
+/// Wasm engines contracts can run on. All of them run the same instrumented code, so a
+/// contract burns the same gas on each.
+#[derive(Clone, Copy, Debug, Hash, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VMKind {
+    /// The wasmi interpreter, which also runs inside the enclave.
+    Wasmi,
+    /// Wasmtime, only available when `skw-vm-engine` is built with the `wasmtime_vm` feature.
+    Wasmtime,
+}
+
+impl Default for VMKind {
+    fn default() -> Self {
+        VMKind::Wasmi
+    }
 }
 
 /// Describes limits for VM and Runtime.
@@ -82,6 +103,15 @@ pub struct VMLimitConfig {
     /// If present, stores max number of functions in one contract
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_functions_number_per_contract: Option<u64>,
+    /// If present, stores max number of elements of the table of a contract
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_table_elements: Option<u32>,
+    /// If present, stores max number of globals in one contract, imported ones included
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_globals_number_per_contract: Option<u64>,
+    /// If present, stores max number of locals declared by one function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_locals_per_function: Option<u64>,
 }
 
 impl VMConfig {
@@ -91,6 +121,7 @@ impl VMConfig {
             grow_mem_cost: 1,
             regular_op_cost: (SAFETY_MULTIPLIER as u32) * 1285457,
             limit_config: VMLimitConfig::test(),
+            vm_kind: VMKind::default(),
         }
     }
 
@@ -109,6 +140,7 @@ impl VMConfig {
             regular_op_cost: 0,
             // We shouldn't have any costs in the limit config.
             limit_config: VMLimitConfig { max_gas_burnt: u64::MAX, ..VMLimitConfig::test() },
+            vm_kind: VMKind::default(),
         }
     }
 }
@@ -157,6 +189,12 @@ impl VMLimitConfig {
             // Unlikely to hit it for normal development.
             max_number_input_data_dependencies: 128,
             max_functions_number_per_contract: Some(10_000),
+            // Same as the functions limit, a table has no use for more elements than functions.
+            max_table_elements: Some(10_000),
+            max_globals_number_per_contract: Some(1_000),
+            // Every local takes a unit of `max_stack_height`, a function declaring more than that
+            // traps as soon as it is called.
+            max_locals_per_function: Some(10_000),
         }
     }
 }
@@ -231,6 +269,39 @@ pub struct ExtCostsConfig {
     /// Cost of calling ecrecover
     pub ecrecover_base: Gas,
 
+    /// Cost of verifying an ed25519 signature base
+    pub ed25519_verify_base: Gas,
+    /// Cost of verifying an ed25519 signature per message byte
+    pub ed25519_verify_byte: Gas,
+
+    /// Cost of verifying an sr25519 signature base
+    pub sr25519_verify_base: Gas,
+    /// Cost of verifying an sr25519 signature per message byte
+    pub sr25519_verify_byte: Gas,
+
+    /// Cost of getting blake2b-256 base
+    pub blake2b_256_base: Gas,
+    /// Cost of getting blake2b-256 per byte
+    pub blake2b_256_byte: Gas,
+
+    /// Cost of an x25519 key agreement
+    pub x25519_base: Gas,
+
+    /// Cost of drawing from the confidential randomness stream base
+    pub random_bytes_base: Gas,
+    /// Cost of drawing from the confidential randomness stream per byte
+    pub random_bytes_byte: Gas,
+
+    /// Cost of sealing a value under the contract sealing key base
+    pub seal_base: Gas,
+    /// Cost of sealing a value under the contract sealing key per byte
+    pub seal_byte: Gas,
+
+    /// Cost of unsealing a value sealed under the contract sealing key base
+    pub unseal_base: Gas,
+    /// Cost of unsealing a value sealed under the contract sealing key per byte
+    pub unseal_byte: Gas,
+
     /// Cost for calling logging.
     pub log_base: Gas,
     /// Cost for logging per byte
@@ -283,7 +354,7 @@ pub struct ExtCostsConfig {
     pub storage_iter_next_base: Gas,
     /// Trie iterator next key byte cost
     pub storage_iter_next_key_byte: Gas,
-    /// Trie iterator next key byte cost
+    /// Trie iterator next value byte cost
     pub storage_iter_next_value_byte: Gas,
 
     /// Cost per touched trie node
@@ -332,6 +403,19 @@ impl ExtCostsConfig {
             // Cost per byte is 3542227. There are 64 bytes in a block.
             ripemd160_block: SAFETY_MULTIPLIER * 226702528,
             ecrecover_base: SAFETY_MULTIPLIER * 1121789875000,
+            ed25519_verify_base: SAFETY_MULTIPLIER * 70000000000,
+            ed25519_verify_byte: SAFETY_MULTIPLIER * 3000000,
+            sr25519_verify_base: SAFETY_MULTIPLIER * 80000000000,
+            sr25519_verify_byte: SAFETY_MULTIPLIER * 3000000,
+            blake2b_256_base: SAFETY_MULTIPLIER * 1513656750,
+            blake2b_256_byte: SAFETY_MULTIPLIER * 5000000,
+            x25519_base: SAFETY_MULTIPLIER * 60000000000,
+            random_bytes_base: SAFETY_MULTIPLIER * 1513656750,
+            random_bytes_byte: SAFETY_MULTIPLIER * 8039117,
+            seal_base: SAFETY_MULTIPLIER * 5000000000,
+            seal_byte: SAFETY_MULTIPLIER * 10000000,
+            unseal_base: SAFETY_MULTIPLIER * 5000000000,
+            unseal_byte: SAFETY_MULTIPLIER * 10000000,
             log_base: SAFETY_MULTIPLIER * 1181104350,
             log_byte: SAFETY_MULTIPLIER * 4399597,
             storage_write_base: SAFETY_MULTIPLIER * 21398912000,
@@ -346,14 +430,16 @@ impl ExtCostsConfig {
             storage_remove_ret_value_byte: SAFETY_MULTIPLIER * 3843852,
             storage_has_key_base: SAFETY_MULTIPLIER * 18013298875,
             storage_has_key_byte: SAFETY_MULTIPLIER * 10263615,
-            storage_iter_create_prefix_base: SAFETY_MULTIPLIER * 0,
-            storage_iter_create_prefix_byte: SAFETY_MULTIPLIER * 0,
-            storage_iter_create_range_base: SAFETY_MULTIPLIER * 0,
-            storage_iter_create_from_byte: SAFETY_MULTIPLIER * 0,
-            storage_iter_create_to_byte: SAFETY_MULTIPLIER * 0,
-            storage_iter_next_base: SAFETY_MULTIPLIER * 0,
-            storage_iter_next_key_byte: SAFETY_MULTIPLIER * 0,
-            storage_iter_next_value_byte: SAFETY_MULTIPLIER * 0,
+            // creating an iterator only stores a cursor, each step seeks the trie again and reads
+            // the value; provisional until the estimator's storage_iter contracts are run
+            storage_iter_create_prefix_base: SAFETY_MULTIPLIER * 2000000000,
+            storage_iter_create_prefix_byte: SAFETY_MULTIPLIER * 2000000,
+            storage_iter_create_range_base: SAFETY_MULTIPLIER * 2000000000,
+            storage_iter_create_from_byte: SAFETY_MULTIPLIER * 2000000,
+            storage_iter_create_to_byte: SAFETY_MULTIPLIER * 2000000,
+            storage_iter_next_base: SAFETY_MULTIPLIER * 36798914125,
+            storage_iter_next_key_byte: SAFETY_MULTIPLIER * 20581126,
+            storage_iter_next_value_byte: SAFETY_MULTIPLIER * 1870335,
             touching_trie_node: SAFETY_MULTIPLIER * 5367318642,
             promise_and_base: SAFETY_MULTIPLIER * 488337800,
             promise_and_per_promise: SAFETY_MULTIPLIER * 1817392,
@@ -387,6 +473,19 @@ impl ExtCostsConfig {
             ripemd160_base: 0,
             ripemd160_block: 0,
             ecrecover_base: 0,
+            ed25519_verify_base: 0,
+            ed25519_verify_byte: 0,
+            sr25519_verify_base: 0,
+            sr25519_verify_byte: 0,
+            blake2b_256_base: 0,
+            blake2b_256_byte: 0,
+            x25519_base: 0,
+            random_bytes_base: 0,
+            random_bytes_byte: 0,
+            seal_base: 0,
+            seal_byte: 0,
+            unseal_base: 0,
+            unseal_byte: 0,
             log_base: 0,
             log_byte: 0,
             storage_write_base: 0,
@@ -445,6 +544,19 @@ pub enum ExtCosts {
     ripemd160_base,
     ripemd160_block,
     ecrecover_base,
+    ed25519_verify_base,
+    ed25519_verify_byte,
+    sr25519_verify_base,
+    sr25519_verify_byte,
+    blake2b_256_base,
+    blake2b_256_byte,
+    x25519_base,
+    random_bytes_base,
+    random_bytes_byte,
+    seal_base,
+    seal_byte,
+    unseal_base,
+    unseal_byte,
     log_base,
     log_byte,
     storage_write_base,
@@ -554,6 +666,19 @@ impl ExtCosts {
             ripemd160_base => config.ripemd160_base,
             ripemd160_block => config.ripemd160_block,
             ecrecover_base => config.ecrecover_base,
+            ed25519_verify_base => config.ed25519_verify_base,
+            ed25519_verify_byte => config.ed25519_verify_byte,
+            sr25519_verify_base => config.sr25519_verify_base,
+            sr25519_verify_byte => config.sr25519_verify_byte,
+            blake2b_256_base => config.blake2b_256_base,
+            blake2b_256_byte => config.blake2b_256_byte,
+            x25519_base => config.x25519_base,
+            random_bytes_base => config.random_bytes_base,
+            random_bytes_byte => config.random_bytes_byte,
+            seal_base => config.seal_base,
+            seal_byte => config.seal_byte,
+            unseal_base => config.unseal_base,
+            unseal_byte => config.unseal_byte,
             log_base => config.log_base,
             log_byte => config.log_byte,
             storage_write_base => config.storage_write_base,
@@ -614,6 +739,19 @@ impl ExtCosts {
             "ripemd160_base",
             "ripemd160_block",
             "ecrecover_base",
+            "ed25519_verify_base",
+            "ed25519_verify_byte",
+            "sr25519_verify_base",
+            "sr25519_verify_byte",
+            "blake2b_256_base",
+            "blake2b_256_byte",
+            "x25519_base",
+            "random_bytes_base",
+            "random_bytes_byte",
+            "seal_base",
+            "seal_byte",
+            "unseal_base",
+            "unseal_byte",
             "log_base",
             "log_byte",
             "storage_write_base",
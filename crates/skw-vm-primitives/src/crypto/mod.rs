@@ -1,7 +1,7 @@
 pub use errors::{ParseKeyError, ParseKeyTypeError, ParseSignatureError};
 pub use signature::{
-    ED25519PublicKey, KeyType, PublicKey, Secp256K1PublicKey, Secp256K1Signature, SecretKey,
-    Signature,
+    ED25519PublicKey, KeyType, PublicKey, SR25519PublicKey, Secp256K1PublicKey,
+    Secp256K1Signature, SecretKey, Signature,
 };
 pub use signer::{EmptySigner, InMemorySigner, Signer};
 
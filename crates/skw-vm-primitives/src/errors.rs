@@ -134,6 +134,12 @@ pub enum PrepareError {
     Memory,
     /// Contract contains too many functions.
     TooManyFunctions,
+    /// Contract declares a table with too many elements.
+    TooLargeTable,
+    /// Contract contains too many globals.
+    TooManyGlobals,
+    /// A function of the contract declares too many locals.
+    TooManyLocals,
 }
 
 #[derive(
@@ -202,6 +208,17 @@ pub enum HostError {
     Deprecated { method_name: String },
     /// General errors for ECDSA recover.
     ECRecoverError { msg: String },
+    /// Malformed input to ed25519_verify or sr25519_verify.
+    SignatureVerifyInvalidInput { msg: String },
+    /// Malformed input to x25519_shared_secret.
+    X25519InvalidInput { msg: String },
+    /// The runner did not provide a shard secret, so confidential randomness and sealing are
+    /// unavailable. This is always the case for view calls.
+    ShardSecretUnavailable,
+    /// Sealing a value under the contract sealing key failed.
+    SealError { msg: String },
+    /// The contract storage keys are blinded, so they can't be iterated or removed by prefix.
+    BlindedStorageKeys,
 
     /// work-around for Traps
     ExternalError(Vec<u8>),
@@ -308,6 +325,8 @@ pub enum ActionErrorKind {
     OnlyImplicitAccountCreationAllowed { account_id: AccountId },
     /// Delete account whose state is large is temporarily banned.
     DeleteAccountWithLargeState { account_id: AccountId },
+    /// The contract storage mode can only be changed while the account has no contract data.
+    ContractStorageNotEmpty { account_id: AccountId },
 }
 
 /// An error happened during Acton execution
@@ -717,6 +736,9 @@ impl fmt::Display for PrepareError {
             Instantiate => write!(f, "Error happened during instantiation."),
             Memory => write!(f, "Error creating memory."),
             TooManyFunctions => write!(f, "Too many functions in contract."),
+            TooLargeTable => write!(f, "Too many elements in the table of contract."),
+            TooManyGlobals => write!(f, "Too many globals in contract."),
+            TooManyLocals => write!(f, "Too many locals in a function of contract."),
         }
     }
 }
@@ -844,6 +866,11 @@ impl std::fmt::Display for HostError {
             ContractSizeExceeded { size, limit } => write!(f, "The size of a contract code in DeployContract action {} exceeds the limit {}", size, limit),
             Deprecated {method_name}=> write!(f, "Attempted to call deprecated host function {}", method_name),
             ECRecoverError { msg } => write!(f, "ECDSA recover error: {}", msg),
+            SignatureVerifyInvalidInput { msg } => write!(f, "Signature verification error: {}", msg),
+            X25519InvalidInput { msg } => write!(f, "x25519 key agreement error: {}", msg),
+            ShardSecretUnavailable => write!(f, "Shard secret is not available in this execution"),
+            SealError { msg } => write!(f, "Seal error: {}", msg),
+            BlindedStorageKeys => write!(f, "Contract storage keys are blinded and can't be iterated"),
             ExternalError(_) => write!(f, "external error"),
             InconsistentStateError(e) => write!(f, "InconsistentStateError: {}", e),
             Unknown => write!(f, "unkonw error"),
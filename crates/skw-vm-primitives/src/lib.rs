@@ -4,6 +4,7 @@ pub mod trie_key;
 pub mod errors;
 pub mod fees;
 pub mod profile;
+pub mod trace;
 pub mod receipt;
 pub mod transaction;
 pub mod serialize;
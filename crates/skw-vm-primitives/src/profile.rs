@@ -8,7 +8,7 @@ use crate::config::{ActionCosts, ExtCosts};
 pub struct DataArray(Box<[u64; Self::LEN]>);
 
 impl DataArray {
-    pub const LEN: usize = Cost::ALL.len();
+    pub const LEN: usize = Cost::WasmInstruction.index() + 1;
 }
 
 impl Index<usize> for DataArray {
@@ -105,6 +105,10 @@ impl ProfileData {
         self[Cost::ExtCost { ext_cost_kind: ext }]
     }
 
+    pub fn wasm_gas(&self) -> u64 {
+        self[Cost::WasmInstruction]
+    }
+
     pub fn host_gas(&self) -> u64 {
         let mut host_gas = 0u64;
         for cost in Cost::ALL {
@@ -214,6 +218,19 @@ impl Cost {
         Cost::ExtCost { ext_cost_kind: ExtCosts::ripemd160_base },
         Cost::ExtCost { ext_cost_kind: ExtCosts::ripemd160_block },
         Cost::ExtCost { ext_cost_kind: ExtCosts::ecrecover_base },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::ed25519_verify_base },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::ed25519_verify_byte },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::sr25519_verify_base },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::sr25519_verify_byte },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::blake2b_256_base },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::blake2b_256_byte },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::x25519_base },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::random_bytes_base },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::random_bytes_byte },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::seal_base },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::seal_byte },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::unseal_base },
+        Cost::ExtCost { ext_cost_kind: ExtCosts::unseal_byte },
         Cost::ExtCost { ext_cost_kind: ExtCosts::log_base },
         Cost::ExtCost { ext_cost_kind: ExtCosts::log_byte },
         Cost::ExtCost { ext_cost_kind: ExtCosts::storage_write_base },
@@ -243,7 +260,7 @@ impl Cost {
         Cost::WasmInstruction,
     ];
 
-    pub fn index(self) -> usize {
+    pub const fn index(self) -> usize {
         match self {
             Cost::ActionCost { action_cost_kind: ActionCosts::create_account } => 0,
             Cost::ActionCost { action_cost_kind: ActionCosts::delete_account } => 1,
@@ -305,7 +322,20 @@ impl Cost {
             Cost::ExtCost { ext_cost_kind: ExtCosts::promise_and_base } => 57,
             Cost::ExtCost { ext_cost_kind: ExtCosts::promise_and_per_promise } => 58,
             Cost::ExtCost { ext_cost_kind: ExtCosts::promise_return } => 59,
-            Cost::WasmInstruction => 62,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::ed25519_verify_base } => 60,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::ed25519_verify_byte } => 61,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::sr25519_verify_base } => 62,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::sr25519_verify_byte } => 63,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::blake2b_256_base } => 64,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::blake2b_256_byte } => 65,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::x25519_base } => 66,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::random_bytes_base } => 67,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::random_bytes_byte } => 68,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::seal_base } => 69,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::seal_byte } => 70,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::unseal_base } => 71,
+            Cost::ExtCost { ext_cost_kind: ExtCosts::unseal_byte } => 72,
+            Cost::WasmInstruction => 73,
             Cost::ExtCost { ext_cost_kind: ExtCosts::__count } => unreachable!(),
         }
     }
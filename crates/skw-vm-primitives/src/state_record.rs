@@ -84,6 +84,7 @@ impl StateRecord {
                 Some(StateRecord::DelayedReceipt(Box::new(receipt)))
             }
             col::DELAYED_RECEIPT_INDICES => None,
+            col::CONTRACT_STORAGE_MODE => None,
             _ => unreachable!(),
         }
     }
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::contract_runtime::{AccountId, Gas};
+use crate::serialize::{base64_format, option_base64_format};
+
+/// Trace of a contract call, recorded only when the call runs in trace mode.
+/// Events are kept in the order they happened: the effects of a host call directly follow it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    pub events: Vec<TraceEvent>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TraceEvent {
+    /// A host function called by the contract with its raw arguments and the gas it charged.
+    HostCall { name: String, args: Vec<u64>, gas_burnt: Gas, gas_used: Gas },
+    StorageRead {
+        #[serde(with = "base64_format")]
+        key: Vec<u8>,
+        #[serde(with = "option_base64_format")]
+        value: Option<Vec<u8>>,
+    },
+    StorageWrite {
+        #[serde(with = "base64_format")]
+        key: Vec<u8>,
+        #[serde(with = "base64_format")]
+        value: Vec<u8>,
+        #[serde(with = "option_base64_format")]
+        evicted: Option<Vec<u8>>,
+    },
+    StorageRemove {
+        #[serde(with = "base64_format")]
+        key: Vec<u8>,
+        #[serde(with = "option_base64_format")]
+        removed: Option<Vec<u8>>,
+    },
+    StorageHasKey {
+        #[serde(with = "base64_format")]
+        key: Vec<u8>,
+        found: bool,
+    },
+    /// A promise that creates a new receipt towards `receiver_id`.
+    PromiseCreated { promise_index: u64, receipt_index: u64, receiver_id: AccountId },
+}
+
+impl ExecutionTrace {
+    #[inline]
+    pub fn push(&mut self, event: TraceEvent) {
+        self.events.push(event);
+    }
+
+    /// Appends the events of a later call, e.g. of the next action of the same receipt.
+    pub fn merge(&mut self, other: ExecutionTrace) {
+        self.events.extend(other.events);
+    }
+
+    /// Sets the gas charged by the host call recorded at `index`.
+    pub fn set_host_call_gas(&mut self, index: usize, burnt: Gas, used: Gas) {
+        if let Some(TraceEvent::HostCall { gas_burnt, gas_used, .. }) = self.events.get_mut(index) {
+            *gas_burnt = burnt;
+            *gas_used = used;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_json_roundtrip() {
+        let mut trace = ExecutionTrace::default();
+        trace.push(TraceEvent::HostCall {
+            name: "storage_write".to_string(),
+            args: vec![3, 0, 5, 8, 0],
+            gas_burnt: 0,
+            gas_used: 0,
+        });
+        trace.set_host_call_gas(0, 10, 12);
+        trace.push(TraceEvent::StorageWrite {
+            key: b"key".to_vec(),
+            value: b"value".to_vec(),
+            evicted: None,
+        });
+
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(json["events"][0]["kind"], "host_call");
+        assert_eq!(json["events"][0]["gas_burnt"], 10);
+        assert_eq!(json["events"][1]["key"], "a2V5");
+        assert_eq!(json["events"][1]["evicted"], serde_json::Value::Null);
+
+        let decoded: ExecutionTrace = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, trace);
+    }
+}
@@ -10,6 +10,7 @@ use crate::crypto::{PublicKey, Signature};
 use crate::contract_runtime::{CryptoHash, hash_bytes, AccountId, Balance, Gas, Nonce};
 use crate::serialize::{base64_format, u128_dec_format_compatible};
 use crate::profile::ProfileData;
+use crate::trace::ExecutionTrace;
 
 use crate::errors::TxExecutionError;
 
@@ -46,6 +47,8 @@ pub enum Action {
     DeployContract(DeployContractAction),
     FunctionCall(FunctionCallAction),
     DeleteAccount(DeleteAccountAction),
+    /// Sets a Wasm code to a receiver_id and encrypts its contract storage
+    DeployEncryptedContract(DeployEncryptedContractAction),
 }
 
 impl Action {
@@ -99,6 +102,32 @@ impl From<DeployContractAction> for Action {
     }
 }
 
+/// Deploy contract action that also switches the contract storage to
+/// `ContractStorageMode::Encrypted`. The mode can only change while the contract storage is empty.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct DeployEncryptedContractAction {
+    /// WebAssembly binary
+    #[serde(with = "base64_format")]
+    pub code: Vec<u8>,
+    /// Whether storage keys are blinded with an HMAC as well
+    pub blind_keys: bool,
+}
+
+impl From<DeployEncryptedContractAction> for Action {
+    fn from(deploy_encrypted_contract_action: DeployEncryptedContractAction) -> Self {
+        Self::DeployEncryptedContract(deploy_encrypted_contract_action)
+    }
+}
+
+impl fmt::Debug for DeployEncryptedContractAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeployEncryptedContractAction")
+            .field("code", &format_args!("{}", crate::logging::pretty_utf8(&self.code)))
+            .field("blind_keys", &self.blind_keys)
+            .finish()
+    }
+}
+
 impl fmt::Debug for DeployContractAction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("DeployContractAction")
@@ -298,6 +327,10 @@ pub struct ExecutionOutcome {
     /// Execution metadata, versioned 
 	/// DIFF: changed to ProfileData
     pub profile_data: Option<ProfileData>,
+    /// Trace of the contract calls, only present if the receipt was applied in trace mode.
+    /// It is not part of the outcome hash.
+    #[borsh_skip]
+    pub trace: Option<ExecutionTrace>,
 }
 
 impl ExecutionOutcome {
@@ -444,6 +477,7 @@ mod tests {
             tokens_burnt: 1234000,
             executor_id: AccountId::system(),
             profile_data: None,
+            trace: None,
         };
         let hashes = outcome.to_hashes();
         assert_eq!(hashes.len(), 3);
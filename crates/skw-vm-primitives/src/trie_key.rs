@@ -30,6 +30,9 @@ pub(crate) mod col {
     pub const DELAYED_RECEIPT: &[u8] = &[7];
     /// This column id is used when storing Key-Value data from a contract on an `account_id`.
     pub const CONTRACT_DATA: &[u8] = &[8];
+    /// This column id is used when storing `primitives::account::ContractStorageMode` of the
+    /// contract deployed on a given `account_id`.
+    pub const CONTRACT_STORAGE_MODE: &[u8] = &[9];
     /// All columns
     pub const NON_DELAYED_RECEIPT_COLUMNS: &[(&[u8], &str)] = &[
         (ACCOUNT, "Account"),
@@ -39,6 +42,7 @@ pub(crate) mod col {
         (PENDING_DATA_COUNT, "PendingDataCount"),
         (POSTPONED_RECEIPT, "PostponedReceipt"),
         (CONTRACT_DATA, "ContractData"),
+        (CONTRACT_STORAGE_MODE, "ContractStorageMode"),
     ];
 }
 
@@ -74,6 +78,9 @@ pub enum TrieKey {
     /// Used to store a key-value record `Vec<u8>` within a contract deployed on a given `AccountId`
     /// and a given key.
     ContractData { account_id: AccountId, key: Vec<u8> },
+    /// Used to store `primitives::account::ContractStorageMode` for a given `AccountId`. Absent for
+    /// contracts that keep their data in plain text.
+    ContractStorageMode { account_id: AccountId },
 }
 
 impl TrieKey {
@@ -113,6 +120,9 @@ impl TrieKey {
                     + ACCOUNT_DATA_SEPARATOR.len()
                     + key.len()
             }
+            TrieKey::ContractStorageMode { account_id } => {
+                col::CONTRACT_STORAGE_MODE.len() + account_id.len()
+            }
         }
     }
 
@@ -165,6 +175,10 @@ impl TrieKey {
                 res.extend(ACCOUNT_DATA_SEPARATOR);
                 res.extend(key);
             }
+            TrieKey::ContractStorageMode { account_id } => {
+                res.extend(col::CONTRACT_STORAGE_MODE);
+                res.extend(account_id.as_ref().try_to_vec().unwrap());
+            }
         };
         debug_assert_eq!(res.len(), expected_len);
         res
@@ -249,6 +263,13 @@ pub mod trie_key_parsers {
         parse_account_id_from_slice(account_id, "ContractCode")
     }
 
+    pub fn parse_account_id_from_contract_storage_mode_key(
+        raw_key: &[u8],
+    ) -> Result<AccountId, std::io::Error> {
+        let account_id = parse_account_id_prefix(col::CONTRACT_STORAGE_MODE, raw_key)?;
+        parse_account_id_from_slice(account_id, "ContractStorageMode")
+    }
+
     #[allow(unused)]
     pub fn parse_account_id_from_raw_key(
         raw_key: &[u8],
@@ -260,6 +281,9 @@ pub mod trie_key_parsers {
             let account_id = match *col {
                 col::ACCOUNT => parse_account_id_from_account_key(raw_key)?,
                 col::CONTRACT_CODE => parse_account_id_from_contract_code_key(raw_key)?,
+                col::CONTRACT_STORAGE_MODE => {
+                    parse_account_id_from_contract_storage_mode_key(raw_key)?
+                }
                 _ => parse_account_id_from_trie_key_with_separator(col, raw_key, col_name)?,
             };
             return Ok(Some(account_id));
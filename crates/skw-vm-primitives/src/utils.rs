@@ -1,6 +1,6 @@
 use byteorder::{LittleEndian, WriteBytesExt};
 
-use crate::contract_runtime::{hash_bytes, CryptoHash};
+use crate::contract_runtime::{hash_bytes, BlockNumber, CryptoHash};
 use crate::receipt::Receipt;
 use crate::transaction::SignedTransaction;
 use std::mem::size_of;
@@ -72,6 +72,22 @@ pub fn create_random_seed(
     res.as_ref().to_vec()
 }
 
+/// Creates the block random seed for `ApplyState` from the shard secret and the block context,
+/// so that it cannot be predicted outside the enclave.
+pub fn create_block_random_seed(
+    shard_secret: &CryptoHash,
+    prev_state_root: &CryptoHash,
+    block_number: BlockNumber,
+) -> CryptoHash {
+    let mut bytes: Vec<u8> = Vec::with_capacity(
+        size_of::<CryptoHash>() + size_of::<CryptoHash>() + size_of::<BlockNumber>(),
+    );
+    bytes.extend_from_slice(shard_secret.as_ref());
+    bytes.extend_from_slice(prev_state_root.as_ref());
+    bytes.extend_from_slice(&block_number.to_le_bytes());
+    hash_bytes(&bytes)
+}
+
 fn create_nonce_with_nonce(base: &CryptoHash, salt: u64) -> CryptoHash {
     let mut nonce: Vec<u8> = base.as_ref().to_owned();
     nonce.extend(index_to_bytes(salt));
@@ -4,15 +4,16 @@ use skw_vm_primitives::receipt::{ActionReceipt, Receipt};
 use skw_vm_primitives::fees::{RuntimeFeesConfig};
 // use skw_vm_primitives::config::{AccountCreationConfig};
 
-use skw_vm_primitives::account::{Account};
+use skw_vm_primitives::account::{Account, ContractStorageMode};
 use skw_vm_primitives::transaction::{
-    Action, DeleteAccountAction, DeployContractAction,
+    Action, DeleteAccountAction, DeployContractAction, DeployEncryptedContractAction,
     FunctionCallAction, TransferAction,
 };
+use skw_vm_primitives::trie_key::trie_key_parsers;
 use skw_vm_primitives::utils::create_random_seed;
 
 use skw_vm_store::{
-    get_code, remove_account, set_code,
+    get_code, get_contract_storage_mode, remove_account, set_code, set_contract_storage_mode,
     StorageError, TrieUpdate,
 };
 use skw_vm_primitives::errors::{
@@ -23,6 +24,7 @@ use skw_vm_host::{VMContext, VMOutcome};
 
 use crate::config::{safe_add_gas, RuntimeConfig};
 use crate::ext::{RuntimeExt};
+use crate::storage_encryption::ContractStorageCipher;
 use crate::{ActionResult, ApplyState};
 use skw_vm_primitives::config::ViewConfig;
 
@@ -83,11 +85,13 @@ pub(crate) fn execute_function_call(
         attached_deposit: function_call.deposit,
         prepaid_gas: function_call.gas,
         random_seed,
+        shard_secret: apply_state.shard_secret,
         view_config,
         output_data_receivers,
+        trace: apply_state.trace_execution,
     };
 
-    skw_vm_engine::WasmiVM::run(
+    skw_vm_engine::run(
         &code,
         &function_call.method_name,
         runtime_ext,
@@ -175,7 +179,7 @@ pub(crate) fn check_actor_permissions(
     account_id: &AccountId,
 ) -> Result<(), ActionError> {
     match action {
-        Action::DeployContract(_) => {
+        Action::DeployContract(_) | Action::DeployEncryptedContract(_) => {
             if actor_id != account_id {
                 return Err(ActionErrorKind::ActorNoPermission {
                     account_id: account_id.clone(),
@@ -228,6 +232,7 @@ pub(crate) fn check_account_existence(
             }
         }
         Action::DeployContract(_)
+        | Action::DeployEncryptedContract(_)
         | Action::FunctionCall(_)
         | Action::DeleteAccount(_) => {
             if account.is_none() {
@@ -264,12 +269,18 @@ pub(crate) fn action_function_call(
         .into());
     }
 
+    let storage_cipher = ContractStorageCipher::for_account(
+        state_update,
+        account_id,
+        apply_state.shard_secret.as_ref(),
+    )?;
     let mut runtime_ext = RuntimeExt::new(
         state_update,
         account_id,
         &action_receipt.signer_id,
         action_receipt.gas_price,
         action_hash,
+        storage_cipher,
     );
 
     let (outcome, err) = execute_function_call(
@@ -361,6 +372,9 @@ pub(crate) fn action_function_call(
         result.logs.extend(outcome.logs.into_iter());
 
         result.profile.merge(&outcome.profile);
+        if let Some(trace) = outcome.trace {
+            result.trace.get_or_insert_with(Default::default).merge(trace);
+        }
         if execution_succeeded {
             account.set_amount(outcome.balance);
             account.set_storage_usage(outcome.storage_usage);
@@ -383,7 +397,45 @@ pub(crate) fn action_deploy_contract(
     deploy_contract: &DeployContractAction,
     apply_state: &ApplyState,
 ) -> Result<(), StorageError> {
-    let code = ContractCode::new(&deploy_contract.code);
+    deploy_code(state_update, account_id, account, &deploy_contract.code, apply_state)
+}
+
+/// Deploys the code and switches the contract storage to `ContractStorageMode::Encrypted`.
+/// Existing contract data would become unreadable, so the mode only changes while there is none.
+pub(crate) fn action_deploy_encrypted_contract(
+    state_update: &mut TrieUpdate,
+    account_id: &AccountId,
+    account: &mut Account,
+    deploy_contract: &DeployEncryptedContractAction,
+    apply_state: &ApplyState,
+    result: &mut ActionResult,
+) -> Result<(), StorageError> {
+    let mode = ContractStorageMode::Encrypted { blind_keys: deploy_contract.blind_keys };
+    if get_contract_storage_mode(state_update, account_id)? != mode {
+        let has_data = state_update
+            .iter(&trie_key_parsers::get_raw_prefix_for_contract_data(account_id, &[]))?
+            .next()
+            .is_some();
+        if has_data {
+            result.result = Err(ActionErrorKind::ContractStorageNotEmpty {
+                account_id: account_id.clone(),
+            }
+            .into());
+            return Ok(());
+        }
+        set_contract_storage_mode(state_update, account_id.clone(), mode);
+    }
+    deploy_code(state_update, account_id, account, &deploy_contract.code, apply_state)
+}
+
+fn deploy_code(
+    state_update: &mut TrieUpdate,
+    account_id: &AccountId,
+    account: &mut Account,
+    code: &[u8],
+    apply_state: &ApplyState,
+) -> Result<(), StorageError> {
+    let code = ContractCode::new(code);
     let prev_code = get_code(state_update, account_id, Some(account.code_hash()))?;
     let prev_code_length = prev_code.map(|code| code.code.len() as u64).unwrap_or_default();
     
@@ -402,15 +454,7 @@ pub(crate) fn action_deploy_contract(
     // Precompile the contract and store result (compiled code or error) in the database.
     // Note, that contract compilation costs are already accounted in deploy cost using
     // special logic in estimator (see get_runtime_config() function).
-    skw_vm_engine::create_module_instance(
-        &code,
-        &apply_state.config.wasm_config,
-        skw_vm_engine::WasmiMemory::new(
-            apply_state.config.wasm_config.limit_config.initial_memory_pages,
-            apply_state.config.wasm_config.limit_config.max_memory_pages,
-        ).expect("Cannot create memory for a contract call").clone()
-    )
-    .ok();
+    skw_vm_engine::precompile(&code, &apply_state.config.wasm_config).ok();
     Ok(())
 }
 
@@ -520,4 +564,88 @@ mod tests {
             })
         );
     }
+
+    fn deploy_encrypted(
+        state_update: &mut TrieUpdate,
+        account: &mut Account,
+        blind_keys: bool,
+    ) -> ActionResult {
+        let apply_state = ApplyState {
+            block_number: 1,
+            prev_block_hash: CryptoHash::default(),
+            block_hash: CryptoHash::default(),
+            gas_price: 0,
+            block_timestamp: 1,
+            gas_limit: None,
+            random_seed: CryptoHash::default(),
+            shard_secret: None,
+            trace_execution: false,
+            config: std::sync::Arc::new(RuntimeConfig::test()),
+        };
+        let mut action_result = ActionResult::default();
+        let deploy_contract = DeployEncryptedContractAction {
+            code: near_test_contracts::rs_contract().to_vec(),
+            blind_keys,
+        };
+        let res = action_deploy_encrypted_contract(
+            state_update,
+            &AccountId::test(),
+            account,
+            &deploy_contract,
+            &apply_state,
+            &mut action_result,
+        );
+        assert!(res.is_ok());
+        action_result
+    }
+
+    #[test]
+    fn test_deploy_encrypted_contract() {
+        let tries = create_tries();
+        let mut state_update = tries.new_trie_update(CryptoHash::default());
+        let account_id = AccountId::test();
+        let mut account = Account::new(100, 0, CryptoHash::default(), 100, 0u64);
+        let code = ContractCode::new(near_test_contracts::rs_contract());
+
+        let action_result = deploy_encrypted(&mut state_update, &mut account, false);
+        assert!(action_result.result.is_ok());
+        assert_eq!(
+            get_contract_storage_mode(&state_update, &account_id).unwrap(),
+            ContractStorageMode::Encrypted { blind_keys: false }
+        );
+        assert_eq!(account.code_hash(), code.hash);
+        assert_eq!(account.storage_usage(), 100 + code.code.len() as u64);
+
+        // redeploying in the same mode keeps the data readable
+        state_update.set(
+            TrieKey::ContractData { account_id: account_id.clone(), key: b"key".to_vec() },
+            b"value".to_vec(),
+        );
+        let action_result = deploy_encrypted(&mut state_update, &mut account, false);
+        assert!(action_result.result.is_ok());
+        assert_eq!(account.storage_usage(), 100 + code.code.len() as u64);
+
+        // switching the mode would strand the existing data
+        let action_result = deploy_encrypted(&mut state_update, &mut account, true);
+        assert_eq!(
+            action_result.result,
+            Err(ActionError {
+                index: None,
+                kind: ActionErrorKind::ContractStorageNotEmpty { account_id: account_id.clone() }
+            })
+        );
+        assert_eq!(
+            get_contract_storage_mode(&state_update, &account_id).unwrap(),
+            ContractStorageMode::Encrypted { blind_keys: false }
+        );
+
+        state_update
+            .remove(TrieKey::ContractData { account_id: account_id.clone(), key: b"key".to_vec() });
+        let action_result = deploy_encrypted(&mut state_update, &mut account, true);
+        assert!(action_result.result.is_ok());
+        assert_eq!(
+            get_contract_storage_mode(&state_update, &account_id).unwrap(),
+            ContractStorageMode::Encrypted { blind_keys: true }
+        );
+    }
 }
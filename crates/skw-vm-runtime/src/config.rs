@@ -5,9 +5,11 @@ use num_traits::pow::Pow;
 use skw_vm_primitives::errors::IntegerOverflowError;
 pub use skw_vm_primitives::num_rational::Rational;
 pub use skw_vm_primitives::config::RuntimeConfig;
+use skw_vm_primitives::config::ActionCosts;
+use skw_vm_primitives::profile::ProfileData;
 use skw_vm_primitives::fees::{transfer_exec_fee, transfer_send_fee, RuntimeFeesConfig};
 use skw_vm_primitives::transaction::{
-    Action, DeployContractAction, FunctionCallAction, Transaction,
+    Action, DeployContractAction, DeployEncryptedContractAction, FunctionCallAction, Transaction,
 };
 use skw_vm_primitives::contract_runtime::{AccountId, Balance, Gas};
 
@@ -61,6 +63,31 @@ macro_rules! safe_add_balance_apply {
     }
 }
 
+/// Gas that needs to be burnt to send this action.
+pub fn send_fee(config: &RuntimeFeesConfig, sender_is_receiver: bool, action: &Action) -> Gas {
+    let cfg = &config.action_creation_config;
+    use Action::*;
+
+    match action {
+        CreateAccount(_) => cfg.create_account_cost.send_fee(sender_is_receiver),
+        DeployContract(DeployContractAction { code })
+        | DeployEncryptedContract(DeployEncryptedContractAction { code, .. }) => {
+            let num_bytes = code.len() as u64;
+            cfg.deploy_contract_cost.send_fee(sender_is_receiver)
+                + cfg.deploy_contract_cost_per_byte.send_fee(sender_is_receiver) * num_bytes
+        }
+        FunctionCall(FunctionCallAction { method_name, args, .. }) => {
+            let num_bytes = method_name.as_bytes().len() as u64 + args.len() as u64;
+            cfg.function_call_cost.send_fee(sender_is_receiver)
+                + cfg.function_call_cost_per_byte.send_fee(sender_is_receiver) * num_bytes
+        }
+        Transfer(_) => {
+            transfer_send_fee(cfg, sender_is_receiver)
+        },
+        DeleteAccount(_) => cfg.delete_account_cost.send_fee(sender_is_receiver),
+    }
+}
+
 /// Total sum of gas that needs to be burnt to send these actions.
 pub fn total_send_fees(
     config: &RuntimeFeesConfig,
@@ -68,33 +95,47 @@ pub fn total_send_fees(
     actions: &[Action],
     _receiver_id: &AccountId,
 ) -> Result<Gas, IntegerOverflowError> {
-    let cfg = &config.action_creation_config;
     let mut result = 0;
-
     for action in actions {
-        use Action::*;
-        let delta = match action {
-            CreateAccount(_) => cfg.create_account_cost.send_fee(sender_is_receiver),
-            DeployContract(DeployContractAction { code }) => {
-                let num_bytes = code.len() as u64;
-                cfg.deploy_contract_cost.send_fee(sender_is_receiver)
-                    + cfg.deploy_contract_cost_per_byte.send_fee(sender_is_receiver) * num_bytes
-            }
-            FunctionCall(FunctionCallAction { method_name, args, .. }) => {
-                let num_bytes = method_name.as_bytes().len() as u64 + args.len() as u64;
-                cfg.function_call_cost.send_fee(sender_is_receiver)
-                    + cfg.function_call_cost_per_byte.send_fee(sender_is_receiver) * num_bytes
-            }
-            Transfer(_) => {
-                transfer_send_fee(cfg, sender_is_receiver)
-            },
-            DeleteAccount(_) => cfg.delete_account_cost.send_fee(sender_is_receiver),
-        };
+        let delta = send_fee(config, sender_is_receiver, action);
         result = safe_add_gas(result, delta)?;
     }
     Ok(result)
 }
 
+/// The kind of action cost the fees of this action are accounted under in a gas profile.
+pub fn action_cost_kind(action: &Action) -> ActionCosts {
+    match action {
+        Action::CreateAccount(_) => ActionCosts::create_account,
+        Action::DeployContract(_) | Action::DeployEncryptedContract(_) => {
+            ActionCosts::deploy_contract
+        }
+        Action::FunctionCall(_) => ActionCosts::function_call,
+        Action::Transfer(_) => ActionCosts::transfer,
+        Action::DeleteAccount(_) => ActionCosts::delete_account,
+    }
+}
+
+/// Profile of the gas burnt for converting the transaction into a receipt, see `tx_cost`.
+pub fn tx_cost_profile(
+    config: &RuntimeFeesConfig,
+    transaction: &Transaction,
+    sender_is_receiver: bool,
+) -> ProfileData {
+    let mut profile = ProfileData::new();
+    profile.add_action_cost(
+        ActionCosts::new_receipt,
+        config.action_receipt_creation_config.send_fee(sender_is_receiver),
+    );
+    for action in &transaction.actions {
+        profile.add_action_cost(
+            action_cost_kind(action),
+            send_fee(config, sender_is_receiver, action),
+        );
+    }
+    profile
+}
+
 pub fn exec_fee(
     config: &RuntimeFeesConfig,
     action: &Action,
@@ -105,7 +146,8 @@ pub fn exec_fee(
 
     match action {
         CreateAccount(_) => cfg.create_account_cost.exec_fee(),
-        DeployContract(DeployContractAction { code }) => {
+        DeployContract(DeployContractAction { code })
+        | DeployEncryptedContract(DeployEncryptedContractAction { code, .. }) => {
             let num_bytes = code.len() as u64;
             cfg.deploy_contract_cost.exec_fee()
                 + cfg.deploy_contract_cost_per_byte.exec_fee() * num_bytes
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use log::debug;
 
@@ -10,9 +11,12 @@ use skw_vm_primitives::transaction::{
 use skw_vm_primitives::trie_key::{trie_key_parsers, TrieKey};
 use skw_vm_primitives::contract_runtime::{AccountId, Balance, CryptoHash, ContractCode};
 use skw_vm_primitives::utils::create_data_id;
-use skw_vm_store::{get_code, TrieUpdate, TrieUpdateValuePtr};
+use skw_vm_store::{get_code, TrieUpdate, TrieUpdateIterator, TrieUpdateValuePtr};
 use skw_vm_primitives::errors::{HostError, VMLogicError};
 use skw_vm_host::{RuntimeExternal as External, ValuePtr};
+use skw_vm_host::types::IteratorIndex;
+
+use crate::storage_encryption::{ContractStorageCipher, ENCRYPTION_OVERHEAD};
 
 pub struct RuntimeExt<'a> {
     trie_update: &'a mut TrieUpdate,
@@ -22,6 +26,20 @@ pub struct RuntimeExt<'a> {
     gas_price: Balance,
     action_hash: &'a CryptoHash,
     data_count: u64,
+    iters: HashMap<IteratorIndex, StorageIterCursor>,
+    last_iter_id: IteratorIndex,
+    storage_cipher: Option<ContractStorageCipher>,
+}
+
+/// Position of a contract storage iterator. Every step seeks the trie update again from `start`,
+/// so no borrow of the trie update outlives a host call.
+struct StorageIterCursor {
+    /// Raw trie key prefix of the iterated contract data.
+    prefix: Vec<u8>,
+    /// Smallest key after `prefix` that was not returned yet.
+    start: Vec<u8>,
+    /// Exclusive upper bound after `prefix`, if any.
+    end: Option<Vec<u8>>,
 }
 
 /// Error used by `RuntimeExt`.
@@ -39,15 +57,28 @@ impl From<ExternalError> for VMLogicError {
     }
 }
 
-pub struct RuntimeExtValuePtr<'a>(TrieUpdateValuePtr<'a>);
+pub struct RuntimeExtValuePtr<'a> {
+    ptr: TrieUpdateValuePtr<'a>,
+    /// The cipher of an encrypted contract storage and the key the value is stored under.
+    cipher: Option<(&'a ContractStorageCipher, Vec<u8>)>,
+}
 
 impl<'a> ValuePtr for RuntimeExtValuePtr<'a> {
     fn len(&self) -> u32 {
-        self.0.len()
+        match &self.cipher {
+            Some(_) => self.ptr.len().saturating_sub(ENCRYPTION_OVERHEAD as u32),
+            None => self.ptr.len(),
+        }
     }
 
     fn deref(&self) -> ExtResult<Vec<u8>> {
-        self.0.deref_value().map_err(wrap_storage_error)
+        let value = self.ptr.deref_value().map_err(wrap_storage_error)?;
+        match &self.cipher {
+            Some((cipher, storage_key)) => {
+                cipher.decrypt(storage_key, &value).map_err(wrap_storage_error)
+            }
+            None => Ok(value),
+        }
     }
 }
 
@@ -58,6 +89,7 @@ impl<'a> RuntimeExt<'a> {
         signer_id: &'a AccountId,
         gas_price: Balance,
         action_hash: &'a CryptoHash,
+        storage_cipher: Option<ContractStorageCipher>,
     ) -> Self {
         RuntimeExt {
             trie_update,
@@ -67,6 +99,9 @@ impl<'a> RuntimeExt<'a> {
             gas_price,
             action_hash,
             data_count: 0,
+            iters: HashMap::new(),
+            last_iter_id: 0,
+            storage_cipher,
         }
     }
 
@@ -85,7 +120,48 @@ impl<'a> RuntimeExt<'a> {
     }
 
     pub fn create_storage_key(&self, key: &[u8]) -> TrieKey {
-        TrieKey::ContractData { account_id: self.account_id.clone(), key: key.to_vec() }
+        let key = match &self.storage_cipher {
+            Some(cipher) => cipher.storage_key(key),
+            None => key.to_vec(),
+        };
+        TrieKey::ContractData { account_id: self.account_id.clone(), key }
+    }
+
+    /// Blinded keys are HMACs of the contract keys, so neither their order nor their prefixes
+    /// mean anything to the contract.
+    fn check_keys_not_blinded(&self) -> ExtResult<()> {
+        match &self.storage_cipher {
+            Some(cipher) if cipher.blinds_keys() => Err(HostError::BlindedStorageKeys.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Wraps a pointer to the value of the contract key `key`.
+    fn value_ptr<'b>(&'b self, key: &[u8], ptr: TrieUpdateValuePtr<'b>) -> Box<dyn ValuePtr + 'b> {
+        let cipher = self.storage_cipher.as_ref().map(|cipher| (cipher, cipher.storage_key(key)));
+        Box::new(RuntimeExtValuePtr { ptr, cipher })
+    }
+
+    /// Registers an iterator over the contract data of the current account, either over all keys
+    /// starting with `prefix` or, when `end` is given, over the keys in `[prefix, end)`.
+    fn create_iterator(&mut self, prefix: &[u8], end: Option<&[u8]>) -> ExtResult<IteratorIndex> {
+        self.check_keys_not_blinded()?;
+        let cursor = match end {
+            Some(end) => StorageIterCursor {
+                prefix: trie_key_parsers::get_raw_prefix_for_contract_data(self.account_id, &[]),
+                start: prefix.to_vec(),
+                end: Some(end.to_vec()),
+            },
+            None => StorageIterCursor {
+                prefix: trie_key_parsers::get_raw_prefix_for_contract_data(self.account_id, prefix),
+                start: vec![],
+                end: None,
+            },
+        };
+        let iterator_index = self.last_iter_id;
+        self.iters.insert(iterator_index, cursor);
+        self.last_iter_id += 1;
+        Ok(iterator_index)
     }
 
     fn new_data_id(&mut self) -> CryptoHash {
@@ -130,7 +206,11 @@ type ExtResult<T> = ::std::result::Result<T, VMLogicError>;
 impl<'a> External for RuntimeExt<'a> {
     fn storage_set(&mut self, key: &[u8], value: &[u8]) -> ExtResult<()> {
         let storage_key = self.create_storage_key(key);
-        self.trie_update.set(storage_key, Vec::from(value));
+        let value = match (&self.storage_cipher, &storage_key) {
+            (Some(cipher), TrieKey::ContractData { key, .. }) => cipher.encrypt(key, value),
+            _ => Vec::from(value),
+        };
+        self.trie_update.set(storage_key, value);
         Ok(())
     }
 
@@ -139,7 +219,7 @@ impl<'a> External for RuntimeExt<'a> {
         self.trie_update
             .get_ref(&storage_key)
             .map_err(wrap_storage_error)
-            .map(|option| option.map(|ptr| Box::new(RuntimeExtValuePtr(ptr)) as Box<_>))
+            .map(|option| option.map(|ptr| self.value_ptr(key, ptr)))
     }
 
     fn storage_remove(&mut self, key: &[u8]) -> ExtResult<()> {
@@ -154,6 +234,7 @@ impl<'a> External for RuntimeExt<'a> {
     }
 
     fn storage_remove_subtree(&mut self, prefix: &[u8]) -> ExtResult<()> {
+        self.check_keys_not_blinded()?;
         let data_keys = self
             .trie_update
             .iter(&trie_key_parsers::get_raw_prefix_for_contract_data(self.account_id, prefix))
@@ -176,6 +257,68 @@ impl<'a> External for RuntimeExt<'a> {
         Ok(())
     }
 
+    fn storage_iter(&mut self, prefix: &[u8]) -> ExtResult<IteratorIndex> {
+        self.create_iterator(prefix, None)
+    }
+
+    fn storage_iter_range(&mut self, start: &[u8], end: &[u8]) -> ExtResult<IteratorIndex> {
+        self.create_iterator(start, Some(end))
+    }
+
+    fn storage_iter_next<'b>(
+        &'b mut self,
+        iterator_idx: IteratorIndex,
+    ) -> ExtResult<Option<(Vec<u8>, Box<dyn ValuePtr + 'b>)>> {
+        let cursor = match self.iters.get_mut(&iterator_idx) {
+            Some(cursor) => cursor,
+            None => {
+                return Err(HostError::InvalidIteratorIndex { iterator_index: iterator_idx }.into())
+            }
+        };
+        let raw_key = TrieUpdateIterator::new(
+            &*self.trie_update,
+            &cursor.prefix,
+            &cursor.start,
+            cursor.end.as_deref(),
+        )
+        .and_then(|mut iter| iter.next().transpose())
+        .map_err(wrap_storage_error)?;
+        let raw_key = match raw_key {
+            Some(raw_key) => raw_key,
+            None => {
+                self.iters.remove(&iterator_idx);
+                return Ok(None);
+            }
+        };
+        // the next step resumes right after the returned key
+        cursor.start = raw_key[cursor.prefix.len()..].to_vec();
+        cursor.start.push(0);
+        let key = trie_key_parsers::parse_data_key_from_contract_data_key(&raw_key, self.account_id)
+            .map_err(|_e| {
+                wrap_storage_error(StorageError::StorageInconsistentState(
+                    "Can't parse data key from raw key for ContractData".to_string(),
+                ))
+            })?
+            .to_vec();
+        let this: &'b Self = self;
+        let value_ptr = this
+            .trie_update
+            .get_ref(&this.create_storage_key(&key))
+            .map_err(wrap_storage_error)?
+            .ok_or_else(|| {
+                wrap_storage_error(StorageError::StorageInconsistentState(
+                    "Iterated key has no value".to_string(),
+                ))
+            })?;
+        let value_ptr = this.value_ptr(&key, value_ptr);
+        Ok(Some((key, value_ptr)))
+    }
+
+    fn storage_iter_drop(&mut self, iterator_idx: IteratorIndex) -> ExtResult<()> {
+        self.iters.remove(&iterator_idx);
+        Ok(())
+    }
+
     fn create_receipt(
         &mut self,
         receipt_indices: Vec<u64>,
@@ -257,6 +400,13 @@ impl<'a> External for RuntimeExt<'a> {
         Ok(())
     }
 
+    fn storage_record_len(&self, key_len: u64, value_len: u64) -> u64 {
+        match &self.storage_cipher {
+            Some(cipher) => cipher.record_len(key_len, value_len),
+            None => key_len + value_len,
+        }
+    }
+
     fn get_touched_nodes_count(&self) -> u64 {
         self.trie_update.trie.counter.get()
     }
@@ -266,3 +416,122 @@ impl<'a> External for RuntimeExt<'a> {
 
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use skw_vm_primitives::contract_runtime::MerkleHash;
+    use skw_vm_store::test_utils::create_tries;
+
+    use super::*;
+
+    fn data_key(key: &[u8]) -> TrieKey {
+        TrieKey::ContractData { account_id: AccountId::test(), key: key.to_vec() }
+    }
+
+    fn read(ext: &RuntimeExt, key: &[u8]) -> ExtResult<Option<Vec<u8>>> {
+        ext.storage_get(key)?.map(|value_ptr| value_ptr.deref()).transpose()
+    }
+
+    fn next_key(ext: &mut RuntimeExt, iterator_idx: IteratorIndex) -> Option<(Vec<u8>, Vec<u8>)> {
+        ext.storage_iter_next(iterator_idx)
+            .unwrap()
+            .map(|(key, value_ptr)| (key, value_ptr.deref().unwrap()))
+    }
+
+    #[test]
+    fn test_storage_iter_resumes_after_writes() {
+        let tries = create_tries();
+        let mut state_update = tries.new_trie_update(MerkleHash::default());
+        let account_id = AccountId::test();
+        let action_hash = CryptoHash::default();
+        let mut ext =
+            RuntimeExt::new(&mut state_update, &account_id, &account_id, 0, &action_hash, None);
+        for key in [&b"a1"[..], b"a2", b"b1"] {
+            ext.storage_set(key, &[key[1]]).unwrap();
+        }
+
+        let iter = ext.storage_iter(b"a").unwrap();
+        assert_eq!(next_key(&mut ext, iter), Some((b"a1".to_vec(), b"1".to_vec())));
+        // a write between two steps is seen by the next seek
+        ext.storage_set(b"a15", b"5").unwrap();
+        assert_eq!(next_key(&mut ext, iter), Some((b"a15".to_vec(), b"5".to_vec())));
+        assert_eq!(next_key(&mut ext, iter), Some((b"a2".to_vec(), b"2".to_vec())));
+        assert_eq!(next_key(&mut ext, iter), None);
+        assert!(ext.storage_iter_next(iter).is_err());
+
+        let iter = ext.storage_iter_range(b"a2", b"b2").unwrap();
+        assert_eq!(next_key(&mut ext, iter), Some((b"a2".to_vec(), b"2".to_vec())));
+        assert_eq!(next_key(&mut ext, iter), Some((b"b1".to_vec(), b"1".to_vec())));
+        assert_eq!(next_key(&mut ext, iter), None);
+
+        let iter = ext.storage_iter(b"a").unwrap();
+        ext.storage_iter_drop(iter).unwrap();
+        assert!(ext.storage_iter_next(iter).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_storage_round_trip() {
+        let tries = create_tries();
+        let mut state_update = tries.new_trie_update(MerkleHash::default());
+        let account_id = AccountId::test();
+        let action_hash = CryptoHash::default();
+        let cipher = || Some(ContractStorageCipher::new(&[1; 32], &account_id, false));
+
+        let mut ext =
+            RuntimeExt::new(&mut state_update, &account_id, &account_id, 0, &action_hash, cipher());
+        ext.storage_set(b"key", b"value").unwrap();
+        ext.storage_set(b"other", b"another value").unwrap();
+        assert_eq!(read(&ext, b"key"), Ok(Some(b"value".to_vec())));
+        assert_eq!(ext.storage_get(b"key").unwrap().unwrap().len(), 5);
+        assert_eq!(ext.storage_record_len(3, 5), 3 + 5 + ENCRYPTION_OVERHEAD as u64);
+        let iter = ext.storage_iter(b"k").unwrap();
+        assert_eq!(next_key(&mut ext, iter), Some((b"key".to_vec(), b"value".to_vec())));
+        ext.storage_remove(b"other").unwrap();
+        assert_eq!(read(&ext, b"other"), Ok(None));
+        ext.storage_set(b"other", b"another value").unwrap();
+        drop(ext);
+
+        // the trie only holds the ciphertext
+        let encrypted = state_update.get(&data_key(b"key")).unwrap().unwrap();
+        assert_eq!(encrypted.len(), 5 + ENCRYPTION_OVERHEAD);
+        assert!(!encrypted.windows(5).any(|window| window == b"value"));
+
+        // swapping values between keys is detected
+        let other = state_update.get(&data_key(b"other")).unwrap().unwrap();
+        state_update.set(data_key(b"key"), other);
+        let ext =
+            RuntimeExt::new(&mut state_update, &account_id, &account_id, 0, &action_hash, cipher());
+        assert!(read(&ext, b"key").is_err());
+        assert_eq!(read(&ext, b"other"), Ok(Some(b"another value".to_vec())));
+    }
+
+    #[test]
+    fn test_blinded_storage_round_trip() {
+        let tries = create_tries();
+        let mut state_update = tries.new_trie_update(MerkleHash::default());
+        let account_id = AccountId::test();
+        let action_hash = CryptoHash::default();
+        let cipher = ContractStorageCipher::new(&[1; 32], &account_id, true);
+        let blinded_key = cipher.storage_key(b"key");
+
+        let mut ext = RuntimeExt::new(
+            &mut state_update,
+            &account_id,
+            &account_id,
+            0,
+            &action_hash,
+            Some(cipher),
+        );
+        ext.storage_set(b"key", b"value").unwrap();
+        assert_eq!(read(&ext, b"key"), Ok(Some(b"value".to_vec())));
+        assert_eq!(ext.storage_has_key(b"key"), Ok(true));
+        assert_eq!(ext.storage_record_len(3, 5), 32 + 5 + ENCRYPTION_OVERHEAD as u64);
+        assert_eq!(ext.storage_iter(b"k"), Err(HostError::BlindedStorageKeys.into()));
+        assert_eq!(ext.storage_remove_subtree(b"k"), Err(HostError::BlindedStorageKeys.into()));
+        drop(ext);
+
+        assert_eq!(state_update.get(&data_key(b"key")), Ok(None));
+        let encrypted = state_update.get(&data_key(&blinded_key)).unwrap().unwrap();
+        assert_eq!(encrypted.len(), 5 + ENCRYPTION_OVERHEAD);
+    }
+}
@@ -5,7 +5,9 @@ use log::debug;
 
 pub use skw_vm_primitives::crypto;
 use skw_vm_primitives::errors::InvalidTxError;
+use skw_vm_primitives::config::ActionCosts;
 use skw_vm_primitives::profile::ProfileData;
+use skw_vm_primitives::trace::ExecutionTrace;
 pub use skw_vm_primitives::apply_state::ApplyState;
 use skw_vm_primitives::fees::RuntimeFeesConfig;
 
@@ -42,8 +44,8 @@ mod balance_checker;
 use crate::actions::*;
 use crate::balance_checker::check_balance;
 use crate::config::{
-    exec_fee, safe_add_balance, safe_add_gas, safe_gas_to_balance, RuntimeConfig,
-    total_deposit, total_prepaid_exec_fees, total_prepaid_gas,
+    action_cost_kind, exec_fee, safe_add_balance, safe_add_gas, safe_gas_to_balance,
+    total_deposit, total_prepaid_exec_fees, total_prepaid_gas, tx_cost_profile, RuntimeConfig,
 };
 use crate::verifier::validate_receipt;
 pub use crate::verifier::{validate_transaction, verify_and_charge_transaction};
@@ -53,6 +55,7 @@ pub mod cache;
 pub mod config;
 pub mod ext;
 pub mod state_viewer;
+pub mod storage_encryption;
 mod verifier;
 
 const EXPECT_ACCOUNT_EXISTS: &str = "account exists, checked above";
@@ -97,6 +100,7 @@ pub struct ActionResult {
     pub logs: Vec<LogEntry>,
     pub new_receipts: Vec<Receipt>,
     pub profile: ProfileData,
+    pub trace: Option<ExecutionTrace>,
 }
 
 impl ActionResult {
@@ -116,6 +120,9 @@ impl ActionResult {
         )?;
         self.gas_used = safe_add_gas(self.gas_used, next_result.gas_used)?;
         self.profile.merge(&next_result.profile);
+        if let Some(trace) = next_result.trace {
+            self.trace.get_or_insert_with(Default::default).merge(trace);
+        }
         self.result = next_result.result;
         self.logs.append(&mut next_result.logs);
         if let Ok(ReturnData::ReceiptIndex(ref mut receipt_index)) = self.result {
@@ -141,6 +148,7 @@ impl Default for ActionResult {
             logs: vec![],
             new_receipts: vec![],
             profile: Default::default(),
+            trace: None,
         }
     }
 }
@@ -222,12 +230,13 @@ impl Runtime {
                         gas_burnt: verification_result.gas_burnt,
                         tokens_burnt: verification_result.burnt_amount,
                         executor_id: transaction.signer_id.clone(),
-
-                        // TODO: profile data is only counted in apply_action, which only happened at process_receipt
-                        // VerificationResult needs updates to incorporate profile data to support profile data of txns
-                        // metadata: ExecutionMetadata::V1,
                         // DIFF: added ProfileData in sync with skw-vm-primitives
-                        profile_data: None,
+                        profile_data: Some(tx_cost_profile(
+                            &apply_state.config.transaction_costs,
+                            transaction,
+                            transaction.signer_id == transaction.receiver_id,
+                        )),
+                        trace: None,
                     },
                 };
                 Ok((receipt, outcome))
@@ -262,6 +271,7 @@ impl Runtime {
         );
         result.gas_burnt += exec_fees;
         result.gas_used += exec_fees;
+        result.profile.add_action_cost(action_cost_kind(action), exec_fees);
         let account_id = &receipt.receiver_id;
 
         let is_the_only_action = actions.len() == 1;
@@ -305,6 +315,16 @@ impl Runtime {
                     apply_state,
                 )?;
             }
+            Action::DeployEncryptedContract(deploy_encrypted_contract) => {
+                action_deploy_encrypted_contract(
+                    state_update,
+                    account_id,
+                    account.as_mut().expect(EXPECT_ACCOUNT_EXISTS),
+                    deploy_encrypted_contract,
+                    apply_state,
+                    &mut result,
+                )?;
+            }
             Action::Transfer(transfer) => {
                 // metrics::ACTION_TRANSFER_TOTAL.inc();
                 if let Some(account) = account.as_mut() {
@@ -396,6 +416,7 @@ impl Runtime {
             apply_state.config.transaction_costs.action_receipt_creation_config.exec_fee();
         result.gas_used = exec_fee;
         result.gas_burnt = exec_fee;
+        result.profile.add_action_cost(ActionCosts::new_receipt, exec_fee);
         // Executing actions one by one
         for (action_index, action) in action_receipt.actions.iter().enumerate() {
             let action_hash = create_action_hash(
@@ -580,6 +601,7 @@ impl Runtime {
                 tokens_burnt,
                 executor_id: account_id.clone(),
                 profile_data: Some(result.profile),
+                trace: result.trace,
             },
         })
     }
@@ -1229,6 +1251,8 @@ mod tests {
             block_timestamp: 100,
             gas_limit: Some(gas_limit),
             random_seed: Default::default(),
+            shard_secret: None,
+            trace_execution: false,
             config: Arc::new(RuntimeConfig::test()),
         };
 
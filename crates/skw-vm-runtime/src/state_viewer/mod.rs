@@ -1,4 +1,5 @@
 use crate::{actions::execute_function_call, ext::RuntimeExt};
+use crate::storage_encryption::ContractStorageCipher;
 use log::debug;
 
 use skw_vm_primitives::config::RuntimeConfig;
@@ -10,12 +11,14 @@ use skw_vm_primitives::{
     trie_key::trie_key_parsers,
     account::{Account},
     contract_runtime::{AccountId, Gas, CryptoHash, ContractCode},
-    views::{StateItem, ViewApplyState, ViewStateResult},
+    views::{StateItem, TrieProofPath, ViewApplyState, ViewStateResult},
+    errors::StorageError,
 };
 
-use skw_vm_store::{get_account, get_code, TrieUpdate};
+use skw_vm_store::{get_account, get_code, Trie, TrieUpdate};
 use skw_vm_host::{ReturnData, ViewConfig};
 use std::{str, time::Instant};
+use std::rc::Rc;
 use std::sync::Arc;
 pub mod errors;
 
@@ -43,6 +46,27 @@ impl TrieViewer {
         Self { state_size_limit, max_gas_burnt_view }
     }
 
+    /// A view of the same committed state that records every trie node it reads.
+    fn recording_update(state_update: &TrieUpdate) -> (Rc<Trie>, TrieUpdate) {
+        let trie = Rc::new(state_update.trie.recording_reads());
+        let update = TrieUpdate::new(trie.clone(), state_update.get_root());
+        (trie, update)
+    }
+
+    /// Trie nodes recorded so far, encoded as a proof against the state root.
+    fn recorded_proof(trie: &Trie) -> TrieProofPath {
+        trie.recorded_storage()
+            .map(|storage| storage.nodes.0.iter().map(to_base64).collect())
+            .unwrap_or_default()
+    }
+
+    /// Proof for a single trie key, read from the unrecorded trie.
+    fn key_proof(state_update: &TrieUpdate, key: &[u8]) -> Result<TrieProofPath, StorageError> {
+        let trie = state_update.trie.recording_reads();
+        trie.get(&state_update.get_root(), key)?;
+        Ok(Self::recorded_proof(&trie))
+    }
+
     pub fn view_account(
         &self,
         state_update: &TrieUpdate,
@@ -55,6 +79,17 @@ impl TrieViewer {
         })
     }
 
+    /// Same as `view_account`, along with the trie nodes proving the account against the state root.
+    pub fn view_account_with_proof(
+        &self,
+        state_update: &TrieUpdate,
+        account_id: &AccountId,
+    ) -> Result<(Account, TrieProofPath), errors::ViewAccountError> {
+        let (trie, recording_update) = Self::recording_update(state_update);
+        let account = self.view_account(&recording_update, account_id)?;
+        Ok((account, Self::recorded_proof(&trie)))
+    }
+
     pub fn view_contract_code(
         &self,
         state_update: &TrieUpdate,
@@ -73,7 +108,12 @@ impl TrieViewer {
         state_update: &TrieUpdate,
         account_id: &AccountId,
         prefix: &[u8],
+        include_proof: bool,
     ) -> Result<ViewStateResult, errors::ViewStateError> {
+        let recording = if include_proof { Some(Self::recording_update(state_update)) } else { None };
+        let unrecorded_update = state_update;
+        let state_update = recording.as_ref().map_or(unrecorded_update, |(_, update)| update);
+
         match get_account(state_update, account_id)? {
             Some(account) => {
                 let code_len = get_code(state_update, account_id, Some(account.code_hash()))?
@@ -104,14 +144,17 @@ impl TrieViewer {
             if !key.starts_with(query.as_ref()) {
                 break;
             }
+            let proof =
+                if include_proof { Self::key_proof(unrecorded_update, &key)? } else { vec![] };
             values.push(StateItem {
                 key: to_base64(&key[acc_sep_len..]),
                 value: to_base64(&value),
-                proof: vec![],
+                proof,
             });
         }
-        // TODO(2076): Add proofs for the storage items.
-        Ok(ViewStateResult { values, proof: vec![] })
+        let proof =
+            recording.as_ref().map(|(trie, _)| Self::recorded_proof(trie)).unwrap_or_default();
+        Ok(ViewStateResult { values, proof })
     }
 
     pub fn call_function(
@@ -133,12 +176,15 @@ impl TrieViewer {
         // TODO(#1015): Add ability to pass public key and originator_id
         let originator_id = contract_id;
         let empty_hash = CryptoHash::default();
+        // without the shard secret, encrypted contract storage can't be served to view calls
+        let storage_cipher = ContractStorageCipher::for_account(&state_update, contract_id, None)?;
         let mut runtime_ext = RuntimeExt::new(
             &mut state_update,
             contract_id,
             originator_id,
             0,
             &empty_hash,
+            storage_cipher,
         );
         
         let config = RuntimeConfig::test();
@@ -151,6 +197,9 @@ impl TrieViewer {
             block_timestamp: view_state.block_timestamp,
             gas_limit: None,
             random_seed: root,
+            // view calls never see the shard secret
+            shard_secret: None,
+            trace_execution: false,
             config: Arc::new(config.clone()),
         };
 
@@ -203,4 +252,158 @@ impl TrieViewer {
             Ok(result)
         }
     }
+
+    /// Same as `call_function`, along with the trie nodes read during the call. Replaying the
+    /// call against these nodes only reproduces the result, so it can be checked against the root.
+    pub fn call_function_with_proof(
+        &self,
+        state_update: TrieUpdate,
+        view_state: ViewApplyState,
+        contract_id: &AccountId,
+        method_name: &str,
+        args: &[u8],
+        logs: &mut Vec<String>,
+    ) -> Result<(Vec<u8>, TrieProofPath), errors::CallFunctionError> {
+        let (trie, recording_update) = Self::recording_update(&state_update);
+        let result =
+            self.call_function(recording_update, view_state, contract_id, method_name, args, logs)?;
+        Ok((result, Self::recorded_proof(&trie)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use skw_vm_primitives::contract_runtime::MerkleHash;
+    use skw_vm_primitives::serialize::from_base64;
+    use skw_vm_primitives::state::StateChangeCause;
+    use skw_vm_primitives::test_utils::account_new;
+    use skw_vm_primitives::trie_key::TrieKey;
+    use skw_vm_store::test_utils::create_tries;
+    use skw_vm_store::{
+        set_account, set_code, verify_account_proof, verify_contract_data_proof, ShardTries,
+    };
+
+    use super::*;
+
+    fn key(value: u64) -> Vec<u8> {
+        value.to_le_bytes().to_vec()
+    }
+
+    /// A committed state holding the test contract with `key(10) => key(20)` in its storage.
+    fn setup_state() -> (ShardTries, MerkleHash, Account) {
+        let tries = create_tries();
+        let mut state_update = tries.new_trie_update(MerkleHash::default());
+        let code = ContractCode::new(near_test_contracts::rs_contract());
+        let account = account_new(10u128.pow(24), code.hash, 0);
+        set_account(&mut state_update, AccountId::test(), &account);
+        set_code(&mut state_update, AccountId::test(), &code);
+        state_update
+            .set(TrieKey::ContractData { account_id: AccountId::test(), key: key(10) }, key(20));
+        state_update.commit(StateChangeCause::InitialState);
+        let trie_changes = state_update.finalize().unwrap().0;
+        let (store_update, root) = tries.apply_all(&trie_changes).unwrap();
+        store_update.commit().unwrap();
+        (tries, root, account)
+    }
+
+    fn view_state() -> ViewApplyState {
+        ViewApplyState {
+            block_number: 1,
+            prev_block_hash: CryptoHash::default(),
+            block_hash: CryptoHash::default(),
+            block_timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn test_view_account_proof() {
+        let (tries, root, account) = setup_state();
+        let viewer = TrieViewer::default();
+        let account_id = AccountId::test();
+
+        let (viewed, proof) =
+            viewer.view_account_with_proof(&tries.new_trie_update(root), &account_id).unwrap();
+        assert_eq!(viewed, account);
+        assert!(verify_account_proof(&root, &proof, &account_id, Some(&account)));
+
+        let mut other = account.clone();
+        other.set_amount(account.amount() + 1);
+        assert!(!verify_account_proof(&root, &proof, &account_id, Some(&other)));
+        assert!(!verify_account_proof(&root, &proof, &account_id, None));
+        assert!(!verify_account_proof(&[1; 32], &proof, &account_id, Some(&account)));
+    }
+
+    #[test]
+    fn test_view_state_proof() {
+        let (tries, root, account) = setup_state();
+        let viewer = TrieViewer::default();
+        let account_id = AccountId::test();
+
+        let result =
+            viewer.view_state(&tries.new_trie_update(root), &account_id, b"", true).unwrap();
+        assert_eq!(result.values.len(), 1);
+        let item = &result.values[0];
+        assert_eq!(from_base64(&item.key).unwrap(), key(10));
+        assert_eq!(from_base64(&item.value).unwrap(), key(20));
+
+        assert!(verify_contract_data_proof(
+            &root,
+            &item.proof,
+            &account_id,
+            &key(10),
+            Some(&key(20))
+        ));
+        assert!(!verify_contract_data_proof(
+            &root,
+            &item.proof,
+            &account_id,
+            &key(10),
+            Some(&key(21))
+        ));
+        assert!(!verify_contract_data_proof(&root, &item.proof, &account_id, &key(10), None));
+        assert!(verify_account_proof(&root, &result.proof, &account_id, Some(&account)));
+
+        let result =
+            viewer.view_state(&tries.new_trie_update(root), &account_id, b"", false).unwrap();
+        assert!(result.proof.is_empty());
+        assert!(result.values[0].proof.is_empty());
+    }
+
+    #[test]
+    fn test_call_function_proof() {
+        let (tries, root, account) = setup_state();
+        let viewer = TrieViewer::default();
+        let account_id = AccountId::test();
+
+        let mut logs = vec![];
+        let (result, proof) = viewer
+            .call_function_with_proof(
+                tries.new_trie_update(root),
+                view_state(),
+                &account_id,
+                "read_value",
+                &key(10),
+                &mut logs,
+            )
+            .unwrap();
+        assert_eq!(result, key(20));
+        assert!(verify_account_proof(&root, &proof, &account_id, Some(&account)));
+        assert!(verify_contract_data_proof(&root, &proof, &account_id, &key(10), Some(&key(20))));
+        assert!(!verify_contract_data_proof(&root, &proof, &account_id, &key(10), None));
+
+        // the proof of a read that found nothing shows the key is absent
+        let (result, proof) = viewer
+            .call_function_with_proof(
+                tries.new_trie_update(root),
+                view_state(),
+                &account_id,
+                "read_value",
+                &key(11),
+                &mut logs,
+            )
+            .unwrap();
+        assert!(result.is_empty());
+        assert!(verify_contract_data_proof(&root, &proof, &account_id, &key(11), None));
+        assert!(!verify_contract_data_proof(&root, &proof, &account_id, &key(11), Some(&key(20))));
+    }
 }
@@ -0,0 +1,152 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use xsalsa20poly1305::aead::{Aead, NewAead, Payload};
+use xsalsa20poly1305::{Key, Nonce, XSalsa20Poly1305};
+
+use skw_vm_primitives::account::ContractStorageMode;
+use skw_vm_primitives::borsh::BorshSerialize;
+use skw_vm_primitives::contract_runtime::{AccountId, CryptoHash};
+use skw_vm_store::{get_contract_storage_mode, StorageError, TrieUpdate};
+
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+
+/// Number of bytes an encrypted value is longer than the plain one.
+pub const ENCRYPTION_OVERHEAD: usize = NONCE_LEN + TAG_LEN;
+
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any size");
+    for part in parts {
+        mac.update(part);
+    }
+    let mut res = [0u8; 32];
+    res.copy_from_slice(&mac.finalize().into_bytes());
+    res
+}
+
+/// Encrypts the contract data of a single account under keys derived from the shard secret, so
+/// that `ColState`, state dumps and state patches carry no readable contract data.
+pub struct ContractStorageCipher {
+    cipher: XSalsa20Poly1305,
+    nonce_key: [u8; 32],
+    blind_key: Option<[u8; 32]>,
+}
+
+impl ContractStorageCipher {
+    pub fn new(shard_secret: &CryptoHash, account_id: &AccountId, blind_keys: bool) -> Self {
+        let account_id = account_id.as_ref().try_to_vec().unwrap();
+        let key = hmac_sha256(shard_secret, &[b"skw-storage-key", &account_id]);
+        let nonce_key = hmac_sha256(shard_secret, &[b"skw-storage-nonce", &account_id]);
+        let blind_key =
+            blind_keys.then(|| hmac_sha256(shard_secret, &[b"skw-storage-blind", &account_id]));
+        Self { cipher: XSalsa20Poly1305::new(Key::from_slice(&key)), nonce_key, blind_key }
+    }
+
+    /// Returns the cipher for the contract data of `account_id`, or `None` if the contract keeps
+    /// its data in plain text.
+    pub fn for_account(
+        state_update: &TrieUpdate,
+        account_id: &AccountId,
+        shard_secret: Option<&CryptoHash>,
+    ) -> Result<Option<Self>, StorageError> {
+        match get_contract_storage_mode(state_update, account_id)? {
+            ContractStorageMode::Plain => Ok(None),
+            ContractStorageMode::Encrypted { blind_keys } => match shard_secret {
+                Some(shard_secret) => Ok(Some(Self::new(shard_secret, account_id, blind_keys))),
+                None => Err(StorageError::StorageInconsistentState(format!(
+                    "Contract storage of {} is encrypted but the shard secret is not available",
+                    account_id
+                ))),
+            },
+        }
+    }
+
+    /// Whether storage keys are replaced by their HMAC. Blinded keys keep no order, so the
+    /// contract data cannot be iterated by prefix.
+    pub fn blinds_keys(&self) -> bool {
+        self.blind_key.is_some()
+    }
+
+    /// Number of bytes the trie spends on a record with a contract key and a plain value of the
+    /// given lengths.
+    pub fn record_len(&self, key_len: u64, value_len: u64) -> u64 {
+        let key_len = if self.blinds_keys() { 32 } else { key_len };
+        key_len + value_len + ENCRYPTION_OVERHEAD as u64
+    }
+
+    /// Maps a key used by the contract to the key written to the trie.
+    pub fn storage_key(&self, key: &[u8]) -> Vec<u8> {
+        match &self.blind_key {
+            Some(blind_key) => hmac_sha256(blind_key, &[key]).to_vec(),
+            None => key.to_vec(),
+        }
+    }
+
+    /// Encrypts `value` written under `storage_key`. The nonce is derived from both, so replaying
+    /// the same writes always produces the same state root. The storage key is authenticated as
+    /// well, so a value moved to another key no longer decrypts.
+    pub fn encrypt(&self, storage_key: &[u8], value: &[u8]) -> Vec<u8> {
+        let key_len = (storage_key.len() as u64).to_le_bytes();
+        let nonce = hmac_sha256(&self.nonce_key, &[&key_len, storage_key, value]);
+        let nonce = Nonce::from_slice(&nonce[..NONCE_LEN]);
+        let mut res = nonce.to_vec();
+        res.extend(
+            self.cipher
+                .encrypt(nonce, Payload { msg: value, aad: storage_key })
+                .expect("encryption of a value cannot fail"),
+        );
+        res
+    }
+
+    /// Decrypts a value read from `storage_key`.
+    pub fn decrypt(&self, storage_key: &[u8], encrypted: &[u8]) -> Result<Vec<u8>, StorageError> {
+        if encrypted.len() < ENCRYPTION_OVERHEAD {
+            return Err(StorageError::StorageInconsistentState(
+                "Encrypted contract data is too short".to_string(),
+            ));
+        }
+        let (nonce, cipher_text) = encrypted.split_at(NONCE_LEN);
+        let payload = Payload { msg: cipher_text, aad: storage_key };
+        self.cipher.decrypt(Nonce::from_slice(nonce), payload).map_err(|_| {
+            StorageError::StorageInconsistentState("Can't decrypt contract data".to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_storage_cipher() {
+        let cipher = ContractStorageCipher::new(&[1; 32], &AccountId::test(), false);
+        assert!(!cipher.blinds_keys());
+        assert_eq!(cipher.storage_key(b"key"), b"key".to_vec());
+
+        let encrypted = cipher.encrypt(b"key", b"value");
+        assert_eq!(encrypted.len(), b"value".len() + ENCRYPTION_OVERHEAD);
+        assert_eq!(encrypted, cipher.encrypt(b"key", b"value"));
+        assert_ne!(encrypted, cipher.encrypt(b"other", b"value"));
+        assert_eq!(cipher.decrypt(b"key", &encrypted).unwrap(), b"value".to_vec());
+        assert_eq!(cipher.record_len(3, 5), 3 + 5 + ENCRYPTION_OVERHEAD as u64);
+
+        let mut tampered = encrypted.clone();
+        tampered[NONCE_LEN] ^= 1;
+        assert!(cipher.decrypt(b"key", &tampered).is_err());
+        assert!(cipher.decrypt(b"key", &encrypted[..ENCRYPTION_OVERHEAD - 1]).is_err());
+        // a value moved under another key is rejected
+        assert!(cipher.decrypt(b"other", &encrypted).is_err());
+
+        // another account or shard can't read the data
+        let other_account = ContractStorageCipher::new(&[1; 32], &AccountId::system(), false);
+        assert!(other_account.decrypt(b"key", &encrypted).is_err());
+        let other_shard = ContractStorageCipher::new(&[2; 32], &AccountId::test(), false);
+        assert!(other_shard.decrypt(b"key", &encrypted).is_err());
+
+        let blinding = ContractStorageCipher::new(&[1; 32], &AccountId::test(), true);
+        assert!(blinding.blinds_keys());
+        assert_eq!(blinding.storage_key(b"key").len(), 32);
+        assert_ne!(blinding.storage_key(b"key"), blinding.storage_key(b"kez"));
+        assert_eq!(blinding.record_len(3, 5), 32 + 5 + ENCRYPTION_OVERHEAD as u64);
+    }
+}
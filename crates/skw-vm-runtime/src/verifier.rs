@@ -3,7 +3,7 @@ use skw_vm_primitives::{
     config::{VMLimitConfig, RuntimeConfig},
     receipt::{ActionReceipt, DataReceipt, Receipt, ReceiptEnum},
     transaction::{
-        Action, DeployContractAction, FunctionCallAction, SignedTransaction,
+        Action, FunctionCallAction, SignedTransaction,
     },
     errors::{
         ActionsValidationError, InvalidTxError, ReceiptValidationError,
@@ -213,21 +213,25 @@ pub fn validate_action(
 ) -> Result<(), ActionsValidationError> {
     match action {
         Action::CreateAccount(_) => Ok(()),
-        Action::DeployContract(a) => validate_deploy_contract_action(limit_config, a),
+        Action::DeployContract(a) => validate_deploy_contract_action(limit_config, &a.code),
+        Action::DeployEncryptedContract(a) => {
+            validate_deploy_contract_action(limit_config, &a.code)
+        }
         Action::FunctionCall(a) => validate_function_call_action(limit_config, a),
         Action::Transfer(_) => Ok(()),
         Action::DeleteAccount(_) => Ok(()),
     }
 }
 
-/// Validates `DeployContractAction`. Checks that the given contract size doesn't exceed the limit.
+/// Validates `DeployContractAction` and `DeployEncryptedContractAction`. Checks that the given
+/// contract size doesn't exceed the limit.
 fn validate_deploy_contract_action(
     limit_config: &VMLimitConfig,
-    action: &DeployContractAction,
+    code: &[u8],
 ) -> Result<(), ActionsValidationError> {
-    if action.code.len() as u64 > limit_config.max_contract_size {
+    if code.len() as u64 > limit_config.max_contract_size {
         return Err(ActionsValidationError::ContractSizeExceeded {
-            size: action.code.len() as u64,
+            size: code.len() as u64,
             limit: limit_config.max_contract_size,
         });
     }
@@ -271,7 +275,7 @@ mod tests {
     use skw_vm_primitives::crypto::{InMemorySigner, KeyType, Signer};
     use skw_vm_primitives::test_utils::account_new;
     use skw_vm_primitives::transaction::{
-        CreateAccountAction, DeleteAccountAction, TransferAction,
+        CreateAccountAction, DeleteAccountAction, DeployContractAction, TransferAction,
     };
     use skw_vm_primitives::contract_runtime::{
         AccountId, Balance, MerkleHash, CryptoHash, Nonce,
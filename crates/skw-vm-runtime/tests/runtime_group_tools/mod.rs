@@ -72,6 +72,8 @@ impl StandaloneRuntime {
             block_timestamp: 0,
             gas_limit: None,
             random_seed: Default::default(),
+            shard_secret: None,
+            trace_execution: false,
             config: Arc::new(runtime_config),
         };
 
@@ -17,7 +17,7 @@ pub use db::{
     LARGEST_TARGET_HEIGHT_KEY, LATEST_KNOWN_KEY, NUM_COLS, SHOULD_COL_GC, SKIP_COL_GC, TAIL_KEY,
 };
 
-use skw_vm_primitives::account::{Account};
+use skw_vm_primitives::account::{Account, ContractStorageMode};
 pub use skw_vm_primitives::errors::StorageError;
 use skw_vm_primitives::contract_runtime::{CryptoHash, ContractCode, AccountId, StateRoot};
 use skw_vm_primitives::receipt::{DelayedReceiptIndices, Receipt, ReceivedData};
@@ -30,7 +30,8 @@ use crate::db::{
     DBOp, DBTransaction, Database, FileDB, GENESIS_JSON_HASH_KEY, GENESIS_STATE_ROOTS_KEY,
 };
 pub use crate::trie::{
-    iterator::TrieIterator, update::TrieUpdate, update::TrieUpdateIterator,
+    iterator::TrieIterator, proof::verify_account_proof, proof::verify_contract_data_proof,
+    proof::verify_encoded_proof, proof::verify_proof, update::TrieUpdate, update::TrieUpdateIterator,
     update::TrieUpdateValuePtr, ApplyStatePartResult, KeyForStateChanges, PartialStorage,
     ShardTries, Trie, TrieChanges, WrappedTrieChanges,
 };
@@ -492,6 +493,22 @@ pub fn get_code(
         .map(|opt| opt.map(|code| ContractCode::new(&code)))
 }
 
+pub fn set_contract_storage_mode(
+    state_update: &mut TrieUpdate,
+    account_id: AccountId,
+    mode: ContractStorageMode,
+) {
+    set(state_update, TrieKey::ContractStorageMode { account_id }, &mode)
+}
+
+pub fn get_contract_storage_mode(
+    state_update: &TrieUpdate,
+    account_id: &AccountId,
+) -> Result<ContractStorageMode, StorageError> {
+    get(state_update, &TrieKey::ContractStorageMode { account_id: account_id.clone() })
+        .map(Option::unwrap_or_default)
+}
+
 /// Removes account, code and all access keys associated to it.
 pub fn remove_account(
     state_update: &mut TrieUpdate,
@@ -499,6 +516,7 @@ pub fn remove_account(
 ) -> Result<(), StorageError> {
     state_update.remove(TrieKey::Account { account_id: account_id.clone() });
     state_update.remove(TrieKey::ContractCode { account_id: account_id.clone() });
+    state_update.remove(TrieKey::ContractStorageMode { account_id: account_id.clone() });
 
     // Removing contract data
     let data_keys = state_update
@@ -24,6 +24,7 @@ use crate::StorageError;
 mod insert_delete;
 pub mod iterator;
 mod nibble_slice;
+pub mod proof;
 mod shard_tries;
 mod state_parts;
 mod trie_storage;
@@ -0,0 +1,111 @@
+use borsh::BorshSerialize;
+
+use skw_vm_primitives::account::Account;
+use skw_vm_primitives::challenge::PartialState;
+use skw_vm_primitives::contract_runtime::{AccountId, StateRoot};
+use skw_vm_primitives::serialize::from_base64;
+use skw_vm_primitives::trie_key::TrieKey;
+use skw_vm_primitives::views::TrieProofPath;
+
+use crate::trie::{PartialStorage, Trie};
+
+/// Decodes the base64 trie nodes returned by the state viewer.
+pub fn decode_proof(proof: &TrieProofPath) -> Option<Vec<Vec<u8>>> {
+    proof.iter().map(|node| from_base64(node).ok()).collect()
+}
+
+/// Checks that the recorded trie nodes in `proof` prove that `key` maps to `expected_value`
+/// under `root`, or that `key` is absent when `expected_value` is `None`.
+///
+/// Only the nodes in `proof` are consulted, so a root taken from the parentchain is enough to
+/// trust the result of an offchain query.
+pub fn verify_proof(
+    root: &StateRoot,
+    proof: &[Vec<u8>],
+    key: &[u8],
+    expected_value: Option<&[u8]>,
+) -> bool {
+    let trie = Trie::from_recorded_storage(PartialStorage { nodes: PartialState(proof.to_vec()) });
+    match trie.get(root, key) {
+        Ok(value) => value.as_deref() == expected_value,
+        // a missing node means the proof does not cover the key
+        Err(_) => false,
+    }
+}
+
+/// Same as `verify_proof`, for a proof encoded the way the state viewer returns it.
+pub fn verify_encoded_proof(
+    root: &StateRoot,
+    proof: &TrieProofPath,
+    key: &[u8],
+    expected_value: Option<&[u8]>,
+) -> bool {
+    match decode_proof(proof) {
+        Some(nodes) => verify_proof(root, &nodes, key, expected_value),
+        None => false,
+    }
+}
+
+/// Checks a proof of the account record of `account_id`.
+pub fn verify_account_proof(
+    root: &StateRoot,
+    proof: &TrieProofPath,
+    account_id: &AccountId,
+    account: Option<&Account>,
+) -> bool {
+    let key = TrieKey::Account { account_id: account_id.clone() }.to_vec();
+    let value = match account.map(|account| account.try_to_vec()).transpose() {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+    verify_encoded_proof(root, proof, &key, value.as_deref())
+}
+
+/// Checks a proof of the contract storage entry `data_key` of `account_id`.
+pub fn verify_contract_data_proof(
+    root: &StateRoot,
+    proof: &TrieProofPath,
+    account_id: &AccountId,
+    data_key: &[u8],
+    value: Option<&[u8]>,
+) -> bool {
+    let key = TrieKey::ContractData { account_id: account_id.clone(), key: data_key.to_vec() }.to_vec();
+    verify_encoded_proof(root, proof, &key, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use skw_vm_primitives::serialize::to_base64;
+
+    use crate::test_utils::{create_test_store, test_populate_trie};
+    use crate::trie::ShardTries;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_recorded_proof() {
+        let store = create_test_store();
+        let tries = ShardTries::new(store);
+        let changes = vec![
+            (b"doge".to_vec(), Some(b"coin".to_vec())),
+            (b"docu".to_vec(), Some(b"value".to_vec())),
+            (b"horse".to_vec(), Some(b"stallion".to_vec())),
+        ];
+        let root = test_populate_trie(&tries, &Trie::empty_root(), changes);
+
+        let trie = tries.get_trie().recording_reads();
+        trie.get(&root, b"doge").unwrap();
+        trie.get(&root, b"dog").unwrap();
+        let nodes = trie.recorded_storage().unwrap().nodes.0;
+
+        assert!(verify_proof(&root, &nodes, b"doge", Some(b"coin")));
+        assert!(verify_proof(&root, &nodes, b"dog", None));
+        assert!(!verify_proof(&root, &nodes, b"doge", Some(b"bone")));
+        assert!(!verify_proof(&root, &nodes, b"horse", Some(b"stallion")));
+        assert!(!verify_proof(&[1; 32], &nodes, b"doge", Some(b"coin")));
+
+        let encoded: TrieProofPath = nodes.iter().map(to_base64).collect();
+        assert!(verify_encoded_proof(&root, &encoded, b"doge", Some(b"coin")));
+        assert!(!verify_encoded_proof(&root, &vec!["!".to_string()], b"doge", Some(b"coin")));
+    }
+}
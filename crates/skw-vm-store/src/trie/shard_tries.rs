@@ -275,7 +275,8 @@ impl WrappedTrieChanges {
             match &change_with_trie_key.trie_key {
                 TrieKey::Account { .. }
                 | TrieKey::ContractCode { .. }
-                | TrieKey::ContractData { .. } => {}
+                | TrieKey::ContractData { .. }
+                | TrieKey::ContractStorageMode { .. } => {}
                 _ => continue,
             };
             let storage_key = KeyForStateChanges::new_from_trie_key(
@@ -8,6 +8,8 @@ function mainCI() {
   execSync('rustup target add wasm32-unknown-unknown');
   execSync('SKIP_WASM_BUILD=1 cargo check --features=runtime-benchmarks --release');
   execSync('SKIP_WASM_BUILD=1 cargo test --release');
+  // the differential tests of the VM engines only build with wasmtime
+  execSync('cargo test -p skw-vm-engine --features wasmtime_vm --release');
   // execSync('./crates/skw-contract-sdk/examples/test_all.sh');
 }
 
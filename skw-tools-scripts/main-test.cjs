@@ -8,6 +8,11 @@ function blockchainCi() {
   const module = process.argv[2] ? `-p ${process.argv[2]}` : "";
 
   execSync(`cargo test ${module} --release`);
+
+  // the differential tests of the VM engines only build with wasmtime
+  if (!process.argv[2] || process.argv[2] === 'skw-vm-engine') {
+    execSync('cargo test -p skw-vm-engine --features wasmtime_vm --release');
+  }
 }
 
 blockchainCi()